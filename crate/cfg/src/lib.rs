@@ -11,8 +11,13 @@ pub use peace_core::progress;
 pub use peace_core::*;
 
 pub use crate::{
-    apply_op_spec::ApplyOpSpec, item_spec::ItemSpec, op_ctx::OpCtx, state::State,
-    state_diff_fn_spec::StateDiffFnSpec, try_fn_spec::TryFnSpec,
+    apply_op_spec::ApplyOpSpec,
+    item_spec::ItemSpec,
+    op_ctx::OpCtx,
+    sensitive::{Sensitive, SensitiveSource},
+    state::State,
+    state_diff_fn_spec::StateDiffFnSpec,
+    try_fn_spec::TryFnSpec,
 };
 
 pub mod accessors;
@@ -21,5 +26,6 @@ pub mod state;
 mod apply_op_spec;
 mod item_spec;
 mod op_ctx;
+mod sensitive;
 mod state_diff_fn_spec;
 mod try_fn_spec;
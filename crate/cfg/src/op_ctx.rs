@@ -1,6 +1,6 @@
 use std::marker::PhantomData;
 
-use peace_core::ItemSpecId;
+use peace_core::{InterruptSignal, ItemSpecId};
 
 #[cfg(feature = "output_progress")]
 use peace_core::progress::ProgressSender;
@@ -10,6 +10,8 @@ use peace_core::progress::ProgressSender;
 pub struct OpCtx<'op> {
     /// ID of the item spec this belongs to.
     pub item_spec_id: &'op ItemSpecId,
+    /// Whether the command has been interrupted.
+    pub interrupt_signal: &'op InterruptSignal,
     /// For item specs to submit progress updates.
     #[cfg(feature = "output_progress")]
     pub progress_sender: ProgressSender<'op>,
@@ -21,16 +23,31 @@ impl<'op> OpCtx<'op> {
     /// Returns a new `OpCtx`.
     pub fn new(
         item_spec_id: &'op ItemSpecId,
+        interrupt_signal: &'op InterruptSignal,
         #[cfg(feature = "output_progress")] progress_sender: ProgressSender<'op>,
     ) -> Self {
         Self {
             item_spec_id,
+            interrupt_signal,
             #[cfg(feature = "output_progress")]
             progress_sender,
             marker: PhantomData,
         }
     }
 
+    /// Returns the `InterruptSignal` for item specs to check if they should
+    /// stop early.
+    pub fn interrupt_signal(&self) -> &InterruptSignal {
+        self.interrupt_signal
+    }
+
+    /// Returns whether the command has been interrupted.
+    ///
+    /// Long running operations may poll this to bail out early.
+    pub fn is_interrupted(&self) -> bool {
+        self.interrupt_signal.is_interrupted()
+    }
+
     /// Returns the `ProgressTracker` for item specs to send progress to.
     #[cfg(feature = "output_progress")]
     pub fn progress_sender(&self) -> &ProgressSender<'op> {
@@ -0,0 +1,148 @@
+use std::fmt;
+
+use peace_fmt::{Presentable, Presenter};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Text shown in place of a sensitive value.
+const REDACTED: &str = "********";
+
+/// Value that must not be shown to the user or written to storage, such as a
+/// token or password.
+///
+/// The value is redacted when formatted with `Display` or `Debug`, or when
+/// presented. When serialized, only the [`SensitiveSource`] of the value is
+/// written, and the value is resolved from that source again when
+/// deserialized.
+///
+/// Item specs access the raw value through [`Sensitive::value`]. Use
+/// [`Sensitive::from_env`] for values that are needed by later commands, such
+/// as flow params, so that each command can read the value again.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let token = Sensitive::<String>::from_env("APP_TOKEN");
+///
+/// assert_eq!("********", token.to_string());
+/// assert_eq!(Some("s3cr3t"), token.value().map(String::as_str));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Sensitive<T> {
+    /// Where the value is read from.
+    source: SensitiveSource,
+    /// The raw value, if it is available in this process.
+    value: Option<T>,
+}
+
+/// Where the value of a [`Sensitive`] is read from.
+///
+/// This is what is written when a `Sensitive` is serialized.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SensitiveSource {
+    /// The value is read from the environment variable with this name.
+    Env(String),
+    /// The value is not stored, and cannot be read again.
+    Redacted,
+}
+
+impl<T> Sensitive<T> {
+    /// Returns a new `Sensitive` wrapping the given value.
+    ///
+    /// The value is not stored, so a `Sensitive` deserialized from this does
+    /// not contain the value. Use [`Sensitive::from_env`] if the value needs
+    /// to be read again.
+    pub fn new(value: T) -> Self {
+        Self {
+            source: SensitiveSource::Redacted,
+            value: Some(value),
+        }
+    }
+
+    /// Returns a new `Sensitive` whose value is read from the environment
+    /// variable with the given name.
+    ///
+    /// The value is `None` if the variable is not set, or is not valid
+    /// unicode.
+    pub fn from_env<Name>(env_var_name: Name) -> Self
+    where
+        Name: Into<String>,
+        T: From<String>,
+    {
+        SensitiveSource::Env(env_var_name.into()).into()
+    }
+
+    /// Returns where the value is read from.
+    pub fn source(&self) -> &SensitiveSource {
+        &self.source
+    }
+
+    /// Returns the raw value, if it is available.
+    ///
+    /// This is `None` if the value could not be read from its source, such as
+    /// when a `Sensitive` created with [`Sensitive::new`] is deserialized.
+    pub fn value(&self) -> Option<&T> {
+        self.value.as_ref()
+    }
+
+    /// Returns the raw value, if it is available.
+    pub fn into_value(self) -> Option<T> {
+        self.value
+    }
+}
+
+impl<T> From<SensitiveSource> for Sensitive<T>
+where
+    T: From<String>,
+{
+    fn from(source: SensitiveSource) -> Self {
+        let value = match &source {
+            SensitiveSource::Env(env_var_name) => std::env::var(env_var_name).ok().map(T::from),
+            SensitiveSource::Redacted => None,
+        };
+
+        Self { source, value }
+    }
+}
+
+impl<T> fmt::Debug for Sensitive<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Sensitive").field(&REDACTED).finish()
+    }
+}
+
+impl<T> fmt::Display for Sensitive<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl<T> Presentable for Sensitive<T> {
+    async fn present<'output, PR>(&self, presenter: &mut PR) -> Result<(), PR::Error>
+    where
+        PR: Presenter<'output>,
+    {
+        presenter.text(REDACTED).await
+    }
+}
+
+impl<T> Serialize for Sensitive<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.source.serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Sensitive<T>
+where
+    T: From<String>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        SensitiveSource::deserialize(deserializer).map(Self::from)
+    }
+}
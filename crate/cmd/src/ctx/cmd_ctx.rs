@@ -1,18 +1,40 @@
 #![allow(clippy::type_complexity)]
 
-use std::ops::{Deref, DerefMut};
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    ops::{Deref, DerefMut},
+    rc::Rc,
+    time::Duration,
+};
 
-use peace_resources::Resources;
+use peace_core::{FlowId, InterruptSignal, Profile};
+use peace_resources::{
+    internal::{FlowParamsFile, ProfileParamsFile, WorkspaceParamsFile},
+    paths::{FlowDir, ProfileDir, ProfileHistoryDir},
+    resources::ts::{Empty, SetUp},
+    Resources,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use peace_resources::paths::ProfileLockFile;
 use peace_rt_model::{
-    params::{KeyUnknown, ParamsKeys, ParamsKeysImpl},
-    Workspace,
+    params::{
+        FlowParams, KeyMaybe, KeyUnknown, ParamsKeys, ParamsKeysImpl, ParamsLimits,
+        ParamsTypeRegs, ProfileParams, WorkspaceParams,
+    },
+    storage::SerializedStorage,
+    Error, Flow, ItemSpecSelection, Storage, Workspace,
 };
+#[cfg(not(target_arch = "wasm32"))]
+use peace_rt_model::{ProfileLock, ProfileLockMode};
+use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
     ctx::{
         cmd_ctx_builder::{
-            MultiProfileNoFlowBuilder, MultiProfileSingleFlowBuilder, NoProfileNoFlowBuilder,
-            SingleProfileNoFlowBuilder, SingleProfileSingleFlowBuilder,
+            self, MultiProfileNoFlowBuilder, MultiProfileSingleFlowBuilder, NoProfileNoFlowBuilder,
+            SingleProfileMultiFlowBuilder, SingleProfileNoFlowBuilder,
+            SingleProfileSingleFlowBuilder,
         },
         CmdCtxBuilder,
     },
@@ -21,7 +43,7 @@ use crate::{
             FlowNotSelected, FlowParamsNone, ProfileNotSelected, ProfileParamsNone,
             WorkspaceParamsNone,
         },
-        SingleProfileSingleFlow,
+        MultiProfileSingleFlow, SingleProfileMultiFlow, SingleProfileSingleFlow,
     },
 };
 
@@ -103,6 +125,26 @@ impl CmdCtx<()> {
         CmdCtxBuilder::multi_profile_single_flow(output, workspace)
     }
 
+    /// Returns a `CmdCtxBuilder` for a single profile and multiple flows.
+    pub fn builder_single_profile_multi_flow<'ctx, E, O>(
+        output: &'ctx mut O,
+        workspace: &'ctx Workspace,
+    ) -> CmdCtxBuilder<
+        'ctx,
+        O,
+        SingleProfileMultiFlowBuilder<
+            E,
+            ProfileNotSelected,
+            FlowNotSelected,
+            ParamsKeysImpl<KeyUnknown, KeyUnknown, KeyUnknown>,
+            WorkspaceParamsNone,
+            ProfileParamsNone,
+            FlowParamsNone,
+        >,
+    > {
+        CmdCtxBuilder::single_profile_multi_flow(output, workspace)
+    }
+
     /// Returns a `CmdCtxBuilder` for a single profile and flow.
     pub fn builder_single_profile_no_flow<'ctx, E, O>(
         output: &'ctx mut O,
@@ -161,6 +203,441 @@ where
 
         CmdCtx { scope }
     }
+
+    /// Sets the maximum duration for applying the flow, or discovering its
+    /// states.
+    ///
+    /// When the timeout elapses, no further item specs are processed. When
+    /// discovering states, each item spec that is in progress fails with
+    /// [`Error::CmdTimeout`]. When applying the flow, item specs that are in
+    /// progress are allowed to complete, and are recorded as timed out.
+    ///
+    /// [`Error::CmdTimeout`]: peace_rt_model::Error::CmdTimeout
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        *self.scope.timeout_mut() = Some(timeout);
+        self
+    }
+
+    /// Sets the maximum number of item specs to execute concurrently.
+    ///
+    /// This defaults to `peace_rt::BUFFERED_FUTURES_MAX`. A `concurrency_limit`
+    /// of `0` is treated as `1`.
+    #[must_use]
+    pub fn with_concurrency_limit(mut self, concurrency_limit: usize) -> Self {
+        *self.scope.concurrency_limit_mut() = Some(concurrency_limit.max(1));
+        self
+    }
+
+    /// Runs only the selected item specs of the flow.
+    ///
+    /// Item specs outside the selection are not run, and their saved and
+    /// desired states are left untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ItemSpecSelectionIdNotFound`] if a selected item spec
+    /// is not in the flow.
+    pub fn with_item_spec_selection(
+        mut self,
+        item_spec_selection: &ItemSpecSelection,
+    ) -> Result<Self, Error> {
+        let item_spec_ids_selected = item_spec_selection.resolve(self.scope.flow().graph())?;
+        *self.scope.item_spec_ids_selected_mut() = Some(item_spec_ids_selected);
+        Ok(self)
+    }
+}
+
+impl<'ctx, E, O, PKeys> CmdCtx<MultiProfileSingleFlow<'ctx, E, O, PKeys>>
+where
+    E: std::error::Error + From<Error> + 'static,
+    PKeys: ParamsKeys + 'static,
+{
+    /// Sets the maximum number of profiles to run commands for concurrently.
+    ///
+    /// This defaults to `peace_rt::BUFFERED_FUTURES_MAX`. A
+    /// `profile_concurrency_limit` of `0` is treated as `1`.
+    #[must_use]
+    pub fn with_profile_concurrency_limit(mut self, profile_concurrency_limit: usize) -> Self {
+        *self.scope.profile_concurrency_limit_mut() = Some(profile_concurrency_limit.max(1));
+        self
+    }
+
+    /// Returns a `CmdCtx` to run single profile commands for one of this
+    /// command's profiles.
+    ///
+    /// The returned `CmdCtx` writes to the given `output`, so that commands
+    /// may be run for multiple profiles concurrently. Its `Resources` are set
+    /// up for the profile in the same way as
+    /// [`CmdCtx::builder_single_profile_single_flow`], and the profile is
+    /// locked if this command was built with `with_lock`.
+    ///
+    /// The returned `CmdCtx` shares this command's [`InterruptSignal`], and
+    /// renders its progress as a group in this command's progress output.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ProfileNotInScope`] if `profile` is not one of this
+    /// command's profiles.
+    ///
+    /// [`InterruptSignal`]: peace_core::InterruptSignal
+    pub async fn profile_cmd_ctx<'s, O2>(
+        &'s self,
+        output: &'s mut O2,
+        profile: &Profile,
+    ) -> Result<CmdCtx<SingleProfileSingleFlow<'s, E, O2, PKeys, SetUp>>, E> {
+        let scope = &self.scope;
+        let (profile_dir, profile_history_dir, flow_dir) = self.profile_dirs_get(profile)?;
+        let profile_params = scope
+            .profile_to_profile_params
+            .get(profile)
+            .cloned()
+            .unwrap_or_default();
+        let flow_params = scope
+            .profile_to_flow_params
+            .get(profile)
+            .cloned()
+            .unwrap_or_default();
+
+        single_profile_single_flow_cmd_ctx(
+            output,
+            scope.workspace(),
+            &scope.storage,
+            #[cfg(feature = "output_progress")]
+            progress_group_add(&scope.multi_progress, profile),
+            profile.clone(),
+            profile_dir.clone(),
+            profile_history_dir.clone(),
+            scope.flow(),
+            flow_dir.clone(),
+            scope.params_type_regs.clone(),
+            scope.workspace_params.clone(),
+            profile_params,
+            flow_params,
+            scope.params_limits.clone(),
+            scope.interrupt_signal().clone(),
+            #[cfg(not(target_arch = "wasm32"))]
+            scope.profile_lock_mode,
+            Resources::new(),
+        )
+        .await
+    }
+
+    /// Returns the `Resources` for one of this command's profiles.
+    ///
+    /// The `Resources` are set up for the profile in the same way as
+    /// [`CmdCtx::builder_single_profile_single_flow`], so that item specs may
+    /// be run using the profile's params.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ProfileNotInScope`] if `profile` is not one of this
+    /// command's profiles.
+    pub async fn profile_resources(&self, profile: &Profile) -> Result<Resources<SetUp>, E> {
+        let scope = &self.scope;
+        let (profile_dir, profile_history_dir, flow_dir) = self.profile_dirs_get(profile)?;
+        let profile_params = scope
+            .profile_to_profile_params
+            .get(profile)
+            .cloned()
+            .unwrap_or_default();
+        let flow_params = scope
+            .profile_to_flow_params
+            .get(profile)
+            .cloned()
+            .unwrap_or_default();
+
+        let workspace = scope.workspace();
+        let flow = scope.flow();
+        let mut resources = Resources::new();
+        params_insert(
+            workspace,
+            &scope.storage,
+            profile_dir,
+            flow_dir,
+            scope.workspace_params.clone(),
+            profile_params,
+            flow_params,
+            &mut resources,
+        );
+
+        cmd_ctx_builder::single_profile_single_flow_resources_setup(
+            workspace,
+            &scope.storage,
+            profile,
+            profile_dir,
+            profile_history_dir,
+            flow,
+            flow_dir,
+            &cmd_ctx_builder::states_type_regs(flow.graph()),
+            scope.params_limits.clone(),
+            scope.interrupt_signal().clone(),
+            resources,
+        )
+        .await
+    }
+
+    /// Returns the profile, profile history, and flow directories of the
+    /// given profile.
+    fn profile_dirs_get(
+        &self,
+        profile: &Profile,
+    ) -> Result<(&ProfileDir, &ProfileHistoryDir, &FlowDir), E> {
+        let scope = &self.scope;
+        match (
+            scope.profile_dirs().get(profile),
+            scope.profile_history_dirs().get(profile),
+            scope.flow_dirs().get(profile),
+        ) {
+            (Some(profile_dir), Some(profile_history_dir), Some(flow_dir)) => {
+                Ok((profile_dir, profile_history_dir, flow_dir))
+            }
+            _ => Err(E::from(Error::ProfileNotInScope {
+                profile: profile.clone(),
+                profiles_in_scope: scope.profiles().to_vec(),
+            })),
+        }
+    }
+}
+
+impl<'ctx, E, O, PKeys> CmdCtx<SingleProfileMultiFlow<'ctx, E, O, PKeys>>
+where
+    E: std::error::Error + From<Error> + 'static,
+    PKeys: ParamsKeys + 'static,
+{
+    /// Returns a `CmdCtx` to run single flow commands for one of this
+    /// command's flows.
+    ///
+    /// The returned `CmdCtx` writes to the given `output`. Its `Resources` are
+    /// set up for the flow in the same way as
+    /// [`CmdCtx::builder_single_profile_single_flow`], using the flow's
+    /// stored flow params, and additionally contain the [`FlowsStatesSaved`]
+    /// of every flow in this command, so that the flow's item specs may read
+    /// the saved states of other flows.
+    ///
+    /// The returned `CmdCtx` shares this command's [`InterruptSignal`], and
+    /// renders its progress as a group in this command's progress output.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::FlowNotInScope`] if `flow_id` is not the ID of one of
+    /// this command's flows.
+    ///
+    /// [`FlowsStatesSaved`]: peace_resources::states::FlowsStatesSaved
+    /// [`InterruptSignal`]: peace_core::InterruptSignal
+    pub async fn flow_cmd_ctx<'s, O2>(
+        &'s self,
+        output: &'s mut O2,
+        flow_id: &FlowId,
+    ) -> Result<CmdCtx<SingleProfileSingleFlow<'s, E, O2, PKeys, SetUp>>, E> {
+        let scope = &self.scope;
+        let (flow, flow_dir) = match scope
+            .flows()
+            .iter()
+            .find(|flow| flow.flow_id() == flow_id)
+            .zip(scope.flow_dirs().get(flow_id))
+        {
+            Some((flow, flow_dir)) => (*flow, flow_dir),
+            None => {
+                return Err(E::from(Error::FlowNotInScope {
+                    flow_id: flow_id.clone(),
+                    flow_ids_in_scope: scope
+                        .flows()
+                        .iter()
+                        .map(|flow| flow.flow_id().clone())
+                        .collect(),
+                }));
+            }
+        };
+        let flow_params = scope
+            .flow_id_to_flow_params
+            .get(flow_id)
+            .cloned()
+            .unwrap_or_default();
+
+        // States are read again instead of cloned from `flows_states_saved`, so that
+        // they are current if a previous command has written them.
+        let workspace = scope.workspace();
+        let flows_states_saved = cmd_ctx_builder::flows_states_saved_read(
+            &scope.storage,
+            scope.profile_dir(),
+            scope.flows(),
+        )
+        .await?;
+        let mut resources = Resources::new();
+        resources.insert(flows_states_saved);
+
+        single_profile_single_flow_cmd_ctx(
+            output,
+            workspace,
+            &scope.storage,
+            #[cfg(feature = "output_progress")]
+            progress_group_add(&scope.multi_progress, flow_id),
+            scope.profile().clone(),
+            scope.profile_dir().clone(),
+            scope.profile_history_dir().clone(),
+            flow,
+            flow_dir.clone(),
+            scope.params_type_regs.clone(),
+            scope.workspace_params.clone(),
+            scope.profile_params.clone(),
+            flow_params,
+            scope.params_limits.clone(),
+            scope.interrupt_signal().clone(),
+            // The profile is already locked by this command, if it was built with `with_lock`.
+            #[cfg(not(target_arch = "wasm32"))]
+            None,
+            resources,
+        )
+        .await
+    }
+}
+
+/// Returns a `SingleProfileSingleFlow` `CmdCtx` derived from a multi profile
+/// or multi flow `CmdCtx`.
+///
+/// The profile is locked if `profile_lock_mode` is set, then `Resources` are
+/// set up in the same way as when the `CmdCtx` is built through
+/// [`CmdCtx::builder_single_profile_single_flow`]. `resources` may contain
+/// additional values to insert before the item specs are set up.
+#[allow(clippy::too_many_arguments)]
+async fn single_profile_single_flow_cmd_ctx<'s, E, O, PKeys>(
+    output: &'s mut O,
+    workspace: &'s Workspace,
+    storage: &Storage,
+    #[cfg(feature = "output_progress")] multi_progress: indicatif::MultiProgress,
+    profile: Profile,
+    profile_dir: ProfileDir,
+    profile_history_dir: ProfileHistoryDir,
+    flow: &'s Flow<E>,
+    flow_dir: FlowDir,
+    params_type_regs: Rc<ParamsTypeRegs<PKeys>>,
+    workspace_params: WorkspaceParams<<PKeys::WorkspaceParamsKMaybe as KeyMaybe>::Key>,
+    profile_params: ProfileParams<<PKeys::ProfileParamsKMaybe as KeyMaybe>::Key>,
+    flow_params: FlowParams<<PKeys::FlowParamsKMaybe as KeyMaybe>::Key>,
+    params_limits: ParamsLimits,
+    interrupt_signal: InterruptSignal,
+    #[cfg(not(target_arch = "wasm32"))] profile_lock_mode: Option<ProfileLockMode>,
+    mut resources: Resources<Empty>,
+) -> Result<CmdCtx<SingleProfileSingleFlow<'s, E, O, PKeys, SetUp>>, E>
+where
+    E: std::error::Error + From<Error> + 'static,
+    PKeys: ParamsKeys + 'static,
+{
+    // Lock the profile before its states are read.
+    #[cfg(not(target_arch = "wasm32"))]
+    let profile_lock = match profile_lock_mode {
+        Some(profile_lock_mode) => {
+            let profile_lock = ProfileLock::acquire(
+                storage,
+                &profile,
+                ProfileLockFile::from(&profile_dir),
+                profile_lock_mode,
+            )
+            .await?;
+            Some(profile_lock)
+        }
+        None => None,
+    };
+
+    params_insert(
+        workspace,
+        storage,
+        &profile_dir,
+        &flow_dir,
+        workspace_params.clone(),
+        profile_params.clone(),
+        flow_params.clone(),
+        &mut resources,
+    );
+    let states_type_regs = cmd_ctx_builder::states_type_regs(flow.graph());
+    let resources = cmd_ctx_builder::single_profile_single_flow_resources_setup(
+        workspace,
+        storage,
+        &profile,
+        &profile_dir,
+        &profile_history_dir,
+        flow,
+        &flow_dir,
+        &states_type_regs,
+        params_limits,
+        interrupt_signal,
+        resources,
+    )
+    .await?;
+
+    #[cfg(feature = "output_progress")]
+    let cmd_progress_tracker = cmd_ctx_builder::cmd_progress_tracker(flow.graph(), multi_progress);
+
+    let scope = SingleProfileSingleFlow::new(
+        output,
+        workspace,
+        #[cfg(feature = "output_progress")]
+        cmd_progress_tracker,
+        profile,
+        profile_dir,
+        profile_history_dir,
+        flow,
+        flow_dir,
+        params_type_regs,
+        workspace_params,
+        profile_params,
+        flow_params,
+        states_type_regs,
+        #[cfg(not(target_arch = "wasm32"))]
+        profile_lock,
+        resources,
+    );
+
+    Ok(CmdCtx { scope })
+}
+
+/// Adds a header for a group of progress bars to the `MultiProgress`, and
+/// returns the `MultiProgress` to add the group's progress bars to.
+#[cfg(feature = "output_progress")]
+fn progress_group_add(
+    multi_progress: &indicatif::MultiProgress,
+    group_name: &dyn std::fmt::Display,
+) -> indicatif::MultiProgress {
+    let progress_bar_header = multi_progress.add(
+        indicatif::ProgressBar::hidden().with_style(
+            indicatif::ProgressStyle::with_template("{msg}")
+                .unwrap_or_else(|_| indicatif::ProgressStyle::default_spinner()),
+        ),
+    );
+    progress_bar_header.set_message(group_name.to_string());
+    multi_progress.clone()
+}
+
+/// Inserts the workspace, profile, and flow params, and their files, into
+/// `resources`.
+#[allow(clippy::too_many_arguments)]
+fn params_insert<WorkspaceParamsK, ProfileParamsK, FlowParamsK>(
+    workspace: &Workspace,
+    storage: &Storage,
+    profile_dir: &ProfileDir,
+    flow_dir: &FlowDir,
+    workspace_params: WorkspaceParams<WorkspaceParamsK>,
+    profile_params: ProfileParams<ProfileParamsK>,
+    flow_params: FlowParams<FlowParamsK>,
+    resources: &mut Resources<Empty>,
+) where
+    WorkspaceParamsK:
+        Clone + Debug + Eq + Hash + DeserializeOwned + Serialize + Send + Sync + 'static,
+    ProfileParamsK:
+        Clone + Debug + Eq + Hash + DeserializeOwned + Serialize + Send + Sync + 'static,
+    FlowParamsK: Clone + Debug + Eq + Hash + DeserializeOwned + Serialize + Send + Sync + 'static,
+{
+    let storage_extension = storage.format().extension();
+    cmd_ctx_builder::workspace_params_insert(workspace_params, resources);
+    resources.insert(WorkspaceParamsFile::from((
+        workspace.dirs().peace_app_dir(),
+        storage_extension,
+    )));
+    cmd_ctx_builder::profile_params_insert(profile_params, resources);
+    resources.insert(ProfileParamsFile::from((profile_dir, storage_extension)));
+    cmd_ctx_builder::flow_params_insert(flow_params, resources);
+    resources.insert(FlowParamsFile::from((flow_dir, storage_extension)));
 }
 
 impl<Scope> Deref for CmdCtx<Scope> {
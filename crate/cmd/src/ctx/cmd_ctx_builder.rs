@@ -1,17 +1,22 @@
 #![allow(clippy::type_complexity)]
 
-use std::{fmt::Debug, hash::Hash};
+use std::{collections::BTreeMap, fmt::Debug, hash::Hash};
 
-use futures::stream::{StreamExt, TryStreamExt};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use peace_core::{FlowId, InterruptSignal, Profile};
 use peace_resources::{
     internal::{FlowParamsFile, ProfileParamsFile, WorkspaceParamsFile},
+    paths::{FlowDir, ProfileDir, ProfileHistoryDir, StatesSavedFile},
     resources::ts::{Empty, SetUp},
+    states::{FlowsStatesSaved, StatesSaved},
     Resources,
 };
 use peace_rt_model::{
     fn_graph::resman::Resource,
-    params::{FlowParams, ProfileParams, WorkspaceParams},
-    Error, ItemSpecGraph, StatesTypeRegs, Storage, Workspace, WorkspaceInitializer,
+    params::{FlowParams, ParamsLimits, ProfileParams, WorkspaceParams},
+    storage::SerializedStorage,
+    Error, Flow, ItemSpecGraph, ProfileLockMode, StatesSerializer, StatesTypeRegs, Storage,
+    Workspace, WorkspaceInitializer,
 };
 use serde::{de::DeserializeOwned, Serialize};
 
@@ -19,6 +24,7 @@ pub use self::{
     multi_profile_no_flow_builder::MultiProfileNoFlowBuilder,
     multi_profile_single_flow_builder::MultiProfileSingleFlowBuilder,
     no_profile_no_flow_builder::NoProfileNoFlowBuilder,
+    single_profile_multi_flow_builder::SingleProfileMultiFlowBuilder,
     single_profile_no_flow_builder::SingleProfileNoFlowBuilder,
     single_profile_single_flow_builder::SingleProfileSingleFlowBuilder,
 };
@@ -26,6 +32,7 @@ pub use self::{
 mod multi_profile_no_flow_builder;
 mod multi_profile_single_flow_builder;
 mod no_profile_no_flow_builder;
+mod single_profile_multi_flow_builder;
 mod single_profile_no_flow_builder;
 mod single_profile_single_flow_builder;
 
@@ -41,17 +48,23 @@ pub struct CmdCtxBuilder<'ctx, O, ScopeBuilder> {
     output: &'ctx mut O,
     /// Workspace that the `peace` tool runs in.
     workspace: &'ctx Workspace,
+    /// Limits that parameter values are checked against, for commands with
+    /// flows.
+    params_limits: ParamsLimits,
+    /// Whether and how to lock the profile, for commands that change state.
+    profile_lock_mode: Option<ProfileLockMode>,
     /// Data held while building `CmdCtx`.
     scope_builder: ScopeBuilder,
 }
 
 /// Serializes workspace params to storage.
-async fn workspace_params_serialize<WorkspaceParamsK>(
+async fn workspace_params_serialize<S, WorkspaceParamsK>(
     workspace_params: &WorkspaceParams<WorkspaceParamsK>,
-    storage: &Storage,
+    storage: &S,
     workspace_params_file: &WorkspaceParamsFile,
 ) -> Result<(), Error>
 where
+    S: SerializedStorage,
     WorkspaceParamsK:
         Clone + Debug + Eq + Hash + DeserializeOwned + Serialize + Send + Sync + 'static,
 {
@@ -66,7 +79,7 @@ where
 }
 
 /// Inserts workspace params into the `Resources` map.
-fn workspace_params_insert<WorkspaceParamsK>(
+pub(crate) fn workspace_params_insert<WorkspaceParamsK>(
     mut workspace_params: WorkspaceParams<WorkspaceParamsK>,
     resources: &mut Resources<Empty>,
 ) where
@@ -83,12 +96,13 @@ fn workspace_params_insert<WorkspaceParamsK>(
 }
 
 /// Serializes profile params to storage.
-async fn profile_params_serialize<ProfileParamsK>(
+async fn profile_params_serialize<S, ProfileParamsK>(
     profile_params: &ProfileParams<ProfileParamsK>,
-    storage: &Storage,
+    storage: &S,
     profile_params_file: &ProfileParamsFile,
 ) -> Result<(), Error>
 where
+    S: SerializedStorage,
     ProfileParamsK:
         Clone + Debug + Eq + Hash + DeserializeOwned + Serialize + Send + Sync + 'static,
 {
@@ -99,7 +113,7 @@ where
 }
 
 /// Inserts profile params into the `Resources` map.
-fn profile_params_insert<ProfileParamsK>(
+pub(crate) fn profile_params_insert<ProfileParamsK>(
     mut profile_params: ProfileParams<ProfileParamsK>,
     resources: &mut Resources<Empty>,
 ) where
@@ -114,12 +128,13 @@ fn profile_params_insert<ProfileParamsK>(
 }
 
 /// Serializes flow params to storage.
-async fn flow_params_serialize<FlowParamsK>(
+async fn flow_params_serialize<S, FlowParamsK>(
     flow_params: &FlowParams<FlowParamsK>,
-    storage: &Storage,
+    storage: &S,
     flow_params_file: &FlowParamsFile,
 ) -> Result<(), Error>
 where
+    S: SerializedStorage,
     FlowParamsK: Clone + Debug + Eq + Hash + DeserializeOwned + Serialize + Send + Sync + 'static,
 {
     WorkspaceInitializer::flow_params_serialize(storage, flow_params, flow_params_file).await?;
@@ -128,7 +143,7 @@ where
 }
 
 /// Inserts flow params into the `Resources` map.
-fn flow_params_insert<FlowParamsK>(
+pub(crate) fn flow_params_insert<FlowParamsK>(
     mut flow_params: FlowParams<FlowParamsK>,
     resources: &mut Resources<Empty>,
 ) where
@@ -218,7 +233,7 @@ pub(crate) async fn profiles_from_peace_app_dir(
 }
 
 /// Registers each item spec's `State` and `StateLogical` for deserialization.
-fn states_type_regs<E>(item_spec_graph: &ItemSpecGraph<E>) -> StatesTypeRegs {
+pub(crate) fn states_type_regs<E>(item_spec_graph: &ItemSpecGraph<E>) -> StatesTypeRegs {
     item_spec_graph
         .iter()
         .fold(StatesTypeRegs::new(), |mut states_type_regs, item_spec| {
@@ -228,7 +243,130 @@ fn states_type_regs<E>(item_spec_graph: &ItemSpecGraph<E>) -> StatesTypeRegs {
         })
 }
 
-async fn item_spec_graph_setup<E>(
+/// Reads the saved states of each flow for the profile.
+///
+/// Flows whose states have not been saved are not included.
+pub(crate) async fn flows_states_saved_read<S, E>(
+    storage: &S,
+    profile_dir: &ProfileDir,
+    flows: &[&Flow<E>],
+) -> Result<FlowsStatesSaved, Error>
+where
+    S: SerializedStorage,
+{
+    let flows_states_saved = stream::iter(flows.iter().map(Result::<_, Error>::Ok))
+        .try_filter_map(|flow| async move {
+            let flow_id = flow.flow_id();
+            let states_type_regs = states_type_regs(flow.graph());
+            let flow_dir = FlowDir::from((profile_dir, flow_id));
+            let states_saved_file =
+                StatesSavedFile::from((&flow_dir, storage.format().extension()));
+            let states_saved = StatesSerializer::<Error>::deserialize_saved_opt(
+                flow_id,
+                storage,
+                states_type_regs.states_current_type_reg(),
+                &states_saved_file,
+            )
+            .await?;
+
+            Ok(states_saved.map(|states_saved| (flow_id.clone(), states_saved)))
+        })
+        .try_collect::<BTreeMap<FlowId, StatesSaved>>()
+        .await?;
+
+    Ok(FlowsStatesSaved::from(flows_states_saved))
+}
+
+/// Inserts the workspace, profile, and flow values into `resources`, reads the
+/// flow's saved states, and sets up the flow's item specs.
+///
+/// This is shared by `SingleProfileSingleFlow` command contexts, whether they
+/// are built directly, or derived from a multi profile or multi flow command
+/// context, so that their `Resources` are set up the same way.
+///
+/// The `storage` is inserted instead of the workspace's storage, as it uses the
+/// storage format recorded in the workspace.
+///
+/// Params are checked against the `params_limits` after the item specs are set
+/// up, so that item spec params inserted or resolved during setup are checked.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn single_profile_single_flow_resources_setup<E>(
+    workspace: &Workspace,
+    storage: &Storage,
+    profile: &Profile,
+    profile_dir: &ProfileDir,
+    profile_history_dir: &ProfileHistoryDir,
+    flow: &Flow<E>,
+    flow_dir: &FlowDir,
+    states_type_regs: &StatesTypeRegs,
+    params_limits: ParamsLimits,
+    interrupt_signal: InterruptSignal,
+    mut resources: Resources<Empty>,
+) -> Result<Resources<SetUp>, E>
+where
+    E: std::error::Error + From<Error>,
+{
+    {
+        let (app_name, workspace_dirs, _storage) = workspace.clone().into_inner();
+        let (workspace_dir, peace_dir, peace_app_dir) = workspace_dirs.into_inner();
+
+        resources.insert(app_name);
+        resources.insert(storage.clone());
+        resources.insert(workspace_dir);
+        resources.insert(peace_dir);
+        resources.insert(peace_app_dir);
+        resources.insert(profile_dir.clone());
+        resources.insert(profile_history_dir.clone());
+        resources.insert(profile.clone());
+        resources.insert(flow_dir.clone());
+        resources.insert(flow.flow_id().clone());
+        resources.insert(interrupt_signal);
+    }
+
+    // States are read instead of passed in, so that they are current if a previous
+    // command has written them.
+    let states_saved = StatesSerializer::<Error>::deserialize_saved_opt(
+        flow.flow_id(),
+        storage,
+        states_type_regs.states_current_type_reg(),
+        &StatesSavedFile::from((flow_dir, storage.format().extension())),
+    )
+    .await?;
+    if let Some(states_saved) = states_saved {
+        resources.insert(states_saved);
+    }
+
+    // Call each `ItemSpec`'s initialization function.
+    let mut resources = item_spec_graph_setup(flow.graph(), resources).await?;
+
+    // Guard against unusual workspace, profile, flow, and item spec param values.
+    params_limits.check(&resources)?;
+    resources.insert(params_limits);
+
+    Ok(resources)
+}
+
+/// Returns a `CmdProgressTracker` with a progress bar for each item spec in
+/// the graph, added to the given `MultiProgress`.
+#[cfg(feature = "output_progress")]
+pub(crate) fn cmd_progress_tracker<E>(
+    item_spec_graph: &ItemSpecGraph<E>,
+    multi_progress: indicatif::MultiProgress,
+) -> peace_rt_model::CmdProgressTracker {
+    let progress_trackers = item_spec_graph.iter_insertion().fold(
+        peace_rt_model::IndexMap::with_capacity(item_spec_graph.node_count()),
+        |mut progress_trackers, item_spec| {
+            let progress_bar = multi_progress.add(indicatif::ProgressBar::hidden());
+            let progress_tracker = peace_core::progress::ProgressTracker::new(progress_bar);
+            progress_trackers.insert(item_spec.id().clone(), progress_tracker);
+            progress_trackers
+        },
+    );
+
+    peace_rt_model::CmdProgressTracker::new(multi_progress, progress_trackers)
+}
+
+pub(crate) async fn item_spec_graph_setup<E>(
     item_spec_graph: &ItemSpecGraph<E>,
     resources: Resources<Empty>,
 ) -> Result<Resources<SetUp>, E>
@@ -3,3 +3,64 @@
 #[peace_code_gen::cmd_ctx_builder_impl]
 #[derive(Debug)]
 pub struct MultiProfileSingleFlowBuilder;
+
+impl<
+    'ctx,
+    E,
+    O,
+    ProfileSelection,
+    FlowSelection,
+    PKeys,
+    WorkspaceParamsSelection,
+    ProfileParamsSelection,
+    FlowParamsSelection,
+>
+    crate::ctx::CmdCtxBuilder<
+        'ctx,
+        O,
+        MultiProfileSingleFlowBuilder<
+            E,
+            ProfileSelection,
+            FlowSelection,
+            PKeys,
+            WorkspaceParamsSelection,
+            ProfileParamsSelection,
+            FlowParamsSelection,
+        >,
+    >
+where
+    PKeys: peace_rt_model::params::ParamsKeys + 'static,
+{
+    /// Locks each profile for the lifetime of the `CmdCtx` returned by
+    /// [`CmdCtx::profile_cmd_ctx`] for that profile.
+    ///
+    /// This should be used for commands that write states to each profile,
+    /// such as `EnsureCmd` and `CleanCmd`. See
+    /// [`CmdCtxBuilder::with_lock`][with_lock_spsf] for how the lock behaves.
+    ///
+    /// [`CmdCtx::profile_cmd_ctx`]: crate::ctx::CmdCtx::profile_cmd_ctx
+    /// [with_lock_spsf]: crate::ctx::CmdCtxBuilder#method.with_lock
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_lock(mut self, profile_lock_mode: peace_rt_model::ProfileLockMode) -> Self {
+        self.profile_lock_mode = Some(profile_lock_mode);
+        self
+    }
+
+    /// Sets the limits that each profile's parameter values are checked
+    /// against.
+    ///
+    /// The limits are checked when the `CmdCtx` for a profile is returned by
+    /// [`CmdCtx::profile_cmd_ctx`]. See
+    /// [`CmdCtxBuilder::with_params_limits`][with_params_limits_spsf] for how
+    /// the limits are checked.
+    ///
+    /// [`CmdCtx::profile_cmd_ctx`]: crate::ctx::CmdCtx::profile_cmd_ctx
+    /// [with_params_limits_spsf]: crate::ctx::CmdCtxBuilder#method.with_params_limits
+    pub fn with_params_limits(
+        mut self,
+        params_limits: peace_rt_model::params::ParamsLimits,
+    ) -> Self {
+        self.params_limits = params_limits;
+        self
+    }
+}
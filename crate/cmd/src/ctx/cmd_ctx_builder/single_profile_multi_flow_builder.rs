@@ -0,0 +1,66 @@
+/// Data stored by `CmdCtxBuilder` while building a
+/// `CmdCtx<SingleProfileMultiFlow>`.
+#[peace_code_gen::cmd_ctx_builder_impl]
+#[derive(Debug)]
+pub struct SingleProfileMultiFlowBuilder;
+
+impl<
+    'ctx,
+    E,
+    O,
+    ProfileSelection,
+    FlowSelection,
+    PKeys,
+    WorkspaceParamsSelection,
+    ProfileParamsSelection,
+    FlowParamsSelection,
+>
+    crate::ctx::CmdCtxBuilder<
+        'ctx,
+        O,
+        SingleProfileMultiFlowBuilder<
+            E,
+            ProfileSelection,
+            FlowSelection,
+            PKeys,
+            WorkspaceParamsSelection,
+            ProfileParamsSelection,
+            FlowParamsSelection,
+        >,
+    >
+where
+    PKeys: peace_rt_model::params::ParamsKeys + 'static,
+{
+    /// Locks the profile for the lifetime of the built command context.
+    ///
+    /// The lock is acquired before the profile's params and each flow's
+    /// states are read, and is shared by the `CmdCtx` of each flow returned
+    /// by [`CmdCtx::flow_cmd_ctx`]. See
+    /// [`CmdCtxBuilder::with_lock`][with_lock_spsf] for how the lock is
+    /// acquired.
+    ///
+    /// [`CmdCtx::flow_cmd_ctx`]: crate::ctx::CmdCtx::flow_cmd_ctx
+    /// [with_lock_spsf]: crate::ctx::CmdCtxBuilder#method.with_lock
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_lock(mut self, profile_lock_mode: peace_rt_model::ProfileLockMode) -> Self {
+        self.profile_lock_mode = Some(profile_lock_mode);
+        self
+    }
+
+    /// Sets the limits that each flow's parameter values are checked against.
+    ///
+    /// The limits are checked when the `CmdCtx` for a flow is returned by
+    /// [`CmdCtx::flow_cmd_ctx`]. See
+    /// [`CmdCtxBuilder::with_params_limits`][with_params_limits_spsf] for how
+    /// the limits are checked.
+    ///
+    /// [`CmdCtx::flow_cmd_ctx`]: crate::ctx::CmdCtx::flow_cmd_ctx
+    /// [with_params_limits_spsf]: crate::ctx::CmdCtxBuilder#method.with_params_limits
+    pub fn with_params_limits(
+        mut self,
+        params_limits: peace_rt_model::params::ParamsLimits,
+    ) -> Self {
+        self.params_limits = params_limits;
+        self
+    }
+}
@@ -3,3 +3,73 @@
 #[peace_code_gen::cmd_ctx_builder_impl]
 #[derive(Debug)]
 pub struct SingleProfileSingleFlowBuilder;
+
+impl<
+    'ctx,
+    E,
+    O,
+    ProfileSelection,
+    FlowSelection,
+    PKeys,
+    WorkspaceParamsSelection,
+    ProfileParamsSelection,
+    FlowParamsSelection,
+>
+    crate::ctx::CmdCtxBuilder<
+        'ctx,
+        O,
+        SingleProfileSingleFlowBuilder<
+            E,
+            ProfileSelection,
+            FlowSelection,
+            PKeys,
+            WorkspaceParamsSelection,
+            ProfileParamsSelection,
+            FlowParamsSelection,
+        >,
+    >
+where
+    PKeys: peace_rt_model::params::ParamsKeys + 'static,
+{
+    /// Locks the profile for the lifetime of the built command context.
+    ///
+    /// This should be used for commands that write states, such as
+    /// `EnsureCmd` and `CleanCmd`, so that concurrent commands for the same
+    /// profile do not overwrite each other's states. The lock is acquired
+    /// before the profile's params and states are read, and is released when
+    /// the `CmdCtx` is dropped.
+    ///
+    /// A lock held by a process on this host that is no longer running is
+    /// treated as stale, and is replaced.
+    ///
+    /// When the `CmdCtx` is built, [`Error::ProfileLocked`] is returned if
+    /// another command holds the lock, and does not release it within the
+    /// time allowed by `profile_lock_mode`.
+    ///
+    /// [`Error::ProfileLocked`]: peace_rt_model::Error::ProfileLocked
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_lock(mut self, profile_lock_mode: peace_rt_model::ProfileLockMode) -> Self {
+        self.profile_lock_mode = Some(profile_lock_mode);
+        self
+    }
+
+    /// Sets the limits that parameter values are checked against.
+    ///
+    /// The limits are checked against the workspace, profile, flow, and item
+    /// spec params when the `CmdCtx` is built, after the item specs are set
+    /// up. They are checked again before each item is applied, after its
+    /// params are resolved, so values mapped from a predecessor's state are
+    /// also checked.
+    ///
+    /// The limits are inserted into `Resources`, so they may be [overridden]
+    /// after the `CmdCtx` is built.
+    ///
+    /// [overridden]: peace_rt_model::params::ParamsLimits::set_overridden
+    pub fn with_params_limits(
+        mut self,
+        params_limits: peace_rt_model::params::ParamsLimits,
+    ) -> Self {
+        self.params_limits = params_limits;
+        self
+    }
+}
@@ -2,6 +2,7 @@ pub use self::{
     multi_profile_no_flow::{MultiProfileNoFlow, MultiProfileNoFlowView},
     multi_profile_single_flow::MultiProfileSingleFlow,
     no_profile_no_flow::NoProfileNoFlow,
+    single_profile_multi_flow::SingleProfileMultiFlow,
     single_profile_no_flow::{SingleProfileNoFlow, SingleProfileNoFlowView},
     single_profile_single_flow::{SingleProfileSingleFlow, SingleProfileSingleFlowView},
 };
@@ -11,5 +12,6 @@ pub mod type_params;
 mod multi_profile_no_flow;
 mod multi_profile_single_flow;
 mod no_profile_no_flow;
+mod single_profile_multi_flow;
 mod single_profile_no_flow;
 mod single_profile_single_flow;
@@ -1,16 +1,16 @@
-use std::{collections::BTreeMap, fmt::Debug, hash::Hash};
+use std::{collections::BTreeMap, fmt::Debug, hash::Hash, rc::Rc};
 
-use peace_core::Profile;
+use peace_core::{InterruptSignal, Profile};
 use peace_resources::{
     paths::{FlowDir, PeaceAppDir, PeaceDir, ProfileDir, ProfileHistoryDir, WorkspaceDir},
     states::StatesSaved,
 };
 use peace_rt_model::{
     params::{
-        FlowParams, KeyKnown, KeyMaybe, ParamsKeys, ParamsKeysImpl, ParamsTypeRegs, ProfileParams,
-        WorkspaceParams,
+        FlowParams, KeyKnown, KeyMaybe, ParamsKeys, ParamsKeysImpl, ParamsLimits, ParamsTypeRegs,
+        ProfileParams, WorkspaceParams,
     },
-    Flow, StatesTypeRegs, Workspace,
+    Flow, StatesTypeRegs, Storage, Workspace,
 };
 use serde::{de::DeserializeOwned, Serialize};
 
@@ -96,14 +96,14 @@ where
     /// [`WorkspaceParams`]: peace_rt_model::params::WorkspaceParams
     /// [`ProfileParams`]: peace_rt_model::params::ProfileParams
     /// [`FlowParams`]: peace_rt_model::params::FlowParams
-    params_type_regs: ParamsTypeRegs<PKeys>,
+    pub(crate) params_type_regs: Rc<ParamsTypeRegs<PKeys>>,
     /// Workspace params.
-    workspace_params: WorkspaceParams<<PKeys::WorkspaceParamsKMaybe as KeyMaybe>::Key>,
+    pub(crate) workspace_params: WorkspaceParams<<PKeys::WorkspaceParamsKMaybe as KeyMaybe>::Key>,
     /// Profile params for the profile.
-    profile_to_profile_params:
+    pub(crate) profile_to_profile_params:
         BTreeMap<Profile, ProfileParams<<PKeys::ProfileParamsKMaybe as KeyMaybe>::Key>>,
     /// Flow params for the selected flow.
-    profile_to_flow_params:
+    pub(crate) profile_to_flow_params:
         BTreeMap<Profile, FlowParams<<PKeys::FlowParamsKMaybe as KeyMaybe>::Key>>,
     /// Type registries to deserialize [`StatesSavedFile`] and
     /// [`StatesDesiredFile`].
@@ -113,6 +113,20 @@ where
     states_type_regs: StatesTypeRegs,
     /// Saved states for each profile for the selected flow.
     profile_to_states_saved: BTreeMap<Profile, Option<StatesSaved>>,
+    /// Storage with the storage format recorded in the workspace.
+    pub(crate) storage: Storage,
+    /// Maximum number of profiles to run commands for concurrently, if any.
+    profile_concurrency_limit: Option<usize>,
+    /// Limits that each profile's parameter values are checked against.
+    pub(crate) params_limits: ParamsLimits,
+    /// Whether and how to lock each profile when its `CmdCtx` is created.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) profile_lock_mode: Option<peace_rt_model::ProfileLockMode>,
+    /// Signal to interrupt the commands run for each profile.
+    interrupt_signal: InterruptSignal,
+    /// Renders the progress of each profile's command as a group.
+    #[cfg(feature = "output_progress")]
+    pub(crate) multi_progress: indicatif::MultiProgress,
 }
 
 impl<'ctx, E, O, PKeys> MultiProfileSingleFlow<'ctx, E, O, PKeys>
@@ -141,6 +155,13 @@ where
         >,
         states_type_regs: StatesTypeRegs,
         profile_to_states_saved: BTreeMap<Profile, Option<StatesSaved>>,
+        storage: Storage,
+        params_limits: ParamsLimits,
+        #[cfg(not(target_arch = "wasm32"))]
+        profile_lock_mode: Option<peace_rt_model::ProfileLockMode>,
+        interrupt_signal: InterruptSignal,
+        #[cfg(feature = "output_progress")]
+        multi_progress: indicatif::MultiProgress,
     ) -> Self {
         Self {
             output,
@@ -150,12 +171,20 @@ where
             profile_history_dirs,
             flow,
             flow_dirs,
-            params_type_regs,
+            params_type_regs: Rc::new(params_type_regs),
             workspace_params,
             profile_to_profile_params,
             profile_to_flow_params,
             states_type_regs,
             profile_to_states_saved,
+            storage,
+            profile_concurrency_limit: None,
+            params_limits,
+            #[cfg(not(target_arch = "wasm32"))]
+            profile_lock_mode,
+            interrupt_signal,
+            #[cfg(feature = "output_progress")]
+            multi_progress,
         }
     }
 
@@ -240,6 +269,26 @@ where
     pub fn profile_to_states_saved(&self) -> &BTreeMap<Profile, Option<StatesSaved>> {
         &self.profile_to_states_saved
     }
+
+    /// Returns the maximum number of profiles to run commands for
+    /// concurrently, if any.
+    pub fn profile_concurrency_limit(&self) -> Option<usize> {
+        self.profile_concurrency_limit
+    }
+
+    /// Returns a mutable reference to the maximum number of profiles to run
+    /// commands for concurrently.
+    pub fn profile_concurrency_limit_mut(&mut self) -> &mut Option<usize> {
+        &mut self.profile_concurrency_limit
+    }
+
+    /// Returns the signal to interrupt the commands run for each profile.
+    ///
+    /// This is shared with each `CmdCtx` returned by `profile_cmd_ctx`, so
+    /// interrupting it stops the commands for all profiles.
+    pub fn interrupt_signal(&self) -> &InterruptSignal {
+        &self.interrupt_signal
+    }
 }
 
 impl<'ctx, E, O, WorkspaceParamsK, ProfileParamsKMaybe, FlowParamsKMaybe>
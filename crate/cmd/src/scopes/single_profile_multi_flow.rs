@@ -0,0 +1,301 @@
+use std::{collections::BTreeMap, fmt::Debug, hash::Hash, rc::Rc};
+
+use peace_core::{FlowId, InterruptSignal, Profile};
+use peace_resources::{
+    paths::{FlowDir, PeaceAppDir, PeaceDir, ProfileDir, ProfileHistoryDir, WorkspaceDir},
+    states::FlowsStatesSaved,
+};
+use peace_rt_model::{
+    params::{
+        FlowParams, KeyKnown, KeyMaybe, ParamsKeys, ParamsKeysImpl, ParamsLimits, ParamsTypeRegs,
+        ProfileParams, WorkspaceParams,
+    },
+    Flow, Storage, Workspace,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// A command that works with one profile, and multiple flows.
+///
+/// ```bash
+/// path/to/repo/.peace/envman
+/// |- 📝 workspace_params.yaml    # ✅ can read or write `WorkspaceParams`
+/// |
+/// |- 🌏 internal_dev_a
+/// |   |- 📝 profile_params.yaml  # ✅ can read or write `ProfileParams`
+/// |   |
+/// |   |- 🌊 network                  # ✅ can read `FlowId`
+/// |   |   |- 📝 flow_params.yaml     # ✅ can read `FlowParams`
+/// |   |   |- 📋 states_desired.yaml  # ❌ cannot read or write `StatesDesired`
+/// |   |   |- 📋 states_saved.yaml    # ✅ can read `StatesSaved`
+/// |   |
+/// |   |- 🌊 app                      # ✅ can read `FlowId`
+/// |   |   |- 📝 flow_params.yaml     # ✅ can read `FlowParams`
+/// |   |   |- 📋 states_desired.yaml  # ❌ cannot read or write `StatesDesired`
+/// |   |   |- 📋 states_saved.yaml    # ✅ can read `StatesSaved`
+/// |   |
+/// |   |- 🌊 ..                       # ❌ cannot read or write unselected `Flow`s
+/// |
+/// |- 🌏 ..                       # ❌ cannot read or write other `Profile` information
+/// ```
+///
+/// ## Capabilities
+///
+/// This kind of command can:
+///
+/// * Read or write workspace parameters.
+/// * Read or write a single profile's parameters.
+/// * Read flow parameters for each of the selected flows.
+/// * Read flow state for each of the selected flows.
+/// * Create a `SingleProfileSingleFlow` command context for each flow, whose
+///   item specs may read the other flows' saved states through
+///   [`FlowsStatesSaved`].
+///
+/// This kind of command cannot:
+///
+/// * Write flow parameters -- see `SingleProfileSingleFlow`.
+/// * Read or write other profiles' information -- see
+///   `MultiProfileSingleFlow`.
+#[derive(Debug)]
+pub struct SingleProfileMultiFlow<'ctx, E, O, PKeys>
+where
+    PKeys: ParamsKeys + 'static,
+{
+    /// Output endpoint to return values / errors, and write progress
+    /// information to.
+    ///
+    /// See [`OutputWrite`].
+    ///
+    /// [`OutputWrite`]: peace_rt_model_core::OutputWrite
+    output: &'ctx mut O,
+    /// Workspace that the `peace` tool runs in.
+    workspace: &'ctx Workspace,
+    /// The profile this command operates on.
+    profile: Profile,
+    /// Profile directory that stores params and flows.
+    profile_dir: ProfileDir,
+    /// Directory to store profile execution history.
+    profile_history_dir: ProfileHistoryDir,
+    /// The chosen process flows.
+    flows: Vec<&'ctx Flow<E>>,
+    /// Flow directories that store params and states, keyed by flow ID.
+    flow_dirs: BTreeMap<FlowId, FlowDir>,
+    /// Type registries for [`WorkspaceParams`], [`ProfileParams`], and
+    /// [`FlowParams`] deserialization.
+    ///
+    /// [`WorkspaceParams`]: peace_rt_model::params::WorkspaceParams
+    /// [`ProfileParams`]: peace_rt_model::params::ProfileParams
+    /// [`FlowParams`]: peace_rt_model::params::FlowParams
+    pub(crate) params_type_regs: Rc<ParamsTypeRegs<PKeys>>,
+    /// Workspace params.
+    pub(crate) workspace_params: WorkspaceParams<<PKeys::WorkspaceParamsKMaybe as KeyMaybe>::Key>,
+    /// Profile params for the profile.
+    pub(crate) profile_params: ProfileParams<<PKeys::ProfileParamsKMaybe as KeyMaybe>::Key>,
+    /// Flow params for each flow, keyed by flow ID.
+    pub(crate) flow_id_to_flow_params:
+        BTreeMap<FlowId, FlowParams<<PKeys::FlowParamsKMaybe as KeyMaybe>::Key>>,
+    /// Saved states of each flow whose states have been saved.
+    flows_states_saved: FlowsStatesSaved,
+    /// Storage with the storage format recorded in the workspace.
+    pub(crate) storage: Storage,
+    /// Limits that parameter values are checked against.
+    pub(crate) params_limits: ParamsLimits,
+    /// Lock on the profile, held for the lifetime of this scope.
+    #[cfg(not(target_arch = "wasm32"))]
+    profile_lock: Option<peace_rt_model::ProfileLock>,
+    /// Signal shared with each flow's `CmdCtx` to interrupt execution.
+    interrupt_signal: InterruptSignal,
+    /// Progress output shared with each flow's `CmdCtx`.
+    #[cfg(feature = "output_progress")]
+    pub(crate) multi_progress: indicatif::MultiProgress,
+}
+
+impl<'ctx, E, O, PKeys> SingleProfileMultiFlow<'ctx, E, O, PKeys>
+where
+    PKeys: ParamsKeys + 'static,
+{
+    /// Returns a new `SingleProfileMultiFlow` scope.
+    #[allow(clippy::too_many_arguments)] // Constructed by proc macro
+    pub(crate) fn new(
+        output: &'ctx mut O,
+        workspace: &'ctx Workspace,
+        profile: Profile,
+        profile_dir: ProfileDir,
+        profile_history_dir: ProfileHistoryDir,
+        flows: Vec<&'ctx Flow<E>>,
+        flow_dirs: BTreeMap<FlowId, FlowDir>,
+        params_type_regs: ParamsTypeRegs<PKeys>,
+        workspace_params: WorkspaceParams<<PKeys::WorkspaceParamsKMaybe as KeyMaybe>::Key>,
+        profile_params: ProfileParams<<PKeys::ProfileParamsKMaybe as KeyMaybe>::Key>,
+        flow_id_to_flow_params: BTreeMap<
+            FlowId,
+            FlowParams<<PKeys::FlowParamsKMaybe as KeyMaybe>::Key>,
+        >,
+        flows_states_saved: FlowsStatesSaved,
+        storage: Storage,
+        params_limits: ParamsLimits,
+        #[cfg(not(target_arch = "wasm32"))] profile_lock: Option<peace_rt_model::ProfileLock>,
+        interrupt_signal: InterruptSignal,
+        #[cfg(feature = "output_progress")]
+        multi_progress: indicatif::MultiProgress,
+    ) -> Self {
+        Self {
+            output,
+            workspace,
+            profile,
+            profile_dir,
+            profile_history_dir,
+            flows,
+            flow_dirs,
+            params_type_regs: Rc::new(params_type_regs),
+            workspace_params,
+            profile_params,
+            flow_id_to_flow_params,
+            flows_states_saved,
+            storage,
+            params_limits,
+            #[cfg(not(target_arch = "wasm32"))]
+            profile_lock,
+            interrupt_signal,
+            #[cfg(feature = "output_progress")]
+            multi_progress,
+        }
+    }
+
+    /// Returns a reference to the output.
+    pub fn output(&self) -> &O {
+        self.output
+    }
+
+    /// Returns a mutable reference to the output.
+    pub fn output_mut(&mut self) -> &mut O {
+        self.output
+    }
+
+    /// Returns the workspace that the `peace` tool runs in.
+    pub fn workspace(&self) -> &Workspace {
+        self.workspace
+    }
+
+    /// Returns a reference to the workspace directory.
+    pub fn workspace_dir(&self) -> &WorkspaceDir {
+        self.workspace.dirs().workspace_dir()
+    }
+
+    /// Returns a reference to the `.peace` directory.
+    pub fn peace_dir(&self) -> &PeaceDir {
+        self.workspace.dirs().peace_dir()
+    }
+
+    /// Returns a reference to the `.peace/$app` directory.
+    pub fn peace_app_dir(&self) -> &PeaceAppDir {
+        self.workspace.dirs().peace_app_dir()
+    }
+
+    /// Returns a reference to the profile.
+    pub fn profile(&self) -> &Profile {
+        &self.profile
+    }
+
+    /// Returns a reference to the profile directory.
+    pub fn profile_dir(&self) -> &ProfileDir {
+        &self.profile_dir
+    }
+
+    /// Returns a reference to the profile history directory.
+    pub fn profile_history_dir(&self) -> &ProfileHistoryDir {
+        &self.profile_history_dir
+    }
+
+    /// Returns the flows, in the order they were selected.
+    pub fn flows(&self) -> &[&'ctx Flow<E>] {
+        &self.flows
+    }
+
+    /// Returns the flow directories keyed by each flow ID.
+    pub fn flow_dirs(&self) -> &BTreeMap<FlowId, FlowDir> {
+        &self.flow_dirs
+    }
+
+    /// Returns the type registries for [`WorkspaceParams`], [`ProfileParams`],
+    /// and [`FlowParams`] deserialization.
+    ///
+    /// [`WorkspaceParams`]: peace_rt_model::params::WorkspaceParams
+    /// [`ProfileParams`]: peace_rt_model::params::ProfileParams
+    /// [`FlowParams`]: peace_rt_model::params::FlowParams
+    pub fn params_type_regs(&self) -> &ParamsTypeRegs<PKeys> {
+        &self.params_type_regs
+    }
+
+    /// Returns the saved states of each flow whose states have been saved.
+    pub fn flows_states_saved(&self) -> &FlowsStatesSaved {
+        &self.flows_states_saved
+    }
+
+    /// Returns the lock on the profile, if this command was built with
+    /// `with_lock`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn profile_lock(&self) -> Option<&peace_rt_model::ProfileLock> {
+        self.profile_lock.as_ref()
+    }
+
+    /// Returns the interrupt signal shared with each flow's `CmdCtx`.
+    pub fn interrupt_signal(&self) -> &InterruptSignal {
+        &self.interrupt_signal
+    }
+}
+
+impl<'ctx, E, O, WorkspaceParamsK, ProfileParamsKMaybe, FlowParamsKMaybe>
+    SingleProfileMultiFlow<
+        'ctx,
+        E,
+        O,
+        ParamsKeysImpl<KeyKnown<WorkspaceParamsK>, ProfileParamsKMaybe, FlowParamsKMaybe>,
+    >
+where
+    WorkspaceParamsK:
+        Clone + Debug + Eq + Hash + DeserializeOwned + Serialize + Send + Sync + 'static,
+    ProfileParamsKMaybe: KeyMaybe,
+    FlowParamsKMaybe: KeyMaybe,
+{
+    /// Returns the workspace params.
+    pub fn workspace_params(&self) -> &WorkspaceParams<WorkspaceParamsK> {
+        &self.workspace_params
+    }
+}
+
+impl<'ctx, E, O, WorkspaceParamsKMaybe, ProfileParamsK, FlowParamsKMaybe>
+    SingleProfileMultiFlow<
+        'ctx,
+        E,
+        O,
+        ParamsKeysImpl<WorkspaceParamsKMaybe, KeyKnown<ProfileParamsK>, FlowParamsKMaybe>,
+    >
+where
+    WorkspaceParamsKMaybe: KeyMaybe,
+    ProfileParamsK:
+        Clone + Debug + Eq + Hash + DeserializeOwned + Serialize + Send + Sync + 'static,
+    FlowParamsKMaybe: KeyMaybe,
+{
+    /// Returns the profile params.
+    pub fn profile_params(&self) -> &ProfileParams<ProfileParamsK> {
+        &self.profile_params
+    }
+}
+
+impl<'ctx, E, O, WorkspaceParamsKMaybe, ProfileParamsKMaybe, FlowParamsK>
+    SingleProfileMultiFlow<
+        'ctx,
+        E,
+        O,
+        ParamsKeysImpl<WorkspaceParamsKMaybe, ProfileParamsKMaybe, KeyKnown<FlowParamsK>>,
+    >
+where
+    WorkspaceParamsKMaybe: KeyMaybe,
+    ProfileParamsKMaybe: KeyMaybe,
+    FlowParamsK: Clone + Debug + Eq + Hash + DeserializeOwned + Serialize + Send + Sync + 'static,
+{
+    /// Returns the flow params for each flow.
+    pub fn flow_id_to_flow_params(&self) -> &BTreeMap<FlowId, FlowParams<FlowParamsK>> {
+        &self.flow_id_to_flow_params
+    }
+}
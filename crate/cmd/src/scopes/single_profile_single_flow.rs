@@ -1,6 +1,6 @@
-use std::{fmt::Debug, hash::Hash};
+use std::{collections::HashSet, fmt::Debug, hash::Hash, rc::Rc, time::Duration};
 
-use peace_core::Profile;
+use peace_core::{ItemSpecId, Profile};
 use peace_resources::{
     paths::{FlowDir, PeaceAppDir, PeaceDir, ProfileDir, ProfileHistoryDir, WorkspaceDir},
     resources::ts::SetUp,
@@ -80,7 +80,7 @@ where
     /// [`WorkspaceParams`]: peace_rt_model::params::WorkspaceParams
     /// [`ProfileParams`]: peace_rt_model::params::ProfileParams
     /// [`FlowParams`]: peace_rt_model::params::FlowParams
-    params_type_regs: ParamsTypeRegs<PKeys>,
+    params_type_regs: Rc<ParamsTypeRegs<PKeys>>,
     /// Workspace params.
     workspace_params: WorkspaceParams<<PKeys::WorkspaceParamsKMaybe as KeyMaybe>::Key>,
     /// Profile params for the profile.
@@ -93,6 +93,15 @@ where
     /// [`StatesSavedFile`]: peace_resources::paths::StatesSavedFile
     /// [`StatesDesiredFile`]: peace_resources::paths::StatesDesiredFile
     states_type_regs: StatesTypeRegs,
+    /// Maximum duration for applying the flow, if any.
+    timeout: Option<Duration>,
+    /// Maximum number of item specs to execute concurrently, if any.
+    concurrency_limit: Option<usize>,
+    /// IDs of the item specs to run, if only a subset of the flow is run.
+    item_spec_ids_selected: Option<HashSet<ItemSpecId>>,
+    /// Lock on the profile, held while this command runs.
+    #[cfg(not(target_arch = "wasm32"))]
+    profile_lock: Option<peace_rt_model::ProfileLock>,
     /// `Resources` for flow execution.
     resources: Resources<TS>,
 }
@@ -175,6 +184,12 @@ where
     /// [`StatesSavedFile`]: peace_resources::paths::StatesSavedFile
     /// [`StatesDesiredFile`]: peace_resources::paths::StatesDesiredFile
     pub states_type_regs: &'view StatesTypeRegs,
+    /// Maximum duration for applying the flow, if any.
+    pub timeout: Option<Duration>,
+    /// Maximum number of item specs to execute concurrently, if any.
+    pub concurrency_limit: Option<usize>,
+    /// IDs of the item specs to run, if only a subset of the flow is run.
+    pub item_spec_ids_selected: Option<&'view HashSet<ItemSpecId>>,
     /// `Resources` for flow execution.
     pub resources: &'view mut Resources<TS>,
 }
@@ -184,6 +199,10 @@ where
     PKeys: ParamsKeys + 'static,
 {
     /// Returns a new `SingleProfileSingleFlow` scope.
+    ///
+    /// `params_type_regs` may be shared, so that a `SingleProfileSingleFlow`
+    /// scope can be created for each profile of a `MultiProfileSingleFlow`
+    /// scope, or each flow of a `SingleProfileMultiFlow` scope.
     #[allow(clippy::too_many_arguments)] // Constructed by proc macro
     pub(crate) fn new(
         output: &'ctx mut O,
@@ -195,11 +214,13 @@ where
         profile_history_dir: ProfileHistoryDir,
         flow: &'ctx Flow<E>,
         flow_dir: FlowDir,
-        params_type_regs: ParamsTypeRegs<PKeys>,
+        params_type_regs: impl Into<Rc<ParamsTypeRegs<PKeys>>>,
         workspace_params: WorkspaceParams<<PKeys::WorkspaceParamsKMaybe as KeyMaybe>::Key>,
         profile_params: ProfileParams<<PKeys::ProfileParamsKMaybe as KeyMaybe>::Key>,
         flow_params: FlowParams<<PKeys::FlowParamsKMaybe as KeyMaybe>::Key>,
         states_type_regs: StatesTypeRegs,
+        #[cfg(not(target_arch = "wasm32"))]
+        profile_lock: Option<peace_rt_model::ProfileLock>,
         resources: Resources<SetUp>,
     ) -> Self {
         Self {
@@ -212,11 +233,16 @@ where
             profile_history_dir,
             flow,
             flow_dir,
-            params_type_regs,
+            params_type_regs: params_type_regs.into(),
             workspace_params,
             profile_params,
             flow_params,
             states_type_regs,
+            timeout: None,
+            concurrency_limit: None,
+            item_spec_ids_selected: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            profile_lock,
             resources,
         }
     }
@@ -245,6 +271,11 @@ where
             profile_params,
             flow_params,
             states_type_regs,
+            timeout,
+            concurrency_limit,
+            item_spec_ids_selected,
+            #[cfg(not(target_arch = "wasm32"))]
+            profile_lock: _,
             resources,
         } = self;
 
@@ -263,6 +294,9 @@ where
             profile_params,
             flow_params,
             states_type_regs,
+            timeout: *timeout,
+            concurrency_limit: *concurrency_limit,
+            item_spec_ids_selected: item_spec_ids_selected.as_ref(),
             resources,
         }
     }
@@ -354,6 +388,52 @@ where
         &self.states_type_regs
     }
 
+    /// Returns the maximum duration for applying the flow, if any.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Returns a mutable reference to the maximum duration for applying the
+    /// flow.
+    pub fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
+
+    /// Returns the maximum number of item specs to execute concurrently, if
+    /// any.
+    pub fn concurrency_limit(&self) -> Option<usize> {
+        self.concurrency_limit
+    }
+
+    /// Returns a mutable reference to the maximum number of item specs to
+    /// execute concurrently.
+    pub fn concurrency_limit_mut(&mut self) -> &mut Option<usize> {
+        &mut self.concurrency_limit
+    }
+
+    /// Returns the IDs of the item specs to run, if only a subset of the flow
+    /// is run.
+    pub fn item_spec_ids_selected(&self) -> Option<&HashSet<ItemSpecId>> {
+        self.item_spec_ids_selected.as_ref()
+    }
+
+    /// Returns a mutable reference to the IDs of the item specs to run.
+    pub fn item_spec_ids_selected_mut(&mut self) -> &mut Option<HashSet<ItemSpecId>> {
+        &mut self.item_spec_ids_selected
+    }
+
+    /// Returns the lock on the profile, if it is held by this command.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn profile_lock(&self) -> Option<&peace_rt_model::ProfileLock> {
+        self.profile_lock.as_ref()
+    }
+
+    /// Returns a mutable reference to the lock on the profile.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn profile_lock_mut(&mut self) -> &mut Option<peace_rt_model::ProfileLock> {
+        &mut self.profile_lock
+    }
+
     /// Returns a reference to the `Resources` for flow execution.
     pub fn resources(&self) -> &Resources<TS> {
         &self.resources
@@ -388,6 +468,11 @@ where
             profile_params,
             flow_params,
             states_type_regs,
+            timeout,
+            concurrency_limit,
+            item_spec_ids_selected,
+            #[cfg(not(target_arch = "wasm32"))]
+            profile_lock,
             resources,
         } = self;
 
@@ -408,6 +493,11 @@ where
             profile_params,
             flow_params,
             states_type_regs,
+            timeout,
+            concurrency_limit,
+            item_spec_ids_selected,
+            #[cfg(not(target_arch = "wasm32"))]
+            profile_lock,
             resources,
         }
     }
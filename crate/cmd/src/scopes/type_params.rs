@@ -2,7 +2,7 @@
 
 pub use self::{
     flow_params_selection::{FlowParamsNone, FlowParamsSome},
-    flow_selection::{FlowNotSelected, FlowSelected},
+    flow_selection::{FlowNotSelected, FlowSelected, FlowsSelected},
     profile_params_selection::{ProfileParamsNone, ProfileParamsSome, ProfileParamsSomeMulti},
     profile_selection::{
         ProfileFilterFn, ProfileFromWorkspaceParam, ProfileNotSelected, ProfileSelected,
@@ -7,3 +7,7 @@ pub struct FlowNotSelected;
 /// A `Flow` is selected.
 #[derive(Debug)]
 pub struct FlowSelected<'ctx, E>(pub(crate) &'ctx Flow<E>);
+
+/// Multiple `Flow`s are selected.
+#[derive(Debug)]
+pub struct FlowsSelected<'ctx, E>(pub(crate) Vec<&'ctx Flow<E>>);
@@ -5,4 +5,6 @@ pub enum FlowCount {
     None,
     /// One flow is accessed.
     One,
+    /// Multiple flows are accessed.
+    Multiple,
 }
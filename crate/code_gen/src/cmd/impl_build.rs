@@ -129,8 +129,10 @@ fn impl_build_for(
                 type_params.push(profile_selection.type_param());
             }
         }
-        if scope.flow_count() == FlowCount::One {
-            type_params.push(flow_selection.type_param());
+        match scope.flow_count() {
+            FlowCount::None => {}
+            FlowCount::One => type_params.push(flow_selection.type_param()),
+            FlowCount::Multiple => type_params.push(flow_selection.type_param_multi()),
         }
 
         type_params.push(parse_quote!(PKeys));
@@ -156,7 +158,22 @@ fn impl_build_for(
 
     let workspace_dirs_and_storage_borrow = quote! {
         let workspace_dirs = self.workspace.dirs();
-        let storage = self.workspace.storage();
+
+        // Items are read and written in the storage format recorded in the workspace.
+        let storage_format_file =
+            peace_resources::internal::StorageFormatFile::from(workspace_dirs.peace_app_dir());
+        let storage_format_recorded =
+            peace_rt_model::WorkspaceInitializer::storage_format_deserialize(
+                self.workspace.storage(),
+                &storage_format_file,
+            )
+            .await?;
+        let storage = &match storage_format_recorded {
+            Some(storage_format) => self.workspace.storage().clone().with_format(storage_format),
+            None => self.workspace.storage().clone(),
+        };
+        let storage_extension =
+            peace_rt_model::storage::SerializedStorage::format(storage).extension();
     };
     let (workspace_params_deserialize, workspace_params_serialize, workspace_params_insert) =
         workspace_params_load_save(workspace_params_selection);
@@ -171,9 +188,10 @@ fn impl_build_for(
     let profile_s_ref = profile_s_ref(scope, profile_selection);
     let cmd_dirs = cmd_dirs(scope);
     let dirs_to_create = dirs_to_create(scope);
+    let flow_dirs_to_create = flow_dirs_to_create(scope);
     let scope_fields = scope_fields(scope);
     let states_saved_read_and_pg_init = states_saved_read_and_pg_init(scope);
-    let resources_insert = resources_insert(scope);
+    let profile_lock_acquire = profile_lock_acquire(scope);
 
     let scope_builder_deconstruct = scope_builder_deconstruct(
         scope_struct,
@@ -235,10 +253,23 @@ fn impl_build_for(
 
                 // Values shared by subsequent function calls.
                 // let workspace_dirs = self.workspace.dirs();
-                // let storage = self.workspace.storage();
+                // let storage_format_file = StorageFormatFile::from(workspace_dirs.peace_app_dir());
+                // let storage_format_recorded = WorkspaceInitializer::storage_format_deserialize(
+                //     self.workspace.storage(),
+                //     &storage_format_file,
+                // )
+                // .await?;
+                // let storage = &match storage_format_recorded {
+                //     Some(storage_format) => {
+                //         self.workspace.storage().clone().with_format(storage_format)
+                //     }
+                //     None => self.workspace.storage().clone(),
+                // };
+                // let storage_extension = storage.format().extension();
                 #workspace_dirs_and_storage_borrow
 
-                // let workspace_params_file = WorkspaceParamsFile::from(workspace_dirs.peace_app_dir());
+                // let workspace_params_file =
+                //     WorkspaceParamsFile::from((workspace_dirs.peace_app_dir(), storage_extension));
                 // self.workspace_params_merge(&workspace_params_file).await?;
                 #workspace_params_deserialize
 
@@ -330,9 +361,15 @@ fn impl_build_for(
                     #dirs_to_create
                 ];
 
+                // === Multi Flow === //
+                // let dirs_to_create = dirs_to_create
+                //     .into_iter()
+                //     .chain(flow_dirs.values().map(AsRef::<std::path::Path>::as_ref));
+                #flow_dirs_to_create
+
                 // === Profile Params === //
                 // --- Single --- //
-                // let profile_params_file = ProfileParamsFile::from(&profile_dir);
+                // let profile_params_file = ProfileParamsFile::from((&profile_dir, storage_extension));
                 // self.profile_params_merge(&profile_params_file).await?;
                 // --- Multi --- //
                 // let profile_to_profile_params = futures::stream::iter(
@@ -342,7 +379,10 @@ fn impl_build_for(
                 //     )
                 //     .and_then(|(profile, profile_dir)| async move {
                 //         let profile_params_file =
-                //             peace_resources::internal::ProfileParamsFile::from(profile_dir);
+                //             peace_resources::internal::ProfileParamsFile::from((
+                //                 profile_dir,
+                //                 storage_extension,
+                //             ));
                 //
                 //         let profile_params = self
                 //             .#params_deserialize_method_name(&profile_params_file)
@@ -361,7 +401,7 @@ fn impl_build_for(
 
                 // === Flow Params === //
                 // --- Single --- //
-                // let flow_params_file = ProfileParamsFile::from(&flow_dir);
+                // let flow_params_file = FlowParamsFile::from((&flow_dir, storage_extension));
                 // self.flow_params_merge(&flow_params_file).await?;
                 // --- Multi --- //
                 // let profile_to_flow_params = futures::stream::iter(
@@ -371,7 +411,10 @@ fn impl_build_for(
                 //     )
                 //     .and_then(|(profile, flow_dir)| async move {
                 //         let flow_params_file =
-                //             peace_resources::internal::FlowParamsFile::from(flow_dir);
+                //             peace_resources::internal::FlowParamsFile::from((
+                //                 flow_dir,
+                //                 storage_extension,
+                //             ));
                 //
                 //         let flow_params = self
                 //             .#params_deserialize_method_name(&flow_params_file)
@@ -389,12 +432,16 @@ fn impl_build_for(
                 #flow_params_deserialize
 
                 // Create directories and write init parameters to storage.
-                #[cfg(target_arch = "wasm32")]
                 peace_rt_model::WorkspaceInitializer::dirs_create(storage, dirs_to_create).await?;
+                if storage_format_recorded.is_none() {
+                    peace_rt_model::WorkspaceInitializer::storage_format_serialize(
+                        storage,
+                        &storage_format_file,
+                    )
+                    .await?;
+                }
                 #[cfg(not(target_arch = "wasm32"))]
-                {
-                    peace_rt_model::WorkspaceInitializer::dirs_create(dirs_to_create).await?;
-
+                if peace_rt_model::storage::SerializedStorage::backend(storage).is_file_system() {
                     let workspace_dir = workspace_dirs.workspace_dir();
                     std::env::set_current_dir(workspace_dir).map_err(|error| {
                         peace_rt_model::Error::Native(peace_rt_model::NativeError::CurrentDirSet {
@@ -407,6 +454,7 @@ fn impl_build_for(
                 // let crate::ctx::CmdCtxBuilder {
                 //     output,
                 //     workspace,
+                //     params_limits,
                 //     scope_builder:
                 //         #scope_builder_name {
                 //             profile_selection: ProfileSelected(profile)
@@ -423,6 +471,26 @@ fn impl_build_for(
                 // } = self;
                 #scope_builder_deconstruct
 
+                // === SingleProfileSingleFlow, SingleProfileMultiFlow === //
+                // // Lock the profile before its params and states are read or written.
+                // #[cfg(not(target_arch = "wasm32"))]
+                // let profile_lock = match profile_lock_mode {
+                //     Some(profile_lock_mode) => {
+                //         let profile_lock_file =
+                //             peace_resources::paths::ProfileLockFile::from(&profile_dir);
+                //         let profile_lock = peace_rt_model::ProfileLock::acquire(
+                //             storage,
+                //             &profile,
+                //             profile_lock_file,
+                //             profile_lock_mode,
+                //         )
+                //         .await?;
+                //         Some(profile_lock)
+                //     }
+                //     None => None,
+                // };
+                #profile_lock_acquire
+
                 // Serialize params to `PeaceAppDir`.
 
                 // crate::ctx::cmd_ctx_builder::workspace_params_serialize(
@@ -466,25 +534,6 @@ fn impl_build_for(
                 // resources.insert(flow_params_file);
                 #flow_params_insert
 
-                // Insert resources
-                //
-                // === SingleProfileSingleFlow === //
-                // {
-                //     let (app_name, workspace_dirs, storage) = workspace.clone().into_inner();
-                //     let (workspace_dir, peace_dir, peace_app_dir) = workspace_dirs.into_inner();
-                //
-                //     resources.insert(app_name);
-                //     resources.insert(storage);
-                //     resources.insert(workspace_dir);
-                //     resources.insert(peace_dir);
-                //     resources.insert(peace_app_dir);
-                //     resources.insert(profile_dir.clone());
-                //     resources.insert(profile_history_dir.clone());
-                //     resources.insert(profile.clone());
-                //     resources.insert(flow_dir.clone());
-                //     resources.insert(flow.flow_id().clone());
-                // }
-                #resources_insert
 
                 // === MultiProfileSingleFlow === //
                 // let states_type_regs = crate::ctx::cmd_ctx_builder::states_type_regs(flow.graph());
@@ -496,7 +545,8 @@ fn impl_build_for(
                 //         .map(Result::<_, peace_rt_model::Error>::Ok)
                 //     )
                 //     .and_then(|(profile, flow_dir)| async move {
-                //         let states_saved_file = peace_resources::paths::StatesSavedFile::from(flow_dir);
+                //         let states_saved_file =
+                //             peace_resources::paths::StatesSavedFile::from((flow_dir, storage_extension));
                 //
                 //         let states_saved = peace_rt_model::StatesSerializer::<peace_rt_model::Error>::deserialize_saved_opt(
                 //             flow_id,
@@ -517,47 +567,41 @@ fn impl_build_for(
                 //     >()
                 //     .await?;
                 //
+                // === MultiProfileSingleFlow === //
+                // let interrupt_signal = peace_core::InterruptSignal::new();
+                // #[cfg(feature = "output_progress")]
+                // let multi_progress = indicatif::MultiProgress::with_draw_target(
+                //     indicatif::ProgressDrawTarget::hidden()
+                // );
+                //
                 // === SingleProfileSingleFlow === //
-                // // Set up resources for the flow's item spec graph
-                // let states_saved_file = peace_resources::paths::StatesSavedFile::from(&flow_dir);
+                // // Insert workspace, profile, and flow values, check params limits, and
+                // // set up resources for the flow's item spec graph.
                 // let states_type_regs = crate::ctx::cmd_ctx_builder::states_type_regs(flow.graph());
-                // let states_saved = peace_rt_model::StatesSerializer::<peace_rt_model::Error>::deserialize_saved_opt(
-                //     flow.flow_id(),
-                //     storage,
-                //     states_type_regs.states_current_type_reg(),
-                //     &states_saved_file,
-                // )
-                // .await?
-                // .map(Into::<peace_resources::states::StatesSaved>::into);
-                // if let Some(states_saved) = states_saved {
-                //     resources.insert(states_saved);
-                // }
-                //
-                // // Call each `ItemSpec`'s initialization function.
-                // let resources = crate::ctx::cmd_ctx_builder::item_spec_graph_setup(
-                //     flow.graph(),
-                //     resources
-                // )
-                // .await?;
+                // let resources =
+                //     crate::ctx::cmd_ctx_builder::single_profile_single_flow_resources_setup(
+                //         workspace,
+                //         storage,
+                //         &profile,
+                //         &profile_dir,
+                //         &profile_history_dir,
+                //         flow,
+                //         &flow_dir,
+                //         &states_type_regs,
+                //         params_limits,
+                //         peace_core::InterruptSignal::new(),
+                //         resources,
+                //     )
+                //     .await?;
                 //
                 // // output_progress CmdProgressTracker initialization
                 // #[cfg(feature = "output_progress")]
-                // let cmd_progress_tracker = {
-                //     let multi_progress = indicatif::MultiProgress::with_draw_target(
+                // let cmd_progress_tracker = crate::ctx::cmd_ctx_builder::cmd_progress_tracker(
+                //     flow.graph(),
+                //     indicatif::MultiProgress::with_draw_target(
                 //         indicatif::ProgressDrawTarget::hidden()
-                //     );
-                //     let progress_trackers = item_spec_graph.iter_insertion().fold(
-                //         peace_rt_model::IndexMap::with_capacity(item_spec_graph.node_count()),
-                //         |mut progress_trackers, item_spec| {
-                //             let progress_bar = multi_progress.add(indicatif::ProgressBar::hidden());
-                //             let progress_tracker = indicatif::style::ProgressTracker::new(progress_bar);
-                //             progress_trackers.insert(item_spec.id().clone(), progress_tracker);
-                //             progress_trackers
-                //         },
-                //     );
-                //
-                //     peace_rt_model::CmdProgressTracker::new(multi_progress, progress_trackers)
-                // };
+                //     ),
+                // );
                 #states_saved_read_and_pg_init
 
                 let params_type_regs = params_type_regs_builder.build();
@@ -705,12 +749,14 @@ fn scope_builder_deconstruct(
         },
     }
 
-    if scope.flow_count() == FlowCount::One {
-        match flow_selection {
-            FlowSelection::Selected => scope_builder_fields.push(parse_quote! {
-                flow_selection: crate::scopes::type_params::FlowSelected(flow)
-            }),
-        }
+    match (scope.flow_count(), flow_selection) {
+        (FlowCount::None, _) => {}
+        (FlowCount::One, FlowSelection::Selected) => scope_builder_fields.push(parse_quote! {
+            flow_selection: crate::scopes::type_params::FlowSelected(flow)
+        }),
+        (FlowCount::Multiple, FlowSelection::Selected) => scope_builder_fields.push(parse_quote! {
+            flow_selection: crate::scopes::type_params::FlowsSelected(flows)
+        }),
     }
 
     scope_builder_fields.push(parse_quote!(params_type_regs_builder));
@@ -726,10 +772,28 @@ fn scope_builder_deconstruct(
         marker: std::marker::PhantomData
     });
 
+    // Params limits are only settable for commands with flows, as they are checked
+    // against a flow's `Resources`, and profile locks are only settable for commands
+    // that write a flow's states.
+    let (params_limits_deconstruct, profile_lock_mode_deconstruct): (FieldValue, FieldValue) =
+        match scope {
+            Scope::SingleProfileSingleFlow
+            | Scope::MultiProfileSingleFlow
+            | Scope::SingleProfileMultiFlow => {
+                (parse_quote!(params_limits), parse_quote!(profile_lock_mode))
+            }
+            Scope::MultiProfileNoFlow | Scope::NoProfileNoFlow | Scope::SingleProfileNoFlow => (
+                parse_quote!(params_limits: _),
+                parse_quote!(profile_lock_mode: _),
+            ),
+        };
+
     quote! {
         let crate::ctx::CmdCtxBuilder {
             output,
             workspace,
+            #params_limits_deconstruct,
+            #profile_lock_mode_deconstruct,
             scope_builder: #scope_builder_name {
                 // profile_selection: ProfileSelected(profile),
                 // flow_selection: FlowSelected(flow),
@@ -771,9 +835,10 @@ fn workspace_params_load_save(
         }
         WorkspaceParamsSelection::Some => {
             let workspace_params_deserialize = quote! {
-                let workspace_params_file = peace_resources::internal::WorkspaceParamsFile::from(
-                    workspace_dirs.peace_app_dir()
-                );
+                let workspace_params_file = peace_resources::internal::WorkspaceParamsFile::from((
+                    workspace_dirs.peace_app_dir(),
+                    storage_extension,
+                ));
 
                 self.workspace_params_merge(&workspace_params_file).await?;
             };
@@ -833,9 +898,10 @@ fn profile_params_load_save(
             }
             ProfileParamsSelection::Some => {
                 let profile_params_deserialize = quote! {
-                    let profile_params_file = peace_resources::internal::ProfileParamsFile::from(
-                        &profile_dir
-                    );
+                    let profile_params_file = peace_resources::internal::ProfileParamsFile::from((
+                        &profile_dir,
+                        storage_extension,
+                    ));
 
                     self.profile_params_merge(&profile_params_file).await?;
                 };
@@ -877,7 +943,6 @@ fn profile_params_load_save(
                         ParamsScope::Profile.params_deserialize_method_name();
 
                     quote! {
-                        let storage = self.workspace.storage();
                         let params_type_regs_builder = &self.scope_builder.params_type_regs_builder;
                         let profile_to_profile_params = futures::stream::iter(
                             profile_dirs
@@ -886,7 +951,10 @@ fn profile_params_load_save(
                             )
                             .and_then(|(profile, profile_dir)| async move {
                                 let profile_params_file =
-                                    peace_resources::internal::ProfileParamsFile::from(profile_dir);
+                                    peace_resources::internal::ProfileParamsFile::from((
+                                        profile_dir,
+                                        storage_extension,
+                                    ));
 
                                 let profile_params = Self::#params_deserialize_method_name(
                                     storage,
@@ -945,6 +1013,73 @@ fn flow_params_load_save(
             proc_macro2::TokenStream::new(),
             proc_macro2::TokenStream::new(),
         ),
+        ProfileCount::One if scope.flow_count() == FlowCount::Multiple => {
+            let flow_params_deserialize = match flow_params_selection {
+                FlowParamsSelection::None => quote! {
+                    let flow_id_to_flow_params = std::collections::BTreeMap::<
+                        peace_core::FlowId,
+                        peace_rt_model::params::FlowParams<
+                            <
+                                PKeys::FlowParamsKMaybe as
+                                peace_rt_model::params::KeyMaybe
+                            >::Key
+                        >
+                    >::new();
+                },
+                FlowParamsSelection::Some => {
+                    let params_deserialize_method_name =
+                        ParamsScope::Flow.params_deserialize_method_name();
+                    quote! {
+                        let params_type_regs_builder = &self.scope_builder.params_type_regs_builder;
+                        let flow_id_to_flow_params = futures::stream::iter(
+                            flow_dirs
+                                .iter()
+                                .map(Result::<_, peace_rt_model::Error>::Ok)
+                            )
+                            .and_then(|(flow_id, flow_dir)| async move {
+                                let flow_params_file =
+                                    peace_resources::internal::FlowParamsFile::from((
+                                        flow_dir,
+                                        storage_extension,
+                                    ));
+
+                                let flow_params = Self::#params_deserialize_method_name(
+                                    storage,
+                                    params_type_regs_builder,
+                                    &flow_params_file
+                                )
+                                .await?
+                                .unwrap_or_default();
+
+                                Ok((flow_id.clone(), flow_params))
+                            })
+                            .try_collect::<
+                                std::collections::BTreeMap<
+                                    peace_core::FlowId,
+                                    peace_rt_model::params::FlowParams<
+                                        <
+                                            PKeys::FlowParamsKMaybe as
+                                            peace_rt_model::params::KeyMaybe
+                                        >::Key
+                                    >
+                                >
+                            >()
+                            .await?;
+                    }
+                }
+            };
+            // Storage is not supported.
+            let flow_params_serialize = proc_macro2::TokenStream::new();
+
+            // Insertion into resources is not supported.
+            let flow_params_insert = proc_macro2::TokenStream::new();
+
+            (
+                flow_params_deserialize,
+                flow_params_serialize,
+                flow_params_insert,
+            )
+        }
         ProfileCount::One => match flow_params_selection {
             FlowParamsSelection::None => {
                 let flow_params_deserialize = quote! {
@@ -963,9 +1098,10 @@ fn flow_params_load_save(
             }
             FlowParamsSelection::Some => {
                 let flow_params_deserialize = quote! {
-                    let flow_params_file = peace_resources::internal::FlowParamsFile::from(
-                        &flow_dir
-                    );
+                    let flow_params_file = peace_resources::internal::FlowParamsFile::from((
+                        &flow_dir,
+                        storage_extension,
+                    ));
 
                     self.flow_params_merge(&flow_params_file).await?;
                 };
@@ -1006,7 +1142,6 @@ fn flow_params_load_save(
                     let params_deserialize_method_name =
                         ParamsScope::Flow.params_deserialize_method_name();
                     quote! {
-                        let storage = self.workspace.storage();
                         let params_type_regs_builder = &self.scope_builder.params_type_regs_builder;
                         let profile_to_flow_params = futures::stream::iter(
                             flow_dirs
@@ -1015,7 +1150,10 @@ fn flow_params_load_save(
                             )
                             .and_then(|(profile, flow_dir)| async move {
                                 let flow_params_file =
-                                    peace_resources::internal::FlowParamsFile::from(flow_dir);
+                                    peace_resources::internal::FlowParamsFile::from((
+                                        flow_dir,
+                                        storage_extension,
+                                    ));
 
                                 let flow_params = Self::#params_deserialize_method_name(
                                     storage,
@@ -1211,6 +1349,37 @@ fn cmd_dirs(scope: Scope) -> proc_macro2::TokenStream {
         }
     }
 
+    if scope.flow_count() == FlowCount::Multiple {
+        match scope.profile_count() {
+            ProfileCount::None | ProfileCount::Multiple => {
+                unreachable!("Multiple flows are only supported for a single profile.")
+            }
+            ProfileCount::One => {
+                dirs_tokens.extend(quote! {
+                    let flow_dirs = self
+                        .scope_builder
+                        .flow_selection
+                        .0
+                        .iter()
+                        .fold(std::collections::BTreeMap::<
+                                peace_core::FlowId,
+                                peace_resources::paths::FlowDir
+                            >::new(
+                        ), |mut flow_dirs, flow| {
+                            let flow_dir = peace_resources::paths::FlowDir::from((
+                                &profile_dir,
+                                flow.flow_id()
+                            ));
+
+                            flow_dirs.insert(flow.flow_id().clone(), flow_dir);
+
+                            flow_dirs
+                        });
+                });
+            }
+        }
+    }
+
     dirs_tokens
 }
 
@@ -1251,6 +1420,19 @@ fn dirs_to_create(scope: Scope) -> proc_macro2::TokenStream {
     dirs_tokens
 }
 
+/// Chains each flow directory to the directories to create, when there are
+/// multiple flows.
+fn flow_dirs_to_create(scope: Scope) -> proc_macro2::TokenStream {
+    match scope.flow_count() {
+        FlowCount::None | FlowCount::One => proc_macro2::TokenStream::new(),
+        FlowCount::Multiple => quote! {
+            let dirs_to_create = dirs_to_create
+                .into_iter()
+                .chain(flow_dirs.values().map(AsRef::<std::path::Path>::as_ref));
+        },
+    }
+}
+
 fn scope_fields(scope: Scope) -> Punctuated<FieldValue, Comma> {
     let mut scope_fields = Punctuated::<FieldValue, Token![,]>::new();
 
@@ -1262,7 +1444,8 @@ fn scope_fields(scope: Scope) -> Punctuated<FieldValue, Comma> {
         Scope::MultiProfileNoFlow
         | Scope::NoProfileNoFlow
         | Scope::SingleProfileNoFlow
-        | Scope::MultiProfileSingleFlow => {}
+        | Scope::MultiProfileSingleFlow
+        | Scope::SingleProfileMultiFlow => {}
         Scope::SingleProfileSingleFlow => {
             scope_fields.push(parse_quote! {
                 #[cfg(feature = "output_progress")]
@@ -1297,6 +1480,10 @@ fn scope_fields(scope: Scope) -> Punctuated<FieldValue, Comma> {
                 scope_fields.push(parse_quote!(flow_dirs));
             }
         },
+        FlowCount::Multiple => {
+            scope_fields.push(parse_quote!(flows));
+            scope_fields.push(parse_quote!(flow_dirs));
+        }
     }
 
     scope_fields.push(parse_quote!(params_type_regs));
@@ -1325,6 +1512,9 @@ fn scope_fields(scope: Scope) -> Punctuated<FieldValue, Comma> {
                 scope_fields.push(parse_quote!(profile_to_flow_params));
             }
         },
+        FlowCount::Multiple => {
+            scope_fields.push(parse_quote!(flow_id_to_flow_params));
+        }
     }
 
     match scope {
@@ -1332,9 +1522,38 @@ fn scope_fields(scope: Scope) -> Punctuated<FieldValue, Comma> {
         Scope::MultiProfileSingleFlow => {
             scope_fields.push(parse_quote!(states_type_regs));
             scope_fields.push(parse_quote!(profile_to_states_saved));
+            scope_fields.push(parse_quote!(storage));
+            scope_fields.push(parse_quote!(params_limits));
+            scope_fields.push(parse_quote! {
+                #[cfg(not(target_arch = "wasm32"))]
+                profile_lock_mode
+            });
+            scope_fields.push(parse_quote!(interrupt_signal));
+            scope_fields.push(parse_quote! {
+                #[cfg(feature = "output_progress")]
+                multi_progress
+            });
+        }
+        Scope::SingleProfileMultiFlow => {
+            scope_fields.push(parse_quote!(flows_states_saved));
+            scope_fields.push(parse_quote!(storage));
+            scope_fields.push(parse_quote!(params_limits));
+            scope_fields.push(parse_quote! {
+                #[cfg(not(target_arch = "wasm32"))]
+                profile_lock
+            });
+            scope_fields.push(parse_quote!(interrupt_signal));
+            scope_fields.push(parse_quote! {
+                #[cfg(feature = "output_progress")]
+                multi_progress
+            });
         }
         Scope::SingleProfileSingleFlow => {
             scope_fields.push(parse_quote!(states_type_regs));
+            scope_fields.push(parse_quote! {
+                #[cfg(not(target_arch = "wasm32"))]
+                profile_lock
+            });
             scope_fields.push(parse_quote!(resources));
         }
     }
@@ -1361,7 +1580,10 @@ fn states_saved_read_and_pg_init(scope: Scope) -> proc_macro2::TokenStream {
                         .map(Result::<_, peace_rt_model::Error>::Ok)
                     )
                     .and_then(|(profile, flow_dir)| async move {
-                        let states_saved_file = peace_resources::paths::StatesSavedFile::from(flow_dir);
+                        let states_saved_file = peace_resources::paths::StatesSavedFile::from((
+                            flow_dir,
+                            storage_extension,
+                        ));
 
                         let states_saved = peace_rt_model::StatesSerializer::<peace_rt_model::Error>::deserialize_saved_opt(
                             flow_id,
@@ -1381,6 +1603,39 @@ fn states_saved_read_and_pg_init(scope: Scope) -> proc_macro2::TokenStream {
                         >
                     >()
                     .await?;
+
+                // Shared with each profile's `CmdCtx`, so that commands for all profiles
+                // use the same storage format, may be interrupted, and their progress
+                // rendered together.
+                let storage = storage.clone();
+                let interrupt_signal = peace_core::InterruptSignal::new();
+                #[cfg(feature = "output_progress")]
+                let multi_progress = indicatif::MultiProgress::with_draw_target(
+                    indicatif::ProgressDrawTarget::hidden()
+                );
+            }
+        }
+        Scope::SingleProfileMultiFlow => {
+            // Reads previously saved states for each flow. These are not inserted
+            // into resources, as each flow's item specs are set up separately when
+            // creating a `SingleProfileSingleFlow` command context for the flow.
+            quote! {
+                let flows_states_saved = crate::ctx::cmd_ctx_builder::flows_states_saved_read(
+                    storage,
+                    &profile_dir,
+                    &flows,
+                )
+                .await?;
+
+                // Shared with each flow's `CmdCtx`, so that commands for all flows
+                // use the same storage format, may be interrupted, and their progress
+                // rendered together.
+                let storage = storage.clone();
+                let interrupt_signal = peace_core::InterruptSignal::new();
+                #[cfg(feature = "output_progress")]
+                let multi_progress = indicatif::MultiProgress::with_draw_target(
+                    indicatif::ProgressDrawTarget::hidden()
+                );
             }
         }
         Scope::SingleProfileSingleFlow => {
@@ -1402,73 +1657,68 @@ fn states_saved_read_and_pg_init(scope: Scope) -> proc_macro2::TokenStream {
             // It also requires multiple item spec graph setups to work without conflicting
             // with each other.
             quote! {
-                let states_saved_file = peace_resources::paths::StatesSavedFile::from(&flow_dir);
                 let states_type_regs = crate::ctx::cmd_ctx_builder::states_type_regs(flow.graph());
-                let states_current_type_reg = states_type_regs.states_current_type_reg();
-                let flow_id = flow.flow_id();
-                let item_spec_graph = flow.graph();
-                let states_saved = peace_rt_model::StatesSerializer::<peace_rt_model::Error>::deserialize_saved_opt(
-                    flow_id,
-                    storage,
-                    states_current_type_reg,
-                    &states_saved_file,
-                )
-                .await?
-                .map(Into::<peace_resources::states::StatesSaved>::into);
-                if let Some(states_saved) = states_saved {
-                    resources.insert(states_saved);
-                }
-
-                // Call each `ItemSpec`'s initialization function.
-                let resources = crate::ctx::cmd_ctx_builder::item_spec_graph_setup(
-                    item_spec_graph,
-                    resources
-                )
-                .await?;
+                let resources =
+                    crate::ctx::cmd_ctx_builder::single_profile_single_flow_resources_setup(
+                        workspace,
+                        storage,
+                        &profile,
+                        &profile_dir,
+                        &profile_history_dir,
+                        flow,
+                        &flow_dir,
+                        &states_type_regs,
+                        params_limits,
+                        peace_core::InterruptSignal::new(),
+                        resources,
+                    )
+                    .await?;
 
                 // output_progress CmdProgressTracker initialization
                 #[cfg(feature = "output_progress")]
-                let cmd_progress_tracker = {
-                    let multi_progress = indicatif::MultiProgress::with_draw_target(
+                let cmd_progress_tracker = crate::ctx::cmd_ctx_builder::cmd_progress_tracker(
+                    flow.graph(),
+                    indicatif::MultiProgress::with_draw_target(
                         indicatif::ProgressDrawTarget::hidden()
-                    );
-                    let progress_trackers = item_spec_graph.iter_insertion().fold(
-                        peace_rt_model::IndexMap::with_capacity(item_spec_graph.node_count()),
-                        |mut progress_trackers, item_spec| {
-                            let progress_bar = multi_progress.add(indicatif::ProgressBar::hidden());
-                            let progress_tracker = peace_core::progress::ProgressTracker::new(progress_bar);
-                            progress_trackers.insert(item_spec.id().clone(), progress_tracker);
-                            progress_trackers
-                        },
-                    );
-
-                    peace_rt_model::CmdProgressTracker::new(multi_progress, progress_trackers)
-                };
+                    ),
+                );
             }
         }
     }
 }
 
-fn resources_insert(scope: Scope) -> proc_macro2::TokenStream {
-    if scope == Scope::SingleProfileSingleFlow {
-        quote! {
-            {
-                let (app_name, workspace_dirs, storage) = workspace.clone().into_inner();
-                let (workspace_dir, peace_dir, peace_app_dir) = workspace_dirs.into_inner();
-
-                resources.insert(app_name);
-                resources.insert(storage);
-                resources.insert(workspace_dir);
-                resources.insert(peace_dir);
-                resources.insert(peace_app_dir);
-                resources.insert(profile_dir.clone());
-                resources.insert(profile_history_dir.clone());
-                resources.insert(profile.clone());
-                resources.insert(flow_dir.clone());
-                resources.insert(flow.flow_id().clone());
-            }
+fn profile_lock_acquire(scope: Scope) -> proc_macro2::TokenStream {
+    match scope {
+        // Each profile is locked when its `CmdCtx` is created through `profile_cmd_ctx`.
+        Scope::MultiProfileSingleFlow => quote! {
+            #[cfg(target_arch = "wasm32")]
+            let _ = profile_lock_mode;
+        },
+        Scope::SingleProfileSingleFlow | Scope::SingleProfileMultiFlow => quote! {
+            // `CmdCtxBuilder::with_lock` is not available on WASM, so the mode is always `None`.
+            #[cfg(target_arch = "wasm32")]
+            let _ = profile_lock_mode;
+
+            // Lock the profile before its params and states are read or written.
+            #[cfg(not(target_arch = "wasm32"))]
+            let profile_lock = match profile_lock_mode {
+                Some(profile_lock_mode) => {
+                    let profile_lock_file =
+                        peace_resources::paths::ProfileLockFile::from(&profile_dir);
+                    let profile_lock = peace_rt_model::ProfileLock::acquire(
+                        storage,
+                        &profile,
+                        profile_lock_file,
+                        profile_lock_mode,
+                    )
+                    .await?;
+                    Some(profile_lock)
+                }
+                None => None,
+            };
+        },
+        Scope::MultiProfileNoFlow | Scope::NoProfileNoFlow | Scope::SingleProfileNoFlow => {
+            proc_macro2::TokenStream::new()
         }
-    } else {
-        proc_macro2::TokenStream::new()
     }
 }
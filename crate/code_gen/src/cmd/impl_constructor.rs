@@ -76,6 +76,8 @@ pub fn impl_constructor(scope_struct: &ScopeStruct) -> proc_macro2::TokenStream
                 Self {
                     output,
                     workspace,
+                    params_limits: peace_rt_model::params::ParamsLimits::new(),
+                    profile_lock_mode: None,
                     scope_builder,
                 }
             }
@@ -100,7 +102,7 @@ mod scope_builder_type_params {
                 type_params.push(parse_quote!(crate::scopes::type_params::ProfileNotSelected));
             }
         }
-        if scope.flow_count() == FlowCount::One {
+        if scope.flow_count() != FlowCount::None {
             type_params.push(parse_quote!(crate::scopes::type_params::FlowNotSelected));
         }
     }
@@ -157,7 +159,7 @@ mod scope_field_values {
                 ));
             }
         }
-        if scope.flow_count() == FlowCount::One {
+        if scope.flow_count() != FlowCount::None {
             field_values.push(parse_quote!(
                 flow_selection: crate::scopes::type_params::FlowNotSelected
             ));
@@ -164,6 +164,7 @@ fn impl_params_deserialize_for(
             // ) -> Result<Option<WorkspaceParams<K>, peace_rt_model::Error> {
             ) -> Result<Option<#params_module::#params_map_type<#p_keys_key_maybe_key>>, peace_rt_model::Error> {
                 let params_deserialized = peace_rt_model::WorkspaceInitializer::#params_deserialize_method_name::<
+                    _,
                     // <PKeys::WorkspaceParamsKMaybe as KeyMaybe>::Key,
                     #p_keys_key_maybe_key
                 >(
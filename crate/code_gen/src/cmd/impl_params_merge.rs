@@ -164,6 +164,7 @@ fn impl_params_merge_for(
             ) -> Result<(), peace_rt_model::Error> {
                 let storage = self.workspace.storage();
                 let params_deserialized = peace_rt_model::WorkspaceInitializer::#params_deserialize_method_name::<
+                    _,
                     // <PKeys::WorkspaceParamsKMaybe as KeyMaybe>::Key,
                     #p_keys_key_maybe_key
                 >(
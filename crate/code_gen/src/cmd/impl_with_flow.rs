@@ -6,6 +6,9 @@ use syn::{
 use crate::cmd::{type_parameters_impl, FlowCount, ProfileCount, Scope, ScopeStruct};
 
 /// Generates the `with_flow` method for the command context builder.
+///
+/// For scopes with multiple flows, the `with_flows` method is generated
+/// instead.
 pub fn impl_with_flow(scope_struct: &ScopeStruct) -> proc_macro2::TokenStream {
     let scope = scope_struct.scope();
     let scope_builder_name = &scope_struct.item_struct().ident;
@@ -53,12 +56,47 @@ pub fn impl_with_flow(scope_struct: &ScopeStruct) -> proc_macro2::TokenStream {
                 type_params.push(parse_quote!(ProfileSelection));
             }
         }
-        type_params.push(parse_quote!(
-            crate::scopes::type_params::FlowSelected<'ctx, E>
-        ));
+        match scope.flow_count() {
+            FlowCount::None => {
+                unreachable!("`with_flow` is not generated when there are no flows.")
+            }
+            FlowCount::One => type_params.push(parse_quote!(
+                crate::scopes::type_params::FlowSelected<'ctx, E>
+            )),
+            FlowCount::Multiple => type_params.push(parse_quote!(
+                crate::scopes::type_params::FlowsSelected<'ctx, E>
+            )),
+        }
         type_parameters_impl::params_selection_push(&mut type_params, scope);
         type_params
     };
+    let with_flow_fn_signature = match scope.flow_count() {
+        FlowCount::None => {
+            unreachable!("`with_flow` is not generated when there are no flows.")
+        }
+        FlowCount::One => quote! {
+            pub fn with_flow(
+                self,
+                flow: &'ctx peace_rt_model::Flow<E>,
+            )
+        },
+        FlowCount::Multiple => quote! {
+            pub fn with_flows<Flows>(
+                self,
+                flows: Flows,
+            )
+        },
+    };
+    let with_flow_fn_where_clause = match scope.flow_count() {
+        FlowCount::None => {
+            unreachable!("`with_flow` is not generated when there are no flows.")
+        }
+        FlowCount::One => proc_macro2::TokenStream::new(),
+        FlowCount::Multiple => quote! {
+            where
+                Flows: IntoIterator<Item = &'ctx peace_rt_model::Flow<E>>,
+        },
+    };
 
     let scope_builder_fields_flow_not_selected = scope_builder_fields_flow_not_selected(scope);
     let scope_builder_fields_flow_selected = scope_builder_fields_flow_selected(scope);
@@ -92,10 +130,11 @@ pub fn impl_with_flow(scope_struct: &ScopeStruct) -> proc_macro2::TokenStream {
         where
             PKeys: #params_module::ParamsKeys + 'static,
         {
-            pub fn with_flow(
-                self,
-                flow: &'ctx peace_rt_model::Flow<E>,
-            ) -> crate::ctx::CmdCtxBuilder<
+            // pub fn with_flow(
+            //     self,
+            //     flow: &'ctx peace_rt_model::Flow<E>,
+            // )
+            #with_flow_fn_signature -> crate::ctx::CmdCtxBuilder<
                 'ctx,
                 O,
                 #scope_builder_name<
@@ -108,10 +147,14 @@ pub fn impl_with_flow(scope_struct: &ScopeStruct) -> proc_macro2::TokenStream {
                     // FlowParamsSelection,
                     #scope_builder_type_params_flow_selected
                 >,
-            > {
+            >
+            #with_flow_fn_where_clause
+            {
                 let Self {
                     output,
                     workspace,
+                    params_limits,
+                    profile_lock_mode,
                     scope_builder:
                         #scope_builder_name {
                             // profile_selection,
@@ -139,6 +182,8 @@ pub fn impl_with_flow(scope_struct: &ScopeStruct) -> proc_macro2::TokenStream {
                 crate::ctx::CmdCtxBuilder {
                     output,
                     workspace,
+                    params_limits,
+                    profile_lock_mode,
                     scope_builder,
                 }
             }
@@ -168,9 +213,19 @@ fn scope_builder_fields_flow_not_selected(scope: Scope) -> Punctuated<FieldValue
 fn scope_builder_fields_flow_selected(scope: Scope) -> Punctuated<FieldValue, Comma> {
     let mut field_values = Punctuated::<FieldValue, Token![,]>::new();
     field_values.push(parse_quote!(profile_selection));
-    field_values.push(parse_quote!(
-        flow_selection: crate::scopes::type_params::FlowSelected(flow)
-    ));
+    match scope.flow_count() {
+        FlowCount::None => {
+            unreachable!("`with_flow` is not generated when there are no flows.")
+        }
+        FlowCount::One => field_values.push(parse_quote!(
+            flow_selection: crate::scopes::type_params::FlowSelected(flow)
+        )),
+        FlowCount::Multiple => field_values.push(parse_quote!(
+            flow_selection: crate::scopes::type_params::FlowsSelected(
+                flows.into_iter().collect()
+            )
+        )),
+    }
     field_values.push(parse_quote!(params_type_regs_builder));
     field_values.push(parse_quote!(workspace_params_selection));
     if scope.profile_params_supported() {
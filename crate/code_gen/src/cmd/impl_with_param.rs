@@ -23,6 +23,7 @@ pub fn impl_with_param(scope_struct: &ScopeStruct) -> proc_macro2::TokenStream {
                         Scope::NoProfileNoFlow => return impl_tokens,
 
                         Scope::SingleProfileNoFlow |
+                        Scope::SingleProfileMultiFlow |
                         Scope::SingleProfileSingleFlow => {
                             // implement method
                         }
@@ -35,6 +36,8 @@ pub fn impl_with_param(scope_struct: &ScopeStruct) -> proc_macro2::TokenStream {
                         Scope::MultiProfileSingleFlow |
                         // No flow commands do not support flow params.
                         Scope::SingleProfileNoFlow |
+                        // Multi flow commands may read, but not write flow params.
+                        Scope::SingleProfileMultiFlow |
                         Scope::NoProfileNoFlow => return impl_tokens,
 
                         Scope::SingleProfileSingleFlow => {
@@ -193,6 +196,8 @@ fn impl_with_param_key_unknown(
                 let Self {
                     output,
                     workspace,
+                    params_limits,
+                    profile_lock_mode,
                     scope_builder:
                         #scope_builder_name {
                             // profile_selection,
@@ -235,6 +240,8 @@ fn impl_with_param_key_unknown(
                 crate::ctx::CmdCtxBuilder {
                     output,
                     workspace,
+                    params_limits,
+                    profile_lock_mode,
                     scope_builder,
                 }
             }
@@ -374,6 +381,8 @@ fn impl_with_param_key_known(
                 let Self {
                     output,
                     workspace,
+                    params_limits,
+                    profile_lock_mode,
                     scope_builder:
                         #scope_builder_name {
                             // profile_selection,
@@ -412,6 +421,8 @@ fn impl_with_param_key_known(
                 crate::ctx::CmdCtxBuilder {
                     output,
                     workspace,
+                    params_limits,
+                    profile_lock_mode,
                     scope_builder,
                 }
             }
@@ -38,6 +38,7 @@ pub fn impl_with_params_k(scope_struct: &ScopeStruct) -> proc_macro2::TokenStrea
                 // Single profile params will also have `with_*_param_value` from the
                 // `impl_with_param_value` module.
                 Scope::SingleProfileNoFlow |
+                Scope::SingleProfileMultiFlow |
                 Scope::SingleProfileSingleFlow |
                 // Multi profile commands need to register the type of each profile param.
                 Scope::MultiProfileNoFlow |
@@ -179,6 +180,8 @@ fn impl_with_params_k_key_unknown(
                 let Self {
                     output,
                     workspace,
+                    params_limits,
+                    profile_lock_mode,
                     scope_builder:
                         #scope_builder_name {
                             // profile_selection,
@@ -216,6 +219,8 @@ fn impl_with_params_k_key_unknown(
                 crate::ctx::CmdCtxBuilder {
                     output,
                     workspace,
+                    params_limits,
+                    profile_lock_mode,
                     scope_builder,
                 }
             }
@@ -349,6 +354,8 @@ fn impl_with_param_key_known(
                 let Self {
                     output,
                     workspace,
+                    params_limits,
+                    profile_lock_mode,
                     scope_builder:
                         #scope_builder_name {
                             // profile_selection,
@@ -382,6 +389,8 @@ fn impl_with_param_key_known(
                 crate::ctx::CmdCtxBuilder {
                     output,
                     workspace,
+                    params_limits,
+                    profile_lock_mode,
                     scope_builder,
                 }
             }
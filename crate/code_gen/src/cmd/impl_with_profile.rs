@@ -20,7 +20,7 @@ pub fn impl_with_profile(scope_struct: &ScopeStruct) -> proc_macro2::TokenStream
 
     let scope_params = {
         let mut type_params = Punctuated::<GenericArgument, Token![,]>::new();
-        if scope.flow_count() == FlowCount::One {
+        if scope.flow_count() != FlowCount::None {
             type_params.push(parse_quote!(FlowSelection));
         }
         type_parameters_impl::params_selection_push(&mut type_params, scope);
@@ -80,6 +80,8 @@ pub fn impl_with_profile(scope_struct: &ScopeStruct) -> proc_macro2::TokenStream
                 let Self {
                     output,
                     workspace,
+                    params_limits,
+                    profile_lock_mode,
                     scope_builder:
                         #scope_builder_name {
                             // profile_selection: ProfileNotSelected,
@@ -107,6 +109,8 @@ pub fn impl_with_profile(scope_struct: &ScopeStruct) -> proc_macro2::TokenStream
                 crate::ctx::CmdCtxBuilder {
                     output,
                     workspace,
+                    params_limits,
+                    profile_lock_mode,
                     scope_builder,
                 }
             }
@@ -127,7 +131,7 @@ pub fn impl_with_profile_from_workspace_param(
 
     let impl_params_with_workspace_params_k = {
         let mut type_params = Punctuated::<GenericArgument, Token![,]>::new();
-        if scope.flow_count() == FlowCount::One {
+        if scope.flow_count() != FlowCount::None {
             type_params.push(parse_quote!(FlowSelection));
         }
         if scope.profile_params_supported() {
@@ -150,7 +154,7 @@ pub fn impl_with_profile_from_workspace_param(
 
     let scope_params_with_workspace_params_k = {
         let mut type_params = Punctuated::<GenericArgument, Token![,]>::new();
-        if scope.flow_count() == FlowCount::One {
+        if scope.flow_count() != FlowCount::None {
             type_params.push(parse_quote!(FlowSelection));
         }
 
@@ -252,6 +256,8 @@ pub fn impl_with_profile_from_workspace_param(
                 let Self {
                     output,
                     workspace,
+                    params_limits,
+                    profile_lock_mode,
                     scope_builder:
                         #scope_builder_name {
                             // profile_selection: ProfileNotSelected,
@@ -279,6 +285,8 @@ pub fn impl_with_profile_from_workspace_param(
                 crate::ctx::CmdCtxBuilder {
                     output,
                     workspace,
+                    params_limits,
+                    profile_lock_mode,
                     scope_builder,
                 }
             }
@@ -291,7 +299,7 @@ fn scope_builder_fields_profile_not_selected(scope: Scope) -> Punctuated<FieldVa
     field_values.push(parse_quote!(
         profile_selection: crate::scopes::type_params::ProfileNotSelected
     ));
-    if scope.flow_count() == FlowCount::One {
+    if scope.flow_count() != FlowCount::None {
         field_values.push(parse_quote!(flow_selection));
     }
     field_values.push(parse_quote!(params_type_regs_builder));
@@ -312,7 +320,7 @@ fn scope_builder_fields_profile_selected(scope: Scope) -> Punctuated<FieldValue,
     field_values.push(parse_quote!(
         profile_selection: crate::scopes::type_params::ProfileSelected(profile)
     ));
-    if scope.flow_count() == FlowCount::One {
+    if scope.flow_count() != FlowCount::None {
         field_values.push(parse_quote!(flow_selection));
     }
     field_values.push(parse_quote!(params_type_regs_builder));
@@ -333,7 +341,7 @@ fn scope_builder_fields_profile_from_workspace(scope: Scope) -> Punctuated<Field
     field_values.push(parse_quote!(
         profile_selection: crate::scopes::type_params::ProfileFromWorkspaceParam(workspace_param_k)
     ));
-    if scope.flow_count() == FlowCount::One {
+    if scope.flow_count() != FlowCount::None {
         field_values.push(parse_quote!(flow_selection));
     }
     field_values.push(parse_quote!(params_type_regs_builder));
@@ -18,7 +18,7 @@ pub fn impl_with_profile_filter(scope_struct: &ScopeStruct) -> proc_macro2::Toke
 
     let scope_params = {
         let mut type_params = Punctuated::<GenericArgument, Token![,]>::new();
-        if scope.flow_count() == FlowCount::One {
+        if scope.flow_count() != FlowCount::None {
             type_params.push(parse_quote!(FlowSelection));
         }
         type_parameters_impl::params_selection_push(&mut type_params, scope);
@@ -81,6 +81,8 @@ pub fn impl_with_profile_filter(scope_struct: &ScopeStruct) -> proc_macro2::Toke
                 let Self {
                     output,
                     workspace,
+                    params_limits,
+                    profile_lock_mode,
                     scope_builder:
                         #scope_builder_name {
                             // profile_selection: ProfileNotSelected,
@@ -108,6 +110,8 @@ pub fn impl_with_profile_filter(scope_struct: &ScopeStruct) -> proc_macro2::Toke
                 crate::ctx::CmdCtxBuilder {
                     output,
                     workspace,
+                    params_limits,
+                    profile_lock_mode,
                     scope_builder,
                 }
             }
@@ -120,7 +124,7 @@ fn scope_builder_fields_profile_not_selected(scope: Scope) -> Punctuated<FieldVa
     field_values.push(parse_quote!(
         profile_selection: crate::scopes::type_params::ProfileNotSelected
     ));
-    if scope.flow_count() == FlowCount::One {
+    if scope.flow_count() != FlowCount::None {
         field_values.push(parse_quote!(flow_selection));
     }
     field_values.push(parse_quote!(params_type_regs_builder));
@@ -141,7 +145,7 @@ fn scope_builder_fields_profile_filter_fn(scope: Scope) -> Punctuated<FieldValue
     field_values.push(parse_quote!(
         profile_selection: crate::scopes::type_params::ProfileFilterFn(Box::new(profile_filter_fn))
     ));
-    if scope.flow_count() == FlowCount::One {
+    if scope.flow_count() != FlowCount::None {
         field_values.push(parse_quote!(flow_selection));
     }
     field_values.push(parse_quote!(params_type_regs_builder));
@@ -12,6 +12,8 @@ pub enum Scope {
     MultiProfileSingleFlow,
     /// A command that only works with workspace parameters.
     NoProfileNoFlow,
+    /// A command that works with one profile and multiple flows.
+    SingleProfileMultiFlow,
     /// A command that works with a single profile, without any item specs.
     SingleProfileNoFlow,
     /// A command that works with one profile and one flow.
@@ -27,6 +29,9 @@ impl Scope {
                 parse_quote!(crate::scopes::MultiProfileSingleFlow)
             }
             Scope::NoProfileNoFlow => parse_quote!(crate::scopes::NoProfileNoFlow),
+            Scope::SingleProfileMultiFlow => {
+                parse_quote!(crate::scopes::SingleProfileMultiFlow)
+            }
             Scope::SingleProfileNoFlow => parse_quote!(crate::scopes::SingleProfileNoFlow),
             Scope::SingleProfileSingleFlow => {
                 parse_quote!(crate::scopes::SingleProfileSingleFlow)
@@ -40,6 +45,7 @@ impl Scope {
             Scope::MultiProfileNoFlow => "multi_profile_no_flow",
             Scope::MultiProfileSingleFlow => "multi_profile_single_flow",
             Scope::NoProfileNoFlow => "no_profile_no_flow",
+            Scope::SingleProfileMultiFlow => "single_profile_multi_flow",
             Scope::SingleProfileNoFlow => "single_profile_no_flow",
             Scope::SingleProfileSingleFlow => "single_profile_single_flow",
         }
@@ -51,6 +57,7 @@ impl Scope {
             Scope::MultiProfileNoFlow => ProfileCount::Multiple,
             Scope::MultiProfileSingleFlow => ProfileCount::Multiple,
             Scope::NoProfileNoFlow => ProfileCount::None,
+            Scope::SingleProfileMultiFlow => ProfileCount::One,
             Scope::SingleProfileNoFlow => ProfileCount::One,
             Scope::SingleProfileSingleFlow => ProfileCount::One,
         }
@@ -62,6 +69,7 @@ impl Scope {
             Scope::MultiProfileNoFlow => FlowCount::None,
             Scope::MultiProfileSingleFlow => FlowCount::One,
             Scope::NoProfileNoFlow => FlowCount::None,
+            Scope::SingleProfileMultiFlow => FlowCount::Multiple,
             Scope::SingleProfileNoFlow => FlowCount::None,
             Scope::SingleProfileSingleFlow => FlowCount::One,
         }
@@ -76,10 +84,13 @@ impl Scope {
     }
 
     /// Returns whether this scope supports accessing flow params.
+    ///
+    /// For multiple flows, flow params may be read but not written, and the
+    /// param types of every flow are registered with the same key type.
     pub fn flow_params_supported(self) -> bool {
         match self.flow_count() {
             FlowCount::None => false,
-            FlowCount::One => true,
+            FlowCount::One | FlowCount::Multiple => true,
         }
     }
 }
@@ -13,7 +13,7 @@ pub(crate) fn passthrough(
             field_values.push(parse_quote!(profile_selection));
         }
     }
-    if scope.flow_count() == FlowCount::One {
+    if scope.flow_count() != FlowCount::None {
         field_values.push(parse_quote!(flow_selection));
     }
 
@@ -65,7 +65,7 @@ pub(crate) fn params_none(
             field_values.push(parse_quote!(profile_selection));
         }
     }
-    if scope.flow_count() == FlowCount::One {
+    if scope.flow_count() != FlowCount::None {
         field_values.push(parse_quote!(flow_selection));
     }
 
@@ -120,7 +120,7 @@ pub(crate) fn params_some(scope: Scope, params_scope: ParamsScope) -> Punctuated
             field_values.push(parse_quote!(profile_selection));
         }
     }
-    if scope.flow_count() == FlowCount::One {
+    if scope.flow_count() != FlowCount::None {
         field_values.push(parse_quote!(flow_selection));
     }
 
@@ -172,7 +172,7 @@ pub(crate) fn params_some_new(
             field_values.push(parse_quote!(profile_selection));
         }
     }
-    if scope.flow_count() == FlowCount::One {
+    if scope.flow_count() != FlowCount::None {
         field_values.push(parse_quote!(flow_selection));
     }
 
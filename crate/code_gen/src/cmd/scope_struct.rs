@@ -33,6 +33,7 @@ const SCOPE_INVALID: &str = "expected struct to be one of:\n\
     * `pub struct MultiProfileNoFlowBuilder;`\n\
     * `pub struct MultiProfileSingleFlowBuilder;`\n\
     * `pub struct NoProfileNoFlowBuilder;`\n\
+    * `pub struct SingleProfileMultiFlowBuilder;`\n\
     * `pub struct SingleProfileNoFlowBuilder;`\n\
     * `pub struct SingleProfileSingleFlowBuilder;`\n\
     \n\
@@ -59,6 +60,8 @@ impl Parse for ScopeStruct {
             Scope::MultiProfileSingleFlow
         } else if struct_ident == "NoProfileNoFlowBuilder" {
             Scope::NoProfileNoFlow
+        } else if struct_ident == "SingleProfileMultiFlowBuilder" {
+            Scope::SingleProfileMultiFlow
         } else if struct_ident == "SingleProfileNoFlowBuilder" {
             Scope::SingleProfileNoFlow
         } else if struct_ident == "SingleProfileSingleFlowBuilder" {
@@ -104,7 +104,7 @@ mod fields {
                 fields_named.named.extend(fields.named);
             }
         }
-        if scope.flow_count() == FlowCount::One {
+        if scope.flow_count() != FlowCount::None {
             let fields: FieldsNamed = parse_quote!({
                 /// Identifier or name of the chosen process flow.
                 pub(crate) flow_selection: FlowSelection
@@ -16,7 +16,7 @@ pub fn profile_and_flow_selection_push(
             type_params.push(parse_quote!(ProfileSelection));
         }
     }
-    if scope.flow_count() == FlowCount::One {
+    if scope.flow_count() != FlowCount::None {
         type_params.push(parse_quote!(FlowSelection));
     }
 }
@@ -52,6 +52,12 @@ impl FlowSelection {
             Self::Selected => parse_quote!(crate::scopes::type_params::FlowSelected<'ctx, E>),
         }
     }
+
+    pub(crate) fn type_param_multi(&self) -> GenericArgument {
+        match self {
+            Self::Selected => parse_quote!(crate::scopes::type_params::FlowsSelected<'ctx, E>),
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -0,0 +1,30 @@
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+/// Identifier for a group of item specs whose execution concurrency is
+/// limited together, `Cow<'static, str>` newtype.
+///
+/// Must begin with a letter or underscore, and contain only letters, numbers,
+/// and underscores.
+///
+/// # Examples
+///
+/// The following are all examples of valid `ConcurrencyGroupId`s:
+///
+/// ```rust
+/// # use peace_core::{concurrency_group_id, ConcurrencyGroupId};
+/// #
+/// let _snake = concurrency_group_id!("snake_case");
+/// let _camel = concurrency_group_id!("camelCase");
+/// let _pascal = concurrency_group_id!("PascalCase");
+/// ```
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ConcurrencyGroupId(Cow<'static, str>);
+
+crate::id_newtype!(
+    ConcurrencyGroupId,
+    ConcurrencyGroupIdInvalidFmt,
+    concurrency_group_id,
+    code_inline
+);
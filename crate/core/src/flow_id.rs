@@ -32,7 +32,7 @@ use serde::{Deserialize, Serialize};
 /// let _camel = flow_id!("camelCase");
 /// let _pascal = flow_id!("PascalCase");
 /// ```
-#[derive(Clone, Debug, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Deserialize, Serialize, PartialOrd, Ord)]
 pub struct FlowId(Cow<'static, str>);
 
 crate::id_newtype!(FlowId, FlowIdInvalidFmt, flow_id, code_inline);
@@ -0,0 +1,62 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use tokio::sync::Notify;
+
+/// Signal to stop execution early, e.g. when the user presses `Ctrl-C`.
+///
+/// Cloning this shares the underlying flag, so a clone may be moved into a
+/// signal handler, and calling [`interrupt`] on it is visible to every other
+/// clone.
+///
+/// When interrupted, commands do not begin processing any more item specs,
+/// but item specs that are in progress are allowed to complete. Item spec
+/// implementations may poll [`is_interrupted`] to bail out of long running
+/// operations, or await [`interrupted`] alongside them.
+///
+/// [`interrupt`]: Self::interrupt
+/// [`interrupted`]: Self::interrupted
+/// [`is_interrupted`]: Self::is_interrupted
+#[derive(Clone, Debug, Default)]
+pub struct InterruptSignal(Arc<InterruptSignalInner>);
+
+#[derive(Debug, Default)]
+struct InterruptSignalInner {
+    /// Whether this signal has been interrupted.
+    interrupted: AtomicBool,
+    /// Wakes tasks waiting in [`InterruptSignal::interrupted`].
+    notify: Notify,
+}
+
+impl InterruptSignal {
+    /// Returns a new `InterruptSignal` that has not been interrupted.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks this signal as interrupted.
+    pub fn interrupt(&self) {
+        self.0.interrupted.store(true, Ordering::SeqCst);
+        self.0.notify.notify_waiters();
+    }
+
+    /// Returns whether this signal has been interrupted.
+    pub fn is_interrupted(&self) -> bool {
+        self.0.interrupted.load(Ordering::SeqCst)
+    }
+
+    /// Waits until this signal is interrupted.
+    ///
+    /// This returns immediately if the signal has already been interrupted.
+    pub async fn interrupted(&self) {
+        // `Notified` receives `notify_waiters` calls made after it is created, so
+        // creating it before checking the flag means an interrupt is not missed.
+        let notified = self.0.notify.notified();
+        if self.is_interrupted() {
+            return;
+        }
+        notified.await;
+    }
+}
@@ -15,11 +15,15 @@
 //! [peace#67]: https://github.com/azriel91/peace/issues/67
 
 // Re-exports
-pub use peace_static_check_macros::{app_name, flow_id, item_spec_id, profile};
+pub use peace_static_check_macros::{
+    app_name, concurrency_group_id, flow_id, item_spec_id, profile,
+};
 
 pub use crate::{
     app_name::{AppName, AppNameInvalidFmt},
+    concurrency_group_id::{ConcurrencyGroupId, ConcurrencyGroupIdInvalidFmt},
     flow_id::{FlowId, FlowIdInvalidFmt},
+    interrupt_signal::InterruptSignal,
     item_spec_id::{ItemSpecId, ItemSpecIdInvalidFmt},
     op_check_status::OpCheckStatus,
     profile::{Profile, ProfileInvalidFmt},
@@ -29,7 +33,9 @@ pub use crate::{
 pub mod progress;
 
 mod app_name;
+mod concurrency_group_id;
 mod flow_id;
+mod interrupt_signal;
 mod item_spec_id;
 mod op_check_status;
 mod profile;
@@ -8,5 +8,6 @@ pub use crate::data::Data;
 
 pub mod accessors;
 pub mod marker;
+pub mod params;
 
 mod data;
@@ -1,9 +1,22 @@
 //! Markers for `State`s inserted into `Resources`.
 //!
-//! For `SingleProfileSingleFlow` commands, `Current<ItemSpec::State>(None)` and
-//! `Desired<ItemSpec::State>(None)` are inserted into `Resources` when the
-//! command context is built, and automatically mutated to `Some` when either
-//! `StateCurrentFnSpec` or `StateDesiredFnSpec` is executed.
+//! `Current<ItemSpec::State>(None)`, `Desired<ItemSpec::State>(None)`,
+//! `ApplyDry<ItemSpec::State>(None)`, and `Clean<ItemSpec::State>(None)` are
+//! inserted into `Resources` when each item spec is set up, and are set to
+//! `Some` by the runtime as soon as the item spec's state is produced:
+//!
+//! * `Current`: When the current state is discovered or applied. When
+//!   cleaning an item that does not exist, this is set to its clean state.
+//! * `Desired`: When the desired state is discovered, or the saved desired
+//!   state is used to ensure the item.
+//! * `ApplyDry`: When the item is dry applied. If the item does not need to
+//!   be applied, this is set to its current state.
+//! * `Clean`: When the clean state is computed for cleaning the item.
+//!
+//! As a successor's functions are only run after its predecessors' have
+//! completed, a successor may reference a predecessor's state in its `Data`,
+//! such as `R<'op, Current<S3BucketState>>`, and it will be `Some` if the
+//! command has produced that state for the predecessor.
 
 pub use self::{apply_dry::ApplyDry, clean::Clean, current::Current, desired::Desired};
 
@@ -7,7 +7,8 @@ use serde::{Deserialize, Serialize};
 ///
 /// An `ApplyDry<ItemSpec::State>` is set to `Some` whenever an item spec is dry
 /// applied, enabling a subsequent successor's params to access that value when
-/// the successor's `apply_dry` function is run.
+/// the successor's `apply_dry` function is run. If the item spec does not need
+/// to be applied, this is set to its current state.
 ///
 /// Note: A successor's dry-applied state is dependent on the predecessor's
 /// dry-applied state, which should be in sync with its saved state after
@@ -6,8 +6,9 @@ use serde::{Deserialize, Serialize};
 /// dependent on the state of a predecessor's state.
 ///
 /// A `Current<ItemSpec::State>` is set to `Some` whenever an item spec's
-/// current state is discovered. enabling a subsequent successor's params to
-/// access that value when the successor's **desired** state function is run.
+/// current state is discovered or applied, enabling a subsequent successor's
+/// params to access that value when the successor's **desired** state function
+/// is run.
 ///
 /// Note: A successor's desired state is dependent on the predecessor's desired
 /// state, which should be in sync with its current state after
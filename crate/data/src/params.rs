@@ -0,0 +1,35 @@
+//! Types to specify item spec parameters, whose values may be referenced from
+//! predecessors' states.
+//!
+//! * `ValueSpec<T>`: Specifies a field value to be stored, provided, or mapped
+//!   from a predecessor's `State`.
+//! * `ParamsSpec`: Specifies how to resolve each field of an item spec's
+//!   params.
+//! * `Params`: Links an item spec's params type with its `ParamsSpec` type.
+//!
+//! `#[derive(Params)]` on an item spec's params type generates a
+//! `{Params}Spec` type whose fields are `ValueSpec`s. The runtime resolves the
+//! spec before each of the item spec's functions are run, and writes the
+//! params to the `Option<Params>` in `Resources`, which item specs read through
+//! `ROpt` or `WOpt`.
+
+pub use peace_data_derive::Params;
+
+pub use self::{
+    mapping_fn::MappingFn, params_resolve_error::ParamsResolveError, params_spec::ParamsSpec,
+    value_resolution_mode::ValueResolutionMode, value_spec::ValueSpec,
+};
+
+mod mapping_fn;
+mod params_resolve_error;
+mod params_spec;
+mod value_resolution_mode;
+mod value_spec;
+
+/// Links an item spec's params type with its [`ParamsSpec`] type.
+///
+/// This is implemented by `#[derive(Params)]`.
+pub trait Params {
+    /// The specification of how to resolve each field of these params.
+    type Spec: ParamsSpec<Params = Self>;
+}
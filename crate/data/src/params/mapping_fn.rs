@@ -0,0 +1,84 @@
+use std::{fmt, ops::Deref, sync::Arc};
+
+use fn_graph::{resman::Resource, Resources};
+
+use crate::{
+    marker::{ApplyDry, Current, Desired},
+    params::ValueResolutionMode,
+};
+
+/// Function that maps a predecessor's `State` to a field value.
+///
+/// This is constructed through [`ValueSpec::from_map`].
+///
+/// [`ValueSpec::from_map`]: crate::params::ValueSpec::from_map
+#[allow(clippy::type_complexity)]
+pub struct MappingFn<T> {
+    /// Type name of the predecessor's `State`.
+    from_type_name: &'static str,
+    /// Looks up the predecessor's `State` and maps it to the field value.
+    fn_map: Arc<dyn Fn(&Resources, ValueResolutionMode) -> Option<T> + Send + Sync>,
+}
+
+impl<T> MappingFn<T> {
+    /// Returns a new `MappingFn` that maps a `U` state to the field value.
+    pub fn new<U, F>(f: F) -> Self
+    where
+        U: fmt::Debug + Send + Sync + 'static,
+        F: Fn(&U) -> Option<T> + Send + Sync + 'static,
+    {
+        let fn_map = move |resources: &Resources, value_resolution_mode| match value_resolution_mode
+        {
+            ValueResolutionMode::Current => state_map::<Current<U>, _, _>(resources, &f),
+            ValueResolutionMode::Desired => state_map::<Desired<U>, _, _>(resources, &f)
+                .or_else(|| state_map::<Current<U>, _, _>(resources, &f)),
+            ValueResolutionMode::ApplyDry => state_map::<ApplyDry<U>, _, _>(resources, &f)
+                .or_else(|| state_map::<Current<U>, _, _>(resources, &f)),
+        };
+
+        Self {
+            from_type_name: std::any::type_name::<U>(),
+            fn_map: Arc::new(fn_map),
+        }
+    }
+
+    /// Returns the type name of the predecessor's `State`.
+    pub fn from_type_name(&self) -> &'static str {
+        self.from_type_name
+    }
+
+    /// Maps the predecessor's `State` to the field value, if the state exists.
+    pub fn map(
+        &self,
+        resources: &Resources,
+        value_resolution_mode: ValueResolutionMode,
+    ) -> Option<T> {
+        (self.fn_map)(resources, value_resolution_mode)
+    }
+}
+
+/// Maps the state within the `Marker` resource, if it is present.
+fn state_map<Marker, U, T>(resources: &Resources, f: &dyn Fn(&U) -> Option<T>) -> Option<T>
+where
+    Marker: Deref<Target = Option<U>> + Resource,
+{
+    let marker = resources.try_borrow::<Marker>().ok()?;
+    marker.as_ref().and_then(f)
+}
+
+impl<T> Clone for MappingFn<T> {
+    fn clone(&self) -> Self {
+        Self {
+            from_type_name: self.from_type_name,
+            fn_map: Arc::clone(&self.fn_map),
+        }
+    }
+}
+
+impl<T> fmt::Debug for MappingFn<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("MappingFn")
+            .field(&self.from_type_name)
+            .finish()
+    }
+}
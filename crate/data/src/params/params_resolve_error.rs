@@ -0,0 +1,50 @@
+use std::fmt;
+
+use crate::params::ValueResolutionMode;
+
+/// Failed to resolve a field of an item spec's params.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParamsResolveError {
+    /// Field is [`ValueSpec::Stored`], but the params were not in `Resources`.
+    ///
+    /// [`ValueSpec::Stored`]: crate::params::ValueSpec::Stored
+    Stored {
+        /// Name of the field.
+        field_name: &'static str,
+    },
+    /// Field is [`ValueSpec::FromMap`], but the predecessor's state was not
+    /// available, or the mapping function returned `None`.
+    ///
+    /// [`ValueSpec::FromMap`]: crate::params::ValueSpec::FromMap
+    FromMap {
+        /// Name of the field.
+        field_name: &'static str,
+        /// Type name of the predecessor's `State`.
+        from_type_name: &'static str,
+        /// Which of the predecessor's states the value was resolved from.
+        value_resolution_mode: ValueResolutionMode,
+    },
+}
+
+impl fmt::Display for ParamsResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stored { field_name } => {
+                write!(
+                    f,
+                    "`{field_name}` is stored, but the params were not found."
+                )
+            }
+            Self::FromMap {
+                field_name,
+                from_type_name,
+                value_resolution_mode,
+            } => write!(
+                f,
+                "`{field_name}` could not be mapped from the {value_resolution_mode} `{from_type_name}`."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParamsResolveError {}
@@ -0,0 +1,25 @@
+use fn_graph::Resources;
+
+use crate::params::{ParamsResolveError, ValueResolutionMode};
+
+/// Specifies how to resolve each field of an item spec's params.
+///
+/// This is implemented by `#[derive(Params)]` on the `{Params}Spec` type.
+pub trait ParamsSpec {
+    /// The params type that this spec resolves to.
+    type Params;
+
+    /// Resolves the params from values in `Resources`.
+    ///
+    /// # Parameters
+    ///
+    /// * `resources`: Resources holding the stored params and the states of
+    ///   predecessors.
+    /// * `value_resolution_mode`: Which of the predecessors' states to map
+    ///   field values from.
+    fn resolve(
+        &self,
+        resources: &Resources,
+        value_resolution_mode: ValueResolutionMode,
+    ) -> Result<Self::Params, ParamsResolveError>;
+}
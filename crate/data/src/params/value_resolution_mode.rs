@@ -0,0 +1,45 @@
+use std::fmt;
+
+/// Which of a predecessor's `State`s a [`ValueSpec::FromMap`] is resolved from.
+///
+/// For modes with a fallback, if the predecessor's `State` for the mode has
+/// not been discovered, or the mapping function returns `None` for it, the
+/// fallback `State` is used.
+///
+/// [`ValueSpec::FromMap`]: crate::params::ValueSpec::FromMap
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValueResolutionMode {
+    /// Resolves from [`Current<State>`], without a fallback.
+    ///
+    /// Used when discovering current states, applying, and cleaning. Values
+    /// are not resolved from the desired state, as the predecessor may not
+    /// exist in that state yet.
+    ///
+    /// [`Current<State>`]: crate::marker::Current
+    Current,
+    /// Resolves from [`Desired<State>`], falling back to [`Current<State>`].
+    ///
+    /// Used when discovering desired states.
+    ///
+    /// [`Current<State>`]: crate::marker::Current
+    /// [`Desired<State>`]: crate::marker::Desired
+    Desired,
+    /// Resolves from [`ApplyDry<State>`], falling back to [`Current<State>`].
+    ///
+    /// Used when dry applying, where the predecessor's `ApplyDry<State>` is
+    /// only set if it needs to be applied.
+    ///
+    /// [`ApplyDry<State>`]: crate::marker::ApplyDry
+    /// [`Current<State>`]: crate::marker::Current
+    ApplyDry,
+}
+
+impl fmt::Display for ValueResolutionMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Current => write!(f, "current"),
+            Self::Desired => write!(f, "desired"),
+            Self::ApplyDry => write!(f, "apply dry"),
+        }
+    }
+}
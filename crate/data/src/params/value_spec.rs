@@ -0,0 +1,95 @@
+use std::fmt;
+
+use fn_graph::Resources;
+
+use crate::params::{MappingFn, ParamsResolveError, ValueResolutionMode};
+
+/// Specifies how to resolve the value of an item spec params field.
+///
+/// # Examples
+///
+/// ```rust
+/// # use peace_data::params::ValueSpec;
+/// #
+/// # #[derive(Debug)]
+/// # pub enum S3BucketState {
+/// #     None,
+/// #     Some { name: String },
+/// # }
+/// #
+/// let object_key = ValueSpec::from(String::from("web_app.tar"));
+/// let bucket_name = ValueSpec::<String>::from_map(|s3_bucket_state: &S3BucketState| {
+///     match s3_bucket_state {
+///         S3BucketState::None => None,
+///         S3BucketState::Some { name } => Some(name.clone()),
+///     }
+/// });
+/// # let _ = (object_key, bucket_name);
+/// ```
+#[derive(Clone, Debug)]
+pub enum ValueSpec<T> {
+    /// Uses the field value of the params already in `Resources`, such as
+    /// params inserted as a flow param.
+    Stored,
+    /// Uses the provided value.
+    Value(T),
+    /// Maps the value from a predecessor's `State`.
+    FromMap(MappingFn<T>),
+}
+
+impl<T> ValueSpec<T> {
+    /// Returns a `ValueSpec` that maps the value from a predecessor's `U`
+    /// state.
+    ///
+    /// The mapping function is run by the runtime after the predecessor's
+    /// state is discovered or applied. If it returns `None`, the fallback state
+    /// for the [`ValueResolutionMode`] is used, if the mode has one.
+    pub fn from_map<U, F>(f: F) -> Self
+    where
+        U: fmt::Debug + Send + Sync + 'static,
+        F: Fn(&U) -> Option<T> + Send + Sync + 'static,
+    {
+        Self::FromMap(MappingFn::new(f))
+    }
+
+    /// Resolves the value of this field.
+    ///
+    /// # Parameters
+    ///
+    /// * `resources`: Resources holding the states of predecessors.
+    /// * `value_resolution_mode`: Which of the predecessors' states to map the
+    ///   value from.
+    /// * `field_name`: Name of the field, used in the error.
+    /// * `value_stored`: Value of the field in the params already in
+    ///   `Resources`, if any.
+    pub fn resolve(
+        &self,
+        resources: &Resources,
+        value_resolution_mode: ValueResolutionMode,
+        field_name: &'static str,
+        value_stored: Option<&T>,
+    ) -> Result<T, ParamsResolveError>
+    where
+        T: Clone,
+    {
+        match self {
+            Self::Stored => value_stored
+                .cloned()
+                .ok_or(ParamsResolveError::Stored { field_name }),
+            Self::Value(value) => Ok(value.clone()),
+            Self::FromMap(mapping_fn) => mapping_fn.map(resources, value_resolution_mode).ok_or(
+                ParamsResolveError::FromMap {
+                    field_name,
+                    from_type_name: mapping_fn.from_type_name(),
+                    value_resolution_mode,
+                },
+            ),
+        }
+    }
+}
+
+impl<T> From<T> for ValueSpec<T> {
+    fn from(value: T) -> Self {
+        Self::Value(value)
+    }
+}
@@ -14,6 +14,8 @@ use syn::{
     FieldsNamed, FieldsUnnamed, Ident, Lifetime, Type, WhereClause, WherePredicate,
 };
 
+mod params;
+
 /// Used to `#[derive]` the `Data` trait.
 ///
 /// For regular usage, use `#[derive(Data)]`
@@ -29,6 +31,22 @@ pub fn data_access(input: TokenStream) -> TokenStream {
     gen.into()
 }
 
+/// Used to `#[derive]` the `Params` trait.
+///
+/// This generates a `{Params}Spec` type, whose fields are `ValueSpec`s of the
+/// params' fields. `PhantomData` fields are not included in the spec.
+///
+/// For peace crates, also add the `#[peace_internal]` attribute, which
+/// references the `peace_data` crate instead of the `peace::data` re-export.
+#[proc_macro_derive(Params, attributes(peace_internal))]
+pub fn params(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).expect("Params derive: Code failed to be parsed.");
+
+    let gen = params::impl_params(&ast);
+
+    gen.into()
+}
+
 fn impl_data_access(ast: &DeriveInput) -> proc_macro2::TokenStream {
     let name = &ast.ident;
 
@@ -0,0 +1,181 @@
+use proc_macro2::TokenStream;
+use syn::{
+    Attribute, DataStruct, DeriveInput, Fields, FieldsNamed, Ident, Type, TypePath, WherePredicate,
+};
+
+/// Generates the `{Params}Spec` type, and `Params` and `ParamsSpec` impls.
+pub fn impl_params(ast: &DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let spec_name = format_ident!("{}Spec", name);
+    let spec_doc = format!("Specification of how to resolve [`{name}`] values.");
+
+    let peace_data_path = ast
+        .attrs
+        .iter()
+        .find(super::peace_internal)
+        .map(|_| quote!(peace_data))
+        .unwrap_or_else(|| quote!(peace::data));
+
+    let fields = match &ast.data {
+        syn::Data::Struct(DataStruct {
+            fields: Fields::Named(FieldsNamed { named, .. }),
+            ..
+        }) => named,
+        _ => panic!("Params derive: Only structs with named fields are supported."),
+    };
+
+    let (phantom_fields, value_fields): (Vec<_>, Vec<_>) =
+        fields.iter().partition(|field| is_phantom_data(&field.ty));
+    let phantom_idents = phantom_fields
+        .iter()
+        .map(|field| field.ident.as_ref().expect("Named field has an ident."))
+        .collect::<Vec<&Ident>>();
+    let phantom_tys = phantom_fields
+        .iter()
+        .map(|field| &field.ty)
+        .collect::<Vec<&Type>>();
+    let idents = value_fields
+        .iter()
+        .map(|field| field.ident.as_ref().expect("Named field has an ident."))
+        .collect::<Vec<&Ident>>();
+    let tys = value_fields
+        .iter()
+        .map(|field| &field.ty)
+        .collect::<Vec<&Type>>();
+    let field_vises = value_fields.iter().map(|field| &field.vis);
+    let field_docs = value_fields
+        .iter()
+        .map(|field| {
+            field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path.is_ident("doc"))
+                .collect::<Vec<&Attribute>>()
+        })
+        .collect::<Vec<_>>();
+    let field_names = idents.iter().map(|ident| ident.to_string());
+
+    let generics = &ast.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let mut generics_clone = generics.clone();
+    generics_clone.make_where_clause().predicates.extend(
+        tys.iter()
+            .map(|ty| -> WherePredicate { parse_quote!(#ty: Clone) }),
+    );
+    let (_, _, where_clause_clone) = generics_clone.split_for_impl();
+
+    let mut generics_debug = generics.clone();
+    generics_debug.make_where_clause().predicates.extend(
+        tys.iter()
+            .map(|ty| -> WherePredicate { parse_quote!(#ty: std::fmt::Debug) }),
+    );
+    let (_, _, where_clause_debug) = generics_debug.split_for_impl();
+
+    let mut generics_resolve = generics_clone.clone();
+    generics_resolve
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#name #ty_generics: #peace_data_path::resman::Resource));
+    let (_, _, where_clause_resolve) = generics_resolve.split_for_impl();
+
+    quote! {
+        #[doc = #spec_doc]
+        #vis struct #spec_name #generics #where_clause {
+            #(
+                #(#field_docs)*
+                #field_vises #idents: #peace_data_path::params::ValueSpec<#tys>,
+            )*
+            #( #phantom_idents: #phantom_tys, )*
+        }
+
+        impl #impl_generics #spec_name #ty_generics #where_clause {
+            #[doc = concat!("Returns a new `", stringify!(#spec_name), "`.")]
+            #[allow(clippy::too_many_arguments)]
+            pub fn new(
+                #( #idents: impl Into<#peace_data_path::params::ValueSpec<#tys>>, )*
+            ) -> Self {
+                Self {
+                    #( #idents: #idents.into(), )*
+                    #( #phantom_idents: std::marker::PhantomData, )*
+                }
+            }
+        }
+
+        impl #impl_generics Clone for #spec_name #ty_generics #where_clause_clone {
+            fn clone(&self) -> Self {
+                Self {
+                    #( #idents: self.#idents.clone(), )*
+                    #( #phantom_idents: std::marker::PhantomData, )*
+                }
+            }
+        }
+
+        impl #impl_generics std::fmt::Debug for #spec_name #ty_generics #where_clause_debug {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(stringify!(#spec_name))
+                    #( .field(stringify!(#idents), &self.#idents) )*
+                    .finish()
+            }
+        }
+
+        impl #impl_generics From<#name #ty_generics> for #spec_name #ty_generics #where_clause {
+            fn from(params: #name #ty_generics) -> Self {
+                Self {
+                    #( #idents: #peace_data_path::params::ValueSpec::Value(params.#idents), )*
+                    #( #phantom_idents: std::marker::PhantomData, )*
+                }
+            }
+        }
+
+        impl #impl_generics #peace_data_path::params::ParamsSpec
+            for #spec_name #ty_generics
+            #where_clause_resolve
+        {
+            type Params = #name #ty_generics;
+
+            fn resolve(
+                &self,
+                resources: &#peace_data_path::Resources,
+                value_resolution_mode: #peace_data_path::params::ValueResolutionMode,
+            ) -> Result<Self::Params, #peace_data_path::params::ParamsResolveError> {
+                let params_stored = resources.try_borrow::<#name #ty_generics>().ok();
+                let params_stored = params_stored.as_deref();
+
+                Ok(#name {
+                    #(
+                        #idents: self.#idents.resolve(
+                            resources,
+                            value_resolution_mode,
+                            #field_names,
+                            params_stored.map(|params| &params.#idents),
+                        )?,
+                    )*
+                    #( #phantom_idents: std::marker::PhantomData, )*
+                })
+            }
+        }
+
+        impl #impl_generics #peace_data_path::params::Params
+            for #name #ty_generics
+            #where_clause_resolve
+        {
+            type Spec = #spec_name #ty_generics;
+        }
+    }
+}
+
+/// Returns whether the type is a `PhantomData`, which is not specified in the
+/// `{Params}Spec`.
+fn is_phantom_data(ty: &Type) -> bool {
+    matches!(
+        ty,
+        Type::Path(TypePath { path, .. })
+            if path
+                .segments
+                .last()
+                .map(|segment| segment.ident == "PhantomData")
+                .unwrap_or(false)
+    )
+}
@@ -6,7 +6,8 @@
 pub use self::{
     flow_params_file::FlowParamsFile, op_check_statuses::OpCheckStatuses,
     profile_params_file::ProfileParamsFile, state_diffs_mut::StateDiffsMut, states_mut::StatesMut,
-    workspace_dirs::WorkspaceDirs, workspace_params_file::WorkspaceParamsFile,
+    storage_format_file::StorageFormatFile, workspace_dirs::WorkspaceDirs,
+    workspace_params_file::WorkspaceParamsFile,
 };
 
 mod flow_params_file;
@@ -14,5 +15,6 @@ mod op_check_statuses;
 mod profile_params_file;
 mod state_diffs_mut;
 mod states_mut;
+mod storage_format_file;
 mod workspace_dirs;
 mod workspace_params_file;
@@ -9,6 +9,9 @@ use crate::paths::FlowDir;
 /// See `FlowParamsFile::from<&FlowDir>` if you want to construct a
 /// `FlowParamsFile` with the conventional `$flow_dir/flow_params.yaml`
 /// path.
+///
+/// Use `FlowParamsFile::from<(&FlowDir, &str)>` to construct the path with the
+/// extension of the workspace's storage format.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct FlowParamsFile(PathBuf);
 
@@ -17,6 +20,8 @@ crate::paths::pathbuf_newtype!(FlowParamsFile);
 impl FlowParamsFile {
     /// File name of the initialization parameters file.
     pub const NAME: &'static str = "flow_params.yaml";
+    /// File name of the initialization parameters file, without the extension.
+    pub const STEM: &'static str = "flow_params";
 }
 
 impl From<&FlowDir> for FlowParamsFile {
@@ -26,3 +31,11 @@ impl From<&FlowDir> for FlowParamsFile {
         Self(path)
     }
 }
+
+impl From<(&FlowDir, &str)> for FlowParamsFile {
+    fn from((flow_dir, extension): (&FlowDir, &str)) -> Self {
+        let path = flow_dir.join(Self::STEM).with_extension(extension);
+
+        Self(path)
+    }
+}
@@ -9,6 +9,9 @@ use crate::paths::ProfileDir;
 /// See `ProfileParamsFile::from<&ProfileDir>` if you want to construct a
 /// `ProfileParamsFile` with the conventional `$profile_dir/profile_params.yaml`
 /// path.
+///
+/// Use `ProfileParamsFile::from<(&ProfileDir, &str)>` to construct the path
+/// with the extension of the workspace's storage format.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ProfileParamsFile(PathBuf);
 
@@ -17,6 +20,8 @@ crate::paths::pathbuf_newtype!(ProfileParamsFile);
 impl ProfileParamsFile {
     /// File name of the initialization parameters file.
     pub const NAME: &'static str = "profile_params.yaml";
+    /// File name of the initialization parameters file, without the extension.
+    pub const STEM: &'static str = "profile_params";
 }
 
 impl From<&ProfileDir> for ProfileParamsFile {
@@ -26,3 +31,11 @@ impl From<&ProfileDir> for ProfileParamsFile {
         Self(path)
     }
 }
+
+impl From<(&ProfileDir, &str)> for ProfileParamsFile {
+    fn from((profile_dir, extension): (&ProfileDir, &str)) -> Self {
+        let path = profile_dir.join(Self::STEM).with_extension(extension);
+
+        Self(path)
+    }
+}
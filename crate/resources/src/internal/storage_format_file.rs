@@ -0,0 +1,31 @@
+use std::path::PathBuf;
+
+use crate::paths::PeaceAppDir;
+
+/// Path to the file that records the storage format of the workspace.
+///
+/// Typically `$workspace_dir/.peace/$app/storage_format`.
+///
+/// The file contains the extension of the format, e.g. `yaml`. It does not
+/// have an extension itself, so that it is found regardless of the format.
+///
+/// See `StorageFormatFile::from<&PeaceAppDir>` if you want to construct a
+/// `StorageFormatFile` with the conventional `$peace_dir/$app/storage_format`
+/// path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StorageFormatFile(PathBuf);
+
+crate::paths::pathbuf_newtype!(StorageFormatFile);
+
+impl StorageFormatFile {
+    /// File name of the storage format file.
+    pub const NAME: &'static str = "storage_format";
+}
+
+impl From<&PeaceAppDir> for StorageFormatFile {
+    fn from(peace_app_dir: &PeaceAppDir) -> Self {
+        let path = peace_app_dir.join(Self::NAME);
+
+        Self(path)
+    }
+}
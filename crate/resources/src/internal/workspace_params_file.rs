@@ -9,6 +9,9 @@ use crate::paths::PeaceAppDir;
 /// See `WorkspaceParamsFile::from<&PeaceAppDir>` if you want to construct a
 /// `WorkspaceParamsFile` with the conventional
 /// `$peace_dir/$app/workspace_params.yaml` path.
+///
+/// Use `WorkspaceParamsFile::from<(&PeaceAppDir, &str)>` to construct the path
+/// with the extension of the workspace's storage format.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct WorkspaceParamsFile(PathBuf);
 
@@ -17,6 +20,8 @@ crate::paths::pathbuf_newtype!(WorkspaceParamsFile);
 impl WorkspaceParamsFile {
     /// File name of the workspace parameters file.
     pub const NAME: &'static str = "workspace_params.yaml";
+    /// File name of the workspace parameters file, without the extension.
+    pub const STEM: &'static str = "workspace_params";
 }
 
 impl From<&PeaceAppDir> for WorkspaceParamsFile {
@@ -26,3 +31,11 @@ impl From<&PeaceAppDir> for WorkspaceParamsFile {
         Self(path)
     }
 }
+
+impl From<(&PeaceAppDir, &str)> for WorkspaceParamsFile {
+    fn from((peace_app_dir, extension): (&PeaceAppDir, &str)) -> Self {
+        let path = peace_app_dir.join(Self::STEM).with_extension(extension);
+
+        Self(path)
+    }
+}
@@ -28,7 +28,7 @@
 //! |- .peace
 //!     |- profile1 / main / default
 //!     |   |- .history
-//!     |   |   |- 00000000_2022-08-21T20_48_02_init.yaml
+//!     |   |   |- 00000000_2022-08-21T20_48_02_ensure.yaml
 //!     |   |   |- 00000001_2022-08-21T20_48_07_dev_env_discover.yaml
 //!     |   |   |- 00000002_2022-08-21T20_50_32_dev_env_deploy.yaml  # dry
 //!     |   |   |- 00000003_2022-08-21T20_50_43_dev_env_deploy.yaml
@@ -37,10 +37,13 @@
 //!     |   |   |- 00000006_2022-08-23T13_02_14_artifact_discover.yaml
 //!     |   |   |- 00000007_2022-08-23T13_07_31_artifact_publish.yaml
 //!     |   |
+//!     |   |- .lock.yaml  # Exists while a command holds the profile's lock.
+//!     |   |
 //!     |   |- .meta.yaml  # Store the last discovered time so we can inform the user.
 //!     |   |              # Should time be stored per item spec, or per invocation?
 //!     |   |
 //!     |   |- dev_env  # flow name
+//!     |   |   |- ensure_plan.yaml  # Written by `EnsureCmd::exec_dry_plan`.
 //!     |   |   |- states_desired.yaml
 //!     |   |   |- states_saved.yaml
 //!     |   |
@@ -55,7 +58,7 @@
 //!     |
 //!     |- production
 //!     |   |- .history
-//!     |   |   |- 00000000_2022-08-21T20_48_02_init.yaml
+//!     |   |   |- 00000000_2022-08-21T20_48_02_ensure.yaml
 //!     |   |   |- 00000001_2022-08-21T20_48_07_discover.yaml
 //!     |   |
 //!     |   |- customer_one
@@ -70,16 +73,19 @@
 //! ```
 
 pub use self::{
-    flow_dir::FlowDir, peace_app_dir::PeaceAppDir, peace_dir::PeaceDir, profile_dir::ProfileDir,
-    profile_history_dir::ProfileHistoryDir, states_desired_file::StatesDesiredFile,
+    ensure_plan_file::EnsurePlanFile, flow_dir::FlowDir, peace_app_dir::PeaceAppDir,
+    peace_dir::PeaceDir, profile_dir::ProfileDir, profile_history_dir::ProfileHistoryDir,
+    profile_lock_file::ProfileLockFile, states_desired_file::StatesDesiredFile,
     states_saved_file::StatesSavedFile, workspace_dir::WorkspaceDir,
 };
 
+mod ensure_plan_file;
 mod flow_dir;
 mod peace_app_dir;
 mod peace_dir;
 mod profile_dir;
 mod profile_history_dir;
+mod profile_lock_file;
 mod states_desired_file;
 mod states_saved_file;
 mod workspace_dir;
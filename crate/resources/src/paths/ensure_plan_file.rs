@@ -0,0 +1,40 @@
+use std::path::PathBuf;
+
+use crate::paths::FlowDir;
+
+/// Path to the file that stores the ensure plan generated by a dry run.
+///
+/// Typically `$workspace_dir/.peace/$profile/$flow_id/ensure_plan.yaml`.
+///
+/// See `EnsurePlanFile::from<&FlowDir>` if you want to construct an
+/// `EnsurePlanFile` with the conventional `$flow_dir/ensure_plan.yaml` path.
+///
+/// Use `EnsurePlanFile::from<(&FlowDir, &str)>` to construct the path with the
+/// extension of the workspace's storage format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnsurePlanFile(PathBuf);
+
+crate::paths::pathbuf_newtype!(EnsurePlanFile);
+
+impl EnsurePlanFile {
+    /// File name of the ensure plan file.
+    pub const NAME: &'static str = "ensure_plan.yaml";
+    /// File name of the ensure plan file, without the extension.
+    pub const STEM: &'static str = "ensure_plan";
+}
+
+impl From<&FlowDir> for EnsurePlanFile {
+    fn from(flow_dir: &FlowDir) -> Self {
+        let path = flow_dir.join(Self::NAME);
+
+        Self(path)
+    }
+}
+
+impl From<(&FlowDir, &str)> for EnsurePlanFile {
+    fn from((flow_dir, extension): (&FlowDir, &str)) -> Self {
+        let path = flow_dir.join(Self::STEM).with_extension(extension);
+
+        Self(path)
+    }
+}
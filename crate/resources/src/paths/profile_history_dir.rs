@@ -6,8 +6,9 @@ use crate::paths::ProfileDir;
 ///
 /// Typically `$workspace_dir/.peace/$app/$profile/.history`.
 ///
-/// This directory is intended to contain significant command execution
-/// summaries. Currently it is not written to yet.
+/// This directory contains significant command execution summaries. Each
+/// `EnsureCmd` and `CleanCmd` execution appends a history record to this
+/// directory.
 ///
 /// See `ProfileHistoryDir::from<&ProfileDir>` if you want to construct a
 /// `ProfileHistoryDir` with the conventional `$profile_dir/.history` path.
@@ -0,0 +1,30 @@
+use std::path::PathBuf;
+
+use crate::paths::ProfileDir;
+
+/// Path to the file that marks a profile as locked by a running command.
+///
+/// Typically `$workspace_dir/.peace/$app/$profile/.lock.yaml`.
+///
+/// This file exists while a command holds the profile's lock, and records the
+/// process ID and host name of that command.
+///
+/// See `ProfileLockFile::from<&ProfileDir>` if you want to construct a
+/// `ProfileLockFile` with the conventional `$profile_dir/.lock.yaml` path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProfileLockFile(PathBuf);
+
+crate::paths::pathbuf_newtype!(ProfileLockFile);
+
+impl ProfileLockFile {
+    /// File name of the profile lock file.
+    pub const NAME: &'static str = ".lock.yaml";
+}
+
+impl From<&ProfileDir> for ProfileLockFile {
+    fn from(profile_dir: &ProfileDir) -> Self {
+        let path = profile_dir.join(Self::NAME);
+
+        Self(path)
+    }
+}
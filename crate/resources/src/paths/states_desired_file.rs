@@ -9,6 +9,9 @@ use crate::paths::FlowDir;
 /// See `StatesDesiredFile::from<&FlowDir>` if you want to construct a
 /// `StatesDesiredFile` with the conventional `$flow_dir/states_desired.yaml`
 /// path.
+///
+/// Use `StatesDesiredFile::from<(&FlowDir, &str)>` to construct the path with
+/// the extension of the workspace's storage format.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct StatesDesiredFile(PathBuf);
 
@@ -17,6 +20,8 @@ crate::paths::pathbuf_newtype!(StatesDesiredFile);
 impl StatesDesiredFile {
     /// File name of the desired states file.
     pub const NAME: &'static str = "states_desired.yaml";
+    /// File name of the desired states file, without the extension.
+    pub const STEM: &'static str = "states_desired";
 }
 
 impl From<&FlowDir> for StatesDesiredFile {
@@ -26,3 +31,11 @@ impl From<&FlowDir> for StatesDesiredFile {
         Self(path)
     }
 }
+
+impl From<(&FlowDir, &str)> for StatesDesiredFile {
+    fn from((flow_dir, extension): (&FlowDir, &str)) -> Self {
+        let path = flow_dir.join(Self::STEM).with_extension(extension);
+
+        Self(path)
+    }
+}
@@ -9,6 +9,9 @@ use crate::paths::FlowDir;
 /// See `StatesSavedFile::from<&FlowDir>` if you want to construct a
 /// `StatesSavedFile` with the conventional `$flow_dir/states_saved.yaml`
 /// path.
+///
+/// Use `StatesSavedFile::from<(&FlowDir, &str)>` to construct the path with the
+/// extension of the workspace's storage format.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct StatesSavedFile(PathBuf);
 
@@ -17,6 +20,8 @@ crate::paths::pathbuf_newtype!(StatesSavedFile);
 impl StatesSavedFile {
     /// File name of the states file.
     pub const NAME: &'static str = "states_saved.yaml";
+    /// File name of the states file, without the extension.
+    pub const STEM: &'static str = "states_saved";
 }
 
 impl From<&FlowDir> for StatesSavedFile {
@@ -26,3 +31,11 @@ impl From<&FlowDir> for StatesSavedFile {
         Self(path)
     }
 }
+
+impl From<(&FlowDir, &str)> for StatesSavedFile {
+    fn from((flow_dir, extension): (&FlowDir, &str)) -> Self {
+        let path = flow_dir.join(Self::STEM).with_extension(extension);
+
+        Self(path)
+    }
+}
@@ -1,8 +1,10 @@
 //! Resources that track current and desired states, and state diffs.
 
 pub use self::{
-    state_diffs::StateDiffs, states_cleaned::StatesCleaned, states_cleaned_dry::StatesCleanedDry,
-    states_current::StatesCurrent, states_desired::StatesDesired, states_ensured::StatesEnsured,
+    flows_states_saved::FlowsStatesSaved, state_diffs::StateDiffs, state_orphaned::StateOrphaned,
+    state_orphaned_value::StateOrphanedValue, states_cleaned::StatesCleaned,
+    states_cleaned_dry::StatesCleanedDry, states_current::StatesCurrent,
+    states_desired::StatesDesired, states_ensured::StatesEnsured,
     states_ensured_dry::StatesEnsuredDry, states_saved::StatesSaved,
 };
 
@@ -13,11 +15,14 @@ use std::{marker::PhantomData, ops::Deref};
 use peace_core::ItemSpecId;
 use peace_fmt::{Presentable, Presenter};
 use serde::Serialize;
-use type_reg::untagged::{BoxDtDisplay, TypeMap};
+use type_reg::untagged::{BoxDataTypeDowncast, BoxDtDisplay, TypeMap};
 
 use crate::internal::StatesMut;
 
+mod flows_states_saved;
 mod state_diffs;
+mod state_orphaned;
+mod state_orphaned_value;
 mod states_cleaned;
 mod states_cleaned_dry;
 mod states_current;
@@ -59,6 +64,17 @@ impl<TS> States<TS> {
     pub fn into_inner(self) -> TypeMap<ItemSpecId, BoxDtDisplay> {
         self.0
     }
+
+    /// Returns the states of item specs that are no longer in the flow.
+    ///
+    /// These are stored states whose item spec ID was not registered when
+    /// the states were deserialized.
+    pub fn orphans(&self) -> impl Iterator<Item = (&ItemSpecId, &StateOrphaned)> {
+        self.0.iter().filter_map(|(item_spec_id, state)| {
+            BoxDataTypeDowncast::<StateOrphaned>::downcast_ref(state)
+                .map(|state_orphaned| (item_spec_id, state_orphaned))
+        })
+    }
 }
 
 impl<TS> Clone for States<TS> {
@@ -0,0 +1,73 @@
+use std::{
+    collections::BTreeMap,
+    ops::{Deref, DerefMut},
+};
+
+use peace_core::FlowId;
+
+use crate::states::StatesSaved;
+
+/// Saved `State`s of each flow for a profile. `BTreeMap<FlowId, StatesSaved>`
+/// newtype.
+///
+/// This allows an item spec in one flow to read the saved states of items in
+/// another flow, such as an application flow reading the saved state of a
+/// network flow's subnet.
+///
+/// # Implementors
+///
+/// [`FlowsStatesSaved`] is a read-only resource, inserted into [`Resources`]
+/// for each flow's `CmdCtx` created from a `SingleProfileMultiFlow` `CmdCtx`.
+/// Flows whose states have not been saved are not present in the map.
+///
+/// As it is not inserted for other scopes, it should be referenced through
+/// [`RMaybe`] in an item spec's [`Data`]:
+///
+/// ```rust
+/// # use peace_data::{accessors::RMaybe, Data};
+/// # use peace_resources::states::FlowsStatesSaved;
+/// #
+/// #[derive(Data, Debug)]
+/// pub struct AppInstallData<'op> {
+///     /// Saved states of other flows in the profile.
+///     flows_states_saved: RMaybe<'op, FlowsStatesSaved>,
+/// }
+/// ```
+///
+/// [`Data`]: peace_data::Data
+/// [`RMaybe`]: peace_data::accessors::RMaybe
+/// [`Resources`]: crate::Resources
+#[derive(Clone, Debug, Default)]
+pub struct FlowsStatesSaved(BTreeMap<FlowId, StatesSaved>);
+
+impl FlowsStatesSaved {
+    /// Returns a new `FlowsStatesSaved` map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the inner map.
+    pub fn into_inner(self) -> BTreeMap<FlowId, StatesSaved> {
+        self.0
+    }
+}
+
+impl Deref for FlowsStatesSaved {
+    type Target = BTreeMap<FlowId, StatesSaved>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for FlowsStatesSaved {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<BTreeMap<FlowId, StatesSaved>> for FlowsStatesSaved {
+    fn from(flows_states_saved: BTreeMap<FlowId, StatesSaved>) -> Self {
+        Self(flows_states_saved)
+    }
+}
@@ -0,0 +1,44 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::states::StateOrphanedValue;
+
+/// Stored state of an item spec that is no longer in the flow.
+///
+/// When an item spec is removed from a flow, its state can no longer be
+/// deserialized into its `State` type. Instead of failing to load the
+/// [`States`], the stored value is retained as-is, so that it is written back
+/// when the states are serialized again. The value is held independently of
+/// the storage format, so this works for YAML, JSON, and MessagePack states.
+///
+/// Use [`States::orphans`] to list these states.
+///
+/// [`States`]: crate::states::States
+/// [`States::orphans`]: crate::states::States::orphans
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct StateOrphaned(StateOrphanedValue);
+
+impl StateOrphaned {
+    /// Returns a new `StateOrphaned`.
+    pub fn new(value: StateOrphanedValue) -> Self {
+        Self(value)
+    }
+
+    /// Returns the stored value of the state.
+    pub fn value(&self) -> &StateOrphanedValue {
+        &self.0
+    }
+
+    /// Returns the inner value.
+    pub fn into_inner(self) -> StateOrphanedValue {
+        self.0
+    }
+}
+
+impl fmt::Display for StateOrphaned {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "orphaned, item spec is not in the flow")
+    }
+}
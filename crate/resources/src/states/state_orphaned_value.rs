@@ -0,0 +1,201 @@
+use std::{collections::BTreeSet, fmt, sync::Mutex};
+
+use serde::{
+    de::{EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor},
+    ser::{SerializeMap, SerializeSeq},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// Variant names of enums that have been serialized.
+///
+/// Serializers take variant names as `&'static str`, so each distinct name is
+/// leaked once and reused.
+static VARIANT_NAMES: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+/// Stored value of an orphaned state, independent of the storage format.
+///
+/// This is deserialized from whichever format the states are stored in, and
+/// serializes back to the same structure, so orphaned states are retained in
+/// YAML, JSON, and MessagePack workspaces alike.
+#[derive(Clone, Debug, PartialEq)]
+pub enum StateOrphanedValue {
+    /// A null or unit value.
+    Unit,
+    /// A boolean.
+    Bool(bool),
+    /// A signed integer.
+    I64(i64),
+    /// An unsigned integer.
+    U64(u64),
+    /// A floating point number.
+    F64(f64),
+    /// A string.
+    String(String),
+    /// Binary data.
+    Bytes(Vec<u8>),
+    /// A sequence of values.
+    Seq(Vec<StateOrphanedValue>),
+    /// A map of keys to values, in their stored order.
+    Map(Vec<(StateOrphanedValue, StateOrphanedValue)>),
+    /// An enum variant with a value, such as a YAML `!Variant` tagged value.
+    ///
+    /// Formats without tags store enum variants as a single entry map, which
+    /// is deserialized as a [`Map`](Self::Map).
+    Variant(String, Box<StateOrphanedValue>),
+}
+
+impl Serialize for StateOrphanedValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Unit => serializer.serialize_unit(),
+            Self::Bool(value) => serializer.serialize_bool(*value),
+            Self::I64(value) => serializer.serialize_i64(*value),
+            Self::U64(value) => serializer.serialize_u64(*value),
+            Self::F64(value) => serializer.serialize_f64(*value),
+            Self::String(value) => serializer.serialize_str(value),
+            Self::Bytes(value) => serializer.serialize_bytes(value),
+            Self::Seq(values) => {
+                let mut seq = serializer.serialize_seq(Some(values.len()))?;
+                values
+                    .iter()
+                    .try_for_each(|value| seq.serialize_element(value))?;
+                seq.end()
+            }
+            Self::Map(entries) => {
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                entries
+                    .iter()
+                    .try_for_each(|(key, value)| map.serialize_entry(key, value))?;
+                map.end()
+            }
+            Self::Variant(variant, value) => serializer.serialize_newtype_variant(
+                "StateOrphanedValue",
+                0,
+                variant_name_static(variant),
+                value,
+            ),
+        }
+    }
+}
+
+/// Returns the `'static` variant name for the given name.
+fn variant_name_static(variant: &str) -> &'static str {
+    let mut variant_names = VARIANT_NAMES
+        .lock()
+        .unwrap_or_else(|poison_error| poison_error.into_inner());
+    match variant_names.get(variant) {
+        Some(variant_name) => variant_name,
+        None => {
+            let variant_name: &'static str = Box::leak(variant.to_owned().into_boxed_str());
+            variant_names.insert(variant_name);
+            variant_name
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for StateOrphanedValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(StateOrphanedValueVisitor)
+    }
+}
+
+struct StateOrphanedValueVisitor;
+
+impl<'de> Visitor<'de> for StateOrphanedValueVisitor {
+    type Value = StateOrphanedValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "any value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Self::Value, E> {
+        Ok(StateOrphanedValue::Bool(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E> {
+        Ok(StateOrphanedValue::I64(value))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E> {
+        Ok(StateOrphanedValue::U64(value))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E> {
+        Ok(StateOrphanedValue::F64(value))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(StateOrphanedValue::String(value.to_owned()))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Self::Value, E> {
+        Ok(StateOrphanedValue::String(value))
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E> {
+        Ok(StateOrphanedValue::Bytes(value.to_vec()))
+    }
+
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(StateOrphanedValue::Bytes(value))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(StateOrphanedValue::Unit)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(StateOrphanedValue::Unit)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        StateOrphanedValue::deserialize(deserializer)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        StateOrphanedValue::deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(StateOrphanedValue::Seq(values))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entries = Vec::with_capacity(map.size_hint().unwrap_or(0));
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(StateOrphanedValue::Map(entries))
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let (variant, variant_access) = data.variant::<String>()?;
+        let value = variant_access.newtype_variant::<StateOrphanedValue>()?;
+        Ok(StateOrphanedValue::Variant(variant, Box::new(value)))
+    }
+}
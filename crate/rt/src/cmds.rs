@@ -10,8 +10,11 @@
 
 pub use self::{
     clean_cmd::CleanCmd, diff_cmd::DiffCmd, ensure_cmd::EnsureCmd,
+    history_list_cmd::HistoryListCmd, history_show_cmd::HistoryShowCmd, orphans_cmd::OrphansCmd,
+    profiles_diff_cmd::ProfilesDiffCmd, rollback_cmd::RollbackCmd,
     states_desired_display_cmd::StatesDesiredDisplayCmd, states_discover_cmd::StatesDiscoverCmd,
-    states_saved_display_cmd::StatesSavedDisplayCmd,
+    states_drift_cmd::StatesDriftCmd, states_saved_display_cmd::StatesSavedDisplayCmd,
+    storage_format_migrate_cmd::StorageFormatMigrateCmd,
 };
 
 pub mod sub;
@@ -19,6 +22,13 @@ pub mod sub;
 mod clean_cmd;
 mod diff_cmd;
 mod ensure_cmd;
+mod history_list_cmd;
+mod history_show_cmd;
+mod orphans_cmd;
+mod profiles_diff_cmd;
+mod rollback_cmd;
 mod states_desired_display_cmd;
 mod states_discover_cmd;
+mod states_drift_cmd;
 mod states_saved_display_cmd;
+mod storage_format_migrate_cmd;
@@ -1,6 +1,10 @@
 use std::{fmt::Debug, marker::PhantomData};
 
-use peace_cmd::{ctx::CmdCtx, scopes::SingleProfileSingleFlow};
+use futures::FutureExt;
+use peace_cmd::{
+    ctx::CmdCtx,
+    scopes::{MultiProfileSingleFlow, SingleProfileSingleFlow},
+};
 use peace_resources::{
     resources::ts::SetUp,
     states::{
@@ -8,9 +12,17 @@ use peace_resources::{
         StatesCleaned, StatesCleanedDry, StatesSaved,
     },
 };
-use peace_rt_model::{outcomes::CmdOutcome, output::OutputWrite, params::ParamsKeys, Error};
+use peace_rt_model::{
+    outcomes::{CmdOutcome, ProfilesCmdOutcome},
+    output::OutputWrite,
+    params::ParamsKeys,
+    Error, InMemoryTextOutput,
+};
 
-use crate::cmds::sub::{ApplyCmd, ApplyFor};
+use crate::{
+    cmds::sub::{ApplyCmd, ApplyErrorMode, ApplyFor, StatesSavedReadCmd},
+    profiles_exec::ProfilesExec,
+};
 
 #[derive(Debug)]
 pub struct CleanCmd<E, O, PKeys>(PhantomData<(E, O, PKeys)>);
@@ -48,11 +60,22 @@ where
     pub async fn exec_dry(
         cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
         states_saved: &StatesSaved,
+    ) -> Result<CmdOutcome<StatesCleanedDry, E>, E> {
+        Self::exec_dry_with_error_mode(cmd_ctx, states_saved, ApplyErrorMode::StopOnError).await
+    }
+
+    /// Same as [`Self::exec_dry`], but proceeds according to the given
+    /// [`ApplyErrorMode`] when an item fails.
+    pub async fn exec_dry_with_error_mode(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        states_saved: &StatesSaved,
+        apply_error_mode: ApplyErrorMode,
     ) -> Result<CmdOutcome<StatesCleanedDry, E>, E> {
         Ok(ApplyCmd::<E, O, PKeys, Cleaned, CleanedDry>::exec_dry(
             cmd_ctx,
             states_saved,
             ApplyFor::Clean,
+            apply_error_mode,
         )
         .await)
     }
@@ -90,8 +113,56 @@ where
         cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
         states_saved: &StatesSaved,
     ) -> Result<CmdOutcome<StatesCleaned, E>, E> {
-        ApplyCmd::<E, O, PKeys, Cleaned, CleanedDry>::exec(cmd_ctx, states_saved, ApplyFor::Clean)
-            .await
+        Self::exec_with_error_mode(cmd_ctx, states_saved, ApplyErrorMode::StopOnError).await
+    }
+
+    /// Same as [`Self::exec`], but proceeds according to the given
+    /// [`ApplyErrorMode`] when an item fails.
+    ///
+    /// With [`ApplyErrorMode::ContinueOnError`], only the `ItemSpec`s that
+    /// depend on a failed `ItemSpec` are skipped. The status of each
+    /// `ItemSpec` is recorded in [`CmdOutcome::item_statuses`].
+    pub async fn exec_with_error_mode(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        states_saved: &StatesSaved,
+        apply_error_mode: ApplyErrorMode,
+    ) -> Result<CmdOutcome<StatesCleaned, E>, E> {
+        ApplyCmd::<E, O, PKeys, Cleaned, CleanedDry>::exec(
+            cmd_ctx,
+            states_saved,
+            ApplyFor::Clean,
+            apply_error_mode,
+        )
+        .await
+    }
+
+    /// Runs [`Self::exec`] for each profile of a `MultiProfileSingleFlow`
+    /// command.
+    ///
+    /// Each profile is cleaned using its saved states, so
+    /// [`StatesDiscoverCmd::current_and_desired_multi_profile`] must have run
+    /// prior to this command. Profiles are cleaned concurrently, up to the limit
+    /// set by `CmdCtx::with_profile_concurrency_limit`.
+    ///
+    /// An error that stops a profile from being cleaned is recorded in
+    /// [`ProfilesCmdOutcome::profile_to_error`], and does not stop other
+    /// profiles from being cleaned.
+    ///
+    /// [`StatesDiscoverCmd::current_and_desired_multi_profile`]: crate::cmds::StatesDiscoverCmd::current_and_desired_multi_profile
+    pub async fn exec_multi_profile(
+        cmd_ctx: &mut CmdCtx<MultiProfileSingleFlow<'_, E, O, PKeys>>,
+    ) -> Result<ProfilesCmdOutcome<StatesCleaned, E>, E> {
+        ProfilesExec::exec(cmd_ctx, |profile_cmd_ctx| {
+            async move {
+                let states_saved =
+                    StatesSavedReadCmd::<E, InMemoryTextOutput, PKeys>::exec(profile_cmd_ctx)
+                        .await?;
+
+                CleanCmd::<E, InMemoryTextOutput, PKeys>::exec(profile_cmd_ctx, &states_saved).await
+            }
+            .boxed_local()
+        })
+        .await
     }
 }
 
@@ -24,6 +24,9 @@ where
     /// At the end of this function, [`Resources`] will be populated with
     /// [`StatesCurrent`], [`StatesDesired`], and [`StateDiffs`].
     ///
+    /// If an item spec selection is set on the `CmdCtx`, only the selected
+    /// item specs are diffed.
+    ///
     /// [`ItemSpec`]: peace_cfg::ItemSpec
     /// [`StatesCurrent`]: peace_resources::StatesCurrent
     /// [`StatesDesired`]: peace_resources::StatesDesired
@@ -37,7 +40,10 @@ where
         let states_desired = StatesDesiredReadCmd::<E, O, PKeys>::exec(cmd_ctx).await?;
 
         let SingleProfileSingleFlowView {
-            flow, resources, ..
+            flow,
+            item_spec_ids_selected,
+            resources,
+            ..
         } = cmd_ctx.scope_mut().view();
         let item_spec_graph = flow.graph();
 
@@ -49,6 +55,12 @@ where
                 .stream()
                 .map(Result::<_, E>::Ok)
                 .try_filter_map(|item_spec| async move {
+                    if let Some(item_spec_ids_selected) = item_spec_ids_selected {
+                        if !item_spec_ids_selected.contains(item_spec.id()) {
+                            return Ok(None);
+                        }
+                    }
+
                     let state_diff_opt = item_spec
                         .state_diff_exec_with_states_saved(
                             resources_ref,
@@ -1,16 +1,32 @@
 use std::{fmt::Debug, marker::PhantomData};
 
-use peace_cmd::{ctx::CmdCtx, scopes::SingleProfileSingleFlow};
+use futures::FutureExt;
+use peace_cmd::{
+    ctx::CmdCtx,
+    scopes::{MultiProfileSingleFlow, SingleProfileSingleFlow},
+};
 use peace_resources::{
+    internal::StatesMut,
+    paths::{EnsurePlanFile, FlowDir},
     resources::ts::SetUp,
     states::{
-        ts::{Ensured, EnsuredDry},
-        StatesEnsured, StatesEnsuredDry, StatesSaved,
+        ts::{Desired, Ensured, EnsuredDry},
+        StatesDesired, StatesEnsured, StatesEnsuredDry, StatesSaved,
     },
+    type_reg::untagged::{BoxDtDisplay, TypeMap},
+};
+use peace_rt_model::{
+    outcomes::{CmdOutcome, EnsurePlan, HistoryCmd, ProfilesCmdOutcome},
+    output::OutputWrite,
+    params::ParamsKeys,
+    storage::SerializedStorage,
+    EnsurePlanSerializer, Error, InMemoryTextOutput, Storage,
 };
-use peace_rt_model::{outcomes::CmdOutcome, output::OutputWrite, params::ParamsKeys, Error};
 
-use crate::cmds::sub::{ApplyCmd, ApplyFor};
+use crate::{
+    cmds::sub::{ApplyCmd, ApplyErrorMode, ApplyFor, StatesSavedReadCmd},
+    profiles_exec::ProfilesExec,
+};
 
 #[derive(Debug)]
 pub struct EnsureCmd<E, O, PKeys>(PhantomData<(E, O, PKeys)>);
@@ -48,15 +64,86 @@ where
     pub async fn exec_dry(
         cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
         states_saved: &StatesSaved,
+    ) -> Result<CmdOutcome<StatesEnsuredDry, E>, E> {
+        Self::exec_dry_with_error_mode(cmd_ctx, states_saved, ApplyErrorMode::StopOnError).await
+    }
+
+    /// Same as [`Self::exec_dry`], but proceeds according to the given
+    /// [`ApplyErrorMode`] when an item fails.
+    pub async fn exec_dry_with_error_mode(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        states_saved: &StatesSaved,
+        apply_error_mode: ApplyErrorMode,
     ) -> Result<CmdOutcome<StatesEnsuredDry, E>, E> {
         Ok(ApplyCmd::<E, O, PKeys, Ensured, EnsuredDry>::exec_dry(
             cmd_ctx,
             states_saved,
             ApplyFor::Ensure,
+            apply_error_mode,
         )
         .await)
     }
 
+    /// Same as [`Self::exec_dry`], but returns an [`EnsurePlan`] of what would
+    /// be ensured, and writes it to `$flow_dir/ensure_plan.yaml`.
+    ///
+    /// The plan holds each `ItemSpec`'s saved, current, and target states, the
+    /// diff between them, and whether execution is required. It is only
+    /// written when the dry run succeeds for every `ItemSpec`, and is not
+    /// interrupted, as an interrupted plan would not hold every `ItemSpec`.
+    ///
+    /// Once reviewed, the plan may be read using [`EnsurePlanReadCmd`], and
+    /// passed to [`Self::exec_with_plan`] to ensure exactly what the dry run
+    /// showed.
+    ///
+    /// [`EnsurePlanReadCmd`]: crate::cmds::sub::EnsurePlanReadCmd
+    pub async fn exec_dry_plan(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        states_saved: &StatesSaved,
+    ) -> Result<CmdOutcome<EnsurePlan, E>, E> {
+        let CmdOutcome {
+            value: (_states_ensured_dry, item_applies),
+            errors,
+            retries,
+            item_statuses,
+            interrupted,
+            history_write_error,
+        } = ApplyCmd::<E, O, PKeys, Ensured, EnsuredDry>::exec_dry_with_item_applies(
+            cmd_ctx,
+            states_saved,
+            ApplyFor::Ensure,
+            ApplyErrorMode::StopOnError,
+        )
+        .await;
+
+        let ensure_plan = item_applies.into_iter().fold(
+            TypeMap::new_typed(),
+            |mut ensure_plan, (item_spec_id, item_apply)| {
+                ensure_plan.insert_raw(item_spec_id, item_apply);
+                ensure_plan
+            },
+        );
+        let ensure_plan = EnsurePlan::from(ensure_plan);
+
+        if errors.is_empty() && !interrupted {
+            let resources = cmd_ctx.resources();
+            let flow_dir = resources.borrow::<FlowDir>();
+            let storage = resources.borrow::<Storage>();
+            let ensure_plan_file = EnsurePlanFile::from((&*flow_dir, storage.format().extension()));
+
+            EnsurePlanSerializer::serialize(&*storage, &ensure_plan, &ensure_plan_file).await?;
+        }
+
+        Ok(CmdOutcome {
+            value: ensure_plan,
+            errors,
+            retries,
+            item_statuses,
+            interrupted,
+            history_write_error,
+        })
+    }
+
     /// Conditionally runs [`ApplyOpSpec`]`::`[`exec`] for each
     /// [`ItemSpec`].
     ///
@@ -90,8 +177,184 @@ where
         cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
         states_saved: &StatesSaved,
     ) -> Result<CmdOutcome<StatesEnsured, E>, E> {
-        ApplyCmd::<E, O, PKeys, Ensured, EnsuredDry>::exec(cmd_ctx, states_saved, ApplyFor::Ensure)
-            .await
+        Self::exec_with_error_mode(cmd_ctx, states_saved, ApplyErrorMode::StopOnError).await
+    }
+
+    /// Same as [`Self::exec`], but proceeds according to the given
+    /// [`ApplyErrorMode`] when an item fails.
+    ///
+    /// With [`ApplyErrorMode::ContinueOnError`], only the `ItemSpec`s that
+    /// depend on a failed `ItemSpec` are skipped. The status of each
+    /// `ItemSpec` is recorded in [`CmdOutcome::item_statuses`].
+    pub async fn exec_with_error_mode(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        states_saved: &StatesSaved,
+        apply_error_mode: ApplyErrorMode,
+    ) -> Result<CmdOutcome<StatesEnsured, E>, E> {
+        ApplyCmd::<E, O, PKeys, Ensured, EnsuredDry>::exec(
+            cmd_ctx,
+            states_saved,
+            ApplyFor::Ensure,
+            apply_error_mode,
+        )
+        .await
+    }
+
+    /// Runs [`Self::exec`] for each profile of a `MultiProfileSingleFlow`
+    /// command.
+    ///
+    /// Each profile is ensured using its saved states, so
+    /// [`StatesDiscoverCmd::current_and_desired_multi_profile`] must have run
+    /// prior to this command. Profiles are ensured concurrently, up to the limit
+    /// set by `CmdCtx::with_profile_concurrency_limit`.
+    ///
+    /// An error that stops a profile from being ensured is recorded in
+    /// [`ProfilesCmdOutcome::profile_to_error`], and does not stop other
+    /// profiles from being ensured.
+    ///
+    /// [`StatesDiscoverCmd::current_and_desired_multi_profile`]: crate::cmds::StatesDiscoverCmd::current_and_desired_multi_profile
+    pub async fn exec_multi_profile(
+        cmd_ctx: &mut CmdCtx<MultiProfileSingleFlow<'_, E, O, PKeys>>,
+    ) -> Result<ProfilesCmdOutcome<StatesEnsured, E>, E> {
+        ProfilesExec::exec(cmd_ctx, |profile_cmd_ctx| {
+            async move {
+                let states_saved =
+                    StatesSavedReadCmd::<E, InMemoryTextOutput, PKeys>::exec(profile_cmd_ctx)
+                        .await?;
+
+                EnsureCmd::<E, InMemoryTextOutput, PKeys>::exec(profile_cmd_ctx, &states_saved)
+                    .await
+            }
+            .boxed_local()
+        })
+        .await
+    }
+
+    /// Ensures each [`ItemSpec`] towards its target state in the given
+    /// [`EnsurePlan`], refusing to run if the plan is stale.
+    ///
+    /// Before anything is applied, each `ItemSpec` is checked again in a dry
+    /// run towards its target state in the plan, and its current state, state
+    /// diff, and [`OpCheckStatus`] are compared with the plan. If any differ,
+    /// no `ItemSpec` is applied, and [`Error::EnsurePlanStale`] is returned.
+    ///
+    /// If an `ItemSpec` in the flow is not in the plan,
+    /// [`Error::EnsurePlanItemSpecNotFound`] is returned.
+    ///
+    /// [`ItemSpec`]: peace_cfg::ItemSpec
+    /// [`OpCheckStatus`]: peace_cfg::OpCheckStatus
+    pub async fn exec_with_plan(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        states_saved: &StatesSaved,
+        ensure_plan: &EnsurePlan,
+    ) -> Result<CmdOutcome<StatesEnsured, E>, E> {
+        let states_desired = Self::states_desired_from_plan(cmd_ctx, ensure_plan)?;
+        Self::ensure_plan_verify(cmd_ctx, states_saved, &states_desired, ensure_plan).await?;
+
+        ApplyCmd::<E, O, PKeys, Ensured, EnsuredDry>::exec_with_states_desired(
+            cmd_ctx,
+            states_saved,
+            &states_desired,
+            ApplyErrorMode::StopOnError,
+            HistoryCmd::Ensure,
+        )
+        .await
+    }
+
+    /// Returns the target state of each selected `ItemSpec` in the ensure plan.
+    fn states_desired_from_plan(
+        cmd_ctx: &CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        ensure_plan: &EnsurePlan,
+    ) -> Result<StatesDesired, E> {
+        let scope = cmd_ctx.scope();
+        let item_spec_ids_selected = scope.item_spec_ids_selected();
+        let mut states_desired_mut = StatesMut::<Desired>::new();
+        for item_spec in scope.flow().graph().iter() {
+            let item_spec_id = item_spec.id();
+            if let Some(item_spec_ids_selected) = item_spec_ids_selected {
+                if !item_spec_ids_selected.contains(item_spec_id) {
+                    continue;
+                }
+            }
+
+            let item_apply_planned = ensure_plan.get_raw(item_spec_id).ok_or_else(|| {
+                Error::EnsurePlanItemSpecNotFound {
+                    item_spec_id: item_spec_id.clone(),
+                }
+            })?;
+            states_desired_mut.insert_raw(item_spec_id.clone(), item_apply_planned.state_target());
+        }
+
+        Ok(StatesDesired::from(states_desired_mut))
+    }
+
+    /// Returns an error if any `ItemSpec`'s current state, state diff, or
+    /// `OpCheckStatus` differs from the ensure plan when checked towards its
+    /// planned target state.
+    async fn ensure_plan_verify(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        states_saved: &StatesSaved,
+        states_desired: &StatesDesired,
+        ensure_plan: &EnsurePlan,
+    ) -> Result<(), E> {
+        let CmdOutcome {
+            value: (_states_ensured_dry, item_applies),
+            errors,
+            ..
+        } = ApplyCmd::<E, O, PKeys, Ensured, EnsuredDry>::exec_dry_with_states_desired(
+            cmd_ctx,
+            states_saved,
+            states_desired,
+            ApplyErrorMode::StopOnError,
+        )
+        .await;
+        if let Some((_item_spec_id, error)) = errors.into_iter().next() {
+            return Err(error);
+        }
+
+        let mut item_spec_ids_stale = Vec::new();
+        for item_spec_id in states_desired.keys() {
+            let item_apply_planned = ensure_plan
+                .get_raw(item_spec_id)
+                .expect("unreachable: `states_desired` is built from the ensure plan.");
+            let matches_plan = match item_applies.get(item_spec_id) {
+                Some(item_apply) => {
+                    item_apply.op_check_status() == item_apply_planned.op_check_status()
+                        && Self::state_eq(
+                            &item_apply.state_current(),
+                            &item_apply_planned.state_current(),
+                        )?
+                        && Self::state_eq(
+                            &item_apply.state_diff(),
+                            &item_apply_planned.state_diff(),
+                        )?
+                }
+                // The item spec was not checked, e.g. because of an interruption.
+                None => false,
+            };
+            if !matches_plan {
+                item_spec_ids_stale.push(item_spec_id.clone());
+            }
+        }
+
+        if item_spec_ids_stale.is_empty() {
+            Ok(())
+        } else {
+            Err(E::from(Error::EnsurePlanStale {
+                item_spec_ids: item_spec_ids_stale,
+            }))
+        }
+    }
+
+    /// Returns whether two states are equal, by comparing their serialized
+    /// values.
+    fn state_eq(state_a: &BoxDtDisplay, state_b: &BoxDtDisplay) -> Result<bool, E> {
+        let state_a =
+            serde_yaml::to_value(state_a).map_err(|error| Error::StatesSerialize(error.into()))?;
+        let state_b =
+            serde_yaml::to_value(state_b).map_err(|error| Error::StatesSerialize(error.into()))?;
+
+        Ok(state_a == state_b)
     }
 }
 
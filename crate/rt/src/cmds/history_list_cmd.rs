@@ -0,0 +1,65 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use peace_cmd::{ctx::CmdCtx, scopes::SingleProfileSingleFlow};
+use peace_resources::{paths::ProfileHistoryDir, resources::ts::SetUp};
+use peace_rt_model::{
+    outcomes::HistorySummary, output::OutputWrite, params::ParamsKeys, Error, Storage,
+};
+
+use crate::history_records::HistoryRecords;
+
+/// Lists the `EnsureCmd` and `CleanCmd` executions recorded in the profile's
+/// history.
+#[derive(Debug)]
+pub struct HistoryListCmd<E, O, PKeys>(PhantomData<(E, O, PKeys)>);
+
+impl<E, O, PKeys> HistoryListCmd<E, O, PKeys>
+where
+    E: std::error::Error + From<Error> + Send + 'static,
+    PKeys: ParamsKeys + 'static,
+    O: OutputWrite<E>,
+{
+    /// Presents the [`HistorySummary`] of each record in the profile's
+    /// history, ordered from oldest to newest.
+    ///
+    /// Records of every flow in the profile are listed.
+    pub async fn exec(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+    ) -> Result<Vec<HistorySummary>, E> {
+        let history_summaries_result = Self::summaries_read(cmd_ctx).await;
+
+        let output = cmd_ctx.output_mut();
+        match history_summaries_result {
+            Ok(history_summaries) => {
+                output.present(&history_summaries).await?;
+                Ok(history_summaries)
+            }
+            Err(e) => {
+                output.write_err(&e).await?;
+                Err(e)
+            }
+        }
+    }
+
+    async fn summaries_read(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+    ) -> Result<Vec<HistorySummary>, E> {
+        let resources = cmd_ctx.resources();
+        let profile_history_dir = resources.borrow::<ProfileHistoryDir>();
+        let storage = resources.borrow::<Storage>();
+
+        let history_summaries =
+            HistoryRecords::summaries_read(&storage, &profile_history_dir).await?;
+
+        drop(profile_history_dir);
+        drop(storage);
+
+        Ok(history_summaries)
+    }
+}
+
+impl<E, O, PKeys> Default for HistoryListCmd<E, O, PKeys> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
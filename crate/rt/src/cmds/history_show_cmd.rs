@@ -0,0 +1,79 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use peace_cmd::{ctx::CmdCtx, scopes::SingleProfileSingleFlow};
+use peace_resources::{paths::ProfileHistoryDir, resources::ts::SetUp};
+use peace_rt_model::{
+    outcomes::HistoryRecord, output::OutputWrite, params::ParamsKeys, Error, Storage,
+};
+
+use crate::history_records::HistoryRecords;
+
+/// Shows an `EnsureCmd` or `CleanCmd` execution recorded in the profile's
+/// history.
+#[derive(Debug)]
+pub struct HistoryShowCmd<E, O, PKeys>(PhantomData<(E, O, PKeys)>);
+
+impl<E, O, PKeys> HistoryShowCmd<E, O, PKeys>
+where
+    E: std::error::Error + From<Error> + Send + 'static,
+    PKeys: ParamsKeys + 'static,
+    O: OutputWrite<E>,
+{
+    /// Presents the [`HistoryRecord`] with the given index, including the
+    /// [`ItemApply`] and error of each item.
+    ///
+    /// The record must have been recorded for the flow in the `cmd_ctx`, as
+    /// the flow's item spec types are needed to deserialize the
+    /// `ItemApply`s.
+    ///
+    /// [`ItemApply`]: peace_rt_model::outcomes::ItemApply
+    pub async fn exec(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        index: u32,
+    ) -> Result<HistoryRecord, E> {
+        let history_record_result = Self::record_read(cmd_ctx, index).await;
+
+        let output = cmd_ctx.output_mut();
+        match history_record_result {
+            Ok(history_record) => {
+                output.present(&history_record).await?;
+                Ok(history_record)
+            }
+            Err(e) => {
+                output.write_err(&e).await?;
+                Err(e)
+            }
+        }
+    }
+
+    pub(crate) async fn record_read(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        index: u32,
+    ) -> Result<HistoryRecord, E> {
+        let flow_id = cmd_ctx.scope().flow().flow_id();
+        let item_apply_type_reg = cmd_ctx.scope().states_type_regs().item_apply_type_reg();
+        let resources = cmd_ctx.resources();
+        let profile_history_dir = resources.borrow::<ProfileHistoryDir>();
+        let storage = resources.borrow::<Storage>();
+
+        let history_record = HistoryRecords::record_read(
+            &storage,
+            &profile_history_dir,
+            item_apply_type_reg,
+            flow_id,
+            index,
+        )
+        .await?;
+
+        drop(profile_history_dir);
+        drop(storage);
+
+        Ok(history_record)
+    }
+}
+
+impl<E, O, PKeys> Default for HistoryShowCmd<E, O, PKeys> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
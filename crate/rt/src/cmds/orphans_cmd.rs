@@ -0,0 +1,153 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use peace_cmd::{
+    ctx::CmdCtx,
+    scopes::{SingleProfileSingleFlow, SingleProfileSingleFlowView},
+};
+use peace_resources::{
+    paths::{FlowDir, StatesSavedFile},
+    resources::ts::SetUp,
+    states::{StatesCleaned, StatesCleanedDry, StatesSaved},
+    type_reg::untagged::TypeMap,
+    Resources,
+};
+use peace_rt_model::{
+    outcomes::CmdOutcome, output::OutputWrite, params::ParamsKeys, storage::SerializedStorage,
+    Error, StatesSerializer, Storage,
+};
+
+use crate::cmds::{sub::StatesSavedReadCmd, CleanCmd};
+
+/// Lists and cleans items whose states are saved, but whose item specs are no
+/// longer in the flow.
+///
+/// When an item spec is removed from a flow, its state remains in
+/// `states_saved.yaml` as a [`StateOrphaned`], and the resources it created
+/// are not cleaned by [`CleanCmd`].
+///
+/// [`StateOrphaned`]: peace_resources::states::StateOrphaned
+#[derive(Debug)]
+pub struct OrphansCmd<E, O, PKeys>(PhantomData<(E, O, PKeys)>);
+
+impl<E, O, PKeys> OrphansCmd<E, O, PKeys>
+where
+    E: std::error::Error + From<Error> + Send + 'static,
+    PKeys: ParamsKeys + 'static,
+    O: OutputWrite<E>,
+{
+    /// Displays and returns the orphaned states in `states_saved.yaml`.
+    ///
+    /// These are the saved states whose item spec ID is not in the flow's
+    /// graph.
+    ///
+    /// Either [`StatesCurrentDiscoverCmd`] or [`StatesDiscoverCmd`] must have
+    /// run prior to this command to read the saved states.
+    ///
+    /// [`StatesCurrentDiscoverCmd`]: crate::cmds::sub::StatesCurrentDiscoverCmd
+    /// [`StatesDiscoverCmd`]: crate::cmds::StatesDiscoverCmd
+    pub async fn list(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+    ) -> Result<StatesSaved, E> {
+        let states_orphaned_result = StatesSavedReadCmd::<E, O, PKeys>::exec(cmd_ctx)
+            .await
+            .map(|states_saved| Self::states_orphaned(&states_saved));
+
+        let output = cmd_ctx.output_mut();
+        match states_orphaned_result {
+            Ok(states_orphaned) => {
+                output.present(&states_orphaned).await?;
+                Ok(states_orphaned)
+            }
+            Err(e) => {
+                output.write_err(&e).await?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Runs [`CleanCmd::exec_dry`] with the retired item specs of orphaned
+    /// items.
+    ///
+    /// See [`Self::clean`] for the flow that `cmd_ctx` must be built with.
+    pub async fn clean_dry(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+    ) -> Result<CmdOutcome<StatesCleanedDry, E>, E> {
+        let states_saved = StatesSavedReadCmd::<E, O, PKeys>::exec(cmd_ctx).await?;
+
+        CleanCmd::<E, O, PKeys>::exec_dry(cmd_ctx, &states_saved).await
+    }
+
+    /// Cleans orphaned items using their retired item specs.
+    ///
+    /// `cmd_ctx` must be built with a flow that has the same ID as the
+    /// current flow, whose graph contains a *retired* item spec for each
+    /// orphaned item to clean. A retired item spec uses the ID of the item
+    /// spec that was removed from the flow, and only needs to be able to
+    /// discover and clean the item's resources.
+    ///
+    /// Items that are cleaned successfully are removed from
+    /// `states_saved.yaml`, so they are no longer listed as orphans. The
+    /// saved states of the current flow's item specs are orphans from the
+    /// perspective of the retired flow, and are retained.
+    ///
+    /// Either [`StatesCurrentDiscoverCmd`] or [`StatesDiscoverCmd`] must have
+    /// run prior to this command to read the saved states.
+    ///
+    /// [`StatesCurrentDiscoverCmd`]: crate::cmds::sub::StatesCurrentDiscoverCmd
+    /// [`StatesDiscoverCmd`]: crate::cmds::StatesDiscoverCmd
+    pub async fn clean(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+    ) -> Result<CmdOutcome<StatesCleaned, E>, E> {
+        let states_saved = StatesSavedReadCmd::<E, O, PKeys>::exec(cmd_ctx).await?;
+
+        let cmd_outcome = CleanCmd::<E, O, PKeys>::exec(cmd_ctx, &states_saved).await?;
+
+        let item_spec_ids_cleaned = cmd_outcome
+            .item_statuses
+            .iter()
+            .filter(|(_item_spec_id, item_apply_status)| item_apply_status.is_successful())
+            .map(|(item_spec_id, _item_apply_status)| item_spec_id);
+        let mut states_remaining = cmd_outcome.value.clone().into_inner();
+        item_spec_ids_cleaned.for_each(|item_spec_id| {
+            states_remaining.shift_remove(item_spec_id);
+        });
+
+        let SingleProfileSingleFlowView { resources, .. } = cmd_ctx.view();
+        Self::serialize_saved(resources, &StatesSaved::from(states_remaining)).await?;
+
+        Ok(cmd_outcome)
+    }
+
+    /// Returns the orphaned states within `states_saved`.
+    fn states_orphaned(states_saved: &StatesSaved) -> StatesSaved {
+        let mut states_orphaned = TypeMap::new_typed();
+        states_saved
+            .orphans()
+            .for_each(|(item_spec_id, state_orphaned)| {
+                states_orphaned.insert(item_spec_id.clone(), state_orphaned.clone());
+            });
+
+        StatesSaved::from(states_orphaned)
+    }
+
+    /// Writes the saved states that remain after cleaning to
+    /// `states_saved.yaml`.
+    async fn serialize_saved(
+        resources: &Resources<SetUp>,
+        states_saved: &StatesSaved,
+    ) -> Result<(), E> {
+        let flow_dir = resources.borrow::<FlowDir>();
+        let storage = resources.borrow::<Storage>();
+        let states_saved_file = StatesSavedFile::from((&*flow_dir, storage.format().extension()));
+
+        StatesSerializer::serialize(&*storage, states_saved, &states_saved_file).await?;
+
+        Ok(())
+    }
+}
+
+impl<E, O, PKeys> Default for OrphansCmd<E, O, PKeys> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
@@ -0,0 +1,165 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use futures::{StreamExt, TryStreamExt};
+use peace_cfg::Profile;
+use peace_cmd::{ctx::CmdCtx, scopes::MultiProfileSingleFlow};
+use peace_resources::{
+    internal::StateDiffsMut,
+    states::{StateDiffs, StatesSaved},
+    type_reg::untagged::BoxDtDisplay,
+};
+use peace_rt_model::{outcomes::ProfilesStateDiff, output::OutputWrite, params::ParamsKeys, Error};
+
+/// Diffs the saved states of two profiles.
+#[derive(Debug)]
+pub struct ProfilesDiffCmd<E, O, PKeys>(PhantomData<(E, O, PKeys)>);
+
+impl<E, O, PKeys> ProfilesDiffCmd<E, O, PKeys>
+where
+    E: std::error::Error + From<Error> + Send + 'static,
+    PKeys: ParamsKeys + 'static,
+    O: OutputWrite<E>,
+{
+    /// Runs [`StateDiffFnSpec`] for each [`ItemSpec`], between the saved
+    /// states of `profile_a` and `profile_b`.
+    ///
+    /// The diffs describe what is different in `profile_b` compared to
+    /// `profile_a`. Item specs with a saved state in only one profile are
+    /// reported as [`ProfilesStateDiff::Added`] or
+    /// [`ProfilesStateDiff::Removed`].
+    ///
+    /// Both profiles must be loaded by the `CmdCtx`, and either
+    /// [`StatesCurrentDiscoverCmd`] or [`StatesDiscoverCmd`] must have run for
+    /// each profile prior to this command to read the saved states.
+    ///
+    /// [`ItemSpec`]: peace_cfg::ItemSpec
+    /// [`StateDiffFnSpec`]: peace_cfg::ItemSpec::StateDiffFnSpec
+    /// [`StatesCurrentDiscoverCmd`]: crate::cmds::sub::StatesCurrentDiscoverCmd
+    /// [`StatesDiscoverCmd`]: crate::cmds::StatesDiscoverCmd
+    /// [`ProfilesStateDiff::Added`]: peace_rt_model::outcomes::ProfilesStateDiff::Added
+    /// [`ProfilesStateDiff::Removed`]: peace_rt_model::outcomes::ProfilesStateDiff::Removed
+    pub async fn exec(
+        cmd_ctx: &mut CmdCtx<MultiProfileSingleFlow<'_, E, O, PKeys>>,
+        profile_a: &Profile,
+        profile_b: &Profile,
+    ) -> Result<StateDiffs, E> {
+        let states_saved_pair_result =
+            Self::states_saved(cmd_ctx, profile_a).and_then(|states_saved_a| {
+                Self::states_saved(cmd_ctx, profile_b)
+                    .map(|states_saved_b| (states_saved_a.clone(), states_saved_b.clone()))
+            });
+        let (states_saved_a, states_saved_b) = match states_saved_pair_result {
+            Ok(states_saved_pair) => states_saved_pair,
+            Err(e) => {
+                cmd_ctx.output_mut().write_err(&e).await?;
+                return Err(e);
+            }
+        };
+
+        Self::exec_with_states_saved(cmd_ctx, profile_b, &states_saved_a, &states_saved_b).await
+    }
+
+    /// Runs [`StateDiffFnSpec`] for each [`ItemSpec`], between the given
+    /// states.
+    ///
+    /// This may be used to diff freshly discovered states, by discovering
+    /// each profile's current states using a `SingleProfileSingleFlow`
+    /// `CmdCtx`, and passing in `StatesSaved::from(states_current)`.
+    ///
+    /// The `StateDiffFnSpec`s are run using `profile_b`'s resources.
+    ///
+    /// [`ItemSpec`]: peace_cfg::ItemSpec
+    /// [`StateDiffFnSpec`]: peace_cfg::ItemSpec::StateDiffFnSpec
+    pub async fn exec_with_states_saved(
+        cmd_ctx: &mut CmdCtx<MultiProfileSingleFlow<'_, E, O, PKeys>>,
+        profile_b: &Profile,
+        states_saved_a: &StatesSaved,
+        states_saved_b: &StatesSaved,
+    ) -> Result<StateDiffs, E> {
+        let state_diffs_result =
+            Self::state_diffs_discover(cmd_ctx, profile_b, states_saved_a, states_saved_b).await;
+
+        let output = cmd_ctx.output_mut();
+        match state_diffs_result {
+            Ok(state_diffs) => {
+                output.present(&state_diffs).await?;
+                Ok(state_diffs)
+            }
+            Err(e) => {
+                output.write_err(&e).await?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Returns the saved states of the given profile.
+    fn states_saved<'ctx>(
+        cmd_ctx: &'ctx CmdCtx<MultiProfileSingleFlow<'_, E, O, PKeys>>,
+        profile: &Profile,
+    ) -> Result<&'ctx StatesSaved, E> {
+        let states_saved_opt = cmd_ctx
+            .profile_to_states_saved()
+            .get(profile)
+            .ok_or_else(|| Error::ProfileNotInScope {
+                profile: profile.clone(),
+                profiles_in_scope: cmd_ctx.profiles().to_vec(),
+            })?;
+
+        states_saved_opt.as_ref().ok_or_else(|| {
+            E::from(Error::ProfileStatesCurrentDiscoverRequired {
+                profile: profile.clone(),
+            })
+        })
+    }
+
+    async fn state_diffs_discover(
+        cmd_ctx: &mut CmdCtx<MultiProfileSingleFlow<'_, E, O, PKeys>>,
+        profile_b: &Profile,
+        states_saved_a: &StatesSaved,
+        states_saved_b: &StatesSaved,
+    ) -> Result<StateDiffs, E> {
+        let resources = cmd_ctx.profile_resources(profile_b).await?;
+        let item_spec_graph = cmd_ctx.flow().graph();
+
+        let resources_ref = &resources;
+        let state_diffs_mut = item_spec_graph
+            .stream()
+            .map(Result::<_, E>::Ok)
+            .try_filter_map(|item_spec| async move {
+                let item_spec_id = item_spec.id();
+                let state_diff_opt = match (
+                    states_saved_a.get_raw(item_spec_id),
+                    states_saved_b.get_raw(item_spec_id),
+                ) {
+                    (Some(_), Some(_)) => {
+                        item_spec
+                            .state_diff_exec_with_states_saved_pair(
+                                resources_ref,
+                                states_saved_a,
+                                states_saved_b,
+                            )
+                            .await?
+                    }
+                    (None, Some(state_b)) => Some(BoxDtDisplay::new(ProfilesStateDiff::Added {
+                        state: state_b.clone(),
+                    })),
+                    (Some(state_a), None) => Some(BoxDtDisplay::new(ProfilesStateDiff::Removed {
+                        state: state_a.clone(),
+                    })),
+                    (None, None) => None,
+                };
+
+                Ok(state_diff_opt.map(|state_diff| (item_spec_id.clone(), state_diff)))
+            })
+            .try_collect::<StateDiffsMut>()
+            .await?;
+
+        Ok(StateDiffs::from(state_diffs_mut))
+    }
+}
+
+impl<E, O, PKeys> Default for ProfilesDiffCmd<E, O, PKeys> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
@@ -0,0 +1,170 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use peace_cmd::{ctx::CmdCtx, scopes::SingleProfileSingleFlow};
+use peace_resources::{
+    internal::StateDiffsMut,
+    resources::ts::SetUp,
+    states::{
+        ts::{Ensured, EnsuredDry},
+        StateDiffs, StatesDesired, StatesEnsured, StatesSaved,
+    },
+};
+use peace_rt_model::{
+    outcomes::{CmdOutcome, HistoryCmd},
+    output::OutputWrite,
+    params::ParamsKeys,
+    Error,
+};
+
+use crate::cmds::sub::{ApplyCmd, ApplyErrorMode};
+
+/// Ensures items towards the desired states of a previous execution.
+///
+/// The desired states may come from a record in the profile's history, or
+/// from a previous `states_desired.yaml` snapshot.
+#[derive(Debug)]
+pub struct RollbackCmd<E, O, PKeys>(PhantomData<(E, O, PKeys)>);
+
+impl<E, O, PKeys> RollbackCmd<E, O, PKeys>
+where
+    E: std::error::Error + From<Error> + Send + 'static,
+    PKeys: ParamsKeys + 'static,
+    O: OutputWrite<E>,
+{
+    /// Presents the diff between each item's current state and its target
+    /// state in the history record with the given index, without applying
+    /// any changes.
+    ///
+    /// The target state of each item is the desired state that the recorded
+    /// execution applied the item towards.
+    ///
+    /// Records of clean executions cannot be rolled back to, and return
+    /// [`Error::RollbackHistoryRecordClean`].
+    pub async fn exec_dry(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        states_saved: &StatesSaved,
+        index: u32,
+    ) -> Result<CmdOutcome<StateDiffs, E>, E> {
+        let states_desired = Self::states_desired_from_history(cmd_ctx, index).await?;
+        Self::exec_dry_with_states_desired(cmd_ctx, states_saved, &states_desired).await
+    }
+
+    /// Ensures each item towards its target state in the history record with
+    /// the given index.
+    ///
+    /// The diff from a dry run is presented before the items are applied.
+    /// Items that are not in the history record are not applied.
+    ///
+    /// Records of clean executions cannot be rolled back to, and return
+    /// [`Error::RollbackHistoryRecordClean`].
+    pub async fn exec(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        states_saved: &StatesSaved,
+        index: u32,
+    ) -> Result<CmdOutcome<StatesEnsured, E>, E> {
+        let states_desired = Self::states_desired_from_history(cmd_ctx, index).await?;
+        Self::exec_with_states_desired(cmd_ctx, states_saved, &states_desired).await
+    }
+
+    /// Presents the diff between each item's current state and its state in
+    /// `states_desired`, without applying any changes.
+    pub async fn exec_dry_with_states_desired(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        states_saved: &StatesSaved,
+        states_desired: &StatesDesired,
+    ) -> Result<CmdOutcome<StateDiffs, E>, E> {
+        let CmdOutcome {
+            value: (_states_ensured_dry, item_applies),
+            errors,
+            retries,
+            item_statuses,
+            interrupted,
+            history_write_error,
+        } = ApplyCmd::<E, O, PKeys, Ensured, EnsuredDry>::exec_dry_with_states_desired(
+            cmd_ctx,
+            states_saved,
+            states_desired,
+            ApplyErrorMode::StopOnError,
+        )
+        .await;
+
+        let mut state_diffs_mut = StateDiffsMut::new();
+        state_diffs_mut.extend(item_applies.iter().map(|(item_spec_id, item_apply)| {
+            (item_spec_id.clone(), item_apply.state_diff())
+        }));
+        let state_diffs = StateDiffs::from(state_diffs_mut);
+        cmd_ctx.output_mut().present(&state_diffs).await?;
+
+        Ok(CmdOutcome {
+            value: state_diffs,
+            errors,
+            retries,
+            item_statuses,
+            interrupted,
+            history_write_error,
+        })
+    }
+
+    /// Ensures each item towards its state in `states_desired`.
+    ///
+    /// The diff from a dry run is presented before the items are applied.
+    /// Items that do not have a state in `states_desired` are not applied.
+    ///
+    /// If the dry run fails for any item, no items are applied, and the
+    /// error is returned.
+    pub async fn exec_with_states_desired(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        states_saved: &StatesSaved,
+        states_desired: &StatesDesired,
+    ) -> Result<CmdOutcome<StatesEnsured, E>, E> {
+        let CmdOutcome { errors, .. } =
+            Self::exec_dry_with_states_desired(cmd_ctx, states_saved, states_desired).await?;
+        if let Some((_item_spec_id, error)) = errors.into_iter().next() {
+            return Err(error);
+        }
+
+        ApplyCmd::<E, O, PKeys, Ensured, EnsuredDry>::exec_with_states_desired(
+            cmd_ctx,
+            states_saved,
+            states_desired,
+            ApplyErrorMode::StopOnError,
+            HistoryCmd::Rollback,
+        )
+        .await
+    }
+
+    /// Returns the target state of each item in the history record with the
+    /// given index.
+    ///
+    /// The target states of a clean execution are the cleaned states, so
+    /// records of clean executions are rejected.
+    async fn states_desired_from_history(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        index: u32,
+    ) -> Result<StatesDesired, E> {
+        use peace_resources::{internal::StatesMut, states::ts::Desired};
+
+        use crate::cmds::HistoryShowCmd;
+
+        let history_record = HistoryShowCmd::<E, O, PKeys>::record_read(cmd_ctx, index).await?;
+        if history_record.summary.cmd == HistoryCmd::Clean {
+            return Err(E::from(Error::RollbackHistoryRecordClean { index }));
+        }
+
+        let mut states_desired_mut = StatesMut::<Desired>::new();
+        history_record
+            .item_applies
+            .iter()
+            .for_each(|(item_spec_id, item_apply)| {
+                states_desired_mut.insert_raw(item_spec_id.clone(), item_apply.state_target());
+            });
+
+        Ok(StatesDesired::from(states_desired_mut))
+    }
+}
+
+impl<E, O, PKeys> Default for RollbackCmd<E, O, PKeys> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
@@ -1,9 +1,16 @@
-use std::{fmt::Debug, marker::PhantomData};
+use std::{
+    collections::HashSet, fmt::Debug, marker::PhantomData, pin::pin, sync::Mutex,
+    time::Duration,
+};
 
-use peace_cfg::{ItemSpecId, OpCtx};
+use futures::{
+    future::{self, Either},
+    FutureExt,
+};
+use peace_cfg::{InterruptSignal, ItemSpecId, OpCtx};
 use peace_cmd::{
     ctx::CmdCtx,
-    scopes::{SingleProfileSingleFlow, SingleProfileSingleFlowView},
+    scopes::{MultiProfileSingleFlow, SingleProfileSingleFlow, SingleProfileSingleFlowView},
 };
 use peace_resources::{
     internal::StatesMut,
@@ -16,10 +23,19 @@ use peace_resources::{
     type_reg::untagged::BoxDtDisplay,
     Resources,
 };
-use peace_rt_model::{output::OutputWrite, params::ParamsKeys, Error, IndexMap, Storage};
-use tokio::sync::mpsc;
+use peace_rt_model::{
+    outcomes::{CmdOutcome, ProfilesCmdOutcome},
+    output::OutputWrite,
+    params::ParamsKeys,
+    storage::SerializedStorage,
+    Error, InMemoryTextOutput, IndexMap, Storage,
+};
+use tokio::sync::{mpsc, mpsc::UnboundedSender};
 
-use crate::BUFFERED_FUTURES_MAX;
+use crate::{
+    concurrency_groups::ConcurrencyGroups, profiles_exec::ProfilesExec,
+    states_unselected::StatesUnselected, BUFFERED_FUTURES_MAX,
+};
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "output_progress")] {
@@ -33,6 +49,7 @@ cfg_if::cfg_if! {
             },
         };
         use peace_rt_model::CmdProgressTracker;
+        use tokio::sync::mpsc::Sender;
     }
 }
 
@@ -52,19 +69,20 @@ where
     /// `$flow_dir/states_saved.yaml`.
     ///
     /// If any `StateCurrentFnSpec` needs to read the `State` from a previous
-    /// `ItemSpec`, the predecessor should insert a copy / clone of their state
-    /// into `Resources`, and the successor should references it in their
-    /// [`Data`].
+    /// `ItemSpec`, the successor's params may be mapped from the predecessor's
+    /// state through [`ItemSpecWrapper::with_params_spec`], which is resolved
+    /// after the predecessor's state is discovered.
     ///
     /// [`try_exec`]: peace_cfg::TryFnSpec::try_exec
-    /// [`Data`]: peace_cfg::TryFnSpec::Data
+    /// [`ItemSpecWrapper::with_params_spec`]: peace_rt_model::ItemSpecWrapper::with_params_spec
     /// [`ItemSpec`]: peace_cfg::ItemSpec
     /// [`StateCurrentFnSpec`]: peace_cfg::ItemSpec::StateCurrentFnSpec
     pub async fn current(
         cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
     ) -> Result<StatesCurrent, E> {
-        Self::exec(cmd_ctx, DiscoverFor::Current)
+        Self::exec(cmd_ctx, DiscoverFor::Current, true)
             .await
+            .map(|cmd_outcome| cmd_outcome.value)
             .map(|(states_current, _states_desired)| states_current)
     }
 
@@ -75,19 +93,20 @@ where
     /// `$flow_dir/states_desired.yaml`.
     ///
     /// If any `StateDesiredFnSpec` needs to read the `State` from a previous
-    /// `ItemSpec`, the predecessor should insert a copy / clone of their state
-    /// into `Resources`, and the successor should references it in their
-    /// [`Data`].
+    /// `ItemSpec`, the successor's params may be mapped from the predecessor's
+    /// state through [`ItemSpecWrapper::with_params_spec`], which is resolved
+    /// after the predecessor's state is discovered.
     ///
     /// [`try_exec`]: peace_cfg::TryFnSpec::try_exec
-    /// [`Data`]: peace_cfg::TryFnSpec::Data
+    /// [`ItemSpecWrapper::with_params_spec`]: peace_rt_model::ItemSpecWrapper::with_params_spec
     /// [`ItemSpec`]: peace_cfg::ItemSpec
     /// [`StateDesiredFnSpec`]: peace_cfg::ItemSpec::StateDesiredFnSpec
     pub async fn desired(
         cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
     ) -> Result<StatesDesired, E> {
-        Self::exec(cmd_ctx, DiscoverFor::Desired)
+        Self::exec(cmd_ctx, DiscoverFor::Desired, true)
             .await
+            .map(|cmd_outcome| cmd_outcome.value)
             .map(|(_states_current, states_desired)| states_desired)
     }
 
@@ -100,23 +119,70 @@ where
     /// `$flow_dir/states_desired.yaml`.
     ///
     /// If any `StateCurrentFnSpec` needs to read the `State` from a previous
-    /// `ItemSpec`, the predecessor should insert a copy / clone of their state
-    /// into `Resources`, and the successor should references it in their
-    /// [`Data`].
+    /// `ItemSpec`, the successor's params may be mapped from the predecessor's
+    /// state through [`ItemSpecWrapper::with_params_spec`], which is resolved
+    /// after the predecessor's state is discovered.
     ///
     /// If any `StateDesiredFnSpec` needs to read the `State` from a previous
-    /// `ItemSpec`, the predecessor should insert a copy / clone of their state
-    /// into `Resources`, and the successor should references it in their
-    /// [`Data`].
+    /// `ItemSpec`, the successor's params may be mapped from the predecessor's
+    /// state through [`ItemSpecWrapper::with_params_spec`], which is resolved
+    /// after the predecessor's state is discovered.
     ///
     /// [`try_exec`]: peace_cfg::TryFnSpec::try_exec
-    /// [`Data`]: peace_cfg::TryFnSpec::Data
+    /// [`ItemSpecWrapper::with_params_spec`]: peace_rt_model::ItemSpecWrapper::with_params_spec
     /// [`ItemSpec`]: peace_cfg::ItemSpec
     /// [`StateDesiredFnSpec`]: peace_cfg::ItemSpec::StateDesiredFnSpec
     pub async fn current_and_desired(
         cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
     ) -> Result<(StatesCurrent, StatesDesired), E> {
-        Self::exec(cmd_ctx, DiscoverFor::CurrentAndDesired).await
+        Self::exec(cmd_ctx, DiscoverFor::CurrentAndDesired, true)
+            .await
+            .map(|cmd_outcome| cmd_outcome.value)
+    }
+
+    /// Same as [`Self::current_and_desired`], but returns the discovered states
+    /// in a [`CmdOutcome`], with the errors and the number of retries made
+    /// while discovering each item's states.
+    pub async fn current_and_desired_with_outcome(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+    ) -> Result<CmdOutcome<(StatesCurrent, StatesDesired), E>, E> {
+        Self::exec(cmd_ctx, DiscoverFor::CurrentAndDesired, true).await
+    }
+
+    /// Runs [`Self::current_and_desired`] for each profile of a
+    /// `MultiProfileSingleFlow` command.
+    ///
+    /// Profiles are discovered concurrently, up to the limit set by
+    /// `CmdCtx::with_profile_concurrency_limit`.
+    ///
+    /// An error that stops a profile's states from being discovered is recorded
+    /// in [`ProfilesCmdOutcome::profile_to_error`], and does not stop other
+    /// profiles' states from being discovered.
+    pub async fn current_and_desired_multi_profile(
+        cmd_ctx: &mut CmdCtx<MultiProfileSingleFlow<'_, E, O, PKeys>>,
+    ) -> Result<ProfilesCmdOutcome<(StatesCurrent, StatesDesired), E>, E> {
+        ProfilesExec::exec(cmd_ctx, |profile_cmd_ctx| {
+            StatesDiscoverCmd::<E, InMemoryTextOutput, PKeys>::current_and_desired_with_outcome(
+                profile_cmd_ctx,
+            )
+            .boxed_local()
+        })
+        .await
+    }
+
+    /// Runs [`StateCurrentFnSpec`]`::`[`try_exec`] for each [`ItemSpec`],
+    /// without serializing the states to `$flow_dir/states_saved.yaml`.
+    ///
+    /// [`try_exec`]: peace_cfg::TryFnSpec::try_exec
+    /// [`ItemSpec`]: peace_cfg::ItemSpec
+    /// [`StateCurrentFnSpec`]: peace_cfg::ItemSpec::StateCurrentFnSpec
+    pub(crate) async fn current_without_serialize(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+    ) -> Result<StatesCurrent, E> {
+        Self::exec(cmd_ctx, DiscoverFor::Current, false)
+            .await
+            .map(|cmd_outcome| cmd_outcome.value)
+            .map(|(states_current, _states_desired)| states_current)
     }
 
     /// Runs [`StateCurrentFnSpec`]`::`[`try_exec`] for each [`ItemSpec`].
@@ -126,27 +192,52 @@ where
     /// `$flow_dir/states_saved.yaml`.
     ///
     /// If any `StateCurrentFnSpec` needs to read the `State` from a previous
-    /// `ItemSpec`, the predecessor should insert a copy / clone of their state
-    /// into `Resources`, and the successor should references it in their
-    /// [`Data`].
+    /// `ItemSpec`, the successor's params may be mapped from the predecessor's
+    /// state through [`ItemSpecWrapper::with_params_spec`], which is resolved
+    /// after the predecessor's state is discovered.
+    ///
+    /// If an item spec selection is set on the `CmdCtx`, only the selected
+    /// item specs' states are discovered and returned, and the previously
+    /// written states of the other item specs are retained in storage.
     ///
+    /// If `serialize` is `false`, the discovered states are not written to
+    /// storage.
+    ///
+    /// If a timeout is set through [`CmdCtx::with_timeout`], and it elapses
+    /// before all states are discovered, no further states are discovered,
+    /// and each `ItemSpec` whose states are being discovered fails with
+    /// [`Error::CmdTimeout`].
+    ///
+    /// If the [`InterruptSignal`] in `Resources` is interrupted, no further
+    /// states are discovered, and `ItemSpec`s whose states are being
+    /// discovered are allowed to complete. The previously written states of
+    /// `ItemSpec`s that were not reached are retained in storage, and
+    /// [`CmdOutcome::interrupted`] is set.
+    ///
+    /// [`CmdCtx::with_timeout`]: peace_cmd::ctx::CmdCtx::with_timeout
     /// [`try_exec`]: peace_cfg::TryFnSpec::try_exec
-    /// [`Data`]: peace_cfg::TryFnSpec::Data
+    /// [`ItemSpecWrapper::with_params_spec`]: peace_rt_model::ItemSpecWrapper::with_params_spec
     /// [`ItemSpec`]: peace_cfg::ItemSpec
     /// [`StateCurrentFnSpec`]: peace_cfg::ItemSpec::StateCurrentFnSpec
     async fn exec(
         cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
         discover_for: DiscoverFor,
-    ) -> Result<(StatesCurrent, StatesDesired), E> {
+        serialize: bool,
+    ) -> Result<CmdOutcome<(StatesCurrent, StatesDesired), E>, E> {
         let SingleProfileSingleFlowView {
             #[cfg(feature = "output_progress")]
             output,
             #[cfg(feature = "output_progress")]
             cmd_progress_tracker,
             flow,
+            states_type_regs,
+            timeout,
+            concurrency_limit,
+            item_spec_ids_selected,
             resources,
             ..
         } = cmd_ctx.scope_mut().view();
+        let concurrency_limit = concurrency_limit.unwrap_or(BUFFERED_FUTURES_MAX);
 
         cfg_if::cfg_if! {
             if #[cfg(feature = "output_progress")] {
@@ -164,47 +255,92 @@ where
 
         let (outcomes_tx, mut outcomes_rx) = mpsc::unbounded_channel::<ItemDiscoverOutcome<E>>();
 
+        let interrupt_signal = InterruptSignal::clone(&resources.borrow::<InterruptSignal>());
         let resources_ref = &*resources;
+        let concurrency_groups = ConcurrencyGroups::new(flow.graph());
+        let item_spec_ids_in_progress = Mutex::new(Vec::<ItemSpecId>::new());
         let execution_task = async move {
+            let interrupt_signal = &interrupt_signal;
             #[cfg(feature = "output_progress")]
             let progress_tx = &progress_tx;
             let outcomes_tx = &outcomes_tx;
+            let concurrency_groups = &concurrency_groups;
+            let item_spec_ids_in_progress = &item_spec_ids_in_progress;
 
-            flow.graph()
-                .for_each_concurrent(BUFFERED_FUTURES_MAX, |item_spec| async move {
+            let graph_task = flow
+                .graph()
+                .try_for_each_concurrent(concurrency_limit, |item_spec| async move {
                     let item_spec_id = item_spec.id();
+                    if let Some(item_spec_ids_selected) = item_spec_ids_selected {
+                        if !item_spec_ids_selected.contains(item_spec_id) {
+                            return Ok(());
+                        }
+                    }
+
+                    let _concurrency_group_permit = concurrency_groups.acquire(item_spec).await;
+
+                    // Don't begin discovering any more states once interrupted.
+                    //
+                    // Returning `Err` stops the graph from scheduling successors, and states
+                    // for item specs that have completed are still collected.
+                    if interrupt_signal.is_interrupted() {
+                        outcomes_tx
+                            .send(ItemDiscoverOutcome::Interrupted)
+                            .expect("unreachable: `outcomes_rx` is in a sibling task.");
+
+                        return Err(());
+                    }
+
+                    item_spec_ids_in_progress
+                        .lock()
+                        .expect(
+                            "unreachable: `item_spec_ids_in_progress` lock is not held across an await.",
+                        )
+                        .push(item_spec_id.clone());
+
                     let op_ctx = OpCtx::new(
                         item_spec_id,
+                        interrupt_signal,
                         #[cfg(feature = "output_progress")]
                         ProgressSender::new(item_spec_id, progress_tx),
                     );
 
-                    let (state_current_result, state_desired_result) = match discover_for {
+                    let (state_current_outcome, state_desired_outcome) = match discover_for {
                         DiscoverFor::Current => {
-                            let state_current_result = item_spec
-                                .state_current_try_exec(op_ctx, resources_ref)
-                                .await;
+                            let state_current_outcome =
+                                item_spec.state_current_try_exec(op_ctx, resources_ref).await;
 
-                            (Some(state_current_result), None)
+                            (Some(state_current_outcome), None)
                         }
                         DiscoverFor::Desired => {
-                            let state_desired_result = item_spec
-                                .state_desired_try_exec(op_ctx, resources_ref)
-                                .await;
+                            let state_desired_outcome =
+                                item_spec.state_desired_try_exec(op_ctx, resources_ref).await;
 
-                            (None, Some(state_desired_result))
+                            (None, Some(state_desired_outcome))
                         }
                         DiscoverFor::CurrentAndDesired => {
-                            let state_current_result = item_spec
-                                .state_current_try_exec(op_ctx, resources_ref)
-                                .await;
-                            let state_desired_result = item_spec
-                                .state_desired_try_exec(op_ctx, resources_ref)
-                                .await;
-
-                            (Some(state_current_result), Some(state_desired_result))
+                            let state_current_outcome =
+                                item_spec.state_current_try_exec(op_ctx, resources_ref).await;
+                            let state_desired_outcome =
+                                item_spec.state_desired_try_exec(op_ctx, resources_ref).await;
+
+                            (Some(state_current_outcome), Some(state_desired_outcome))
                         }
                     };
+                    let retry_count = state_current_outcome
+                        .iter()
+                        .map(|outcome| outcome.retry_count)
+                        .chain(state_desired_outcome.iter().map(|outcome| outcome.retry_count))
+                        .sum::<u32>();
+                    let state_current_result = state_current_outcome.map(|outcome| outcome.result);
+                    let state_desired_result = state_desired_outcome.map(|outcome| outcome.result);
+
+                    item_spec_ids_in_progress
+                        .lock()
+                        .expect(
+                            "unreachable: `item_spec_ids_in_progress` lock is not held across an await.",
+                        )
+                        .retain(|item_spec_id_in_progress| item_spec_id_in_progress != item_spec_id);
 
                     let state_current = if let Some(state_current_result) = state_current_result {
                         #[cfg(feature = "output_progress")]
@@ -232,10 +368,11 @@ where
                                         item_spec_id: item_spec_id.clone(),
                                         state_current: None,
                                         state_desired: None,
+                                        retry_count,
                                         error,
                                     })
                                     .expect("unreachable: `outcomes_rx` is in a sibling task.");
-                                return; // short circuit
+                                return Ok(()); // short circuit
                             }
                         }
                     } else {
@@ -268,10 +405,11 @@ where
                                         item_spec_id: item_spec_id.clone(),
                                         state_current,
                                         state_desired: None,
+                                        retry_count,
                                         error,
                                     })
                                     .expect("unreachable: `outcomes_rx` is in a sibling task.");
-                                return; // short circuit
+                                return Ok(()); // short circuit
                             }
                         }
                     } else {
@@ -283,10 +421,34 @@ where
                             item_spec_id: item_spec_id.clone(),
                             state_current,
                             state_desired,
+                            retry_count,
                         })
                         .expect("unreachable: `outcomes_rx` is in a sibling task.");
-                })
-                .await;
+
+                    Ok(())
+                });
+            let graph_task = async move {
+                let (Ok(()) | Err(())) = graph_task.await.map_err(|_vec_units: Vec<()>| ());
+            };
+
+            match timeout {
+                Some(timeout) => {
+                    let graph_task = pin!(graph_task);
+                    let timeout_task = pin!(peace_rt_model::time::sleep(timeout));
+                    if let Either::Right(((), _graph_task)) =
+                        future::select(graph_task, timeout_task).await
+                    {
+                        Self::item_spec_ids_in_progress_timeout(
+                            #[cfg(feature = "output_progress")]
+                            progress_tx,
+                            outcomes_tx,
+                            item_spec_ids_in_progress,
+                            timeout,
+                        );
+                    }
+                }
+                None => graph_task.await,
+            }
 
             // `progress_tx` is dropped here, so `progress_rx` will safely end.
         };
@@ -296,6 +458,9 @@ where
             crate::progress::Progress::progress_render(output, progress_trackers, progress_rx);
 
         let mut errors = IndexMap::<ItemSpecId, E>::new();
+        let mut retries = IndexMap::<ItemSpecId, u32>::new();
+        let mut item_spec_ids_discovered = HashSet::<ItemSpecId>::new();
+        let mut interrupted = false;
         let outcomes_rx_task = async {
             let mut states_current_mut = StatesMut::<Current>::new();
             let mut states_desired_mut = StatesMut::<Desired>::new();
@@ -306,7 +471,12 @@ where
                         item_spec_id,
                         state_current,
                         state_desired,
+                        retry_count,
                     } => {
+                        item_spec_ids_discovered.insert(item_spec_id.clone());
+                        if retry_count > 0 {
+                            retries.insert(item_spec_id.clone(), retry_count);
+                        }
                         if let Some(state_current) = state_current {
                             states_current_mut.insert_raw(item_spec_id.clone(), state_current);
                         }
@@ -318,9 +488,14 @@ where
                         item_spec_id,
                         state_current,
                         state_desired,
+                        retry_count,
                         error,
                     } => {
+                        item_spec_ids_discovered.insert(item_spec_id.clone());
                         errors.insert(item_spec_id.clone(), error);
+                        if retry_count > 0 {
+                            retries.insert(item_spec_id.clone(), retry_count);
+                        }
 
                         if let Some(state_current) = state_current {
                             states_current_mut.insert_raw(item_spec_id.clone(), state_current);
@@ -329,6 +504,9 @@ where
                             states_desired_mut.insert_raw(item_spec_id, state_desired);
                         }
                     }
+                    ItemDiscoverOutcome::Interrupted => {
+                        interrupted = true;
+                    }
                 }
             }
 
@@ -348,21 +526,134 @@ where
             }
         }
         let (states_current, states_desired) = outcomes_result;
+        if !serialize {
+            return Ok(CmdOutcome {
+                value: (states_current, states_desired),
+                errors,
+                retries,
+                item_statuses: IndexMap::new(),
+                interrupted,
+                history_write_error: None,
+            });
+        }
+
+        // When only some item specs are run, or the command was interrupted, retain the
+        // previously written states of the other item specs. Orphaned states are
+        // always retained.
+        let item_spec_ids_selected = if interrupted {
+            Some(&item_spec_ids_discovered)
+        } else {
+            item_spec_ids_selected
+        };
+        let (states_current_write, states_desired_write) = match item_spec_ids_selected {
+            Some(item_spec_ids_selected) => {
+                let states_current_write = match discover_for {
+                    DiscoverFor::Current | DiscoverFor::CurrentAndDesired => {
+                        let states_saved =
+                            StatesUnselected::saved_read_opt(resources, states_type_regs).await?;
+                        StatesUnselected::merge(
+                            &states_current,
+                            states_saved,
+                            item_spec_ids_selected,
+                        )
+                    }
+                    DiscoverFor::Desired => states_current.clone(),
+                };
+                let states_desired_write = match discover_for {
+                    DiscoverFor::Desired | DiscoverFor::CurrentAndDesired => {
+                        let states_desired_previous =
+                            StatesUnselected::desired_read_opt(resources, states_type_regs).await?;
+                        StatesUnselected::merge(
+                            &states_desired,
+                            states_desired_previous,
+                            item_spec_ids_selected,
+                        )
+                    }
+                    DiscoverFor::Current => states_desired.clone(),
+                };
+
+                (states_current_write, states_desired_write)
+            }
+            None => {
+                let states_current_write = match discover_for {
+                    DiscoverFor::Current | DiscoverFor::CurrentAndDesired => {
+                        let states_saved =
+                            StatesUnselected::saved_read_opt(resources, states_type_regs).await?;
+                        StatesUnselected::merge_orphans(&states_current, states_saved)
+                    }
+                    DiscoverFor::Desired => states_current.clone(),
+                };
+                let states_desired_write = match discover_for {
+                    DiscoverFor::Desired | DiscoverFor::CurrentAndDesired => {
+                        let states_desired_previous =
+                            StatesUnselected::desired_read_opt(resources, states_type_regs).await?;
+                        StatesUnselected::merge_orphans(&states_desired, states_desired_previous)
+                    }
+                    DiscoverFor::Current => states_desired.clone(),
+                };
+
+                (states_current_write, states_desired_write)
+            }
+        };
 
         match discover_for {
             DiscoverFor::Current => {
-                Self::serialize_current(resources, &states_current).await?;
+                Self::serialize_current(resources, &states_current_write).await?;
             }
             DiscoverFor::Desired => {
-                Self::serialize_desired(resources, &states_desired).await?;
+                Self::serialize_desired(resources, &states_desired_write).await?;
             }
             DiscoverFor::CurrentAndDesired => {
-                Self::serialize_current(resources, &states_current).await?;
-                Self::serialize_desired(resources, &states_desired).await?;
+                Self::serialize_current(resources, &states_current_write).await?;
+                Self::serialize_desired(resources, &states_desired_write).await?;
             }
         }
 
-        Ok((states_current, states_desired))
+        Ok(CmdOutcome {
+            value: (states_current, states_desired),
+            errors,
+            retries,
+            item_statuses: IndexMap::new(),
+            interrupted,
+            history_write_error: None,
+        })
+    }
+
+    /// Sends a [`Error::CmdTimeout`] failure for each item spec whose states
+    /// were being discovered when the command timeout elapsed.
+    fn item_spec_ids_in_progress_timeout(
+        #[cfg(feature = "output_progress")] progress_tx: &Sender<ProgressUpdateAndId>,
+        outcomes_tx: &UnboundedSender<ItemDiscoverOutcome<E>>,
+        item_spec_ids_in_progress: &Mutex<Vec<ItemSpecId>>,
+        timeout: Duration,
+    ) {
+        item_spec_ids_in_progress
+            .lock()
+            .expect("unreachable: `item_spec_ids_in_progress` lock is not held across an await.")
+            .drain(..)
+            .for_each(|item_spec_id| {
+                let error = E::from(Error::CmdTimeout {
+                    item_spec_id: item_spec_id.clone(),
+                    timeout,
+                });
+
+                #[cfg(feature = "output_progress")]
+                let _progress_send_unused = progress_tx.try_send(ProgressUpdateAndId {
+                    item_spec_id: item_spec_id.clone(),
+                    progress_update: ProgressUpdate::Complete(ProgressComplete::Fail),
+                    msg_update: ProgressMsgUpdate::Set(format!("{error}")),
+                });
+
+                outcomes_tx
+                    .send(ItemDiscoverOutcome::Fail {
+                        item_spec_id,
+                        state_current: None,
+                        state_desired: None,
+                        retry_count: 0,
+                        error,
+                    })
+                    .expect("unreachable: `outcomes_rx` is in a sibling task.");
+            });
     }
 
     // TODO: This duplicates a bit of code with `ApplyCmd`.
@@ -374,9 +665,9 @@ where
 
         let flow_dir = resources.borrow::<FlowDir>();
         let storage = resources.borrow::<Storage>();
-        let states_saved_file = StatesSavedFile::from(&*flow_dir);
+        let states_saved_file = StatesSavedFile::from((&*flow_dir, storage.format().extension()));
 
-        StatesSerializer::serialize(&storage, states_current, &states_saved_file).await?;
+        StatesSerializer::serialize(&*storage, states_current, &states_saved_file).await?;
 
         drop(flow_dir);
         drop(storage);
@@ -394,9 +685,10 @@ where
 
         let flow_dir = resources.borrow::<FlowDir>();
         let storage = resources.borrow::<Storage>();
-        let states_desired_file = StatesDesiredFile::from(&*flow_dir);
+        let states_desired_file =
+            StatesDesiredFile::from((&*flow_dir, storage.format().extension()));
 
-        StatesSerializer::serialize(&storage, states_desired, &states_desired_file).await?;
+        StatesSerializer::serialize(&*storage, states_desired, &states_desired_file).await?;
 
         drop(flow_dir);
         drop(storage);
@@ -420,14 +712,18 @@ enum ItemDiscoverOutcome<E> {
         item_spec_id: ItemSpecId,
         state_current: Option<BoxDtDisplay>,
         state_desired: Option<BoxDtDisplay>,
+        retry_count: u32,
     },
     /// Discover failed.
     Fail {
         item_spec_id: ItemSpecId,
         state_current: Option<BoxDtDisplay>,
         state_desired: Option<BoxDtDisplay>,
+        retry_count: u32,
         error: E,
     },
+    /// Discover was not started as the command was interrupted.
+    Interrupted,
 }
 
 /// Which states to discover.
@@ -0,0 +1,129 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use futures::{StreamExt, TryStreamExt};
+use peace_cfg::ItemSpecId;
+use peace_cmd::{
+    ctx::CmdCtx,
+    scopes::{SingleProfileSingleFlow, SingleProfileSingleFlowView},
+};
+use peace_resources::{
+    resources::ts::SetUp,
+    states::{StatesCurrent, StatesSaved},
+};
+use peace_rt_model::{
+    outcomes::{StateDrift, StateDrifts},
+    output::OutputWrite,
+    params::ParamsKeys,
+    Error, IndexMap,
+};
+
+use crate::cmds::{sub::StatesSavedReadCmd, StatesDiscoverCmd};
+
+/// Reports whether items' current states have drifted from their saved
+/// states.
+#[derive(Debug)]
+pub struct StatesDriftCmd<E, O, PKeys>(PhantomData<(E, O, PKeys)>);
+
+impl<E, O, PKeys> StatesDriftCmd<E, O, PKeys>
+where
+    E: std::error::Error + From<Error> + Send + 'static,
+    PKeys: ParamsKeys + 'static,
+    O: OutputWrite<E>,
+{
+    /// Discovers current states, and presents whether each [`ItemSpec`]'s
+    /// current state has drifted from its state in `states_saved.yaml`.
+    ///
+    /// The discovered states are not saved, so this may be run periodically to
+    /// detect changes made outside of the automation.
+    ///
+    /// Either [`StatesCurrentDiscoverCmd`] or [`StatesDiscoverCmd`] must have
+    /// run prior to this command to read the saved states.
+    ///
+    /// [`ItemSpec`]: peace_cfg::ItemSpec
+    /// [`StatesCurrentDiscoverCmd`]: crate::cmds::sub::StatesCurrentDiscoverCmd
+    /// [`StatesDiscoverCmd`]: crate::cmds::StatesDiscoverCmd
+    pub async fn exec(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+    ) -> Result<StateDrifts, E> {
+        Self::exec_internal(cmd_ctx, false).await
+    }
+
+    /// Same as [`Self::exec`], but also saves the discovered current states
+    /// to `states_saved.yaml`.
+    pub async fn exec_and_save(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+    ) -> Result<StateDrifts, E> {
+        Self::exec_internal(cmd_ctx, true).await
+    }
+
+    async fn exec_internal(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        save: bool,
+    ) -> Result<StateDrifts, E> {
+        let state_drifts_result = Self::state_drifts_discover(cmd_ctx, save).await;
+
+        let output = cmd_ctx.output_mut();
+        match state_drifts_result {
+            Ok(state_drifts) => {
+                output.present(&state_drifts).await?;
+                Ok(state_drifts)
+            }
+            Err(e) => {
+                output.write_err(&e).await?;
+                Err(e)
+            }
+        }
+    }
+
+    async fn state_drifts_discover(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        save: bool,
+    ) -> Result<StateDrifts, E> {
+        // Saved states must be read before current states are discovered, as
+        // discovering may overwrite them.
+        let states_saved = StatesSavedReadCmd::<E, O, PKeys>::exec(cmd_ctx).await?;
+        let states_current = if save {
+            StatesDiscoverCmd::<E, O, PKeys>::current(cmd_ctx).await?
+        } else {
+            StatesDiscoverCmd::<E, O, PKeys>::current_without_serialize(cmd_ctx).await?
+        };
+
+        let SingleProfileSingleFlowView {
+            flow,
+            item_spec_ids_selected,
+            resources,
+            ..
+        } = cmd_ctx.scope_mut().view();
+        let item_spec_graph = flow.graph();
+
+        let resources_ref = &*resources;
+        let states_saved_ref: &StatesSaved = &states_saved;
+        let states_current_ref: &StatesCurrent = &states_current;
+        let state_drifts = item_spec_graph
+            .stream()
+            .map(Result::<_, E>::Ok)
+            .try_filter_map(|item_spec| async move {
+                if let Some(item_spec_ids_selected) = item_spec_ids_selected {
+                    if !item_spec_ids_selected.contains(item_spec.id()) {
+                        return Ok(None);
+                    }
+                }
+
+                let state_drift = item_spec
+                    .state_drift_exec(resources_ref, states_saved_ref, states_current_ref)
+                    .await?;
+
+                Ok(Some((item_spec.id().clone(), state_drift)))
+            })
+            .try_collect::<IndexMap<ItemSpecId, StateDrift>>()
+            .await?;
+
+        Ok(StateDrifts::from(state_drifts))
+    }
+}
+
+impl<E, O, PKeys> Default for StatesDriftCmd<E, O, PKeys> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
@@ -0,0 +1,230 @@
+use std::{
+    fmt::Debug,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+use peace_cmd::{
+    ctx::CmdCtx,
+    scopes::{SingleProfileSingleFlow, SingleProfileSingleFlowView},
+};
+use peace_resources::{
+    internal::{FlowParamsFile, ProfileParamsFile, StorageFormatFile, WorkspaceParamsFile},
+    paths::{EnsurePlanFile, StatesDesiredFile, StatesSavedFile},
+    resources::ts::SetUp,
+};
+use peace_rt_model::{
+    output::OutputWrite,
+    params::ParamsKeys,
+    storage::{SerializedStorage, StorageFormat},
+    EnsurePlanSerializer, Error, StatesSerializer, Storage, WorkspaceInitializer,
+};
+
+use crate::history_records::HistoryRecords;
+
+/// Changes the storage format of a workspace, and rewrites the flow's params,
+/// states, plans, and history records in that format.
+///
+/// The format is recorded in the workspace, so subsequent commands read and
+/// write items in the new format. Items are renamed to have the new format's
+/// extension, e.g. `states_saved.yaml` is rewritten as `states_saved.json`, and
+/// the items with the previous format's extension are removed.
+///
+/// Items in any format are read, so items of other profiles and flows are
+/// still usable, and are rewritten in the new format when they are next
+/// written. Run this command for each flow to convert their items up front,
+/// e.g. to speed up reading large states.
+#[derive(Debug)]
+pub struct StorageFormatMigrateCmd<E, O, PKeys>(PhantomData<(E, O, PKeys)>);
+
+impl<E, O, PKeys> StorageFormatMigrateCmd<E, O, PKeys>
+where
+    E: std::error::Error + From<Error> + Send + 'static,
+    PKeys: ParamsKeys + 'static,
+    O: OutputWrite<E>,
+{
+    /// Changes the workspace's storage format, rewrites the workspace,
+    /// profile, and flow params, saved states, desired states, ensure plan,
+    /// and history records of the flow in that format, and presents the paths
+    /// of the rewritten items.
+    ///
+    /// Params are only rewritten if the `CmdCtx` is built with their keys.
+    ///
+    /// History records of other flows in the profile are not rewritten, as
+    /// they can only be deserialized by the flow that recorded them.
+    pub async fn exec(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        storage_format: StorageFormat,
+    ) -> Result<Vec<PathBuf>, E> {
+        let paths_migrated_result = Self::migrate(cmd_ctx, storage_format).await;
+
+        let output = cmd_ctx.output_mut();
+        match paths_migrated_result {
+            Ok(paths_migrated) => {
+                let paths_migrated_display = paths_migrated
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<String>>();
+                output.present(&paths_migrated_display).await?;
+                Ok(paths_migrated)
+            }
+            Err(e) => {
+                output.write_err(&e).await?;
+                Err(e)
+            }
+        }
+    }
+
+    async fn migrate(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        storage_format: StorageFormat,
+    ) -> Result<Vec<PathBuf>, E> {
+        let SingleProfileSingleFlowView {
+            workspace,
+            profile_dir,
+            profile_history_dir,
+            flow,
+            flow_dir,
+            workspace_params,
+            profile_params,
+            flow_params,
+            states_type_regs,
+            resources,
+            ..
+        } = cmd_ctx.scope_mut().view();
+        let flow_id = flow.flow_id();
+
+        // Items are read from the previous format's files, as items with
+        // another format's extension are read when they do not exist with this
+        // format's extension.
+        let storage = Storage::clone(&resources.borrow::<Storage>()).with_format(storage_format);
+        let storage_extension = storage_format.extension();
+
+        let mut paths_migrated = Vec::new();
+
+        let workspace_params_file =
+            WorkspaceParamsFile::from((workspace.dirs().peace_app_dir(), storage_extension));
+        let profile_params_file = ProfileParamsFile::from((profile_dir, storage_extension));
+        let flow_params_file = FlowParamsFile::from((flow_dir, storage_extension));
+        if !workspace_params.is_empty() {
+            WorkspaceInitializer::workspace_params_serialize(
+                &storage,
+                workspace_params,
+                &workspace_params_file,
+            )
+            .await?;
+            paths_migrated.push(workspace_params_file.to_path_buf());
+        }
+        if !profile_params.is_empty() {
+            WorkspaceInitializer::profile_params_serialize(
+                &storage,
+                profile_params,
+                &profile_params_file,
+            )
+            .await?;
+            paths_migrated.push(profile_params_file.to_path_buf());
+        }
+        if !flow_params.is_empty() {
+            WorkspaceInitializer::flow_params_serialize(&storage, flow_params, &flow_params_file)
+                .await?;
+            paths_migrated.push(flow_params_file.to_path_buf());
+        }
+
+        let states_saved_file = StatesSavedFile::from((flow_dir, storage_extension));
+        let states_saved = StatesSerializer::<E>::deserialize_saved_opt(
+            flow_id,
+            &storage,
+            states_type_regs.states_current_type_reg(),
+            &states_saved_file,
+        )
+        .await?;
+        if let Some(states_saved) = states_saved {
+            StatesSerializer::<E>::serialize(&storage, &states_saved, &states_saved_file).await?;
+            paths_migrated.push(states_saved_file.to_path_buf());
+        }
+
+        let states_desired_file = StatesDesiredFile::from((flow_dir, storage_extension));
+        let states_desired = StatesSerializer::<E>::deserialize_desired_opt(
+            flow_id,
+            &storage,
+            states_type_regs.states_desired_type_reg(),
+            &states_desired_file,
+        )
+        .await?;
+        if let Some(states_desired) = states_desired {
+            StatesSerializer::<E>::serialize(&storage, &states_desired, &states_desired_file)
+                .await?;
+            paths_migrated.push(states_desired_file.to_path_buf());
+        }
+
+        let ensure_plan_file = EnsurePlanFile::from((flow_dir, storage_extension));
+        if storage.item_contains(&ensure_plan_file).await? {
+            let ensure_plan = EnsurePlanSerializer::<E>::deserialize(
+                flow_id,
+                &storage,
+                states_type_regs.item_apply_type_reg(),
+                &ensure_plan_file,
+            )
+            .await?;
+            EnsurePlanSerializer::<E>::serialize(&storage, &ensure_plan, &ensure_plan_file)
+                .await?;
+            paths_migrated.push(ensure_plan_file.to_path_buf());
+        }
+
+        // History records are renamed as they are migrated, so only the items
+        // before them have previous format's files to remove.
+        let item_paths_migrated_count = paths_migrated.len();
+        let record_paths_migrated = HistoryRecords::records_migrate(
+            &storage,
+            profile_history_dir,
+            states_type_regs.item_apply_type_reg(),
+            flow_id,
+        )
+        .await?;
+        paths_migrated.extend(record_paths_migrated);
+
+        // The format is recorded after the items are rewritten, so that if
+        // this is interrupted, the items are still read in either format.
+        let storage_format_file = StorageFormatFile::from(workspace.dirs().peace_app_dir());
+        WorkspaceInitializer::storage_format_serialize(&storage, &storage_format_file).await?;
+        paths_migrated.push(storage_format_file.to_path_buf());
+
+        // The previous format's files are removed after the format is recorded,
+        // so that if this is interrupted, the items are still read.
+        for item_path in &paths_migrated[..item_paths_migrated_count] {
+            Self::paths_other_formats_remove(&storage, item_path).await?;
+        }
+
+        // The storage and params files in `resources` are replaced, so that
+        // commands that use this `CmdCtx` afterwards use the new format.
+        resources.insert(storage);
+        resources.insert(workspace_params_file);
+        resources.insert(profile_params_file);
+        resources.insert(flow_params_file);
+
+        Ok(paths_migrated)
+    }
+
+    /// Removes the items at the given path with other formats' extensions.
+    ///
+    /// Each item is only removed if it is unchanged since it is read, so that
+    /// an item written by another command in the meantime is kept.
+    async fn paths_other_formats_remove(storage: &Storage, item_path: &Path) -> Result<(), E> {
+        for item_path_other in StorageFormat::paths_other_formats(item_path) {
+            if let Some(bytes) = storage.backend().read(&item_path_other).await? {
+                storage
+                    .backend()
+                    .remove_if_unchanged(&item_path_other, &bytes)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<E, O, PKeys> Default for StorageFormatMigrateCmd<E, O, PKeys> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
@@ -6,7 +6,8 @@
 //! [`OutputWrite`]: peace_rt_model_core::OutputWrite
 
 pub use self::{
-    apply_cmd::{ApplyCmd, ApplyFor},
+    apply_cmd::{ApplyCmd, ApplyErrorMode, ApplyFor},
+    ensure_plan_read_cmd::EnsurePlanReadCmd,
     states_current_discover_cmd::StatesCurrentDiscoverCmd,
     states_desired_discover_cmd::StatesDesiredDiscoverCmd,
     states_desired_read_cmd::StatesDesiredReadCmd,
@@ -14,6 +15,7 @@ pub use self::{
 };
 
 mod apply_cmd;
+mod ensure_plan_read_cmd;
 mod states_current_discover_cmd;
 mod states_desired_discover_cmd;
 mod states_desired_read_cmd;
@@ -1,6 +1,17 @@
-use std::{fmt::Debug, marker::PhantomData};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    marker::PhantomData,
+    pin::pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
 
-use peace_cfg::{ItemSpecId, OpCheckStatus, OpCtx};
+use futures::future::{self, Either};
+
+use peace_cfg::{InterruptSignal, ItemSpecId, OpCheckStatus, OpCtx};
 use peace_cmd::{
     ctx::CmdCtx,
     scopes::{SingleProfileSingleFlow, SingleProfileSingleFlowView},
@@ -13,14 +24,21 @@ use peace_resources::{
     Resources,
 };
 use peace_rt_model::{
-    outcomes::{CmdOutcome, ItemApplyBoxed, ItemApplyPartialBoxed},
+    fn_graph::{daggy::Walker, Edge},
+    outcomes::{
+        CmdOutcome, HistoryCmd, ItemApplyBoxed, ItemApplyPartialBoxed, ItemApplyStatus,
+    },
     output::OutputWrite,
-    params::ParamsKeys,
-    Error, IndexMap, ItemSpecBoxed, ItemSpecRt, Storage,
+    params::{ParamsKeys, ParamsLimits},
+    storage::SerializedStorage,
+    Error, IndexMap, ItemSpecBoxed, ItemSpecGraph, ItemSpecRt, Storage,
 };
 use tokio::sync::{mpsc, mpsc::UnboundedSender};
 
-use crate::BUFFERED_FUTURES_MAX;
+use crate::{
+    concurrency_groups::ConcurrencyGroups, states_unselected::StatesUnselected,
+    BUFFERED_FUTURES_MAX,
+};
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "output_progress")] {
@@ -82,15 +100,71 @@ where
         cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
         states_saved: &StatesSaved,
         apply_for: ApplyFor,
+        apply_error_mode: ApplyErrorMode,
     ) -> CmdOutcome<States<StatesTsApplyDry>, E> {
         let CmdOutcome {
-            value: (states_applied, _states_desired),
+            value: (states_applied, _item_applies),
             errors,
-        } = Self::exec_internal(cmd_ctx, states_saved, apply_for, true).await;
+            retries,
+            item_statuses,
+            interrupted,
+            history_write_error,
+        } = Self::exec_dry_with_item_applies(cmd_ctx, states_saved, apply_for, apply_error_mode)
+            .await;
 
         CmdOutcome {
             value: states_applied,
             errors,
+            retries,
+            item_statuses,
+            interrupted,
+            history_write_error,
+        }
+    }
+
+    /// Same as [`Self::exec_dry`], but also returns the [`ItemApply`] of each
+    /// `ItemSpec` whose dry run was executed, including those that failed.
+    ///
+    /// The `ItemApply`s are ordered in the order that the `ItemSpec`s are
+    /// applied.
+    ///
+    /// [`ItemApply`]: peace_rt_model::outcomes::ItemApply
+    pub async fn exec_dry_with_item_applies(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        states_saved: &StatesSaved,
+        apply_for: ApplyFor,
+        apply_error_mode: ApplyErrorMode,
+    ) -> CmdOutcome<
+        (
+            States<StatesTsApplyDry>,
+            IndexMap<ItemSpecId, ItemApplyBoxed>,
+        ),
+        E,
+    > {
+        let CmdOutcome {
+            value: (states_applied, _states_desired, item_applies),
+            errors,
+            retries,
+            item_statuses,
+            interrupted,
+            history_write_error,
+        } = Self::exec_internal(
+            cmd_ctx,
+            states_saved,
+            apply_for,
+            apply_error_mode,
+            StatesDesiredSource::StateDesiredFnSpec,
+            true,
+        )
+        .await;
+
+        CmdOutcome {
+            value: (states_applied, item_applies),
+            errors,
+            retries,
+            item_statuses,
+            interrupted,
+            history_write_error,
         }
     }
 
@@ -118,6 +192,72 @@ where
     /// State cannot be fetched interleaved with `exec` as it may use
     /// different `Data`.
     ///
+    /// # Error Modes
+    ///
+    /// With [`ApplyErrorMode::StopOnError`], no further `ItemSpec`s are applied
+    /// once any `ItemSpec` fails. With [`ApplyErrorMode::ContinueOnError`],
+    /// only the `ItemSpec`s that transitively depend on a failed `ItemSpec` are
+    /// skipped, and every other `ItemSpec` is still applied.
+    ///
+    /// The status of each `ItemSpec` is recorded in
+    /// [`CmdOutcome::item_statuses`].
+    ///
+    /// # Interruptions
+    ///
+    /// If the [`InterruptSignal`] in `Resources` is interrupted, no further
+    /// `ItemSpec`s are applied, and `ItemSpec`s that are in progress are
+    /// allowed to complete. The states of `ItemSpec`s that have been applied
+    /// are still serialized to `states_saved.yaml`, and
+    /// [`CmdOutcome::interrupted`] is set.
+    ///
+    /// # Timeouts
+    ///
+    /// If a timeout is set through [`CmdCtx::with_timeout`], and it elapses
+    /// before all `ItemSpec`s are applied, no further `ItemSpec`s are applied.
+    /// `ItemSpec`s that are in progress are allowed to complete, or to reach
+    /// their own timeout, and are recorded as [`ItemApplyStatus::TimedOut`]
+    /// with an [`Error::CmdTimeout`] unless they failed with another error.
+    ///
+    /// # Concurrency
+    ///
+    /// Up to [`BUFFERED_FUTURES_MAX`] `ItemSpec`s are applied concurrently,
+    /// which may be changed through [`CmdCtx::with_concurrency_limit`].
+    /// `ItemSpec`s in a concurrency group are further limited to the group's
+    /// limit.
+    ///
+    /// # Item Selection
+    ///
+    /// If an item spec selection is set through
+    /// [`CmdCtx::with_item_spec_selection`], only the selected `ItemSpec`s are
+    /// applied. The saved and desired states of the other `ItemSpec`s are
+    /// retained in `states_saved.yaml` and `states_desired.yaml`.
+    ///
+    /// # Params Limits
+    ///
+    /// If a value in `Resources` is outside its [`ParamsLimits`], no
+    /// `ItemSpec` is applied, and [`Error::ParamsLimitsExceeded`] is returned,
+    /// unless the limits are overridden.
+    ///
+    /// # History
+    ///
+    /// A [`HistoryRecord`] of the execution is appended to the profile's
+    /// `.history` directory, which may be read using [`HistoryListCmd`] and
+    /// [`HistoryShowCmd`].
+    ///
+    /// The record is written after the states are saved, so if it cannot be
+    /// written, the error is returned in the outcome's `history_write_error`
+    /// instead of failing the command.
+    ///
+    /// [`HistoryRecord`]: peace_rt_model::outcomes::HistoryRecord
+    /// [`HistoryListCmd`]: crate::cmds::HistoryListCmd
+    /// [`HistoryShowCmd`]: crate::cmds::HistoryShowCmd
+    /// [`CmdCtx::with_timeout`]: peace_cmd::ctx::CmdCtx::with_timeout
+    /// [`CmdCtx::with_item_spec_selection`]: peace_cmd::ctx::CmdCtx::with_item_spec_selection
+    /// [`CmdCtx::with_concurrency_limit`]: peace_cmd::ctx::CmdCtx::with_concurrency_limit
+    /// [`Error::CmdTimeout`]: peace_rt_model::Error::CmdTimeout
+    /// [`ItemApplyStatus::TimedOut`]: peace_rt_model::outcomes::ItemApplyStatus::TimedOut
+    /// [`Error::ParamsLimitsExceeded`]: peace_rt_model::Error::ParamsLimitsExceeded
+    /// [`InterruptSignal`]: peace_cfg::InterruptSignal
     /// [`exec`]: peace_cfg::ApplyOpSpec::exec
     /// [`ApplyOpSpec::check`]: peace_cfg::ApplyOpSpec::check
     /// [`ApplyOpSpec::exec`]: peace_cfg::ApplyOpSpec::exec
@@ -127,23 +267,211 @@ where
         cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
         states_saved: &StatesSaved,
         apply_for: ApplyFor,
+        apply_error_mode: ApplyErrorMode,
+    ) -> Result<CmdOutcome<States<StatesTsApply>, E>, E> {
+        Self::exec_with_states_desired_source(
+            cmd_ctx,
+            states_saved,
+            apply_for,
+            apply_error_mode,
+            StatesDesiredSource::StateDesiredFnSpec,
+            match apply_for {
+                ApplyFor::Ensure => HistoryCmd::Ensure,
+                ApplyFor::Clean => HistoryCmd::Clean,
+            },
+        )
+        .await
+    }
+
+    /// Conditionally runs [`ApplyOpSpec::exec_dry`] for each [`ItemSpec`],
+    /// ensuring each item towards its state in `states_desired` instead of
+    /// discovering its desired state.
+    ///
+    /// Item specs that do not have a state in `states_desired` are not
+    /// applied.
+    ///
+    /// This also returns the [`ItemApply`] of each `ItemSpec` whose dry run
+    /// was executed, in the order that the `ItemSpec`s are applied.
+    ///
+    /// [`ApplyOpSpec::exec_dry`]: peace_cfg::ApplyOpSpec::exec_dry
+    /// [`ItemApply`]: peace_rt_model::outcomes::ItemApply
+    /// [`ItemSpec`]: peace_cfg::ItemSpec
+    pub async fn exec_dry_with_states_desired(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        states_saved: &StatesSaved,
+        states_desired: &StatesDesired,
+        apply_error_mode: ApplyErrorMode,
+    ) -> CmdOutcome<
+        (
+            States<StatesTsApplyDry>,
+            IndexMap<ItemSpecId, ItemApplyBoxed>,
+        ),
+        E,
+    > {
+        let CmdOutcome {
+            value: (states_applied, _states_desired, item_applies),
+            errors,
+            retries,
+            item_statuses,
+            interrupted,
+            history_write_error,
+        } = Self::exec_internal(
+            cmd_ctx,
+            states_saved,
+            ApplyFor::Ensure,
+            apply_error_mode,
+            StatesDesiredSource::StatesDesired(states_desired),
+            true,
+        )
+        .await;
+
+        CmdOutcome {
+            value: (states_applied, item_applies),
+            errors,
+            retries,
+            item_statuses,
+            interrupted,
+            history_write_error,
+        }
+    }
+
+    /// Conditionally runs [`ApplyOpSpec::exec`] for each [`ItemSpec`],
+    /// ensuring each item towards its state in `states_desired` instead of
+    /// discovering its desired state.
+    ///
+    /// Item specs that do not have a state in `states_desired` are not
+    /// applied, and their desired states in `states_desired.yaml` are
+    /// retained.
+    ///
+    /// The execution is recorded in the profile's history as `history_cmd`.
+    ///
+    /// [`ApplyOpSpec::exec`]: peace_cfg::ApplyOpSpec::exec
+    /// [`ItemSpec`]: peace_cfg::ItemSpec
+    pub async fn exec_with_states_desired(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        states_saved: &StatesSaved,
+        states_desired: &StatesDesired,
+        apply_error_mode: ApplyErrorMode,
+        history_cmd: HistoryCmd,
+    ) -> Result<CmdOutcome<States<StatesTsApply>, E>, E> {
+        Self::exec_with_states_desired_source(
+            cmd_ctx,
+            states_saved,
+            ApplyFor::Ensure,
+            apply_error_mode,
+            StatesDesiredSource::StatesDesired(states_desired),
+            history_cmd,
+        )
+        .await
+    }
+
+    async fn exec_with_states_desired_source(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+        states_saved: &StatesSaved,
+        apply_for: ApplyFor,
+        apply_error_mode: ApplyErrorMode,
+        states_desired_source: StatesDesiredSource<'_>,
+        history_cmd: HistoryCmd,
     ) -> Result<CmdOutcome<States<StatesTsApply>, E>, E> {
+        // Params may have been changed since the `CmdCtx` was built, so their limits
+        // are checked again before any item is applied.
+        if let Ok(params_limits) = cmd_ctx.resources().try_borrow::<ParamsLimits>() {
+            params_limits.check(cmd_ctx.resources())?;
+        }
+
+        let start = chrono::Utc::now();
         let CmdOutcome {
-            value: (states_applied, states_desired),
+            value: (states_applied, states_desired, item_applies),
             errors,
-        } = Self::exec_internal(cmd_ctx, states_saved, apply_for, false).await;
+            retries,
+            item_statuses,
+            interrupted,
+            history_write_error: _,
+        } = Self::exec_internal(
+            cmd_ctx,
+            states_saved,
+            apply_for,
+            apply_error_mode,
+            states_desired_source,
+            false,
+        )
+        .await;
         Self::serialize_saved(cmd_ctx.resources(), &states_applied).await?;
 
         match apply_for {
             ApplyFor::Ensure => {
+                // Retain the desired states of item specs that were not applied, including
+                // orphaned states.
+                let item_spec_ids_selected = cmd_ctx.scope().item_spec_ids_selected();
+                let item_spec_ids_applied = match states_desired_source {
+                    StatesDesiredSource::StateDesiredFnSpec => item_spec_ids_selected.cloned(),
+                    StatesDesiredSource::StatesDesired(states_desired_target) => Some(
+                        states_desired_target
+                            .keys()
+                            .filter(|item_spec_id| {
+                                item_spec_ids_selected
+                                    .map(|item_spec_ids_selected| {
+                                        item_spec_ids_selected.contains(*item_spec_id)
+                                    })
+                                    .unwrap_or(true)
+                            })
+                            .cloned()
+                            .collect::<HashSet<ItemSpecId>>(),
+                    ),
+                };
+                let states_desired = match item_spec_ids_applied {
+                    Some(item_spec_ids_applied) => {
+                        let states_desired_previous = StatesUnselected::desired_read_opt(
+                            cmd_ctx.resources(),
+                            cmd_ctx.scope().states_type_regs(),
+                        )
+                        .await?;
+                        StatesUnselected::merge(
+                            &states_desired,
+                            states_desired_previous,
+                            &item_spec_ids_applied,
+                        )
+                    }
+                    None => {
+                        // Retain orphaned desired states.
+                        let states_desired_previous = StatesUnselected::desired_read_opt(
+                            cmd_ctx.resources(),
+                            cmd_ctx.scope().states_type_regs(),
+                        )
+                        .await?;
+                        StatesUnselected::merge_orphans(&states_desired, states_desired_previous)
+                    }
+                };
                 Self::serialize_desired(cmd_ctx.resources(), &states_desired).await?;
             }
             ApplyFor::Clean => {}
         };
 
+        // The states are saved by this point, so failing to record the history does
+        // not fail the command.
+        let history_write_error = {
+            use peace_rt_model::outcomes::HistorySummary;
+
+            let summary = HistorySummary {
+                index: 0,
+                cmd: history_cmd,
+                flow_id: cmd_ctx.scope().flow().flow_id().clone(),
+                start,
+                end: chrono::Utc::now(),
+                item_statuses: item_statuses.clone(),
+            };
+            Self::history_record_write(cmd_ctx.resources(), summary, item_applies, &errors)
+                .await
+                .err()
+        };
+
         let cmd_outcome = CmdOutcome {
             value: states_applied,
             errors,
+            retries,
+            item_statuses,
+            interrupted,
+            history_write_error,
         };
         Ok(cmd_outcome)
     }
@@ -160,18 +488,31 @@ where
         cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
         states_saved: &StatesSaved,
         apply_for: ApplyFor,
+        apply_error_mode: ApplyErrorMode,
+        states_desired_source: StatesDesiredSource<'_>,
         dry_run: bool,
-    ) -> CmdOutcome<(States<StatesTs>, StatesDesired), E> {
+    ) -> CmdOutcome<
+        (
+            States<StatesTs>,
+            StatesDesired,
+            IndexMap<ItemSpecId, ItemApplyBoxed>,
+        ),
+        E,
+    > {
         let SingleProfileSingleFlowView {
             #[cfg(feature = "output_progress")]
             output,
             #[cfg(feature = "output_progress")]
             cmd_progress_tracker,
             flow,
+            timeout,
+            concurrency_limit,
+            item_spec_ids_selected,
             resources,
             ..
         } = cmd_ctx.view();
         let item_spec_graph = flow.graph();
+        let concurrency_limit = concurrency_limit.unwrap_or(BUFFERED_FUTURES_MAX);
 
         cfg_if::cfg_if! {
             if #[cfg(feature = "output_progress")] {
@@ -201,48 +542,169 @@ where
 
         let (outcomes_tx, mut outcomes_rx) = mpsc::unbounded_channel::<ItemApplyOutcome<E>>();
 
+        let interrupt_signal = InterruptSignal::clone(&resources.borrow::<InterruptSignal>());
         let resources_ref = &*resources;
+        let item_spec_ids_in_progress = Mutex::new(Vec::<ItemSpecId>::new());
+        let timed_out = AtomicBool::new(false);
+        let item_spec_dependencies = Self::item_spec_dependencies(item_spec_graph, apply_for);
+        let item_spec_ids_unsuccessful = Mutex::new(HashSet::<ItemSpecId>::new());
+        let concurrency_groups = ConcurrencyGroups::new(item_spec_graph);
         let execution_task = async move {
+            let interrupt_signal = &interrupt_signal;
             #[cfg(feature = "output_progress")]
             let progress_tx = &progress_tx;
             let outcomes_tx = &outcomes_tx;
+            let item_spec_ids_in_progress = &item_spec_ids_in_progress;
+            let timed_out = &timed_out;
+            let item_spec_dependencies = &item_spec_dependencies;
+            let item_spec_ids_unsuccessful = &item_spec_ids_unsuccessful;
+            let concurrency_groups = &concurrency_groups;
 
-            match apply_for {
-                ApplyFor::Ensure => {
-                    let (Ok(()) | Err(())) = item_spec_graph
-                        .try_for_each_concurrent(BUFFERED_FUTURES_MAX, |item_spec| {
-                            Self::item_apply_exec(
-                                resources_ref,
-                                apply_for,
-                                #[cfg(feature = "output_progress")]
-                                progress_tx,
-                                outcomes_tx,
-                                item_spec,
-                                dry_run,
-                            )
-                        })
-                        .await
-                        .map_err(|_vec_units: Vec<()>| ());
+            let graph_task = async move {
+                match apply_error_mode {
+                    ApplyErrorMode::StopOnError => match apply_for {
+                        ApplyFor::Ensure => {
+                            let (Ok(()) | Err(())) = item_spec_graph
+                                .try_for_each_concurrent(concurrency_limit, |item_spec| {
+                                    Self::item_apply_exec_tracked(
+                                        resources_ref,
+                                        interrupt_signal,
+                                        apply_for,
+                                        #[cfg(feature = "output_progress")]
+                                        progress_tx,
+                                        outcomes_tx,
+                                        item_spec_ids_in_progress,
+                                        timed_out,
+                                        concurrency_groups,
+                                        item_spec_ids_selected,
+                                        states_desired_source,
+                                        item_spec,
+                                        dry_run,
+                                    )
+                                })
+                                .await
+                                .map_err(|_vec_units: Vec<()>| ());
+                        }
+                        ApplyFor::Clean => {
+                            let (Ok(()) | Err(())) = item_spec_graph
+                                .try_for_each_concurrent_rev(concurrency_limit, |item_spec| {
+                                    Self::item_apply_exec_tracked(
+                                        resources_ref,
+                                        interrupt_signal,
+                                        apply_for,
+                                        #[cfg(feature = "output_progress")]
+                                        progress_tx,
+                                        outcomes_tx,
+                                        item_spec_ids_in_progress,
+                                        timed_out,
+                                        concurrency_groups,
+                                        item_spec_ids_selected,
+                                        states_desired_source,
+                                        item_spec,
+                                        dry_run,
+                                    )
+                                })
+                                .await
+                                .map_err(|_vec_units: Vec<()>| ());
+                        }
+                    },
+                    ApplyErrorMode::ContinueOnError => match apply_for {
+                        ApplyFor::Ensure => {
+                            item_spec_graph
+                                .for_each_concurrent(concurrency_limit, |item_spec| {
+                                    Self::item_apply_exec_unless_upstream_unsuccessful(
+                                        resources_ref,
+                                        interrupt_signal,
+                                        apply_for,
+                                        #[cfg(feature = "output_progress")]
+                                        progress_tx,
+                                        outcomes_tx,
+                                        item_spec_ids_in_progress,
+                                        timed_out,
+                                        concurrency_groups,
+                                        item_spec_ids_selected,
+                                        states_desired_source,
+                                        item_spec_dependencies,
+                                        item_spec_ids_unsuccessful,
+                                        item_spec,
+                                        dry_run,
+                                    )
+                                })
+                                .await;
+                        }
+                        ApplyFor::Clean => {
+                            item_spec_graph
+                                .for_each_concurrent_rev(concurrency_limit, |item_spec| {
+                                    Self::item_apply_exec_unless_upstream_unsuccessful(
+                                        resources_ref,
+                                        interrupt_signal,
+                                        apply_for,
+                                        #[cfg(feature = "output_progress")]
+                                        progress_tx,
+                                        outcomes_tx,
+                                        item_spec_ids_in_progress,
+                                        timed_out,
+                                        concurrency_groups,
+                                        item_spec_ids_selected,
+                                        states_desired_source,
+                                        item_spec_dependencies,
+                                        item_spec_ids_unsuccessful,
+                                        item_spec,
+                                        dry_run,
+                                    )
+                                })
+                                .await;
+                        }
+                    },
                 }
-                ApplyFor::Clean => {
-                    let (Ok(()) | Err(())) = item_spec_graph
-                        .try_for_each_concurrent_rev(BUFFERED_FUTURES_MAX, |item_spec| {
-                            Self::item_apply_exec(
-                                resources_ref,
-                                apply_for,
-                                #[cfg(feature = "output_progress")]
-                                progress_tx,
-                                outcomes_tx,
-                                item_spec,
-                                dry_run,
-                            )
-                        })
-                        .await
-                        .map_err(|_vec_units: Vec<()>| ());
+            };
+
+            let item_spec_ids_timed_out = match timeout {
+                Some(timeout) => {
+                    let graph_task = pin!(graph_task);
+                    let timeout_task = pin!(peace_rt_model::time::sleep(timeout));
+                    match future::select(graph_task, timeout_task).await {
+                        Either::Left(((), _timeout_task)) => Vec::new(),
+                        Either::Right(((), graph_task)) => {
+                            // Stop scheduling item specs, but let the ones in progress complete,
+                            // so that their states are not lost part way through applying them.
+                            timed_out.store(true, Ordering::SeqCst);
+                            let item_spec_ids_timed_out = item_spec_ids_in_progress
+                                .lock()
+                                .expect(
+                                    "unreachable: `item_spec_ids_in_progress` lock is not held \
+                                    across an await.",
+                                )
+                                .clone();
+
+                            graph_task.await;
+
+                            #[cfg(feature = "output_progress")]
+                            item_spec_ids_timed_out.iter().for_each(|item_spec_id| {
+                                let _progress_send_unused =
+                                    progress_tx.try_send(ProgressUpdateAndId {
+                                        item_spec_id: item_spec_id.clone(),
+                                        progress_update: ProgressUpdate::Complete(
+                                            ProgressComplete::Fail,
+                                        ),
+                                        msg_update: ProgressMsgUpdate::Set(String::from(
+                                            "timed out",
+                                        )),
+                                    });
+                            });
+
+                            item_spec_ids_timed_out
+                        }
+                    }
                 }
-            }
+                None => {
+                    graph_task.await;
+                    Vec::new()
+                }
+            };
 
             // `progress_tx` is dropped here, so `progress_rx` will safely end.
+            item_spec_ids_timed_out
         };
 
         #[cfg(feature = "output_progress")]
@@ -250,6 +712,10 @@ where
             crate::progress::Progress::progress_render(output, progress_trackers, progress_rx);
 
         let mut errors = IndexMap::<ItemSpecId, E>::new();
+        let mut retries = IndexMap::<ItemSpecId, u32>::new();
+        let mut item_statuses_mut = HashMap::<ItemSpecId, ItemApplyStatus>::new();
+        let mut item_applies_mut = HashMap::<ItemSpecId, ItemApplyBoxed>::new();
+        let mut interrupted = false;
         let outcomes_rx_task = async {
             while let Some(outcome) = outcomes_rx.recv().await {
                 match outcome {
@@ -258,7 +724,11 @@ where
                         item_apply_partial,
                         error,
                     } => {
+                        item_statuses_mut.insert(item_spec_id.clone(), ItemApplyStatus::Fail);
                         errors.insert(item_spec_id.clone(), error);
+                        if item_apply_partial.retry_count() > 0 {
+                            retries.insert(item_spec_id.clone(), item_apply_partial.retry_count());
+                        }
 
                         // Save `state_target` (which is state_desired) if we are not cleaning up.
                         match apply_for {
@@ -274,6 +744,17 @@ where
                         item_spec_id,
                         item_apply,
                     } => {
+                        let item_status = match item_apply.op_check_status() {
+                            OpCheckStatus::ExecNotRequired => ItemApplyStatus::ExecNotRequired,
+                            #[cfg(not(feature = "output_progress"))]
+                            OpCheckStatus::ExecRequired => ItemApplyStatus::Success,
+                            #[cfg(feature = "output_progress")]
+                            OpCheckStatus::ExecRequired { .. } => ItemApplyStatus::Success,
+                        };
+                        item_statuses_mut.insert(item_spec_id.clone(), item_status);
+                        if item_apply.retry_count() > 0 {
+                            retries.insert(item_spec_id.clone(), item_apply.retry_count());
+                        }
                         if let Some(state_applied) = item_apply.state_applied() {
                             states_applied_mut.insert_raw(item_spec_id.clone(), state_applied);
                         } else {
@@ -285,17 +766,23 @@ where
                         match apply_for {
                             ApplyFor::Ensure => {
                                 let state_desired = item_apply.state_target();
-                                states_desired_mut.insert_raw(item_spec_id, state_desired);
+                                states_desired_mut.insert_raw(item_spec_id.clone(), state_desired);
                             }
                             ApplyFor::Clean => {}
                         }
+
+                        item_applies_mut.insert(item_spec_id, item_apply);
                     }
                     ItemApplyOutcome::Fail {
                         item_spec_id,
                         item_apply,
                         error,
                     } => {
+                        item_statuses_mut.insert(item_spec_id.clone(), ItemApplyStatus::Fail);
                         errors.insert(item_spec_id.clone(), error);
+                        if item_apply.retry_count() > 0 {
+                            retries.insert(item_spec_id.clone(), item_apply.retry_count());
+                        }
                         if let Some(state_applied) = item_apply.state_applied() {
                             states_applied_mut.insert_raw(item_spec_id.clone(), state_applied);
                         }
@@ -304,10 +791,19 @@ where
                         match apply_for {
                             ApplyFor::Ensure => {
                                 let state_desired = item_apply.state_target();
-                                states_desired_mut.insert_raw(item_spec_id, state_desired);
+                                states_desired_mut.insert_raw(item_spec_id.clone(), state_desired);
                             }
                             ApplyFor::Clean => {}
                         }
+
+                        item_applies_mut.insert(item_spec_id, item_apply);
+                    }
+                    ItemApplyOutcome::Interrupted { item_spec_id } => {
+                        item_statuses_mut.insert(item_spec_id, ItemApplyStatus::Interrupted);
+                        interrupted = true;
+                    }
+                    ItemApplyOutcome::UpstreamFailed { item_spec_id } => {
+                        item_statuses_mut.insert(item_spec_id, ItemApplyStatus::UpstreamFailed);
                     }
                 }
             }
@@ -315,14 +811,32 @@ where
 
         cfg_if::cfg_if! {
             if #[cfg(feature = "output_progress")] {
-                futures::join!(execution_task, progress_render_task, outcomes_rx_task);
+                let (item_spec_ids_timed_out, (), ()) =
+                    futures::join!(execution_task, progress_render_task, outcomes_rx_task);
 
                 output.progress_end(cmd_progress_tracker).await;
             } else {
-                futures::join!(execution_task, outcomes_rx_task);
+                let (item_spec_ids_timed_out, ()) =
+                    futures::join!(execution_task, outcomes_rx_task);
             }
         }
 
+        // Item specs that were in progress when the command timeout elapsed are
+        // recorded as timed out, even though they were allowed to complete.
+        if let Some(timeout) = timeout {
+            item_spec_ids_timed_out
+                .into_iter()
+                .for_each(|item_spec_id| {
+                    item_statuses_mut.insert(item_spec_id.clone(), ItemApplyStatus::TimedOut);
+                    errors.entry(item_spec_id.clone()).or_insert_with(|| {
+                        E::from(Error::CmdTimeout {
+                            item_spec_id,
+                            timeout,
+                        })
+                    });
+                });
+        }
+
         // TODO: Should we run `StatesCurrentFnSpec` again?
         //
         // i.e. is it part of `ApplyOpSpec::exec`'s contract to return the state.
@@ -336,10 +850,254 @@ where
         let states_applied = states_applied_mut.into();
         let states_desired = states_desired_mut.into();
 
+        // Item specs that have no status were not reached, as execution was stopped
+        // by an interruption, a failure, or the command timeout.
+        let item_status_not_reached = if interrupted {
+            ItemApplyStatus::Interrupted
+        } else {
+            ItemApplyStatus::NotReached
+        };
+        let item_spec_status = |item_spec: &ItemSpecBoxed<E>| {
+            let item_spec_id = item_spec.id();
+            let item_status = item_statuses_mut
+                .get(item_spec_id)
+                .copied()
+                .unwrap_or(item_status_not_reached);
+            (item_spec_id.clone(), item_status)
+        };
+        let item_spec_selected = |item_spec: &&ItemSpecBoxed<E>| {
+            item_spec_ids_selected
+                .map(|item_spec_ids_selected| item_spec_ids_selected.contains(item_spec.id()))
+                .unwrap_or(true)
+        };
+        let item_statuses = match apply_for {
+            ApplyFor::Ensure => item_spec_graph
+                .iter()
+                .filter(item_spec_selected)
+                .map(item_spec_status)
+                .collect(),
+            ApplyFor::Clean => item_spec_graph
+                .iter_rev()
+                .filter(item_spec_selected)
+                .map(item_spec_status)
+                .collect(),
+        };
+        let item_spec_apply = |item_spec: &ItemSpecBoxed<E>| {
+            let item_spec_id = item_spec.id();
+            item_applies_mut
+                .remove(item_spec_id)
+                .map(|item_apply| (item_spec_id.clone(), item_apply))
+        };
+        let item_applies = match apply_for {
+            ApplyFor::Ensure => item_spec_graph.iter().filter_map(item_spec_apply).collect(),
+            ApplyFor::Clean => item_spec_graph
+                .iter_rev()
+                .filter_map(item_spec_apply)
+                .collect(),
+        };
+
         CmdOutcome {
-            value: (states_applied, states_desired),
+            value: (states_applied, states_desired, item_applies),
             errors,
+            retries,
+            item_statuses,
+            interrupted,
+            history_write_error: None,
+        }
+    }
+
+    /// Runs [`Self::item_apply_exec`] once the item spec's concurrency group
+    /// allows it, recording the item spec as in progress while it runs.
+    ///
+    /// Item specs that are not selected are not applied, and are treated as
+    /// successful so that their dependents are still applied.
+    ///
+    /// This allows the item specs that were in progress when the command
+    /// timeout elapses to be recorded as timed out. Once `timed_out` is set, no
+    /// further item specs are applied.
+    #[allow(clippy::too_many_arguments)]
+    async fn item_apply_exec_tracked(
+        resources: &Resources<SetUp>,
+        interrupt_signal: &InterruptSignal,
+        apply_for: ApplyFor,
+        #[cfg(feature = "output_progress")] progress_tx: &Sender<ProgressUpdateAndId>,
+        outcomes_tx: &UnboundedSender<ItemApplyOutcome<E>>,
+        item_spec_ids_in_progress: &Mutex<Vec<ItemSpecId>>,
+        timed_out: &AtomicBool,
+        concurrency_groups: &ConcurrencyGroups,
+        item_spec_ids_selected: Option<&HashSet<ItemSpecId>>,
+        states_desired_source: StatesDesiredSource<'_>,
+        item_spec: &ItemSpecBoxed<E>,
+        dry_run: bool,
+    ) -> Result<(), ()> {
+        let item_spec_id = item_spec.id();
+        if let Some(item_spec_ids_selected) = item_spec_ids_selected {
+            if !item_spec_ids_selected.contains(item_spec_id) {
+                return Ok(());
+            }
+        }
+        if let StatesDesiredSource::StatesDesired(states_desired) = states_desired_source {
+            if states_desired.get_raw(item_spec_id).is_none() {
+                return Ok(());
+            }
+        }
+
+        let _concurrency_group_permit = concurrency_groups.acquire(item_spec).await;
+
+        // Don't begin applying any more item specs once the command timeout has
+        // elapsed. These item specs are recorded as not reached.
+        if timed_out.load(Ordering::SeqCst) {
+            return Err(());
         }
+
+        item_spec_ids_in_progress
+            .lock()
+            .expect("unreachable: `item_spec_ids_in_progress` lock is not held across an await.")
+            .push(item_spec_id.clone());
+
+        let result = Self::item_apply_exec(
+            resources,
+            interrupt_signal,
+            apply_for,
+            #[cfg(feature = "output_progress")]
+            progress_tx,
+            outcomes_tx,
+            states_desired_source,
+            item_spec,
+            dry_run,
+        )
+        .await;
+
+        item_spec_ids_in_progress
+            .lock()
+            .expect("unreachable: `item_spec_ids_in_progress` lock is not held across an await.")
+            .retain(|item_spec_id_in_progress| item_spec_id_in_progress != item_spec_id);
+
+        result
+    }
+
+    /// Runs [`Self::item_apply_exec_tracked`] if none of the item spec's
+    /// dependencies failed or were skipped.
+    ///
+    /// If the item spec is skipped, or fails, it is recorded in
+    /// `item_spec_ids_unsuccessful` so that its dependents are skipped.
+    ///
+    /// Item specs skipped because of an upstream failure are reported as
+    /// [`ItemApplyStatus::UpstreamFailed`], unless the command was
+    /// interrupted, or the command timeout elapsed.
+    #[allow(clippy::too_many_arguments)]
+    async fn item_apply_exec_unless_upstream_unsuccessful(
+        resources: &Resources<SetUp>,
+        interrupt_signal: &InterruptSignal,
+        apply_for: ApplyFor,
+        #[cfg(feature = "output_progress")] progress_tx: &Sender<ProgressUpdateAndId>,
+        outcomes_tx: &UnboundedSender<ItemApplyOutcome<E>>,
+        item_spec_ids_in_progress: &Mutex<Vec<ItemSpecId>>,
+        timed_out: &AtomicBool,
+        concurrency_groups: &ConcurrencyGroups,
+        item_spec_ids_selected: Option<&HashSet<ItemSpecId>>,
+        states_desired_source: StatesDesiredSource<'_>,
+        item_spec_dependencies: &HashMap<ItemSpecId, Vec<ItemSpecId>>,
+        item_spec_ids_unsuccessful: &Mutex<HashSet<ItemSpecId>>,
+        item_spec: &ItemSpecBoxed<E>,
+        dry_run: bool,
+    ) {
+        let item_spec_id = item_spec.id();
+        let upstream_unsuccessful = {
+            let item_spec_ids_unsuccessful = item_spec_ids_unsuccessful.lock().expect(
+                "unreachable: `item_spec_ids_unsuccessful` lock is not held across an await.",
+            );
+            item_spec_dependencies
+                .get(item_spec_id)
+                .map(|dependencies| {
+                    dependencies
+                        .iter()
+                        .any(|dependency| item_spec_ids_unsuccessful.contains(dependency))
+                })
+                .unwrap_or(false)
+        };
+
+        let successful = if upstream_unsuccessful {
+            let item_spec_id = item_spec_id.clone();
+            let outcome = if interrupt_signal.is_interrupted() {
+                Some(ItemApplyOutcome::Interrupted { item_spec_id })
+            } else if timed_out.load(Ordering::SeqCst) {
+                // Recorded as not reached.
+                None
+            } else {
+                Some(ItemApplyOutcome::UpstreamFailed { item_spec_id })
+            };
+            if let Some(outcome) = outcome {
+                outcomes_tx
+                    .send(outcome)
+                    .expect("unreachable: `outcomes_rx` is in a sibling task.");
+            }
+
+            false
+        } else {
+            Self::item_apply_exec_tracked(
+                resources,
+                interrupt_signal,
+                apply_for,
+                #[cfg(feature = "output_progress")]
+                progress_tx,
+                outcomes_tx,
+                item_spec_ids_in_progress,
+                timed_out,
+                concurrency_groups,
+                item_spec_ids_selected,
+                states_desired_source,
+                item_spec,
+                dry_run,
+            )
+            .await
+            .is_ok()
+        };
+
+        if !successful {
+            item_spec_ids_unsuccessful
+                .lock()
+                .expect(
+                    "unreachable: `item_spec_ids_unsuccessful` lock is not held across an await.",
+                )
+                .insert(item_spec_id.clone());
+        }
+    }
+
+    /// Returns the item specs that each item spec depends on, in the order that
+    /// item specs are applied.
+    ///
+    /// When cleaning, item specs are applied in reverse, so an item spec
+    /// depends on its successors.
+    ///
+    /// Only logical edges are followed, as data edges only order item specs
+    /// that access the same data.
+    fn item_spec_dependencies(
+        item_spec_graph: &ItemSpecGraph<E>,
+        apply_for: ApplyFor,
+    ) -> HashMap<ItemSpecId, Vec<ItemSpecId>> {
+        let dag = &item_spec_graph.graph;
+        item_spec_graph
+            .iter_insertion_with_indices()
+            .map(|(fn_id, item_spec)| {
+                let dependencies = match apply_for {
+                    ApplyFor::Ensure => dag
+                        .parents(fn_id)
+                        .iter(dag)
+                        .filter(|(edge_id, _fn_id_parent)| dag[*edge_id] == Edge::Logic)
+                        .map(|(_edge_id, fn_id_parent)| dag[fn_id_parent].id().clone())
+                        .collect::<Vec<ItemSpecId>>(),
+                    ApplyFor::Clean => dag
+                        .children(fn_id)
+                        .iter(dag)
+                        .filter(|(edge_id, _fn_id_child)| dag[*edge_id] == Edge::Logic)
+                        .map(|(_edge_id, fn_id_child)| dag[fn_id_child].id().clone())
+                        .collect::<Vec<ItemSpecId>>(),
+                };
+
+                (item_spec.id().clone(), dependencies)
+            })
+            .collect()
     }
 
     ///
@@ -362,9 +1120,11 @@ where
     /// ```
     async fn item_apply_exec(
         resources: &Resources<SetUp>,
+        interrupt_signal: &InterruptSignal,
         apply_for: ApplyFor,
         #[cfg(feature = "output_progress")] progress_tx: &Sender<ProgressUpdateAndId>,
         outcomes_tx: &UnboundedSender<ItemApplyOutcome<E>>,
+        states_desired_source: StatesDesiredSource<'_>,
         item_spec: &ItemSpecBoxed<E>,
         dry_run: bool,
     ) -> Result<(), ()> {
@@ -374,15 +1134,46 @@ where
             ItemSpecRt::apply_exec
         };
 
+        // Don't begin applying any more item specs once interrupted.
+        //
+        // Returning `Err` stops the graph from scheduling successors, and states
+        // for item specs that have completed are still collected.
+        if interrupt_signal.is_interrupted() {
+            outcomes_tx
+                .send(ItemApplyOutcome::Interrupted {
+                    item_spec_id: item_spec.id().clone(),
+                })
+                .expect("unreachable: `outcomes_rx` is in a sibling task.");
+
+            return Err(());
+        }
+
         let item_spec_id = item_spec.id();
         let op_ctx = OpCtx::new(
             item_spec_id,
+            interrupt_signal,
             #[cfg(feature = "output_progress")]
             ProgressSender::new(item_spec_id, progress_tx),
         );
-        let item_apply = match apply_for {
-            ApplyFor::Ensure => ItemSpecRt::ensure_prepare(&**item_spec, op_ctx, resources).await,
-            ApplyFor::Clean => ItemSpecRt::clean_prepare(&**item_spec, op_ctx, resources).await,
+        let item_apply = match (apply_for, states_desired_source) {
+            (ApplyFor::Ensure, StatesDesiredSource::StateDesiredFnSpec) => {
+                ItemSpecRt::ensure_prepare(&**item_spec, op_ctx, resources).await
+            }
+            (ApplyFor::Ensure, StatesDesiredSource::StatesDesired(states_desired)) => {
+                let state_desired = states_desired
+                    .get_raw(item_spec_id)
+                    .expect("unreachable: Item specs without a desired state are not applied.");
+                ItemSpecRt::ensure_prepare_with_state_desired(
+                    &**item_spec,
+                    op_ctx,
+                    resources,
+                    state_desired,
+                )
+                .await
+            }
+            (ApplyFor::Clean, _) => {
+                ItemSpecRt::clean_prepare(&**item_spec, op_ctx, resources).await
+            }
         };
 
         match item_apply {
@@ -421,6 +1212,25 @@ where
                         return Ok(());
                     }
                 }
+
+                // The interrupt may have been received while preparing.
+                if interrupt_signal.is_interrupted() {
+                    #[cfg(feature = "output_progress")]
+                    let _progress_send_unused = progress_tx.try_send(ProgressUpdateAndId {
+                        item_spec_id: item_spec_id.clone(),
+                        progress_update: ProgressUpdate::Complete(ProgressComplete::Fail),
+                        msg_update: ProgressMsgUpdate::Set(String::from("interrupted")),
+                    });
+
+                    outcomes_tx
+                        .send(ItemApplyOutcome::Interrupted {
+                            item_spec_id: item_spec.id().clone(),
+                        })
+                        .expect("unreachable: `outcomes_rx` is in a sibling task.");
+
+                    return Err(());
+                }
+
                 match apply_fn(&**item_spec, op_ctx, resources, &mut item_apply).await {
                     Ok(()) => {
                         // apply succeeded
@@ -505,9 +1315,9 @@ where
 
         let flow_dir = resources.borrow::<FlowDir>();
         let storage = resources.borrow::<Storage>();
-        let states_saved_file = StatesSavedFile::from(&*flow_dir);
+        let states_saved_file = StatesSavedFile::from((&*flow_dir, storage.format().extension()));
 
-        StatesSerializer::serialize(&storage, states_applied, &states_saved_file).await?;
+        StatesSerializer::serialize(&*storage, states_applied, &states_saved_file).await?;
 
         drop(flow_dir);
         drop(storage);
@@ -515,6 +1325,53 @@ where
         Ok(())
     }
 
+    /// Appends a [`HistoryRecord`] of this execution to the
+    /// [`ProfileHistoryDir`].
+    ///
+    /// The `index` of the `summary` is replaced with the next index in the
+    /// profile's history, which is claimed atomically so that concurrent
+    /// commands do not overwrite each other's records.
+    ///
+    /// [`HistoryRecord`]: peace_rt_model::outcomes::HistoryRecord
+    async fn history_record_write(
+        resources: &Resources<SetUp>,
+        summary: peace_rt_model::outcomes::HistorySummary,
+        item_applies: IndexMap<ItemSpecId, ItemApplyBoxed>,
+        errors: &IndexMap<ItemSpecId, E>,
+    ) -> Result<(), E> {
+        use peace_resources::{paths::ProfileHistoryDir, type_reg::untagged::TypeMap};
+        use peace_rt_model::outcomes::HistoryRecord;
+
+        use crate::history_records::HistoryRecords;
+
+        let profile_history_dir = resources.borrow::<ProfileHistoryDir>();
+        let storage = resources.borrow::<Storage>();
+
+        let item_applies = item_applies.into_iter().fold(
+            TypeMap::<ItemSpecId, ItemApplyBoxed>::new_typed(),
+            |mut type_map, (item_spec_id, item_apply)| {
+                type_map.insert_raw(item_spec_id, item_apply);
+                type_map
+            },
+        );
+        let errors = errors
+            .iter()
+            .map(|(item_spec_id, error)| (item_spec_id.clone(), error.to_string()))
+            .collect::<IndexMap<ItemSpecId, String>>();
+        let mut history_record = HistoryRecord {
+            summary,
+            item_applies,
+            errors,
+        };
+
+        HistoryRecords::record_append(&storage, &profile_history_dir, &mut history_record).await?;
+
+        drop(profile_history_dir);
+        drop(storage);
+
+        Ok(())
+    }
+
     async fn serialize_desired(
         resources: &Resources<SetUp>,
         states_desired: &StatesDesired,
@@ -523,9 +1380,10 @@ where
 
         let flow_dir = resources.borrow::<FlowDir>();
         let storage = resources.borrow::<Storage>();
-        let states_desired_file = StatesDesiredFile::from(&*flow_dir);
+        let states_desired_file =
+            StatesDesiredFile::from((&*flow_dir, storage.format().extension()));
 
-        StatesSerializer::serialize(&storage, states_desired, &states_desired_file).await?;
+        StatesSerializer::serialize(&*storage, states_desired, &states_desired_file).await?;
 
         drop(flow_dir);
         drop(storage);
@@ -563,6 +1421,10 @@ enum ItemApplyOutcome<E> {
         item_apply: ItemApplyBoxed,
         error: E,
     },
+    /// Command was interrupted before the item was applied.
+    Interrupted { item_spec_id: ItemSpecId },
+    /// Item was not applied because an item it depends on was unsuccessful.
+    UpstreamFailed { item_spec_id: ItemSpecId },
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -570,3 +1432,32 @@ pub enum ApplyFor {
     Ensure,
     Clean,
 }
+
+/// Where `ApplyCmd` gets the desired state that each item is ensured towards.
+///
+/// This is not used when cleaning, as items are cleaned towards their clean
+/// state.
+#[derive(Clone, Copy, Debug)]
+enum StatesDesiredSource<'s> {
+    /// Discover each item's desired state using its `StateDesiredFnSpec`.
+    StateDesiredFnSpec,
+    /// Use the given desired states, such as from a previous execution.
+    ///
+    /// Items that do not have a desired state in these states are not
+    /// applied.
+    StatesDesired(&'s StatesDesired),
+}
+
+/// How `ApplyCmd` proceeds when an item fails to be applied.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ApplyErrorMode {
+    /// Stop applying items once any item fails.
+    ///
+    /// Items that are in progress are allowed to complete.
+    #[default]
+    StopOnError,
+    /// Keep applying items that do not depend on a failed item.
+    ///
+    /// Items that transitively depend on a failed item are skipped.
+    ContinueOnError,
+}
@@ -0,0 +1,62 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use peace_cfg::FlowId;
+use peace_cmd::{
+    ctx::CmdCtx,
+    scopes::{SingleProfileSingleFlow, SingleProfileSingleFlowView},
+};
+use peace_resources::{
+    paths::{EnsurePlanFile, FlowDir},
+    resources::ts::SetUp,
+};
+use peace_rt_model::{
+    outcomes::EnsurePlan, params::ParamsKeys, storage::SerializedStorage, EnsurePlanSerializer,
+    Error, Storage,
+};
+
+/// Reads an [`EnsurePlan`] from storage.
+#[derive(Debug)]
+pub struct EnsurePlanReadCmd<E, O, PKeys>(PhantomData<(E, O, PKeys)>);
+
+impl<E, O, PKeys> EnsurePlanReadCmd<E, O, PKeys>
+where
+    E: std::error::Error + From<Error> + Send + 'static,
+    PKeys: ParamsKeys + 'static,
+{
+    /// Reads an [`EnsurePlan`] from storage.
+    ///
+    /// [`EnsureCmd::exec_dry_plan`] must have run prior to this command to
+    /// read the plan.
+    ///
+    /// [`EnsureCmd::exec_dry_plan`]: crate::cmds::EnsureCmd::exec_dry_plan
+    pub async fn exec(
+        cmd_ctx: &mut CmdCtx<SingleProfileSingleFlow<'_, E, O, PKeys, SetUp>>,
+    ) -> Result<EnsurePlan, E> {
+        let SingleProfileSingleFlowView {
+            states_type_regs,
+            resources,
+            ..
+        } = cmd_ctx.scope_mut().view();
+
+        let flow_id = resources.borrow::<FlowId>();
+        let flow_dir = resources.borrow::<FlowDir>();
+        let storage = resources.borrow::<Storage>();
+        let ensure_plan_file = EnsurePlanFile::from((&*flow_dir, storage.format().extension()));
+
+        let ensure_plan = EnsurePlanSerializer::deserialize(
+            &flow_id,
+            &*storage,
+            states_type_regs.item_apply_type_reg(),
+            &ensure_plan_file,
+        )
+        .await?;
+
+        Ok(ensure_plan)
+    }
+}
+
+impl<E, O, PKeys> Default for EnsurePlanReadCmd<E, O, PKeys> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
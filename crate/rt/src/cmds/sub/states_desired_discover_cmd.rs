@@ -1,7 +1,7 @@
 use std::{fmt::Debug, marker::PhantomData};
 
 use futures::stream::{StreamExt, TryStreamExt};
-use peace_cfg::OpCtx;
+use peace_cfg::{InterruptSignal, OpCtx};
 use peace_cmd::{
     ctx::CmdCtx,
     scopes::{SingleProfileSingleFlow, SingleProfileSingleFlowView},
@@ -13,7 +13,9 @@ use peace_resources::{
     states::{ts::Desired, StatesDesired},
     Resources,
 };
-use peace_rt_model::{output::OutputWrite, params::ParamsKeys, Error, Storage};
+use peace_rt_model::{
+    output::OutputWrite, params::ParamsKeys, storage::SerializedStorage, Error, Storage,
+};
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "output_progress")] {
@@ -47,12 +49,12 @@ where
     /// `$flow_dir/states_saved.yaml`.
     ///
     /// If any `StateDesiredFnSpec` needs to read the `State` from a previous
-    /// `ItemSpec`, the predecessor should insert a copy / clone of their state
-    /// into `Resources`, and the successor should references it in their
-    /// [`Data`].
+    /// `ItemSpec`, the successor's params may be mapped from the predecessor's
+    /// state through [`ItemSpecWrapper::with_params_spec`], which is resolved
+    /// after the predecessor's state is discovered.
     ///
     /// [`try_exec`]: peace_cfg::TryFnSpec::try_exec
-    /// [`Data`]: peace_cfg::TryFnSpec::Data
+    /// [`ItemSpecWrapper::with_params_spec`]: peace_rt_model::ItemSpecWrapper::with_params_spec
     /// [`ItemSpec`]: peace_cfg::ItemSpec
     /// [`StateDesiredFnSpec`]: peace_cfg::ItemSpec::StateDesiredFnSpec
     pub async fn exec(
@@ -82,8 +84,10 @@ where
             }
         }
 
+        let interrupt_signal = InterruptSignal::clone(&resources.borrow::<InterruptSignal>());
         let resources_ref = &*resources;
         let execution_task = async move {
+            let interrupt_signal = &interrupt_signal;
             #[cfg(feature = "output_progress")]
             let progress_tx = &progress_tx;
 
@@ -95,13 +99,17 @@ where
                     let item_spec_id = item_spec.id();
                     let op_ctx = OpCtx::new(
                         item_spec_id,
+                        interrupt_signal,
                         #[cfg(feature = "output_progress")]
                         ProgressSender::new(item_spec_id, progress_tx),
                     );
 
+                    // These states are not returned in a `CmdOutcome`, so retries are not
+                    // reported.
                     let state = item_spec
                         .state_desired_try_exec(op_ctx, resources_ref)
-                        .await;
+                        .await
+                        .result;
 
                     #[cfg(feature = "output_progress")]
                     {
@@ -162,9 +170,10 @@ where
 
         let flow_dir = resources.borrow::<FlowDir>();
         let storage = resources.borrow::<Storage>();
-        let states_desired_file = StatesDesiredFile::from(&*flow_dir);
+        let states_desired_file =
+            StatesDesiredFile::from((&*flow_dir, storage.format().extension()));
 
-        StatesSerializer::serialize(&storage, states_desired, &states_desired_file).await?;
+        StatesSerializer::serialize(&*storage, states_desired, &states_desired_file).await?;
 
         drop(flow_dir);
         drop(storage);
@@ -12,7 +12,9 @@ use peace_resources::{
     type_reg::untagged::{BoxDtDisplay, TypeReg},
     Resources,
 };
-use peace_rt_model::{params::ParamsKeys, Error, StatesSerializer, Storage};
+use peace_rt_model::{
+    params::ParamsKeys, storage::SerializedStorage, Error, StatesSerializer, Storage,
+};
 
 /// Reads [`StatesDesired`]s from storage.
 #[derive(Debug)]
@@ -53,11 +55,12 @@ where
         let flow_id = resources.borrow::<FlowId>();
         let flow_dir = resources.borrow::<FlowDir>();
         let storage = resources.borrow::<Storage>();
-        let states_desired_file = StatesDesiredFile::from(&*flow_dir);
+        let states_desired_file =
+            StatesDesiredFile::from((&*flow_dir, storage.format().extension()));
 
         let states_desired = StatesSerializer::deserialize_desired(
             &flow_id,
-            &storage,
+            &*storage,
             states_desired_type_reg,
             &states_desired_file,
         )
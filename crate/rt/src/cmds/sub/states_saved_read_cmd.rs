@@ -12,7 +12,9 @@ use peace_resources::{
     type_reg::untagged::{BoxDtDisplay, TypeReg},
     Resources,
 };
-use peace_rt_model::{params::ParamsKeys, Error, StatesSerializer, Storage};
+use peace_rt_model::{
+    params::ParamsKeys, storage::SerializedStorage, Error, StatesSerializer, Storage,
+};
 
 /// Reads [`StatesSaved`]s from storage.
 #[derive(Debug)]
@@ -53,11 +55,11 @@ where
         let flow_id = resources.borrow::<FlowId>();
         let flow_dir = resources.borrow::<FlowDir>();
         let storage = resources.borrow::<Storage>();
-        let states_saved_file = StatesSavedFile::from(&*flow_dir);
+        let states_saved_file = StatesSavedFile::from((&*flow_dir, storage.format().extension()));
 
         let states_saved = StatesSerializer::deserialize_saved(
             &flow_id,
-            &storage,
+            &*storage,
             states_current_type_reg,
             &states_saved_file,
         )
@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+
+use peace_cfg::ConcurrencyGroupId;
+use peace_rt_model::{ItemSpecBoxed, ItemSpecGraph};
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+/// Limits the number of item specs in each concurrency group that execute
+/// concurrently.
+pub struct ConcurrencyGroups(HashMap<ConcurrencyGroupId, Semaphore>);
+
+impl ConcurrencyGroups {
+    /// Returns the concurrency groups registered in the given item spec graph.
+    pub fn new<E>(item_spec_graph: &ItemSpecGraph<E>) -> Self {
+        let semaphores = item_spec_graph
+            .concurrency_group_limits()
+            .iter()
+            .map(|(concurrency_group_id, limit)| {
+                (concurrency_group_id.clone(), Semaphore::new(*limit))
+            })
+            .collect::<HashMap<_, _>>();
+
+        Self(semaphores)
+    }
+
+    /// Waits until the item spec may execute within its concurrency group.
+    ///
+    /// The item spec may execute while the returned permit is held. `None`
+    /// is returned if the item spec does not belong to a concurrency group
+    /// that has a limit.
+    pub async fn acquire<E>(&self, item_spec: &ItemSpecBoxed<E>) -> Option<SemaphorePermit<'_>> {
+        let semaphore = item_spec
+            .concurrency_group()
+            .and_then(|concurrency_group_id| self.0.get(concurrency_group_id))?;
+
+        Some(
+            semaphore
+                .acquire()
+                .await
+                .expect("unreachable: concurrency group semaphores are never closed."),
+        )
+    }
+}
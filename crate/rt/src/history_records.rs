@@ -0,0 +1,270 @@
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
+
+use peace_cfg::{FlowId, ItemSpecId};
+use peace_resources::{paths::ProfileHistoryDir, type_reg::untagged::TypeReg};
+use peace_rt_model::{
+    outcomes::{HistoryRecord, HistorySummary, ItemApplyBoxed},
+    storage::{SerializedStorage, StorageFormat},
+    Error, Storage,
+};
+
+use self::history_record_details_seed::{HistoryRecordDetails, HistoryRecordDetailsSeed};
+
+mod history_record_details_seed;
+
+/// Reads and writes [`HistoryRecord`]s in the [`ProfileHistoryDir`].
+///
+/// Each record is stored in its own file, named `{index:08}.{extension}`, so
+/// that records are listed in the order they were recorded. The extension is
+/// that of the workspace's storage format, and records with any format's
+/// extension are read.
+pub(crate) struct HistoryRecords;
+
+impl HistoryRecords {
+    /// Returns the index to use for the next history record.
+    async fn index_next(
+        storage: &Storage,
+        profile_history_dir: &ProfileHistoryDir,
+    ) -> Result<u32, Error> {
+        let record_paths = Self::record_paths(storage, profile_history_dir).await?;
+        let index_next = record_paths
+            .last()
+            .map(|(index, _record_path)| index + 1)
+            .unwrap_or(0);
+
+        Ok(index_next)
+    }
+
+    /// Appends the [`HistoryRecord`] to the [`ProfileHistoryDir`].
+    ///
+    /// The record's `index` is set to the next index in the profile's
+    /// history. Each record's file name is derived from its index, and the
+    /// record is only written if that file does not exist, so that when
+    /// commands record their history concurrently, each record is written
+    /// with a different index instead of one overwriting the other.
+    pub(crate) async fn record_append(
+        storage: &Storage,
+        profile_history_dir: &ProfileHistoryDir,
+        history_record: &mut HistoryRecord,
+    ) -> Result<(), Error> {
+        let mut index = Self::index_next(storage, profile_history_dir).await?;
+        loop {
+            history_record.summary.index = index;
+            let record_path = Self::record_path(storage, profile_history_dir, index);
+            let record_bytes = storage
+                .format()
+                .serialize(history_record)
+                .map_err(Error::HistoryRecordSerialize)?;
+
+            if storage
+                .backend()
+                .write_new(&record_path, &record_bytes)
+                .await?
+            {
+                return Ok(());
+            }
+
+            // Another command recorded its history with this index.
+            index += 1;
+        }
+    }
+
+    /// Returns the [`HistorySummary`] of every record in the
+    /// [`ProfileHistoryDir`], ordered by index.
+    pub(crate) async fn summaries_read(
+        storage: &Storage,
+        profile_history_dir: &ProfileHistoryDir,
+    ) -> Result<Vec<HistorySummary>, Error> {
+        let record_paths = Self::record_paths(storage, profile_history_dir).await?;
+
+        let mut history_summaries = Vec::with_capacity(record_paths.len());
+        for (_index, record_path) in record_paths {
+            let history_summary = storage
+                .serialized_read::<HistorySummary, _>(&record_path, |error| {
+                    Error::HistoryRecordDeserialize {
+                        path: record_path.clone(),
+                        error,
+                    }
+                })
+                .await?;
+            history_summaries.push(history_summary);
+        }
+
+        Ok(history_summaries)
+    }
+
+    /// Returns the [`HistoryRecord`] with the given index.
+    ///
+    /// # Parameters
+    ///
+    /// * `storage`: `Storage` to read from.
+    /// * `profile_history_dir`: Directory that contains the records.
+    /// * `item_apply_type_reg`: Type registry with functions to deserialize
+    ///   each item spec's `ItemApply`.
+    /// * `flow_id`: ID of the current flow, which the record must have been
+    ///   recorded for.
+    /// * `index`: Index of the record to read.
+    pub(crate) async fn record_read(
+        storage: &Storage,
+        profile_history_dir: &ProfileHistoryDir,
+        item_apply_type_reg: &TypeReg<ItemSpecId, ItemApplyBoxed>,
+        flow_id: &FlowId,
+        index: u32,
+    ) -> Result<HistoryRecord, Error> {
+        let record_paths = Self::record_paths(storage, profile_history_dir).await?;
+        let record_path = record_paths
+            .into_iter()
+            .find_map(|(record_index, record_path)| (record_index == index).then_some(record_path))
+            .ok_or(Error::HistoryRecordNotFound { index })?;
+
+        Self::record_path_read(storage, item_apply_type_reg, flow_id, index, &record_path).await
+    }
+
+    /// Rewrites each [`HistoryRecord`] of the flow in the storage's format,
+    /// and removes the record in the previous format.
+    ///
+    /// Records of other flows are not rewritten, as the `item_applies` can
+    /// only be deserialized by the flow that recorded them.
+    ///
+    /// Returns the paths of the rewritten records.
+    pub(crate) async fn records_migrate(
+        storage: &Storage,
+        profile_history_dir: &ProfileHistoryDir,
+        item_apply_type_reg: &TypeReg<ItemSpecId, ItemApplyBoxed>,
+        flow_id: &FlowId,
+    ) -> Result<Vec<PathBuf>, Error> {
+        let record_paths = Self::record_paths(storage, profile_history_dir).await?;
+
+        let mut record_paths_migrated = Vec::with_capacity(record_paths.len());
+        for (index, record_path) in record_paths {
+            let history_record = match Self::record_path_read(
+                storage,
+                item_apply_type_reg,
+                flow_id,
+                index,
+                &record_path,
+            )
+            .await
+            {
+                Ok(history_record) => history_record,
+                Err(Error::HistoryRecordFlowIdMismatch { .. }) => continue,
+                Err(error) => return Err(error),
+            };
+
+            let record_path_migrated = Self::record_path(storage, profile_history_dir, index);
+            storage
+                .serialized_write(
+                    &record_path_migrated,
+                    &history_record,
+                    Error::HistoryRecordSerialize,
+                )
+                .await?;
+            if record_path != record_path_migrated {
+                storage.backend().remove(&record_path).await?;
+            }
+            record_paths_migrated.push(record_path_migrated);
+        }
+
+        Ok(record_paths_migrated)
+    }
+
+    /// Returns the [`HistoryRecord`] at the given path.
+    async fn record_path_read(
+        storage: &Storage,
+        item_apply_type_reg: &TypeReg<ItemSpecId, ItemApplyBoxed>,
+        flow_id: &FlowId,
+        index: u32,
+        record_path: &Path,
+    ) -> Result<HistoryRecord, Error> {
+        let map_err = |error| Error::HistoryRecordDeserialize {
+            path: record_path.to_path_buf(),
+            error,
+        };
+        let record_bytes = storage.backend().read(record_path).await?;
+        let record_bytes = record_bytes.ok_or_else(|| Error::ItemNotExists {
+            path: record_path.to_path_buf(),
+        })?;
+
+        // The summary is checked first, as the `item_applies` can only be
+        // deserialized by the flow that recorded them.
+        let summary =
+            StorageFormat::deserialize::<HistorySummary>(&record_bytes).map_err(map_err)?;
+        if &summary.flow_id != flow_id {
+            return Err(Error::HistoryRecordFlowIdMismatch {
+                index,
+                flow_id: summary.flow_id,
+                flow_id_current: flow_id.clone(),
+            });
+        }
+
+        let HistoryRecordDetails {
+            item_applies,
+            errors,
+        } = StorageFormat::deserialize_seed(
+            &record_bytes,
+            HistoryRecordDetailsSeed::new(item_apply_type_reg),
+        )
+        .map_err(map_err)?;
+
+        Ok(HistoryRecord {
+            summary,
+            item_applies,
+            errors,
+        })
+    }
+
+    /// Returns the path of the record with the given index, in the storage's
+    /// format.
+    fn record_path(
+        storage: &Storage,
+        profile_history_dir: &ProfileHistoryDir,
+        index: u32,
+    ) -> PathBuf {
+        let extension = storage.format().extension();
+        profile_history_dir.join(format!("{index:08}.{extension}"))
+    }
+
+    /// Returns the index and path of each record in the
+    /// [`ProfileHistoryDir`], ordered by index.
+    ///
+    /// Files whose names are not a record index, or do not have a storage
+    /// format's extension, are ignored. If a record is stored in more than one
+    /// format, such as when migrating the records is interrupted, the record in
+    /// the storage's format is returned.
+    async fn record_paths(
+        storage: &Storage,
+        profile_history_dir: &ProfileHistoryDir,
+    ) -> Result<Vec<(u32, PathBuf)>, Error> {
+        let extension_current = storage.format().extension();
+        let dir_entries = storage.backend().dir_entries(profile_history_dir).await?;
+        let mut record_paths = dir_entries
+            .into_iter()
+            .filter(|entry_path| {
+                entry_path
+                    .extension()
+                    .and_then(OsStr::to_str)
+                    .is_some_and(|extension| StorageFormat::EXTENSIONS.contains(&extension))
+            })
+            .filter_map(|entry_path| {
+                let index = entry_path
+                    .file_stem()
+                    .and_then(OsStr::to_str)
+                    .and_then(|file_stem| file_stem.parse::<u32>().ok())?;
+
+                Some((index, entry_path))
+            })
+            .collect::<Vec<(u32, PathBuf)>>();
+
+        record_paths.sort_by_key(|(index, record_path)| {
+            let is_current_format =
+                record_path.extension().and_then(OsStr::to_str) == Some(extension_current);
+            (*index, !is_current_format)
+        });
+        record_paths.dedup_by_key(|(index, _record_path)| *index);
+
+        Ok(record_paths)
+    }
+}
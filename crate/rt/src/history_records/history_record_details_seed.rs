@@ -0,0 +1,103 @@
+use std::fmt;
+
+use peace_cfg::ItemSpecId;
+use peace_resources::type_reg::untagged::{TypeMap, TypeReg};
+use peace_rt_model::{outcomes::ItemApplyBoxed, IndexMap};
+use serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, Visitor};
+
+/// Parts of a [`HistoryRecord`] that are deserialized separately from the
+/// [`HistorySummary`].
+///
+/// [`HistoryRecord`]: peace_rt_model::outcomes::HistoryRecord
+/// [`HistorySummary`]: peace_rt_model::outcomes::HistorySummary
+pub(crate) struct HistoryRecordDetails {
+    /// [`ItemApply`] of each item that was applied.
+    ///
+    /// [`ItemApply`]: peace_rt_model::outcomes::ItemApply
+    pub(crate) item_applies: TypeMap<ItemSpecId, ItemApplyBoxed>,
+    /// Error message of each item that failed.
+    pub(crate) errors: IndexMap<ItemSpecId, String>,
+}
+
+/// Deserializes [`HistoryRecordDetails`], ignoring the summary fields.
+pub(crate) struct HistoryRecordDetailsSeed<'r> {
+    /// Type registry with functions to deserialize each item spec's
+    /// `ItemApply`.
+    item_apply_type_reg: &'r TypeReg<ItemSpecId, ItemApplyBoxed>,
+}
+
+impl<'r> HistoryRecordDetailsSeed<'r> {
+    /// Returns a new `HistoryRecordDetailsSeed`.
+    pub(crate) fn new(item_apply_type_reg: &'r TypeReg<ItemSpecId, ItemApplyBoxed>) -> Self {
+        Self {
+            item_apply_type_reg,
+        }
+    }
+}
+
+impl<'de, 'r: 'de> DeserializeSeed<'de> for HistoryRecordDetailsSeed<'r> {
+    type Value = HistoryRecordDetails;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, 'r: 'de> Visitor<'de> for HistoryRecordDetailsSeed<'r> {
+    type Value = HistoryRecordDetails;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a history record")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut item_applies = None;
+        let mut errors = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "item_applies" => {
+                    item_applies = Some(map.next_value_seed(ItemAppliesSeed {
+                        item_apply_type_reg: self.item_apply_type_reg,
+                    })?);
+                }
+                "errors" => errors = Some(map.next_value()?),
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        let item_applies = item_applies.ok_or_else(|| de::Error::missing_field("item_applies"))?;
+        let errors = errors.unwrap_or_default();
+
+        Ok(HistoryRecordDetails {
+            item_applies,
+            errors,
+        })
+    }
+}
+
+/// Deserializes the `ItemApply` of each item spec.
+struct ItemAppliesSeed<'r> {
+    /// Type registry with functions to deserialize each item spec's
+    /// `ItemApply`.
+    item_apply_type_reg: &'r TypeReg<ItemSpecId, ItemApplyBoxed>,
+}
+
+impl<'de, 'r: 'de> DeserializeSeed<'de> for ItemAppliesSeed<'r> {
+    type Value = TypeMap<ItemSpecId, ItemApplyBoxed>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        self.item_apply_type_reg.deserialize_map(deserializer)
+    }
+}
@@ -12,5 +12,11 @@ pub const PROGRESS_COUNT_MAX: usize = 256;
 
 pub mod cmds;
 
+pub(crate) mod concurrency_groups;
+pub(crate) mod profiles_exec;
+pub(crate) mod states_unselected;
+
+pub(crate) mod history_records;
+
 #[cfg(feature = "output_progress")]
 pub(crate) mod progress;
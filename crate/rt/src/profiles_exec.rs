@@ -0,0 +1,89 @@
+use futures::{future::LocalBoxFuture, stream, StreamExt};
+use peace_cmd::{
+    ctx::CmdCtx,
+    scopes::{MultiProfileSingleFlow, SingleProfileSingleFlow},
+};
+use peace_fmt::{
+    presentable::{Heading, HeadingLevel},
+    Presentable,
+};
+use peace_resources::resources::ts::SetUp;
+use peace_rt_model::{
+    outcomes::{CmdOutcome, ProfilesCmdOutcome},
+    output::OutputWrite,
+    params::ParamsKeys,
+    Error, InMemoryTextOutput, IndexMap,
+};
+
+use crate::BUFFERED_FUTURES_MAX;
+
+/// Runs a single profile command for each profile of a multi profile command.
+pub(crate) struct ProfilesExec;
+
+impl ProfilesExec {
+    /// Runs `f` for each profile of `cmd_ctx`, and presents each profile's
+    /// outcome under a heading with the profile name.
+    ///
+    /// Profiles are run concurrently, up to the `CmdCtx`'s profile concurrency
+    /// limit. Each profile's command writes to its own output, so progress is
+    /// not rendered while the profiles run. Outcomes are presented in profile
+    /// order once every profile has run.
+    pub(crate) async fn exec<E, O, PKeys, T, F>(
+        cmd_ctx: &mut CmdCtx<MultiProfileSingleFlow<'_, E, O, PKeys>>,
+        f: F,
+    ) -> Result<ProfilesCmdOutcome<T, E>, E>
+    where
+        E: std::error::Error + From<Error> + Send + 'static,
+        O: OutputWrite<E>,
+        PKeys: ParamsKeys + 'static,
+        T: Presentable,
+        F: for<'f, 'ctx> Fn(
+            &'f mut CmdCtx<SingleProfileSingleFlow<'ctx, E, InMemoryTextOutput, PKeys, SetUp>>,
+        ) -> LocalBoxFuture<'f, Result<CmdOutcome<T, E>, E>>,
+    {
+        let profile_concurrency_limit = cmd_ctx
+            .profile_concurrency_limit()
+            .unwrap_or(BUFFERED_FUTURES_MAX);
+        let cmd_ctx_ref = &*cmd_ctx;
+        let f = &f;
+        let profile_results = stream::iter(cmd_ctx_ref.profiles())
+            .map(|profile| async move {
+                let mut output = InMemoryTextOutput::new();
+                let result = match cmd_ctx_ref.profile_cmd_ctx(&mut output, profile).await {
+                    Ok(mut profile_cmd_ctx) => f(&mut profile_cmd_ctx).await,
+                    Err(e) => Err(e),
+                };
+
+                (profile.clone(), result)
+            })
+            .buffered(profile_concurrency_limit)
+            .collect::<Vec<_>>()
+            .await;
+
+        let mut profile_to_cmd_outcome = IndexMap::with_capacity(profile_results.len());
+        let mut profile_to_error = IndexMap::new();
+        let output = cmd_ctx.output_mut();
+        for (profile, result) in profile_results {
+            let heading = Heading::new(HeadingLevel::Level2, &profile);
+            match result {
+                Ok(cmd_outcome) => {
+                    output.present(&(heading, &cmd_outcome.value)).await?;
+                    for error in cmd_outcome.errors.values() {
+                        output.write_err(error).await?;
+                    }
+                    profile_to_cmd_outcome.insert(profile, cmd_outcome);
+                }
+                Err(error) => {
+                    output.present(&heading).await?;
+                    output.write_err(&error).await?;
+                    profile_to_error.insert(profile, error);
+                }
+            }
+        }
+
+        Ok(ProfilesCmdOutcome {
+            profile_to_cmd_outcome,
+            profile_to_error,
+        })
+    }
+}
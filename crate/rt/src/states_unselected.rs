@@ -0,0 +1,111 @@
+use std::collections::HashSet;
+
+use peace_cfg::{FlowId, ItemSpecId};
+use peace_resources::{
+    paths::{FlowDir, StatesDesiredFile, StatesSavedFile},
+    resources::ts::SetUp,
+    states::{States, StatesDesired, StatesSaved},
+    Resources,
+};
+use peace_rt_model::{
+    storage::SerializedStorage, Error, StatesSerializer, StatesTypeRegs, Storage,
+};
+
+/// Retains the states of item specs outside an item spec selection.
+///
+/// When a command runs a subset of a flow, the states it writes to storage
+/// should still contain the previously written states of the other item specs.
+///
+/// Similarly, the states of item specs that are no longer in the flow are
+/// retained, so that they can still be cleaned up.
+pub struct StatesUnselected;
+
+impl StatesUnselected {
+    /// Returns the previously saved states, if they exist in storage.
+    pub async fn saved_read_opt<E>(
+        resources: &Resources<SetUp>,
+        states_type_regs: &StatesTypeRegs,
+    ) -> Result<Option<StatesSaved>, E>
+    where
+        E: std::error::Error + From<Error> + Send + 'static,
+    {
+        let flow_id = resources.borrow::<FlowId>();
+        let flow_dir = resources.borrow::<FlowDir>();
+        let storage = resources.borrow::<Storage>();
+        let states_saved_file = StatesSavedFile::from((&*flow_dir, storage.format().extension()));
+
+        StatesSerializer::deserialize_saved_opt(
+            &flow_id,
+            &*storage,
+            states_type_regs.states_current_type_reg(),
+            &states_saved_file,
+        )
+        .await
+    }
+
+    /// Returns the previously written desired states, if they exist in
+    /// storage.
+    pub async fn desired_read_opt<E>(
+        resources: &Resources<SetUp>,
+        states_type_regs: &StatesTypeRegs,
+    ) -> Result<Option<StatesDesired>, E>
+    where
+        E: std::error::Error + From<Error> + Send + 'static,
+    {
+        let flow_id = resources.borrow::<FlowId>();
+        let flow_dir = resources.borrow::<FlowDir>();
+        let storage = resources.borrow::<Storage>();
+        let states_desired_file =
+            StatesDesiredFile::from((&*flow_dir, storage.format().extension()));
+
+        StatesSerializer::deserialize_desired_opt(
+            &flow_id,
+            &*storage,
+            states_type_regs.states_desired_type_reg(),
+            &states_desired_file,
+        )
+        .await
+    }
+
+    /// Returns `states` with the states from `states_previous` of item specs
+    /// outside the selection.
+    pub fn merge<TS, TsPrevious>(
+        states: &States<TS>,
+        states_previous: Option<States<TsPrevious>>,
+        item_spec_ids_selected: &HashSet<ItemSpecId>,
+    ) -> States<TS> {
+        let mut states_merged = states.clone().into_inner();
+        if let Some(states_previous) = states_previous {
+            states_previous
+                .into_inner()
+                .into_inner()
+                .into_iter()
+                .filter(|(item_spec_id, _state)| !item_spec_ids_selected.contains(item_spec_id))
+                .for_each(|(item_spec_id, state)| {
+                    states_merged.insert_raw(item_spec_id, state);
+                });
+        }
+
+        States::from(states_merged)
+    }
+
+    /// Returns `states` with the orphaned states from `states_previous`.
+    ///
+    /// Orphaned states are the states of item specs that are no longer in the
+    /// flow.
+    pub fn merge_orphans<TS, TsPrevious>(
+        states: &States<TS>,
+        states_previous: Option<States<TsPrevious>>,
+    ) -> States<TS> {
+        let mut states_merged = states.clone().into_inner();
+        if let Some(states_previous) = states_previous {
+            states_previous
+                .orphans()
+                .for_each(|(item_spec_id, state_orphaned)| {
+                    states_merged.insert(item_spec_id.clone(), state_orphaned.clone());
+                });
+        }
+
+        States::from(states_merged)
+    }
+}
@@ -0,0 +1,70 @@
+use std::marker::PhantomData;
+
+use peace_cfg::{FlowId, ItemSpecId};
+use peace_resources::{paths::EnsurePlanFile, type_reg::untagged::TypeReg};
+use peace_rt_model_core::storage::SerializedStorage;
+
+use crate::{
+    outcomes::{EnsurePlan, ItemApplyBoxed},
+    Error,
+};
+
+/// Reads and writes [`EnsurePlan`]s to storage.
+pub struct EnsurePlanSerializer<E>(PhantomData<E>);
+
+impl<E> EnsurePlanSerializer<E>
+where
+    E: std::error::Error + From<Error> + Send,
+{
+    /// Writes the [`EnsurePlan`] to storage.
+    ///
+    /// # Parameters:
+    ///
+    /// * `storage`: `Storage` to write to.
+    /// * `ensure_plan`: Ensure plan to serialize.
+    /// * `ensure_plan_file`: Path to save the serialized plan to.
+    pub async fn serialize<S>(
+        storage: &S,
+        ensure_plan: &EnsurePlan,
+        ensure_plan_file: &EnsurePlanFile,
+    ) -> Result<(), E>
+    where
+        S: SerializedStorage,
+    {
+        storage
+            .serialized_write(ensure_plan_file, ensure_plan, Error::EnsurePlanSerialize)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns the [`EnsurePlan`] from storage.
+    ///
+    /// # Parameters:
+    ///
+    /// * `flow_id`: ID of the flow that the plan was generated for.
+    /// * `storage`: `Storage` to read from.
+    /// * `item_apply_type_reg`: Type registry with functions to deserialize
+    ///   each item spec's `ItemApply`.
+    /// * `ensure_plan_file`: `EnsurePlanFile` to deserialize.
+    pub async fn deserialize<S>(
+        flow_id: &FlowId,
+        storage: &S,
+        item_apply_type_reg: &TypeReg<ItemSpecId, ItemApplyBoxed>,
+        ensure_plan_file: &EnsurePlanFile,
+    ) -> Result<EnsurePlan, E>
+    where
+        S: SerializedStorage,
+    {
+        let ensure_plan = storage
+            .serialized_typemap_read_opt(item_apply_type_reg, ensure_plan_file, |error| {
+                Error::EnsurePlanDeserialize {
+                    flow_id: flow_id.clone(),
+                    error,
+                }
+            })
+            .await?;
+
+        ensure_plan.ok_or_else(|| E::from(Error::EnsurePlanRequired))
+    }
+}
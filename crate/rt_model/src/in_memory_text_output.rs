@@ -58,7 +58,8 @@ where
     where
         P: Presentable,
     {
-        self.buffer = serde_yaml::to_string(&presentable).map_err(Error::StatesSerialize)?;
+        self.buffer = serde_yaml::to_string(&presentable)
+            .map_err(|error| Error::StatesSerialize(error.into()))?;
 
         Ok(())
     }
@@ -100,6 +100,79 @@ where
     }
 }
 
+impl<IS, E, State, StateDiff, StateCurrentFnSpec, StateDesiredFnSpec, StateDiffFnSpec, ApplyOpSpec>
+    From<
+        ItemSpecWrapper<
+            IS,
+            E,
+            State,
+            StateDiff,
+            StateCurrentFnSpec,
+            StateDesiredFnSpec,
+            StateDiffFnSpec,
+            ApplyOpSpec,
+        >,
+    > for ItemSpecBoxed<E>
+where
+    IS: Clone
+        + Debug
+        + ItemSpec<
+            State = State,
+            StateDiff = StateDiff,
+            StateCurrentFnSpec = StateCurrentFnSpec,
+            StateDesiredFnSpec = StateDesiredFnSpec,
+            StateDiffFnSpec = StateDiffFnSpec,
+            ApplyOpSpec = ApplyOpSpec,
+        > + Send
+        + Sync
+        + 'static,
+    <IS as ItemSpec>::Error: Send + Sync,
+    E: Debug
+        + Send
+        + Sync
+        + std::error::Error
+        + From<<IS as ItemSpec>::Error>
+        + From<crate::Error>
+        + 'static,
+    State: Clone + Debug + fmt::Display + Serialize + DeserializeOwned + Send + Sync + 'static,
+    StateDiff: Clone + Debug + fmt::Display + Serialize + DeserializeOwned + Send + Sync + 'static,
+    StateCurrentFnSpec:
+        Debug + TryFnSpec<Error = <IS as ItemSpec>::Error, Output = State> + Send + Sync + 'static,
+    StateDesiredFnSpec:
+        Debug + TryFnSpec<Error = <IS as ItemSpec>::Error, Output = State> + Send + Sync + 'static,
+    StateDiffFnSpec: Debug
+        + peace_cfg::StateDiffFnSpec<
+            Error = <IS as ItemSpec>::Error,
+            State = State,
+            StateDiff = StateDiff,
+        > + Send
+        + Sync
+        + 'static,
+    ApplyOpSpec: Debug
+        + peace_cfg::ApplyOpSpec<
+            Error = <IS as ItemSpec>::Error,
+            State = State,
+            StateDiff = StateDiff,
+        > + Send
+        + Sync
+        + 'static,
+{
+    fn from(
+        item_spec_wrapper: ItemSpecWrapper<
+            IS,
+            E,
+            State,
+            StateDiff,
+            StateCurrentFnSpec,
+            StateDesiredFnSpec,
+            StateDiffFnSpec,
+            ApplyOpSpec,
+        >,
+    ) -> Self {
+        Self(Box::new(item_spec_wrapper))
+    }
+}
+
 impl<E> DataAccessDyn for ItemSpecBoxed<E> {
     fn borrows(&self) -> TypeIds {
         DataAccessDyn::borrows(self.0.as_ref())
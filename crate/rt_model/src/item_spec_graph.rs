@@ -1,24 +1,46 @@
-use std::ops::{Deref, DerefMut};
+use std::{
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+};
 
 use fn_graph::FnGraph;
+use peace_cfg::ConcurrencyGroupId;
 
 use crate::ItemSpecBoxed;
 
 /// Graph of all [`ItemSpec`]s, `FnGraph<ItemSpecBoxed<E>>` newtype.
 ///
+/// This also holds the concurrency limit for each concurrency group.
+///
 /// [`ItemSpec`]: peace_cfg::ItemSpec
 #[derive(Debug)]
-pub struct ItemSpecGraph<E>(FnGraph<ItemSpecBoxed<E>>);
+pub struct ItemSpecGraph<E>(
+    FnGraph<ItemSpecBoxed<E>>,
+    HashMap<ConcurrencyGroupId, usize>,
+);
 
 // Manual implementation because derive requires `E` to be `Clone`,
 // which causes `graph.clone()` to call `FnGraph::clone`.
 impl<E> Clone for ItemSpecGraph<E> {
     fn clone(&self) -> Self {
-        Self(self.0.clone())
+        Self(self.0.clone(), self.1.clone())
     }
 }
 
 impl<E> ItemSpecGraph<E> {
+    /// Returns a new `ItemSpecGraph` with the given concurrency group limits.
+    pub(crate) fn new(
+        graph: FnGraph<ItemSpecBoxed<E>>,
+        concurrency_group_limits: HashMap<ConcurrencyGroupId, usize>,
+    ) -> Self {
+        Self(graph, concurrency_group_limits)
+    }
+
+    /// Returns the concurrency limit for each concurrency group.
+    pub fn concurrency_group_limits(&self) -> &HashMap<ConcurrencyGroupId, usize> {
+        &self.1
+    }
+
     /// Returns the inner [`FnGraph`].
     pub fn into_inner(self) -> FnGraph<ItemSpecBoxed<E>> {
         self.0
@@ -41,6 +63,6 @@ impl<E> DerefMut for ItemSpecGraph<E> {
 
 impl<E> From<FnGraph<ItemSpecBoxed<E>>> for ItemSpecGraph<E> {
     fn from(graph: FnGraph<ItemSpecBoxed<E>>) -> Self {
-        Self(graph)
+        Self(graph, HashMap::new())
     }
 }
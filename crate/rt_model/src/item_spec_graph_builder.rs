@@ -1,13 +1,22 @@
-use std::ops::{Deref, DerefMut};
+use std::{
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+};
 
 use fn_graph::FnGraphBuilder;
+use peace_cfg::ConcurrencyGroupId;
 
 use crate::{ItemSpecBoxed, ItemSpecGraph};
 
 /// Builder for an [`ItemSpecGraph`], `FnGraphBuilder<ItemSpecBoxed<E>>`
 /// newtype.
+///
+/// This also holds the concurrency limit for each concurrency group.
 #[derive(Debug)]
-pub struct ItemSpecGraphBuilder<E>(FnGraphBuilder<ItemSpecBoxed<E>>);
+pub struct ItemSpecGraphBuilder<E>(
+    FnGraphBuilder<ItemSpecBoxed<E>>,
+    HashMap<ConcurrencyGroupId, usize>,
+);
 
 impl<E> ItemSpecGraphBuilder<E> {
     /// Returns a new `ItemSpecGraphBuilder`.
@@ -20,15 +29,31 @@ impl<E> ItemSpecGraphBuilder<E> {
         self.0
     }
 
+    /// Adds a concurrency group, limiting the number of item specs in the
+    /// group that execute concurrently.
+    ///
+    /// Item specs are added to the group through
+    /// [`ItemSpecWrapper::with_concurrency_group`]. A `limit` of `0` is
+    /// treated as `1`.
+    ///
+    /// [`ItemSpecWrapper::with_concurrency_group`]: crate::ItemSpecWrapper::with_concurrency_group
+    pub fn add_concurrency_group(
+        &mut self,
+        concurrency_group_id: ConcurrencyGroupId,
+        limit: usize,
+    ) {
+        self.1.insert(concurrency_group_id, limit.max(1));
+    }
+
     /// Builds and returns the [`ItemSpecGraph`].
     pub fn build(self) -> ItemSpecGraph<E> {
-        ItemSpecGraph::from(self.0.build())
+        ItemSpecGraph::new(self.0.build(), self.1)
     }
 }
 
 impl<E> Default for ItemSpecGraphBuilder<E> {
     fn default() -> Self {
-        Self(FnGraphBuilder::default())
+        Self(FnGraphBuilder::default(), HashMap::new())
     }
 }
 
@@ -48,6 +73,6 @@ impl<E> DerefMut for ItemSpecGraphBuilder<E> {
 
 impl<E> From<FnGraphBuilder<ItemSpecBoxed<E>>> for ItemSpecGraphBuilder<E> {
     fn from(graph: FnGraphBuilder<ItemSpecBoxed<E>>) -> Self {
-        Self(graph)
+        Self(graph, HashMap::new())
     }
 }
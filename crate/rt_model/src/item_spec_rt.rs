@@ -2,7 +2,7 @@ use std::fmt::Debug;
 
 use dyn_clone::DynClone;
 use fn_graph::{DataAccess, DataAccessDyn};
-use peace_cfg::{async_trait, ItemSpecId, OpCtx};
+use peace_cfg::{async_trait, ConcurrencyGroupId, ItemSpecId, OpCtx};
 use peace_resources::{
     resources::ts::{Empty, SetUp},
     states::{StatesCurrent, StatesDesired, StatesSaved},
@@ -11,8 +11,8 @@ use peace_resources::{
 };
 
 use crate::{
-    outcomes::{ItemApplyBoxed, ItemApplyPartialBoxed},
-    StatesTypeRegs,
+    outcomes::{ItemApplyBoxed, ItemApplyPartialBoxed, StateDrift},
+    RetryOutcome, StatesTypeRegs,
 };
 
 /// Internal trait that erases the types from [`ItemSpec`]
@@ -30,6 +30,9 @@ pub trait ItemSpecRt<E>: Debug + DataAccess + DataAccessDyn + DynClone {
     /// [`ItemSpec::id`]: peace_cfg::ItemSpec::id
     fn id(&self) -> &ItemSpecId;
 
+    /// Returns the concurrency group that this item spec belongs to, if any.
+    fn concurrency_group(&self) -> Option<&ConcurrencyGroupId>;
+
     /// Initializes data for the operation's check and `exec` functions.
     async fn setup(&self, resources: &mut Resources<Empty>) -> Result<(), E>
     where
@@ -37,8 +40,8 @@ pub trait ItemSpecRt<E>: Debug + DataAccess + DataAccessDyn + DynClone {
 
     /// Registers state types with type registries for deserializing from disk.
     ///
-    /// This is necessary to deserialize `StatesSavedFile` and
-    /// `StatesDesiredFile`.
+    /// This is necessary to deserialize `StatesSavedFile`,
+    /// `StatesDesiredFile`, `EnsurePlanFile`, and history records.
     fn state_register(&self, states_type_regs: &mut StatesTypeRegs);
 
     /// Runs [`ItemSpec::state_clean`].
@@ -50,49 +53,65 @@ pub trait ItemSpecRt<E>: Debug + DataAccess + DataAccessDyn + DynClone {
 
     /// Runs [`ItemSpec::StateCurrentFnSpec`]`::`[`try_exec`].
     ///
+    /// The returned outcome holds the number of retries made according to the
+    /// item spec's [`RetryPolicy`].
+    ///
     /// [`ItemSpec::StateCurrentFnSpec`]: peace_cfg::ItemSpec::StateCurrentFnSpec
+    /// [`RetryPolicy`]: crate::RetryPolicy
     /// [`try_exec`]: peace_cfg::TryFnSpec::try_exec
     async fn state_current_try_exec(
         &self,
         op_ctx: OpCtx<'_>,
         resources: &Resources<SetUp>,
-    ) -> Result<Option<BoxDtDisplay>, E>
+    ) -> RetryOutcome<Option<BoxDtDisplay>, E>
     where
         E: Debug + std::error::Error;
 
     /// Runs [`ItemSpec::StateCurrentFnSpec`]`::`[`exec`].
     ///
+    /// The returned outcome holds the number of retries made according to the
+    /// item spec's [`RetryPolicy`].
+    ///
     /// [`ItemSpec::StateCurrentFnSpec`]: peace_cfg::ItemSpec::StateCurrentFnSpec
+    /// [`RetryPolicy`]: crate::RetryPolicy
     /// [`exec`]: peace_cfg::TryFnSpec::exec
     async fn state_current_exec(
         &self,
         op_ctx: OpCtx<'_>,
         resources: &Resources<SetUp>,
-    ) -> Result<BoxDtDisplay, E>
+    ) -> RetryOutcome<BoxDtDisplay, E>
     where
         E: Debug + std::error::Error;
 
     /// Runs [`ItemSpec::StateDesiredFnSpec`]`::`[`try_exec`].
     ///
+    /// The returned outcome holds the number of retries made according to the
+    /// item spec's [`RetryPolicy`].
+    ///
     /// [`ItemSpec::StateDesiredFnSpec`]: peace_cfg::ItemSpec::StateDesiredFnSpec
+    /// [`RetryPolicy`]: crate::RetryPolicy
     /// [`try_exec`]: peace_cfg::TryFnSpec::try_exec
     async fn state_desired_try_exec(
         &self,
         op_ctx: OpCtx<'_>,
         resources: &Resources<SetUp>,
-    ) -> Result<Option<BoxDtDisplay>, E>
+    ) -> RetryOutcome<Option<BoxDtDisplay>, E>
     where
         E: Debug + std::error::Error;
 
     /// Runs [`ItemSpec::StateDesiredFnSpec`]`::`[`exec`].
     ///
+    /// The returned outcome holds the number of retries made according to the
+    /// item spec's [`RetryPolicy`].
+    ///
     /// [`ItemSpec::StateDesiredFnSpec`]: peace_cfg::ItemSpec::StateDesiredFnSpec
+    /// [`RetryPolicy`]: crate::RetryPolicy
     /// [`exec`]: peace_cfg::TryFnSpec::exec
     async fn state_desired_exec(
         &self,
         op_ctx: OpCtx<'_>,
         resources: &Resources<SetUp>,
-    ) -> Result<BoxDtDisplay, E>
+    ) -> RetryOutcome<BoxDtDisplay, E>
     where
         E: Debug + std::error::Error;
 
@@ -120,6 +139,37 @@ pub trait ItemSpecRt<E>: Debug + DataAccess + DataAccessDyn + DynClone {
     where
         E: Debug + std::error::Error;
 
+    /// Returns the diff between the saved [`State`]s of two profiles.
+    ///
+    /// `states_saved_a` is used as the base state, and `states_saved_b` as
+    /// the target state, so the diff describes what is different in profile
+    /// `b`.
+    ///
+    /// [`State`]: peace_cfg::State
+    async fn state_diff_exec_with_states_saved_pair(
+        &self,
+        resources: &Resources<SetUp>,
+        states_saved_a: &StatesSaved,
+        states_saved_b: &StatesSaved,
+    ) -> Result<Option<BoxDtDisplay>, E>
+    where
+        E: Debug + std::error::Error;
+
+    /// Returns whether the current [`State`] has drifted from the saved
+    /// [`State`].
+    ///
+    /// See [`StateDrift`] for how drift is determined.
+    ///
+    /// [`State`]: peace_cfg::State
+    async fn state_drift_exec(
+        &self,
+        resources: &Resources<SetUp>,
+        states_saved: &StatesSaved,
+        states_current: &StatesCurrent,
+    ) -> Result<StateDrift, E>
+    where
+        E: Debug + std::error::Error;
+
     /// Discovers the information needed for an ensure execution.
     ///
     /// This runs the following functions in order:
@@ -141,6 +191,35 @@ pub trait ItemSpecRt<E>: Debug + DataAccess + DataAccessDyn + DynClone {
     where
         E: Debug + std::error::Error;
 
+    /// Discovers the information needed for an ensure execution, using the
+    /// given desired state instead of discovering it.
+    ///
+    /// This runs the following functions in order:
+    ///
+    /// * [`StateCurrentFnSpec::exec`]
+    /// * [`StateDiffFnSpec::exec`]
+    /// * [`ApplyOpSpec::check`]
+    ///
+    /// This is used to apply items towards a previous desired state, such as
+    /// when rolling back to a previous execution.
+    ///
+    /// Returns [`Error::StateDesiredTypeMismatch`] if `state_desired` is not
+    /// this item spec's `State` type.
+    ///
+    /// [`Error::StateDesiredTypeMismatch`]: crate::Error::StateDesiredTypeMismatch
+    ///
+    /// [`StateCurrentFnSpec::exec`]: peace_cfg::ItemSpec::StateCurrentFnSpec
+    /// [`StateDiffFnSpec::exec`]: peace_cfg::ItemSpec::StateDiffFnSpec
+    /// [`ApplyOpSpec::check`]: peace_cfg::ItemSpec::ApplyOpSpec
+    async fn ensure_prepare_with_state_desired(
+        &self,
+        op_ctx: OpCtx<'_>,
+        resources: &Resources<SetUp>,
+        state_desired: &BoxDtDisplay,
+    ) -> Result<ItemApplyBoxed, (E, ItemApplyPartialBoxed)>
+    where
+        E: Debug + std::error::Error;
+
     /// Discovers the information needed for a clean execution.
     ///
     /// This runs the following functions in order:
@@ -0,0 +1,133 @@
+use std::collections::{HashMap, HashSet};
+
+use fn_graph::{daggy::Walker, Edge};
+use peace_cfg::ItemSpecId;
+
+use crate::{Error, ItemSpecGraph};
+
+/// Subset of a flow's item specs that a command operates on.
+///
+/// Item specs outside the selection are not run, and their entries in
+/// `states_saved.yaml` and `states_desired.yaml` are left untouched.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let item_spec_selection = ItemSpecSelection::new([item_spec_id!("app_download")])
+///     .with_closure(ItemSpecSelectionClosure::WithSuccessors);
+///
+/// let mut cmd_ctx = cmd_ctx.with_item_spec_selection(&item_spec_selection)?;
+/// EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ItemSpecSelection {
+    /// IDs of the item specs that are explicitly selected.
+    item_spec_ids: Vec<ItemSpecId>,
+    /// Which other item specs to include.
+    closure: ItemSpecSelectionClosure,
+}
+
+impl ItemSpecSelection {
+    /// Returns a new `ItemSpecSelection` of the given item specs.
+    ///
+    /// Only the given item specs are selected. Use [`Self::with_closure`] to
+    /// include their predecessors or successors.
+    pub fn new<I>(item_spec_ids: I) -> Self
+    where
+        I: IntoIterator<Item = ItemSpecId>,
+    {
+        Self {
+            item_spec_ids: item_spec_ids.into_iter().collect(),
+            closure: ItemSpecSelectionClosure::default(),
+        }
+    }
+
+    /// Sets which other item specs to include in the selection.
+    #[must_use]
+    pub fn with_closure(mut self, closure: ItemSpecSelectionClosure) -> Self {
+        self.closure = closure;
+        self
+    }
+
+    /// Returns the IDs of the item specs that are explicitly selected.
+    pub fn item_spec_ids(&self) -> &[ItemSpecId] {
+        &self.item_spec_ids
+    }
+
+    /// Returns which other item specs are included in the selection.
+    pub fn closure(&self) -> ItemSpecSelectionClosure {
+        self.closure
+    }
+
+    /// Returns the IDs of all item specs in the selection.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ItemSpecSelectionIdNotFound`] if a selected item spec
+    /// is not in the graph.
+    pub fn resolve<E>(
+        &self,
+        item_spec_graph: &ItemSpecGraph<E>,
+    ) -> Result<HashSet<ItemSpecId>, Error> {
+        let item_spec_id_to_fn_id = item_spec_graph
+            .iter_insertion_with_indices()
+            .map(|(fn_id, item_spec)| (item_spec.id(), fn_id))
+            .collect::<HashMap<_, _>>();
+
+        let mut fn_ids_to_visit = self
+            .item_spec_ids
+            .iter()
+            .map(|item_spec_id| {
+                item_spec_id_to_fn_id
+                    .get(item_spec_id)
+                    .copied()
+                    .ok_or_else(|| Error::ItemSpecSelectionIdNotFound {
+                        item_spec_id: item_spec_id.clone(),
+                    })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        // Only logical edges are followed, as data edges between item specs that
+        // access the same data do not mean one item spec depends on the other.
+        let dag = &item_spec_graph.graph;
+        let mut item_spec_ids_selected = HashSet::with_capacity(fn_ids_to_visit.len());
+        while let Some(fn_id) = fn_ids_to_visit.pop() {
+            if !item_spec_ids_selected.insert(dag[fn_id].id().clone()) {
+                continue;
+            }
+
+            match self.closure {
+                ItemSpecSelectionClosure::SelectedOnly => {}
+                ItemSpecSelectionClosure::WithPredecessors => fn_ids_to_visit.extend(
+                    dag.parents(fn_id)
+                        .iter(dag)
+                        .filter(|(edge_id, _fn_id_parent)| dag[*edge_id] == Edge::Logic)
+                        .map(|(_edge_id, fn_id_parent)| fn_id_parent),
+                ),
+                ItemSpecSelectionClosure::WithSuccessors => fn_ids_to_visit.extend(
+                    dag.children(fn_id)
+                        .iter(dag)
+                        .filter(|(edge_id, _fn_id_child)| dag[*edge_id] == Edge::Logic)
+                        .map(|(_edge_id, fn_id_child)| fn_id_child),
+                ),
+            }
+        }
+
+        Ok(item_spec_ids_selected)
+    }
+}
+
+/// Which item specs to include in an [`ItemSpecSelection`], in addition to the
+/// explicitly selected item specs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ItemSpecSelectionClosure {
+    /// Only include the explicitly selected item specs.
+    #[default]
+    SelectedOnly,
+    /// Also include all item specs that the selected item specs transitively
+    /// depend on.
+    WithPredecessors,
+    /// Also include all item specs that transitively depend on the selected
+    /// item specs.
+    WithSuccessors,
+}
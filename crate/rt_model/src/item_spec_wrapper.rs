@@ -1,29 +1,43 @@
 use std::{
     fmt::{self, Debug},
+    future::Future,
     marker::PhantomData,
     ops::{Deref, DerefMut},
+    pin::pin,
+    sync::Arc,
+    time::Duration,
 };
 
 use fn_graph::{DataAccess, DataAccessDyn, TypeIds};
-use peace_cfg::{async_trait, ItemSpec, ItemSpecId, OpCheckStatus, OpCtx, TryFnSpec};
+use futures::future::{self, Either};
+use peace_cfg::{
+    async_trait, ConcurrencyGroupId, ItemSpec, ItemSpecId, OpCheckStatus, OpCtx, TryFnSpec,
+};
 use peace_data::{
     marker::{ApplyDry, Clean, Current, Desired},
+    params::{ParamsResolveError, ParamsSpec, ValueResolutionMode},
     Data,
 };
 use peace_resources::{
     resources::ts::{Empty, SetUp},
     states::{States, StatesCurrent, StatesDesired, StatesSaved},
-    type_reg::untagged::BoxDtDisplay,
+    type_reg::untagged::{BoxDataTypeDowncast, BoxDtDisplay},
     Resources,
 };
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
-    outcomes::{ItemApply, ItemApplyBoxed, ItemApplyPartial, ItemApplyPartialBoxed},
-    ItemSpecRt, StatesTypeRegs,
+    outcomes::{ItemApply, ItemApplyBoxed, ItemApplyPartial, ItemApplyPartialBoxed, StateDrift},
+    params::ParamsLimits,
+    params_spec_rt::ParamsSpecRt,
+    ItemSpecRt, RetryOutcome, RetryPolicy, StatesTypeRegs,
 };
 
 /// Wraps a type implementing [`ItemSpec`].
+///
+/// This also holds the [`RetryPolicy`] and timeout for the item spec's
+/// functions, the concurrency group that the item spec belongs to, and the
+/// spec to resolve the item spec's params from.
 #[allow(clippy::type_complexity)]
 pub struct ItemSpecWrapper<
     IS,
@@ -34,9 +48,21 @@ pub struct ItemSpecWrapper<
     StateDesiredFnSpec,
     StateDiffFnSpec,
     ApplyOpSpec,
->(
-    IS,
-    PhantomData<(
+> where
+    IS: ItemSpec,
+{
+    /// The item spec.
+    item_spec: IS,
+    /// Policy for retrying the item spec's functions when they fail.
+    retry_policy: RetryPolicy<<IS as ItemSpec>::Error>,
+    /// Maximum duration each of the item spec's functions may run for.
+    timeout: Option<Duration>,
+    /// Concurrency group that the item spec belongs to.
+    concurrency_group_id: Option<ConcurrencyGroupId>,
+    /// Spec to resolve the item spec's params from.
+    params_spec: Option<Arc<dyn ParamsSpecRt>>,
+    /// Marker.
+    marker: PhantomData<(
         E,
         State,
         StateDiff,
@@ -45,7 +71,7 @@ pub struct ItemSpecWrapper<
         StateDiffFnSpec,
         ApplyOpSpec,
     )>,
-);
+}
 
 impl<IS, E, State, StateDiff, StateCurrentFnSpec, StateDesiredFnSpec, StateDiffFnSpec, ApplyOpSpec>
     Clone
@@ -60,10 +86,103 @@ impl<IS, E, State, StateDiff, StateCurrentFnSpec, StateDesiredFnSpec, StateDiffF
         ApplyOpSpec,
     >
 where
-    IS: Clone,
+    IS: Clone + ItemSpec,
 {
     fn clone(&self) -> Self {
-        Self(self.0.clone(), PhantomData)
+        Self {
+            item_spec: self.item_spec.clone(),
+            retry_policy: self.retry_policy,
+            timeout: self.timeout,
+            concurrency_group_id: self.concurrency_group_id.clone(),
+            params_spec: self.params_spec.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<IS, E, State, StateDiff, StateCurrentFnSpec, StateDesiredFnSpec, StateDiffFnSpec, ApplyOpSpec>
+    ItemSpecWrapper<
+        IS,
+        E,
+        State,
+        StateDiff,
+        StateCurrentFnSpec,
+        StateDesiredFnSpec,
+        StateDiffFnSpec,
+        ApplyOpSpec,
+    >
+where
+    IS: ItemSpec,
+{
+    /// Sets the policy for retrying this item spec's functions when they fail.
+    #[must_use]
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy<<IS as ItemSpec>::Error>) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Returns the policy for retrying this item spec's functions when they
+    /// fail.
+    pub fn retry_policy(&self) -> &RetryPolicy<<IS as ItemSpec>::Error> {
+        &self.retry_policy
+    }
+
+    /// Sets the maximum duration each of this item spec's functions may run
+    /// for, including retries.
+    ///
+    /// When the timeout elapses, the function fails with
+    /// [`Error::ItemSpecTimeout`].
+    ///
+    /// [`Error::ItemSpecTimeout`]: crate::Error::ItemSpecTimeout
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Returns the maximum duration each of this item spec's functions may run
+    /// for, if any.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Sets the concurrency group that this item spec belongs to.
+    ///
+    /// Item specs in the same group are limited to the number of concurrent
+    /// executions registered for the group through
+    /// [`ItemSpecGraphBuilder::add_concurrency_group`].
+    ///
+    /// [`ItemSpecGraphBuilder::add_concurrency_group`]: crate::ItemSpecGraphBuilder::add_concurrency_group
+    #[must_use]
+    pub fn with_concurrency_group(mut self, concurrency_group_id: ConcurrencyGroupId) -> Self {
+        self.concurrency_group_id = Some(concurrency_group_id);
+        self
+    }
+
+    /// Sets the spec to resolve this item spec's params from.
+    ///
+    /// Each field of the params may be stored, provided, or mapped from a
+    /// predecessor's `State`. The params are resolved before each of this item
+    /// spec's functions are run, so a mapped field uses the predecessor's
+    /// state from the same command execution.
+    ///
+    /// The resolved params are written to the `Option<Params>` resource, which
+    /// is inserted as `None` when the item spec is set up. The item spec reads
+    /// them through [`ROpt`] or [`WOpt`].
+    ///
+    /// See [`ValueSpec`] for how each field is resolved.
+    ///
+    /// [`ROpt`]: peace_data::accessors::ROpt
+    /// [`ValueSpec`]: peace_data::params::ValueSpec
+    /// [`WOpt`]: peace_data::accessors::WOpt
+    #[must_use]
+    pub fn with_params_spec<PS>(mut self, params_spec: PS) -> Self
+    where
+        PS: ParamsSpec + Debug + Send + Sync + 'static,
+        PS::Params: Debug + Send + Sync + 'static,
+    {
+        self.params_spec = Some(Arc::new(params_spec));
+        self
     }
 }
 
@@ -117,10 +236,75 @@ where
         > + Send
         + Sync,
 {
+    /// Runs `f`, failing with [`Error::ItemSpecTimeout`] if it does not
+    /// complete within this item spec's timeout.
+    ///
+    /// [`Error::ItemSpecTimeout`]: crate::Error::ItemSpecTimeout
+    async fn timeout_exec<T, Fut>(&self, f: Fut) -> Result<T, E>
+    where
+        Fut: Future<Output = Result<T, <IS as ItemSpec>::Error>>,
+    {
+        let Some(timeout) = self.timeout else {
+            return f.await.map_err(Into::<E>::into);
+        };
+
+        match future::select(pin!(f), pin!(crate::time::sleep(timeout))).await {
+            Either::Left((result, _sleep)) => result.map_err(Into::<E>::into),
+            Either::Right(((), _f)) => Err(E::from(crate::Error::ItemSpecTimeout {
+                item_spec_id: self.id().clone(),
+                timeout,
+            })),
+        }
+    }
+
+    /// Resolves this item spec's params from its params spec, if any.
+    fn params_resolve<ResourcesTs>(
+        &self,
+        resources: &Resources<ResourcesTs>,
+        value_resolution_mode: ValueResolutionMode,
+    ) -> Result<(), E> {
+        let Some(params_spec) = self.params_spec.as_ref() else {
+            return Ok(());
+        };
+
+        params_spec
+            .resolve(resources, value_resolution_mode)
+            .map_err(|error| self.params_resolve_error(params_spec.as_ref(), error))
+    }
+
+    /// Resolves this item spec's params from its params spec, if any.
+    ///
+    /// Returns `Ok(false)` if a field could not be mapped from a predecessor's
+    /// `State`, such as when the predecessor does not exist yet.
+    fn params_try_resolve<ResourcesTs>(
+        &self,
+        resources: &Resources<ResourcesTs>,
+        value_resolution_mode: ValueResolutionMode,
+    ) -> Result<bool, E> {
+        let Some(params_spec) = self.params_spec.as_ref() else {
+            return Ok(true);
+        };
+
+        match params_spec.resolve(resources, value_resolution_mode) {
+            Ok(()) => Ok(true),
+            Err(ParamsResolveError::FromMap { .. }) => Ok(false),
+            Err(error) => Err(self.params_resolve_error(params_spec.as_ref(), error)),
+        }
+    }
+
+    fn params_resolve_error(&self, params_spec: &dyn ParamsSpecRt, error: ParamsResolveError) -> E {
+        E::from(crate::Error::ParamsResolve {
+            item_spec_id: self.id().clone(),
+            params_type_name: params_spec.params_type_name(),
+            error,
+        })
+    }
+
     async fn state_clean<ResourcesTs>(
         &self,
         resources: &Resources<ResourcesTs>,
     ) -> Result<State, E> {
+        self.params_resolve(resources, ValueResolutionMode::Current)?;
         let state_clean = {
             let data =
                 <<IS as peace_cfg::ItemSpec>::Data<'_> as Data>::borrow(self.id(), resources);
@@ -135,14 +319,25 @@ where
         &self,
         op_ctx: OpCtx<'_>,
         resources: &Resources<ResourcesTs>,
+        retry_count: &mut u32,
     ) -> Result<Option<State>, E> {
-        let state_current = {
-            let data = <<StateCurrentFnSpec as peace_cfg::TryFnSpec>::Data<'_> as Data>::borrow(
-                self.id(),
-                resources,
-            );
-            <StateCurrentFnSpec as TryFnSpec>::try_exec(op_ctx, data).await?
-        };
+        // The current state cannot be discovered if a predecessor's current state,
+        // which this item spec's params are mapped from, does not exist yet.
+        if !self.params_try_resolve(resources, ValueResolutionMode::Current)? {
+            return Ok(None);
+        }
+
+        let item_spec_id = self.id();
+        let state_current = self
+            .timeout_exec(self.retry_policy.exec_counted(op_ctx, retry_count, || async move {
+                let data =
+                    <<StateCurrentFnSpec as peace_cfg::TryFnSpec>::Data<'_> as Data>::borrow(
+                        item_spec_id,
+                        resources,
+                    );
+                <StateCurrentFnSpec as TryFnSpec>::try_exec(op_ctx, data).await
+            }))
+            .await?;
         if let Some(state_current) = state_current.as_ref() {
             resources.borrow_mut::<Current<State>>().0 = Some(state_current.clone());
         }
@@ -154,14 +349,21 @@ where
         &self,
         op_ctx: OpCtx<'_>,
         resources: &Resources<ResourcesTs>,
+        retry_count: &mut u32,
     ) -> Result<State, E> {
-        let state_current = {
-            let data = <<StateCurrentFnSpec as peace_cfg::TryFnSpec>::Data<'_> as Data>::borrow(
-                self.id(),
-                resources,
-            );
-            <StateCurrentFnSpec as TryFnSpec>::exec(op_ctx, data).await?
-        };
+        self.params_resolve(resources, ValueResolutionMode::Current)?;
+
+        let item_spec_id = self.id();
+        let state_current = self
+            .timeout_exec(self.retry_policy.exec_counted(op_ctx, retry_count, || async move {
+                let data =
+                    <<StateCurrentFnSpec as peace_cfg::TryFnSpec>::Data<'_> as Data>::borrow(
+                        item_spec_id,
+                        resources,
+                    );
+                <StateCurrentFnSpec as TryFnSpec>::exec(op_ctx, data).await
+            }))
+            .await?;
         resources.borrow_mut::<Current<State>>().0 = Some(state_current.clone());
 
         Ok(state_current)
@@ -171,13 +373,21 @@ where
         &self,
         op_ctx: OpCtx<'_>,
         resources: &Resources<SetUp>,
+        retry_count: &mut u32,
     ) -> Result<Option<State>, E> {
-        let data = <<StateDesiredFnSpec as peace_cfg::TryFnSpec>::Data<'_> as Data>::borrow(
-            self.id(),
-            resources,
-        );
-        let state_desired =
-            <StateDesiredFnSpec as peace_cfg::TryFnSpec>::try_exec(op_ctx, data).await?;
+        self.params_resolve(resources, ValueResolutionMode::Desired)?;
+
+        let item_spec_id = self.id();
+        let state_desired = self
+            .timeout_exec(self.retry_policy.exec_counted(op_ctx, retry_count, || async move {
+                let data =
+                    <<StateDesiredFnSpec as peace_cfg::TryFnSpec>::Data<'_> as Data>::borrow(
+                        item_spec_id,
+                        resources,
+                    );
+                <StateDesiredFnSpec as peace_cfg::TryFnSpec>::try_exec(op_ctx, data).await
+            }))
+            .await?;
         if let Some(state_desired) = state_desired.as_ref() {
             resources.borrow_mut::<Desired<State>>().0 = Some(state_desired.clone());
         }
@@ -189,26 +399,35 @@ where
         &self,
         op_ctx: OpCtx<'_>,
         resources: &Resources<SetUp>,
+        retry_count: &mut u32,
     ) -> Result<State, E> {
-        let data = <<StateDesiredFnSpec as peace_cfg::TryFnSpec>::Data<'_> as Data>::borrow(
-            self.id(),
-            resources,
-        );
-        let state_desired =
-            <StateDesiredFnSpec as peace_cfg::TryFnSpec>::exec(op_ctx, data).await?;
+        self.params_resolve(resources, ValueResolutionMode::Desired)?;
+
+        let item_spec_id = self.id();
+        let state_desired = self
+            .timeout_exec(self.retry_policy.exec_counted(op_ctx, retry_count, || async move {
+                let data =
+                    <<StateDesiredFnSpec as peace_cfg::TryFnSpec>::Data<'_> as Data>::borrow(
+                        item_spec_id,
+                        resources,
+                    );
+                <StateDesiredFnSpec as peace_cfg::TryFnSpec>::exec(op_ctx, data).await
+            }))
+            .await?;
         resources.borrow_mut::<Desired<State>>().0 = Some(state_desired.clone());
 
         Ok(state_desired)
     }
 
-    async fn state_diff_exec<ResourcesTs, StatesTs>(
+    async fn state_diff_exec<ResourcesTs, StatesTs, StatesTsDesired>(
         &self,
         resources: &Resources<ResourcesTs>,
         states_base: &States<StatesTs>,
-        states_desired: &StatesDesired,
+        states_desired: &States<StatesTsDesired>,
     ) -> Result<Option<StateDiff>, E>
     where
         StatesTs: Debug + Send + Sync + 'static,
+        StatesTsDesired: Debug + Send + Sync + 'static,
     {
         let item_spec_id = <IS as ItemSpec>::id(self);
         let state_base = states_base.get::<State, _>(item_spec_id);
@@ -238,6 +457,8 @@ where
         state_base: &State,
         state_desired: &State,
     ) -> Result<StateDiff, E> {
+        self.params_resolve(resources, ValueResolutionMode::Desired)?;
+
         let state_diff: StateDiff = {
             let data = <<StateDiffFnSpec as peace_cfg::StateDiffFnSpec>::Data<'_> as Data>::borrow(
                 self.id(),
@@ -258,6 +479,8 @@ where
         state_desired: &State,
         state_diff: &StateDiff,
     ) -> Result<OpCheckStatus, E> {
+        self.params_resolve(resources, ValueResolutionMode::Current)?;
+
         let data = <<ApplyOpSpec as peace_cfg::ApplyOpSpec>::Data<'_> as Data>::borrow(
             self.id(),
             resources,
@@ -280,6 +503,8 @@ where
         state_desired: &State,
         state_diff: &StateDiff,
     ) -> Result<State, E> {
+        self.params_resolve(resources, ValueResolutionMode::ApplyDry)?;
+
         let data = <<ApplyOpSpec as peace_cfg::ApplyOpSpec>::Data<'_> as Data>::borrow(
             self.id(),
             resources,
@@ -306,25 +531,124 @@ where
         state_current: &State,
         state_desired: &State,
         state_diff: &StateDiff,
+        retry_count: &mut u32,
     ) -> Result<State, E> {
-        let data = <<ApplyOpSpec as peace_cfg::ApplyOpSpec>::Data<'_> as Data>::borrow(
-            self.id(),
-            resources,
-        );
-        let state_ensured = <ApplyOpSpec as peace_cfg::ApplyOpSpec>::exec(
-            op_ctx,
-            data,
-            state_current,
-            state_desired,
-            state_diff,
-        )
-        .await
-        .map_err(Into::<E>::into)?;
+        self.params_resolve(resources, ValueResolutionMode::Current)?;
+
+        // Params mapped from a predecessor's state are only known once resolved, so
+        // their limits are checked before the item is applied.
+        if let Ok(params_limits) = resources.try_borrow::<ParamsLimits>() {
+            params_limits.check(resources).map_err(E::from)?;
+        }
+
+        let item_spec_id = self.id();
+        let state_ensured = self
+            .timeout_exec(self.retry_policy.exec_counted(op_ctx, retry_count, || async move {
+                let data = <<ApplyOpSpec as peace_cfg::ApplyOpSpec>::Data<'_> as Data>::borrow(
+                    item_spec_id,
+                    resources,
+                );
+                <ApplyOpSpec as peace_cfg::ApplyOpSpec>::exec(
+                    op_ctx,
+                    data,
+                    state_current,
+                    state_desired,
+                    state_diff,
+                )
+                .await
+            }))
+            .await?;
 
         resources.borrow_mut::<Current<State>>().0 = Some(state_ensured.clone());
 
         Ok(state_ensured)
     }
+
+    /// Discovers the information needed for an ensure execution.
+    ///
+    /// If `state_desired` is `None`, the desired state is discovered using
+    /// the `StateDesiredFnSpec`.
+    async fn ensure_prepare_with(
+        &self,
+        op_ctx: OpCtx<'_>,
+        resources: &Resources<SetUp>,
+        state_desired: Option<State>,
+    ) -> Result<ItemApplyBoxed, (E, ItemApplyPartialBoxed)> {
+        let mut item_apply_partial = ItemApplyPartial::<State, StateDiff>::new();
+
+        match self
+            .state_current_exec(op_ctx, resources, &mut item_apply_partial.retry_count)
+            .await
+        {
+            Ok(state_current) => item_apply_partial.state_current = Some(state_current),
+            Err(error) => return Err((error, item_apply_partial.into())),
+        }
+        #[cfg(feature = "output_progress")]
+        op_ctx.progress_sender().reset();
+        match state_desired {
+            Some(state_desired) => {
+                resources.borrow_mut::<Desired<State>>().0 = Some(state_desired.clone());
+                item_apply_partial.state_target = Some(state_desired);
+            }
+            None => match self
+                .state_desired_exec(op_ctx, resources, &mut item_apply_partial.retry_count)
+                .await
+            {
+                Ok(state_desired) => item_apply_partial.state_target = Some(state_desired),
+                Err(error) => return Err((error, item_apply_partial.into())),
+            },
+        }
+        #[cfg(feature = "output_progress")]
+        op_ctx.progress_sender().reset();
+        match self
+            .state_diff_exec_with(
+                resources,
+                item_apply_partial
+                    .state_current
+                    .as_ref()
+                    .expect("unreachable: This is set just above."),
+                item_apply_partial
+                    .state_target
+                    .as_ref()
+                    .expect("unreachable: This is set just above."),
+            )
+            .await
+        {
+            Ok(state_diff) => item_apply_partial.state_diff = Some(state_diff),
+            Err(error) => return Err((error, item_apply_partial.into())),
+        }
+
+        let (Some(state_current), Some(state_target), Some(state_diff)) = (
+            item_apply_partial.state_current.as_ref(),
+            item_apply_partial.state_target.as_ref(),
+            item_apply_partial.state_diff.as_ref(),
+        ) else {
+            unreachable!("These are set just above.");
+        };
+
+        let state_applied = match self
+            .apply_op_check(resources, state_current, state_target, state_diff)
+            .await
+        {
+            Ok(op_check_status) => {
+                item_apply_partial.op_check_status = Some(op_check_status);
+
+                // TODO: write test for this case
+                match op_check_status {
+                    #[cfg(not(feature = "output_progress"))]
+                    OpCheckStatus::ExecRequired => None,
+                    #[cfg(feature = "output_progress")]
+                    OpCheckStatus::ExecRequired { .. } => None,
+                    OpCheckStatus::ExecNotRequired => item_apply_partial.state_current.clone(),
+                }
+            }
+            Err(error) => return Err((error, item_apply_partial.into())),
+        };
+
+        Ok(ItemApply::try_from((item_apply_partial, state_applied))
+            .expect("unreachable: All the fields are set above.")
+            .into())
+    }
 }
 
 impl<IS, E, State, StateDiff, StateCurrentFnSpec, StateDesiredFnSpec, StateDiffFnSpec, ApplyOpSpec>
@@ -340,10 +664,10 @@ impl<IS, E, State, StateDiff, StateCurrentFnSpec, StateDesiredFnSpec, StateDiffF
         ApplyOpSpec,
     >
 where
-    IS: Debug,
+    IS: Debug + ItemSpec,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.0.fmt(f)
+        self.item_spec.fmt(f)
     }
 }
 
@@ -359,11 +683,13 @@ impl<IS, E, State, StateDiff, StateCurrentFnSpec, StateDesiredFnSpec, StateDiffF
         StateDiffFnSpec,
         ApplyOpSpec,
     >
+where
+    IS: ItemSpec,
 {
     type Target = IS;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.item_spec
     }
 }
 
@@ -379,9 +705,11 @@ impl<IS, E, State, StateDiff, StateCurrentFnSpec, StateDesiredFnSpec, StateDiffF
         StateDiffFnSpec,
         ApplyOpSpec,
     >
+where
+    IS: ItemSpec,
 {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &mut self.item_spec
     }
 }
 
@@ -431,7 +759,14 @@ where
         + Sync,
 {
     fn from(item_spec: IS) -> Self {
-        Self(item_spec, PhantomData)
+        Self {
+            item_spec,
+            retry_policy: RetryPolicy::default(),
+            timeout: None,
+            concurrency_group_id: None,
+            params_spec: None,
+            marker: PhantomData,
+        }
     }
 }
 
@@ -480,6 +815,8 @@ where
         > + Send
         + Sync,
 {
+    // The params spec is only known per instance, so the data it accesses is
+    // declared in the `DataAccessDyn` implementation.
     fn borrows() -> TypeIds {
         <<ApplyOpSpec as peace_cfg::ApplyOpSpec>::Data<'_> as DataAccess>::borrows()
     }
@@ -535,11 +872,21 @@ where
         + Sync,
 {
     fn borrows(&self) -> TypeIds {
-        <<ApplyOpSpec as peace_cfg::ApplyOpSpec>::Data<'_> as DataAccess>::borrows()
+        let mut type_ids =
+            <<ApplyOpSpec as peace_cfg::ApplyOpSpec>::Data<'_> as DataAccess>::borrows();
+        if let Some(params_spec) = self.params_spec.as_ref() {
+            type_ids.extend(params_spec.borrows());
+        }
+        type_ids
     }
 
     fn borrow_muts(&self) -> TypeIds {
-        <<ApplyOpSpec as peace_cfg::ApplyOpSpec>::Data<'_> as DataAccess>::borrow_muts()
+        let mut type_ids =
+            <<ApplyOpSpec as peace_cfg::ApplyOpSpec>::Data<'_> as DataAccess>::borrow_muts();
+        if let Some(params_spec) = self.params_spec.as_ref() {
+            type_ids.extend(params_spec.borrow_muts());
+        }
+        type_ids
     }
 }
 
@@ -600,6 +947,10 @@ where
         <IS as ItemSpec>::id(self)
     }
 
+    fn concurrency_group(&self) -> Option<&ConcurrencyGroupId> {
+        self.concurrency_group_id.as_ref()
+    }
+
     async fn setup(&self, resources: &mut Resources<Empty>) -> Result<(), E> {
         // Insert `XMarker<State>` to create entries in `Resources`.
         // This is used for referential param values (#94)
@@ -611,7 +962,15 @@ where
         // Run user defined setup.
         <IS as ItemSpec>::setup(self, resources)
             .await
-            .map_err(Into::<E>::into)
+            .map_err(Into::<E>::into)?;
+
+        // Insert `Option<Params>` to create its entry in `Resources`, so that the params
+        // can be written when they are resolved before the item spec's functions are run.
+        if let Some(params_spec) = self.params_spec.as_ref() {
+            params_spec.setup(resources);
+        }
+
+        Ok(())
     }
 
     fn state_register(&self, states_type_regs: &mut StatesTypeRegs) {
@@ -622,6 +981,10 @@ where
         states_type_regs
             .states_desired_type_reg_mut()
             .register::<State>(<IS as ItemSpec>::id(self).clone());
+
+        states_type_regs
+            .item_apply_type_reg_mut()
+            .register::<ItemApply<State, StateDiff>>(<IS as ItemSpec>::id(self).clone());
     }
 
     async fn state_clean(&self, resources: &Resources<SetUp>) -> Result<BoxDtDisplay, E> {
@@ -635,44 +998,60 @@ where
         &self,
         op_ctx: OpCtx<'_>,
         resources: &Resources<SetUp>,
-    ) -> Result<Option<BoxDtDisplay>, E> {
-        self.state_current_try_exec(op_ctx, resources)
+    ) -> RetryOutcome<Option<BoxDtDisplay>, E> {
+        let mut retry_count = 0;
+        let result = self
+            .state_current_try_exec(op_ctx, resources, &mut retry_count)
             .await
             .map(|state_current| state_current.map(BoxDtDisplay::new))
-            .map_err(Into::<E>::into)
+            .map_err(Into::<E>::into);
+
+        RetryOutcome::new(result, retry_count)
     }
 
     async fn state_current_exec(
         &self,
         op_ctx: OpCtx<'_>,
         resources: &Resources<SetUp>,
-    ) -> Result<BoxDtDisplay, E> {
-        self.state_current_exec(op_ctx, resources)
+    ) -> RetryOutcome<BoxDtDisplay, E> {
+        let mut retry_count = 0;
+        let result = self
+            .state_current_exec(op_ctx, resources, &mut retry_count)
             .await
             .map(BoxDtDisplay::new)
-            .map_err(Into::<E>::into)
+            .map_err(Into::<E>::into);
+
+        RetryOutcome::new(result, retry_count)
     }
 
     async fn state_desired_try_exec(
         &self,
         op_ctx: OpCtx<'_>,
         resources: &Resources<SetUp>,
-    ) -> Result<Option<BoxDtDisplay>, E> {
-        self.state_desired_try_exec(op_ctx, resources)
+    ) -> RetryOutcome<Option<BoxDtDisplay>, E> {
+        let mut retry_count = 0;
+        let result = self
+            .state_desired_try_exec(op_ctx, resources, &mut retry_count)
             .await
             .map(|state_desired| state_desired.map(BoxDtDisplay::new))
-            .map_err(Into::<E>::into)
+            .map_err(Into::<E>::into);
+
+        RetryOutcome::new(result, retry_count)
     }
 
     async fn state_desired_exec(
         &self,
         op_ctx: OpCtx<'_>,
         resources: &Resources<SetUp>,
-    ) -> Result<BoxDtDisplay, E> {
-        self.state_desired_exec(op_ctx, resources)
+    ) -> RetryOutcome<BoxDtDisplay, E> {
+        let mut retry_count = 0;
+        let result = self
+            .state_desired_exec(op_ctx, resources, &mut retry_count)
             .await
             .map(BoxDtDisplay::new)
-            .map_err(Into::<E>::into)
+            .map_err(Into::<E>::into);
+
+        RetryOutcome::new(result, retry_count)
     }
 
     async fn state_diff_exec_with_states_saved(
@@ -699,73 +1078,80 @@ where
             .map_err(Into::<E>::into)
     }
 
-    async fn ensure_prepare(
+    async fn state_diff_exec_with_states_saved_pair(
         &self,
-        op_ctx: OpCtx<'_>,
         resources: &Resources<SetUp>,
-    ) -> Result<ItemApplyBoxed, (E, ItemApplyPartialBoxed)> {
-        let mut item_apply_partial = ItemApplyPartial::<State, StateDiff>::new();
-
-        match self.state_current_exec(op_ctx, resources).await {
-            Ok(state_current) => item_apply_partial.state_current = Some(state_current),
-            Err(error) => return Err((error, item_apply_partial.into())),
-        }
-        #[cfg(feature = "output_progress")]
-        op_ctx.progress_sender().reset();
-        match self.state_desired_exec(op_ctx, resources).await {
-            Ok(state_desired) => item_apply_partial.state_target = Some(state_desired),
-            Err(error) => return Err((error, item_apply_partial.into())),
-        }
-        #[cfg(feature = "output_progress")]
-        op_ctx.progress_sender().reset();
-        match self
-            .state_diff_exec_with(
-                resources,
-                item_apply_partial
-                    .state_current
-                    .as_ref()
-                    .expect("unreachable: This is set just above."),
-                item_apply_partial
-                    .state_target
-                    .as_ref()
-                    .expect("unreachable: This is set just above."),
-            )
+        states_saved_a: &StatesSaved,
+        states_saved_b: &StatesSaved,
+    ) -> Result<Option<BoxDtDisplay>, E> {
+        self.state_diff_exec(resources, states_saved_a, states_saved_b)
             .await
-        {
-            Ok(state_diff) => item_apply_partial.state_diff = Some(state_diff),
-            Err(error) => return Err((error, item_apply_partial.into())),
-        }
+            .map(|state_diff_opt| state_diff_opt.map(BoxDtDisplay::new))
+            .map_err(Into::<E>::into)
+    }
 
-        let (Some(state_current), Some(state_target), Some(state_diff)) = (
-            item_apply_partial.state_current.as_ref(),
-            item_apply_partial.state_target.as_ref(),
-            item_apply_partial.state_diff.as_ref(),
-        ) else {
-            unreachable!("These are set just above.");
+    async fn state_drift_exec(
+        &self,
+        resources: &Resources<SetUp>,
+        states_saved: &StatesSaved,
+        states_current: &StatesCurrent,
+    ) -> Result<StateDrift, E> {
+        let item_spec_id = <IS as ItemSpec>::id(self);
+        let Some(state_saved) = states_saved.get::<State, _>(item_spec_id) else {
+            return Ok(StateDrift::StateSavedNone);
+        };
+        let Some(state_current) = states_current.get::<State, _>(item_spec_id) else {
+            return Ok(StateDrift::StateCurrentNone);
         };
 
-        let state_applied = match self
-            .apply_op_check(resources, state_current, state_target, state_diff)
-            .await
-        {
-            Ok(op_check_status) => {
-                item_apply_partial.op_check_status = Some(op_check_status);
+        // `State` and `StateDiff` are not required to implement `PartialEq`, so we compare
+        // the serialized diffs. Diffing the current state with itself gives the item spec's
+        // representation of "no change".
+        let state_diff = self
+            .state_diff_exec_with(resources, state_saved, state_current)
+            .await?;
+        let state_diff_in_sync = self
+            .state_diff_exec_with(resources, state_current, state_current)
+            .await?;
+        let state_diff_value =
+            serde_yaml::to_value(&state_diff).map_err(crate::Error::StateDiffsSerialize)?;
+        let state_diff_in_sync_value =
+            serde_yaml::to_value(&state_diff_in_sync).map_err(crate::Error::StateDiffsSerialize)?;
+
+        if state_diff_value != state_diff_in_sync_value {
+            Ok(StateDrift::Drifted {
+                state_diff: BoxDtDisplay::new(state_diff),
+            })
+        } else {
+            Ok(StateDrift::InSync)
+        }
+    }
 
-                // TODO: write test for this case
-                match op_check_status {
-                    #[cfg(not(feature = "output_progress"))]
-                    OpCheckStatus::ExecRequired => None,
-                    #[cfg(feature = "output_progress")]
-                    OpCheckStatus::ExecRequired { .. } => None,
-                    OpCheckStatus::ExecNotRequired => item_apply_partial.state_current.clone(),
-                }
-            }
-            Err(error) => return Err((error, item_apply_partial.into())),
+    async fn ensure_prepare(
+        &self,
+        op_ctx: OpCtx<'_>,
+        resources: &Resources<SetUp>,
+    ) -> Result<ItemApplyBoxed, (E, ItemApplyPartialBoxed)> {
+        self.ensure_prepare_with(op_ctx, resources, None).await
+    }
+
+    async fn ensure_prepare_with_state_desired(
+        &self,
+        op_ctx: OpCtx<'_>,
+        resources: &Resources<SetUp>,
+        state_desired: &BoxDtDisplay,
+    ) -> Result<ItemApplyBoxed, (E, ItemApplyPartialBoxed)> {
+        let Some(state_desired) = BoxDataTypeDowncast::<State>::downcast_ref(state_desired) else {
+            let error = crate::Error::StateDesiredTypeMismatch {
+                item_spec_id: self.id().clone(),
+                state_type: std::any::type_name::<State>(),
+            };
+            let item_apply_partial = ItemApplyPartial::<State, StateDiff>::new();
+            return Err((E::from(error), item_apply_partial.into()));
         };
 
-        Ok(ItemApply::try_from((item_apply_partial, state_applied))
-            .expect("unreachable: All the fields are set above.")
-            .into())
+        self.ensure_prepare_with(op_ctx, resources, Some(state_desired.clone()))
+            .await
     }
 
     async fn apply_exec_dry(
@@ -788,6 +1174,7 @@ where
             state_diff,
             op_check_status,
             state_applied,
+            retry_count: _,
         } = item_apply;
 
         match op_check_status {
@@ -807,7 +1194,11 @@ where
 
                 *state_applied = Some(state_applied_dry);
             }
-            OpCheckStatus::ExecNotRequired => {}
+            OpCheckStatus::ExecNotRequired => {
+                // The item is already in its target state, so dry applying it leaves it in its
+                // current state.
+                resources.borrow_mut::<ApplyDry<State>>().0 = Some(state_current.clone());
+            }
         }
 
         Ok(())
@@ -820,14 +1211,21 @@ where
     ) -> Result<ItemApplyBoxed, (E, ItemApplyPartialBoxed)> {
         let mut item_apply_partial = ItemApplyPartial::<State, StateDiff>::new();
 
-        match self.state_current_try_exec(op_ctx, resources).await {
+        match self
+            .state_current_try_exec(op_ctx, resources, &mut item_apply_partial.retry_count)
+            .await
+        {
             Ok(state_current) => {
                 // Hack: Setting ItemApplyPartial state_current to state_clean is a hack.
                 if let Some(state_current) = state_current {
                     item_apply_partial.state_current = Some(state_current);
                 } else {
                     match self.state_clean(resources).await {
-                        Ok(state_clean) => item_apply_partial.state_current = Some(state_clean),
+                        Ok(state_clean) => {
+                            // The item does not exist, so successors see it as clean.
+                            resources.borrow_mut::<Current<State>>().0 = Some(state_clean.clone());
+                            item_apply_partial.state_current = Some(state_clean);
+                        }
                         Err(error) => return Err((error, item_apply_partial.into())),
                     }
                 }
@@ -909,13 +1307,21 @@ where
             state_diff,
             op_check_status,
             state_applied,
+            retry_count,
         } = item_apply;
 
         match op_check_status {
             #[cfg(not(feature = "output_progress"))]
             OpCheckStatus::ExecRequired => {
                 let state_applied_next = self
-                    .apply_op_exec(op_ctx, resources, state_current, state_target, state_diff)
+                    .apply_op_exec(
+                        op_ctx,
+                        resources,
+                        state_current,
+                        state_target,
+                        state_diff,
+                        retry_count,
+                    )
                     .await?;
 
                 *state_applied = Some(state_applied_next);
@@ -923,7 +1329,14 @@ where
             #[cfg(feature = "output_progress")]
             OpCheckStatus::ExecRequired { progress_limit: _ } => {
                 let state_applied_next = self
-                    .apply_op_exec(op_ctx, resources, state_current, state_target, state_diff)
+                    .apply_op_exec(
+                        op_ctx,
+                        resources,
+                        state_current,
+                        state_target,
+                        state_diff,
+                        retry_count,
+                    )
                     .await?;
 
                 *state_applied = Some(state_applied_next);
@@ -14,6 +14,22 @@ pub mod output {
     pub use peace_rt_model_native::output::*;
 }
 
+pub mod storage {
+    pub use peace_rt_model_core::storage::*;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use peace_rt_model_native::FsStorage;
+
+    #[cfg(all(not(target_arch = "wasm32"), feature = "storage_encryption"))]
+    pub use peace_rt_model_native::{EnvKeyProvider, FileKeyProvider};
+
+    #[cfg(all(not(target_arch = "wasm32"), feature = "storage_http"))]
+    pub use peace_rt_model_native::HttpStorage;
+
+    #[cfg(target_arch = "wasm32")]
+    pub use peace_rt_model_web::{IndexedDbStorage, WebStorage};
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub use peace_rt_model_native::*;
 
@@ -21,20 +37,28 @@ pub use peace_rt_model_native::*;
 pub use peace_rt_model_web::*;
 
 pub use crate::{
-    flow::Flow, in_memory_text_output::InMemoryTextOutput, item_spec_boxed::ItemSpecBoxed,
+    ensure_plan_serializer::EnsurePlanSerializer, flow::Flow,
+    in_memory_text_output::InMemoryTextOutput, item_spec_boxed::ItemSpecBoxed,
     item_spec_graph::ItemSpecGraph, item_spec_graph_builder::ItemSpecGraphBuilder,
-    item_spec_rt::ItemSpecRt, item_spec_wrapper::ItemSpecWrapper,
+    item_spec_rt::ItemSpecRt,
+    item_spec_selection::{ItemSpecSelection, ItemSpecSelectionClosure},
+    item_spec_wrapper::ItemSpecWrapper, retry_outcome::RetryOutcome, retry_policy::RetryPolicy,
     states_serializer::StatesSerializer, states_type_regs::StatesTypeRegs,
 };
 
 pub mod outcomes;
 
+mod ensure_plan_serializer;
 mod flow;
 mod in_memory_text_output;
 mod item_spec_boxed;
 mod item_spec_graph;
 mod item_spec_graph_builder;
 mod item_spec_rt;
+mod item_spec_selection;
 mod item_spec_wrapper;
+mod params_spec_rt;
+mod retry_outcome;
+mod retry_policy;
 mod states_serializer;
 mod states_type_regs;
@@ -4,18 +4,31 @@
 //! outcomes to be redisplayed without re-executing commands.
 
 pub use self::{
-    cmd_outcome::CmdOutcome, item_apply::ItemApply, item_apply_boxed::ItemApplyBoxed,
-    item_apply_partial::ItemApplyPartial, item_apply_partial_boxed::ItemApplyPartialBoxed,
-    item_apply_partial_rt::ItemApplyPartialRt, item_apply_rt::ItemApplyRt,
+    cmd_outcome::CmdOutcome, ensure_plan::EnsurePlan, history_cmd::HistoryCmd,
+    history_record::HistoryRecord, history_summary::HistorySummary, item_apply::ItemApply,
+    item_apply_boxed::ItemApplyBoxed, item_apply_partial::ItemApplyPartial,
+    item_apply_partial_boxed::ItemApplyPartialBoxed, item_apply_partial_rt::ItemApplyPartialRt,
+    item_apply_rt::ItemApplyRt, item_apply_status::ItemApplyStatus,
+    profiles_cmd_outcome::ProfilesCmdOutcome, profiles_state_diff::ProfilesStateDiff,
+    state_drift::StateDrift, state_drifts::StateDrifts,
 };
 
 mod cmd_outcome;
+mod ensure_plan;
+mod history_cmd;
+mod history_record;
+mod history_summary;
 mod item_apply;
 mod item_apply_boxed;
 mod item_apply_partial;
 mod item_apply_partial_boxed;
 mod item_apply_partial_rt;
 mod item_apply_rt;
+mod item_apply_status;
+mod profiles_cmd_outcome;
+mod profiles_state_diff;
+mod state_drift;
+mod state_drifts;
 
 macro_rules! box_data_type_newtype {
     ($ty_name:ident, $trait_path:path) => {
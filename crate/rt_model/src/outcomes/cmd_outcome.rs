@@ -1,12 +1,34 @@
 use peace_cfg::ItemSpecId;
 use peace_rt_model_core::IndexMap;
 
+use crate::outcomes::ItemApplyStatus;
+
 #[derive(Clone, Debug)]
 pub struct CmdOutcome<T, E> {
     /// The outcome value.
     pub value: T,
     /// Errors from the command execution.
     pub errors: IndexMap<ItemSpecId, E>,
+    /// Number of retries made for each item, if any were made.
+    pub retries: IndexMap<ItemSpecId, u32>,
+    /// Status of each item after the command execution.
+    ///
+    /// This is only populated by commands that apply items.
+    pub item_statuses: IndexMap<ItemSpecId, ItemApplyStatus>,
+    /// Whether the command was interrupted before all items were processed.
+    ///
+    /// This is set when the [`InterruptSignal`] is interrupted and at least
+    /// one item was not processed because of it.
+    ///
+    /// [`InterruptSignal`]: peace_cfg::InterruptSignal
+    pub interrupted: bool,
+    /// Error writing the history record of the command execution, if any.
+    ///
+    /// This does not mean the command failed, as the history record is
+    /// written after the states are saved.
+    ///
+    /// This is only populated by commands that apply items.
+    pub history_write_error: Option<E>,
 }
 
 impl<T, E> CmdOutcome<T, E> {
@@ -0,0 +1,45 @@
+use std::ops::Deref;
+
+use peace_cfg::ItemSpecId;
+use peace_resources::type_reg::untagged::TypeMap;
+use serde::Serialize;
+
+use crate::outcomes::ItemApplyBoxed;
+
+/// [`ItemApply`] computed by a dry run for each `ItemSpec`.
+/// `TypeMap<ItemSpecId, ItemApplyBoxed>` newtype.
+///
+/// This holds each item's saved, current, and target states, the diff between
+/// them, and whether execution is required. `EnsureCmd` uses this to only
+/// ensure an item when its states have not changed since the plan was made.
+///
+/// [`ItemApply`]: crate::outcomes::ItemApply
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(transparent)] // Needed to serialize as a map instead of a list.
+pub struct EnsurePlan(TypeMap<ItemSpecId, ItemApplyBoxed>);
+
+impl EnsurePlan {
+    /// Returns a new `EnsurePlan` map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the inner map.
+    pub fn into_inner(self) -> TypeMap<ItemSpecId, ItemApplyBoxed> {
+        self.0
+    }
+}
+
+impl Deref for EnsurePlan {
+    type Target = TypeMap<ItemSpecId, ItemApplyBoxed>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<TypeMap<ItemSpecId, ItemApplyBoxed>> for EnsurePlan {
+    fn from(type_map: TypeMap<ItemSpecId, ItemApplyBoxed>) -> Self {
+        Self(type_map)
+    }
+}
@@ -0,0 +1,27 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Command whose execution is recorded in a [`HistoryRecord`].
+///
+/// [`HistoryRecord`]: crate::outcomes::HistoryRecord
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryCmd {
+    /// `EnsureCmd` was executed.
+    Ensure,
+    /// `CleanCmd` was executed.
+    Clean,
+    /// `RollbackCmd` was executed.
+    Rollback,
+}
+
+impl fmt::Display for HistoryCmd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ensure => write!(f, "ensure"),
+            Self::Clean => write!(f, "clean"),
+            Self::Rollback => write!(f, "rollback"),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use peace_cfg::ItemSpecId;
+use peace_fmt::{Presentable, Presenter};
+use peace_resources::type_reg::untagged::TypeMap;
+use peace_rt_model_core::IndexMap;
+use serde::Serialize;
+
+use crate::outcomes::{HistorySummary, ItemApplyBoxed};
+
+/// Record of a command execution in the profile's history.
+///
+/// This is written to the profile's `.history` directory for each `EnsureCmd`
+/// and `CleanCmd` execution, and is returned by `HistoryShowCmd`.
+#[derive(Clone, Debug, Serialize)]
+pub struct HistoryRecord {
+    /// Summary of the command execution.
+    #[serde(flatten)]
+    pub summary: HistorySummary,
+    /// [`ItemApply`] of each item that was applied.
+    ///
+    /// [`ItemApply`]: crate::outcomes::ItemApply
+    pub item_applies: TypeMap<ItemSpecId, ItemApplyBoxed>,
+    /// Error message of each item that failed.
+    pub errors: IndexMap<ItemSpecId, String>,
+}
+
+#[peace_fmt::async_trait(?Send)]
+impl Presentable for HistoryRecord {
+    async fn present<'output, PR>(&self, presenter: &mut PR) -> Result<(), PR::Error>
+    where
+        PR: Presenter<'output>,
+    {
+        self.summary.present(presenter).await?;
+        presenter.text("\n\n").await?;
+        presenter
+            .list_numbered_with(
+                self.summary.item_statuses.iter(),
+                |(item_spec_id, item_apply_status)| {
+                    let error = self
+                        .errors
+                        .get(item_spec_id)
+                        .map(|error| format!(": {error}"))
+                        .unwrap_or_default();
+                    (item_spec_id, format!(": {item_apply_status:?}{error}"))
+                },
+            )
+            .await
+    }
+}
@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+use peace_cfg::{FlowId, ItemSpecId};
+use peace_fmt::{Presentable, Presenter};
+use peace_rt_model_core::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::outcomes::{HistoryCmd, ItemApplyStatus};
+
+/// Summary of a command execution recorded in the profile's history.
+///
+/// This is the part of a [`HistoryRecord`] that can be read without the
+/// flow's item spec types, and is returned by `HistoryListCmd`.
+///
+/// [`HistoryRecord`]: crate::outcomes::HistoryRecord
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct HistorySummary {
+    /// Index of the record within the profile's history, starting from 0.
+    pub index: u32,
+    /// Command that was executed.
+    pub cmd: HistoryCmd,
+    /// ID of the flow that the command was executed for.
+    pub flow_id: FlowId,
+    /// When the command execution began.
+    pub start: DateTime<Utc>,
+    /// When the command execution completed.
+    pub end: DateTime<Utc>,
+    /// Status of each item after the command execution.
+    pub item_statuses: IndexMap<ItemSpecId, ItemApplyStatus>,
+}
+
+#[peace_fmt::async_trait(?Send)]
+impl Presentable for HistorySummary {
+    async fn present<'output, PR>(&self, presenter: &mut PR) -> Result<(), PR::Error>
+    where
+        PR: Presenter<'output>,
+    {
+        let Self {
+            index,
+            cmd,
+            flow_id,
+            start,
+            end,
+            item_statuses: _,
+        } = self;
+
+        presenter.code_inline(&index.to_string()).await?;
+        presenter.text(": ").await?;
+        presenter.tag(&cmd.to_string()).await?;
+        presenter.text(" ").await?;
+        presenter.id(flow_id).await?;
+        presenter.text(&format!(" from {start} to {end}")).await?;
+
+        Ok(())
+    }
+}
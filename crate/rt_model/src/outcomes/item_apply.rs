@@ -24,6 +24,8 @@ pub struct ItemApply<State, StateDiff> {
     pub op_check_status: OpCheckStatus,
     /// The state that was applyd, `None` if execution was not required.
     pub state_applied: Option<State>,
+    /// Number of times the item's functions were retried.
+    pub retry_count: u32,
 }
 
 impl<State, StateDiff> TryFrom<(ItemApplyPartial<State, StateDiff>, Option<State>)>
@@ -40,6 +42,7 @@ impl<State, StateDiff> TryFrom<(ItemApplyPartial<State, StateDiff>, Option<State
             state_target,
             state_diff,
             op_check_status,
+            retry_count,
         } = partial;
 
         if state_current.is_some()
@@ -58,6 +61,7 @@ impl<State, StateDiff> TryFrom<(ItemApplyPartial<State, StateDiff>, Option<State
                 state_diff,
                 op_check_status,
                 state_applied,
+                retry_count,
             })
         } else {
             let partial = ItemApplyPartial {
@@ -66,6 +70,7 @@ impl<State, StateDiff> TryFrom<(ItemApplyPartial<State, StateDiff>, Option<State
                 state_target,
                 state_diff,
                 op_check_status,
+                retry_count,
             };
             Err((partial, state_applied))
         }
@@ -101,6 +106,10 @@ where
         self.state_applied.clone().map(BoxDtDisplay::new)
     }
 
+    fn retry_count(&self) -> u32 {
+        self.retry_count
+    }
+
     fn as_data_type(&self) -> &dyn DataType {
         self
     }
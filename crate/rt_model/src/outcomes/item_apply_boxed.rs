@@ -1,3 +1,5 @@
+use peace_resources::type_reg::untagged::FromDataType;
+
 use crate::outcomes::{ItemApply, ItemApplyRt};
 
 /// A boxed `ItemApply`.
@@ -15,4 +17,13 @@ where
     }
 }
 
+impl<State, StateDiff> FromDataType<ItemApply<State, StateDiff>> for ItemApplyBoxed
+where
+    ItemApply<State, StateDiff>: ItemApplyRt,
+{
+    fn from(item_apply: ItemApply<State, StateDiff>) -> Self {
+        Self(Box::new(item_apply))
+    }
+}
+
 crate::outcomes::box_data_type_newtype!(ItemApplyBoxed, ItemApplyRt);
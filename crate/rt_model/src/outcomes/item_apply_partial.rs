@@ -43,6 +43,8 @@ pub struct ItemApplyPartial<State, StateDiff> {
     pub state_diff: Option<StateDiff>,
     /// Whether item execution is required.
     pub op_check_status: Option<OpCheckStatus>,
+    /// Number of times the item's functions were retried.
+    pub retry_count: u32,
 }
 
 impl<State, StateDiff> ItemApplyPartial<State, StateDiff> {
@@ -60,6 +62,7 @@ impl<State, StateDiff> Default for ItemApplyPartial<State, StateDiff> {
             state_target: None,
             state_diff: None,
             op_check_status: None,
+            retry_count: 0,
         }
     }
 }
@@ -89,6 +92,10 @@ where
         self.op_check_status
     }
 
+    fn retry_count(&self) -> u32 {
+        self.retry_count
+    }
+
     fn as_data_type(&self) -> &dyn DataType {
         self
     }
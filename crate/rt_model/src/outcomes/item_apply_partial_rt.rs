@@ -18,6 +18,9 @@ pub trait ItemApplyPartialRt: DataType {
     /// Returns `op_check_status` as type-erased data.
     fn op_check_status(&self) -> Option<OpCheckStatus>;
 
+    /// Returns the number of times the item's functions were retried.
+    fn retry_count(&self) -> u32;
+
     /// Returns self as a `&dyn DataType`;
     fn as_data_type(&self) -> &dyn DataType;
 
@@ -48,6 +51,10 @@ impl ItemApplyPartialRt for Box<dyn ItemApplyPartialRt> {
         self.as_ref().op_check_status()
     }
 
+    fn retry_count(&self) -> u32 {
+        self.as_ref().retry_count()
+    }
+
     fn as_data_type(&self) -> &dyn DataType {
         self.as_ref().as_data_type()
     }
@@ -21,6 +21,9 @@ pub trait ItemApplyRt: DataType {
     /// Returns `state_applied` as type-erased data.
     fn state_applied(&self) -> Option<BoxDtDisplay>;
 
+    /// Returns the number of times the item's functions were retried.
+    fn retry_count(&self) -> u32;
+
     /// Returns self as a `&dyn DataType`;
     fn as_data_type(&self) -> &dyn DataType;
 
@@ -55,6 +58,10 @@ impl ItemApplyRt for Box<dyn ItemApplyRt> {
         self.as_ref().state_applied()
     }
 
+    fn retry_count(&self) -> u32 {
+        self.as_ref().retry_count()
+    }
+
     fn as_data_type(&self) -> &dyn DataType {
         self.as_ref().as_data_type()
     }
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+/// Outcome of applying an item during an `ApplyCmd` execution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ItemApplyStatus {
+    /// The item was applied successfully.
+    Success,
+    /// Discovering the item's states, or applying the item failed.
+    Fail,
+    /// The item was not applied, because an item that it depends on failed,
+    /// or was not applied.
+    UpstreamFailed,
+    /// The item was not applied, because the command was interrupted.
+    Interrupted,
+    /// The item was not applied, because execution stopped before this item
+    /// was reached.
+    ///
+    /// This happens when another item fails with `ApplyErrorMode::StopOnError`,
+    /// or when the command timeout elapses.
+    NotReached,
+    /// The item was in progress when the command timeout elapsed.
+    ///
+    /// The item was allowed to complete, so it may or may not be in its target
+    /// state.
+    TimedOut,
+    /// The item was already in its target state, so it did not need to be
+    /// applied.
+    ExecNotRequired,
+}
+
+impl ItemApplyStatus {
+    /// Returns whether the item is in its target state after execution.
+    pub fn is_successful(&self) -> bool {
+        match self {
+            Self::Success | Self::ExecNotRequired => true,
+            Self::Fail
+            | Self::UpstreamFailed
+            | Self::Interrupted
+            | Self::NotReached
+            | Self::TimedOut => false,
+        }
+    }
+}
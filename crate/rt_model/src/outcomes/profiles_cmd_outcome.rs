@@ -0,0 +1,29 @@
+use peace_cfg::Profile;
+use peace_rt_model_core::IndexMap;
+
+use crate::outcomes::CmdOutcome;
+
+/// Outcome of a command that is run for multiple profiles.
+///
+/// Outcomes are keyed by [`Profile`], and each [`CmdOutcome`] holds the
+/// errors and statuses keyed by `ItemSpecId`.
+#[derive(Clone, Debug)]
+pub struct ProfilesCmdOutcome<T, E> {
+    /// Outcome of the command for each profile that it ran to completion for.
+    pub profile_to_cmd_outcome: IndexMap<Profile, CmdOutcome<T, E>>,
+    /// Errors that stopped the command from running for a profile.
+    pub profile_to_error: IndexMap<Profile, E>,
+}
+
+impl<T, E> ProfilesCmdOutcome<T, E> {
+    /// Returns whether the command ran successfully for every profile.
+    pub fn is_ok(&self) -> bool {
+        self.profile_to_error.is_empty()
+            && self.profile_to_cmd_outcome.values().all(CmdOutcome::is_ok)
+    }
+
+    /// Returns whether the command encountered any errors for any profile.
+    pub fn is_err(&self) -> bool {
+        !self.is_ok()
+    }
+}
@@ -0,0 +1,34 @@
+use std::fmt;
+
+use peace_resources::type_reg::untagged::BoxDtDisplay;
+use serde::Serialize;
+
+/// Diff of an item's saved state that exists in only one of two profiles.
+///
+/// Items whose states are saved in both profiles are diffed using the item
+/// spec's [`StateDiffFnSpec`] instead.
+///
+/// [`StateDiffFnSpec`]: peace_cfg::ItemSpec::StateDiffFnSpec
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfilesStateDiff {
+    /// The item's state is saved in the second profile, but not the first.
+    Added {
+        /// Saved state in the second profile.
+        state: BoxDtDisplay,
+    },
+    /// The item's state is saved in the first profile, but not the second.
+    Removed {
+        /// Saved state in the first profile.
+        state: BoxDtDisplay,
+    },
+}
+
+impl fmt::Display for ProfilesStateDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added { state } => write!(f, "added: {state}"),
+            Self::Removed { state } => write!(f, "removed: {state}"),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use std::fmt;
+
+use peace_resources::type_reg::untagged::BoxDtDisplay;
+use serde::Serialize;
+
+/// Whether an item's current state has drifted from its saved state.
+///
+/// This is determined using the item spec's [`StateDiffFnSpec`]: the item has
+/// drifted if the diff between its saved and current states differs from the
+/// diff between its current state and itself.
+///
+/// [`StateDiffFnSpec`]: peace_cfg::ItemSpec::StateDiffFnSpec
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StateDrift {
+    /// The current state matches the saved state.
+    InSync,
+    /// The current state differs from the saved state.
+    Drifted {
+        /// Diff between the saved and current states.
+        state_diff: BoxDtDisplay,
+    },
+    /// The item's state has not been saved, so drift cannot be determined.
+    StateSavedNone,
+    /// The item's current state could not be discovered.
+    ///
+    /// This happens when discovering the state fails, or when the state
+    /// depends on a predecessor's state that does not exist.
+    StateCurrentNone,
+}
+
+impl StateDrift {
+    /// Returns whether the item's current state has drifted from its saved
+    /// state.
+    pub fn is_drifted(&self) -> bool {
+        matches!(self, Self::Drifted { .. })
+    }
+}
+
+impl fmt::Display for StateDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InSync => write!(f, "in sync"),
+            Self::Drifted { state_diff } => write!(f, "drifted: {state_diff}"),
+            Self::StateSavedNone => write!(f, "state not saved"),
+            Self::StateCurrentNone => write!(f, "current state not discovered"),
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::ops::Deref;
+
+use peace_cfg::ItemSpecId;
+use peace_fmt::{Presentable, Presenter};
+use peace_rt_model_core::IndexMap;
+use serde::Serialize;
+
+use crate::outcomes::StateDrift;
+
+/// [`StateDrift`] for each `ItemSpec`. `IndexMap<ItemSpecId, StateDrift>`
+/// newtype.
+///
+/// This is returned by `StatesDriftCmd`.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(transparent)] // Needed to serialize as a map instead of a list.
+pub struct StateDrifts(IndexMap<ItemSpecId, StateDrift>);
+
+impl StateDrifts {
+    /// Returns a new `StateDrifts` map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether any item's current state has drifted from its saved
+    /// state.
+    pub fn is_drifted(&self) -> bool {
+        self.0.values().any(StateDrift::is_drifted)
+    }
+
+    /// Returns the inner map.
+    pub fn into_inner(self) -> IndexMap<ItemSpecId, StateDrift> {
+        self.0
+    }
+}
+
+impl Deref for StateDrifts {
+    type Target = IndexMap<ItemSpecId, StateDrift>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<IndexMap<ItemSpecId, StateDrift>> for StateDrifts {
+    fn from(state_drifts: IndexMap<ItemSpecId, StateDrift>) -> Self {
+        Self(state_drifts)
+    }
+}
+
+#[peace_fmt::async_trait(?Send)]
+impl Presentable for StateDrifts {
+    async fn present<'output, PR>(&self, presenter: &mut PR) -> Result<(), PR::Error>
+    where
+        PR: Presenter<'output>,
+    {
+        presenter
+            .list_numbered_with(self.iter(), |(item_spec_id, state_drift)| {
+                (item_spec_id, format!(": {state_drift}"))
+            })
+            .await
+    }
+}
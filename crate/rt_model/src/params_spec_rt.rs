@@ -0,0 +1,82 @@
+use std::{any::TypeId, fmt::Debug};
+
+use fn_graph::TypeIds;
+use peace_data::{
+    params::{ParamsResolveError, ParamsSpec, ValueResolutionMode},
+    Resources,
+};
+
+/// Type-erased [`ParamsSpec`], which resolves an item spec's params into
+/// `Resources`.
+///
+/// The params are resolved into an `Option<Params>` resource, as mapped
+/// fields are only known once predecessors' states are discovered or applied,
+/// and `Resources` cannot be inserted into at that point.
+pub(crate) trait ParamsSpecRt: Debug + Send + Sync {
+    /// Inserts `None` as the `Option<Params>` into `Resources`, if it has not
+    /// already been inserted.
+    ///
+    /// The params are not resolved here, as predecessors' states are not
+    /// known until the item spec's functions are run.
+    fn setup(&self, resources: &mut Resources);
+
+    /// Resolves the params, and replaces the `Option<Params>` in `Resources`.
+    fn resolve(
+        &self,
+        resources: &Resources,
+        value_resolution_mode: ValueResolutionMode,
+    ) -> Result<(), ParamsResolveError>;
+
+    /// Returns the type IDs of the data read when resolving the params.
+    ///
+    /// This is the stored `Params`, which `ValueSpec::Stored` fields are
+    /// resolved from.
+    fn borrows(&self) -> TypeIds;
+
+    /// Returns the type IDs of the data written when resolving the params.
+    ///
+    /// This is the `Option<Params>` that the resolved params are written to.
+    fn borrow_muts(&self) -> TypeIds;
+
+    /// Returns the type name of the params.
+    fn params_type_name(&self) -> &'static str;
+}
+
+impl<PS> ParamsSpecRt for PS
+where
+    PS: ParamsSpec + Debug + Send + Sync,
+    PS::Params: Debug + Send + Sync + 'static,
+{
+    fn setup(&self, resources: &mut Resources) {
+        if !resources.contains::<Option<PS::Params>>() {
+            resources.insert(None::<PS::Params>);
+        }
+    }
+
+    fn resolve(
+        &self,
+        resources: &Resources,
+        value_resolution_mode: ValueResolutionMode,
+    ) -> Result<(), ParamsResolveError> {
+        let params = <PS as ParamsSpec>::resolve(self, resources, value_resolution_mode)?;
+        *resources.borrow_mut::<Option<PS::Params>>() = Some(params);
+
+        Ok(())
+    }
+
+    fn borrows(&self) -> TypeIds {
+        let mut type_ids = TypeIds::new();
+        type_ids.push(TypeId::of::<PS::Params>());
+        type_ids
+    }
+
+    fn borrow_muts(&self) -> TypeIds {
+        let mut type_ids = TypeIds::new();
+        type_ids.push(TypeId::of::<Option<PS::Params>>());
+        type_ids
+    }
+
+    fn params_type_name(&self) -> &'static str {
+        std::any::type_name::<PS::Params>()
+    }
+}
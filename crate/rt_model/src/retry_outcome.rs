@@ -0,0 +1,43 @@
+/// Result of a function that is retried according to a [`RetryPolicy`], with
+/// the number of retries made.
+///
+/// [`RetryPolicy`]: crate::RetryPolicy
+#[derive(Debug)]
+pub struct RetryOutcome<T, E> {
+    /// Result of the last attempt.
+    pub result: Result<T, E>,
+    /// Number of retries made, excluding the first attempt.
+    pub retry_count: u32,
+}
+
+impl<T, E> RetryOutcome<T, E> {
+    /// Returns a new `RetryOutcome`.
+    pub fn new(result: Result<T, E>, retry_count: u32) -> Self {
+        Self {
+            result,
+            retry_count,
+        }
+    }
+
+    /// Maps the successful value, leaving the retry count unchanged.
+    pub fn map<U, F>(self, f: F) -> RetryOutcome<U, E>
+    where
+        F: FnOnce(T) -> U,
+    {
+        RetryOutcome {
+            result: self.result.map(f),
+            retry_count: self.retry_count,
+        }
+    }
+
+    /// Maps the error, leaving the retry count unchanged.
+    pub fn map_err<F, O>(self, f: O) -> RetryOutcome<T, F>
+    where
+        O: FnOnce(E) -> F,
+    {
+        RetryOutcome {
+            result: self.result.map_err(f),
+            retry_count: self.retry_count,
+        }
+    }
+}
@@ -0,0 +1,258 @@
+use std::{
+    collections::hash_map::RandomState,
+    fmt,
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    pin::pin,
+    time::Duration,
+};
+
+use futures::future::{self, Either};
+use peace_cfg::OpCtx;
+
+use crate::RetryOutcome;
+
+#[cfg(feature = "output_progress")]
+use peace_cfg::progress::ProgressMsgUpdate;
+
+/// Policy for retrying an item spec's functions when they fail.
+///
+/// This applies to the item spec's [`StateCurrentFnSpec`],
+/// [`StateDesiredFnSpec`], and [`ApplyOpSpec::exec`] functions.
+///
+/// Between attempts, execution waits for an exponentially increasing delay,
+/// starting at `backoff_initial`, doubling each attempt, and capped at
+/// `backoff_max`. When `jitter` is enabled, the delay is randomly reduced by
+/// up to half, so that concurrent retries are spread out.
+///
+/// The default policy makes a single attempt, i.e. does not retry.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let retry_policy = RetryPolicy::new(5)
+///     .with_backoff(Duration::from_millis(200), Duration::from_secs(10))
+///     .with_retryable(|error: &FileDownloadError| {
+///         matches!(error, FileDownloadError::SrcGet { .. })
+///     });
+///
+/// let item_spec = ItemSpecWrapper::from(file_download_item_spec)
+///     .with_retry_policy(retry_policy)
+///     .into();
+/// graph_builder.add_fn(item_spec);
+/// ```
+///
+/// [`StateCurrentFnSpec`]: peace_cfg::ItemSpec::StateCurrentFnSpec
+/// [`StateDesiredFnSpec`]: peace_cfg::ItemSpec::StateDesiredFnSpec
+/// [`ApplyOpSpec::exec`]: peace_cfg::ApplyOpSpec::exec
+pub struct RetryPolicy<Error> {
+    /// Maximum number of attempts, including the first attempt.
+    max_attempts: u32,
+    /// Delay before the first retry.
+    backoff_initial: Duration,
+    /// Maximum delay between retries.
+    backoff_max: Duration,
+    /// Whether to randomize the delay between retries.
+    jitter: bool,
+    /// Returns whether an error may be retried.
+    retryable: fn(&Error) -> bool,
+}
+
+impl<Error> RetryPolicy<Error> {
+    /// Default delay before the first retry.
+    pub const BACKOFF_INITIAL_DEFAULT: Duration = Duration::from_millis(500);
+    /// Default maximum delay between retries.
+    pub const BACKOFF_MAX_DEFAULT: Duration = Duration::from_secs(30);
+
+    /// Returns a new `RetryPolicy` that makes up to `max_attempts` attempts.
+    ///
+    /// All errors are retryable, and the default backoff is used.
+    ///
+    /// A `max_attempts` of `0` is treated as `1`.
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            backoff_initial: Self::BACKOFF_INITIAL_DEFAULT,
+            backoff_max: Self::BACKOFF_MAX_DEFAULT,
+            jitter: true,
+            retryable: |_error| true,
+        }
+    }
+
+    /// Sets the initial and maximum delays between retries.
+    #[must_use]
+    pub fn with_backoff(mut self, backoff_initial: Duration, backoff_max: Duration) -> Self {
+        self.backoff_initial = backoff_initial;
+        self.backoff_max = backoff_max.max(backoff_initial);
+        self
+    }
+
+    /// Sets whether to randomize the delay between retries.
+    #[must_use]
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets the function that decides whether an error may be retried.
+    #[must_use]
+    pub fn with_retryable(mut self, retryable: fn(&Error) -> bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
+    /// Returns the maximum number of attempts, including the first attempt.
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns the delay before the first retry.
+    pub fn backoff_initial(&self) -> Duration {
+        self.backoff_initial
+    }
+
+    /// Returns the maximum delay between retries.
+    pub fn backoff_max(&self) -> Duration {
+        self.backoff_max
+    }
+
+    /// Returns whether the delay between retries is randomized.
+    pub fn jitter(&self) -> bool {
+        self.jitter
+    }
+
+    /// Returns whether the given error may be retried.
+    pub fn is_retryable(&self, error: &Error) -> bool {
+        (self.retryable)(error)
+    }
+
+    /// Returns the delay before the given retry, without jitter.
+    ///
+    /// `retry` is `1` for the first retry, i.e. the second attempt.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.backoff_initial
+            .saturating_mul(factor)
+            .min(self.backoff_max)
+    }
+
+    /// Runs `f` until it succeeds, returns a non-retryable error, or the
+    /// maximum number of attempts is reached.
+    ///
+    /// Retries are not made once the command is interrupted, including while
+    /// waiting for the delay between attempts.
+    ///
+    /// # Parameters
+    ///
+    /// * `op_ctx`: Used to check for interruptions and report retries.
+    /// * `f`: The function to run.
+    pub async fn exec<T, F, Fut>(&self, op_ctx: OpCtx<'_>, f: F) -> RetryOutcome<T, Error>
+    where
+        Error: fmt::Display,
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        let mut retry_count = 0;
+        let result = self.exec_counted(op_ctx, &mut retry_count, f).await;
+
+        RetryOutcome::new(result, retry_count)
+    }
+
+    /// Same as [`Self::exec`], but increments `retry_count` for each retry
+    /// made.
+    ///
+    /// This allows the retries to be counted when the returned future is
+    /// dropped before it completes, such as when an item spec times out.
+    pub(crate) async fn exec_counted<T, F, Fut>(
+        &self,
+        op_ctx: OpCtx<'_>,
+        retry_count: &mut u32,
+        f: F,
+    ) -> Result<T, Error>
+    where
+        Error: fmt::Display,
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match f().await {
+                Ok(t) => return Ok(t),
+                Err(error) => {
+                    if attempt >= self.max_attempts
+                        || op_ctx.is_interrupted()
+                        || !self.is_retryable(&error)
+                    {
+                        return Err(error);
+                    }
+
+                    let delay = self.backoff_jittered(attempt);
+
+                    #[cfg(feature = "output_progress")]
+                    op_ctx
+                        .progress_sender()
+                        .tick(ProgressMsgUpdate::Set(format!(
+                            "{error}, retrying ({retry}/{retries_max})",
+                            retry = attempt,
+                            retries_max = self.max_attempts - 1,
+                        )));
+
+                    let sleep = pin!(crate::time::sleep(delay));
+                    let interrupted = pin!(op_ctx.interrupt_signal().interrupted());
+                    if let Either::Right(((), _sleep)) = future::select(sleep, interrupted).await {
+                        return Err(error);
+                    }
+
+                    attempt += 1;
+                    *retry_count += 1;
+                }
+            }
+        }
+    }
+
+    /// Returns the delay before the given retry, with jitter if enabled.
+    fn backoff_jittered(&self, retry: u32) -> Duration {
+        let backoff = self.backoff(retry);
+        if self.jitter {
+            // `RandomState` is randomly seeded, which avoids adding a dependency on a
+            // random number generator.
+            let mut hasher = RandomState::new().build_hasher();
+            hasher.write_u32(retry);
+            let random = hasher.finish();
+
+            let half = backoff / 2;
+            let half_nanos = u64::try_from(half.as_nanos()).unwrap_or(u64::MAX);
+            let jitter_nanos = random.checked_rem(half_nanos).unwrap_or(0);
+
+            half + Duration::from_nanos(jitter_nanos)
+        } else {
+            backoff
+        }
+    }
+}
+
+impl<Error> Clone for RetryPolicy<Error> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Error> Copy for RetryPolicy<Error> {}
+
+impl<Error> fmt::Debug for RetryPolicy<Error> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("backoff_initial", &self.backoff_initial)
+            .field("backoff_max", &self.backoff_max)
+            .field("jitter", &self.jitter)
+            .field("retryable", &"fn(&Error) -> bool")
+            .finish()
+    }
+}
+
+impl<Error> Default for RetryPolicy<Error> {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
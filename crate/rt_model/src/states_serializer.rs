@@ -10,9 +10,25 @@ use peace_resources::{
     type_reg::untagged::{BoxDtDisplay, TypeReg},
 };
 
-use crate::{Error, Storage};
+use peace_rt_model_core::storage::SerializedStorage;
+
+#[cfg(feature = "error_reporting")]
+use peace_rt_model_core::storage::StorageFormatError;
+
+use crate::Error;
+
+use self::states_deserialize_seed::StatesDeserializeSeed;
+
+mod states_deserialize_seed;
 
 /// Reads [`StatesSaved`] and [`StatesDesired`] from storage.
+///
+/// States of item specs that are no longer in the flow are retained as
+/// [`StateOrphaned`], so that they are written back when the states are
+/// serialized again. Item specs that have been added to the flow have no
+/// state in the deserialized [`States`].
+///
+/// [`StateOrphaned`]: peace_resources::states::StateOrphaned
 pub struct StatesSerializer<E>(PhantomData<E>);
 
 impl<E> StatesSerializer<E>
@@ -24,27 +40,22 @@ where
     ///
     /// # Parameters:
     ///
-    /// * `storage`: `Storage` to read from.
+    /// * `storage`: `Storage` to write to.
     /// * `states`: States to serialize.
     /// * `states_file_path`: Path to save the serialized states to.
     ///
     /// [`ItemSpec`]: peace_cfg::ItemSpec
-    pub async fn serialize<TS>(
-        storage: &Storage,
+    pub async fn serialize<S, TS>(
+        storage: &S,
         states: &States<TS>,
         states_file_path: &Path,
     ) -> Result<(), E>
     where
+        S: SerializedStorage,
         TS: Send + Sync,
     {
         storage
-            .serialized_write(
-                #[cfg(not(target_arch = "wasm32"))]
-                "StatesSerializer::serialize".to_string(),
-                states_file_path,
-                states,
-                Error::StatesSerialize,
-            )
+            .serialized_write(states_file_path, states, Error::StatesSerialize)
             .await?;
 
         Ok(())
@@ -61,15 +72,16 @@ where
     /// * `states_saved_file`: `StatesSavedFile` to deserialize.
     ///
     /// [`ItemSpec`]: peace_cfg::ItemSpec
-    pub async fn deserialize_saved(
+    pub async fn deserialize_saved<S>(
         flow_id: &FlowId,
-        storage: &Storage,
+        storage: &S,
         states_type_reg: &TypeReg<ItemSpecId, BoxDtDisplay>,
         states_saved_file: &StatesSavedFile,
-    ) -> Result<StatesSaved, E> {
-        let states = Self::deserialize_internal::<Saved>(
-            #[cfg(not(target_arch = "wasm32"))]
-            "StatesSerializer::deserialize_saved".to_string(),
+    ) -> Result<StatesSaved, E>
+    where
+        S: SerializedStorage,
+    {
+        let states = Self::deserialize_internal::<S, Saved>(
             flow_id,
             storage,
             states_type_reg,
@@ -91,15 +103,16 @@ where
     /// * `states_desired_file`: `StatesDesiredFile` to deserialize.
     ///
     /// [`ItemSpec`]: peace_cfg::ItemSpec
-    pub async fn deserialize_desired(
+    pub async fn deserialize_desired<S>(
         flow_id: &FlowId,
-        storage: &Storage,
+        storage: &S,
         states_type_reg: &TypeReg<ItemSpecId, BoxDtDisplay>,
         states_desired_file: &StatesDesiredFile,
-    ) -> Result<StatesDesired, E> {
-        let states = Self::deserialize_internal::<Desired>(
-            #[cfg(not(target_arch = "wasm32"))]
-            "StatesSerializer::deserialize_desired".to_string(),
+    ) -> Result<StatesDesired, E>
+    where
+        S: SerializedStorage,
+    {
+        let states = Self::deserialize_internal::<S, Desired>(
             flow_id,
             storage,
             states_type_reg,
@@ -121,84 +134,39 @@ where
     /// * `states_saved_file`: `StatesSavedFile` to deserialize.
     ///
     /// [`ItemSpec`]: peace_cfg::ItemSpec
-    pub async fn deserialize_saved_opt(
+    pub async fn deserialize_saved_opt<S>(
         flow_id: &FlowId,
-        storage: &Storage,
+        storage: &S,
         states_type_reg: &TypeReg<ItemSpecId, BoxDtDisplay>,
         states_saved_file: &StatesSavedFile,
-    ) -> Result<Option<StatesSaved>, E> {
-        Self::deserialize_internal(
-            #[cfg(not(target_arch = "wasm32"))]
-            "StatesSerializer::deserialize_saved_opt".to_string(),
-            flow_id,
-            storage,
-            states_type_reg,
-            states_saved_file,
-        )
-        .await
+    ) -> Result<Option<StatesSaved>, E>
+    where
+        S: SerializedStorage,
+    {
+        Self::deserialize_internal(flow_id, storage, states_type_reg, states_saved_file).await
     }
 
-    /// Returns the [`States`] of all [`ItemSpec`]s if it exists on disk.
+    /// Returns the [`StatesDesired`] of all [`ItemSpec`]s if it exists on
+    /// disk.
     ///
     /// # Parameters:
     ///
     /// * `storage`: `Storage` to read from.
     /// * `states_type_reg`: Type registry with functions to deserialize each
     ///   item spec state.
-    /// * `states_saved_file`: `StatesSavedFile` to deserialize.
-    ///
-    /// # Type Parameters
-    ///
-    /// * `TS`: The states type state to use, such as [`ts::Current`] or
-    ///   [`ts::Saved`].
+    /// * `states_desired_file`: `StatesDesiredFile` to deserialize.
     ///
     /// [`ItemSpec`]: peace_cfg::ItemSpec
-    /// [`ts::Current`]: peace_resources::states::ts::Current
-    /// [`ts::Saved`]: peace_resources::states::ts::Saved
-    #[cfg(not(target_arch = "wasm32"))]
-    async fn deserialize_internal<TS>(
-        thread_name: String,
+    pub async fn deserialize_desired_opt<S>(
         flow_id: &FlowId,
-        storage: &Storage,
+        storage: &S,
         states_type_reg: &TypeReg<ItemSpecId, BoxDtDisplay>,
-        states_file_path: &Path,
-    ) -> Result<Option<States<TS>>, E>
+        states_desired_file: &StatesDesiredFile,
+    ) -> Result<Option<StatesDesired>, E>
     where
-        TS: Send + Sync,
+        S: SerializedStorage,
     {
-        let states_opt = storage
-            .serialized_typemap_read_opt(thread_name, states_type_reg, states_file_path, |error| {
-                #[cfg(not(feature = "error_reporting"))]
-                {
-                    Error::StatesDeserialize {
-                        flow_id: flow_id.clone(),
-                        error,
-                    }
-                }
-                #[cfg(feature = "error_reporting")]
-                {
-                    use miette::NamedSource;
-
-                    let file_contents = std::fs::read_to_string(states_file_path).unwrap();
-
-                    let (error_span, error_message, context_span) =
-                        Self::error_and_context(&file_contents, &error);
-                    let states_file_source =
-                        NamedSource::new(states_file_path.to_string_lossy(), file_contents);
-
-                    Error::StatesDeserialize {
-                        flow_id: flow_id.clone(),
-                        states_file_source,
-                        error_span,
-                        error_message,
-                        context_span,
-                        error,
-                    }
-                }
-            })
-            .await?;
-
-        Ok(states_opt)
+        Self::deserialize_internal(flow_id, storage, states_type_reg, states_desired_file).await
     }
 
     /// Returns the [`States`] of all [`ItemSpec`]s if it exists on disk.
@@ -218,20 +186,22 @@ where
     /// [`ItemSpec`]: peace_cfg::ItemSpec
     /// [`ts::Current`]: peace_resources::states::ts::Current
     /// [`ts::Saved`]: peace_resources::states::ts::Saved
-    #[cfg(target_arch = "wasm32")]
-    async fn deserialize_internal<TS>(
+    async fn deserialize_internal<S, TS>(
         flow_id: &FlowId,
-        storage: &Storage,
+        storage: &S,
         states_type_reg: &TypeReg<ItemSpecId, BoxDtDisplay>,
         states_file_path: &Path,
     ) -> Result<Option<States<TS>>, E>
     where
+        S: SerializedStorage,
         TS: Send + Sync,
     {
+        let states_seed = StatesDeserializeSeed::<TS>::new(states_type_reg);
         let states_opt = storage
-            .serialized_typemap_read_opt(states_type_reg, states_file_path, |error| {
+            .serialized_seed_read_opt(states_seed, states_file_path, |file_contents, error| {
                 #[cfg(not(feature = "error_reporting"))]
                 {
+                    let _ = file_contents;
                     Error::StatesDeserialize {
                         flow_id: flow_id.clone(),
                         error,
@@ -239,14 +209,18 @@ where
                 }
                 #[cfg(feature = "error_reporting")]
                 {
+                    use std::sync::Arc;
+
                     use miette::NamedSource;
 
-                    let file_contents = std::fs::read_to_string(states_file_path).unwrap();
+                    let file_contents = String::from_utf8_lossy(file_contents).into_owned();
 
                     let (error_span, error_message, context_span) =
                         Self::error_and_context(&file_contents, &error);
-                    let states_file_source =
-                        NamedSource::new(states_file_path.to_string_lossy(), file_contents);
+                    let states_file_source = Arc::new(NamedSource::new(
+                        states_file_path.to_string_lossy(),
+                        file_contents,
+                    ));
 
                     Error::StatesDeserialize {
                         flow_id: flow_id.clone(),
@@ -265,6 +239,43 @@ where
 
     /// Returns the error location and message to pass to miette.
     ///
+    /// Locations are only reported for YAML and JSON errors, as MessagePack
+    /// items are not readable as text.
+    #[cfg(feature = "error_reporting")]
+    fn error_and_context(
+        file_contents: &str,
+        error: &StorageFormatError,
+    ) -> (
+        Option<miette::SourceOffset>,
+        String,
+        Option<miette::SourceOffset>,
+    ) {
+        match error {
+            StorageFormatError::Yaml(error) => Self::yaml_error_and_context(file_contents, error),
+            #[cfg(feature = "storage_format_json")]
+            StorageFormatError::Json(error) => {
+                let error_span = (error.line() != 0).then(|| {
+                    miette::SourceOffset::from_location(file_contents, error.line(), error.column())
+                });
+                let error_string = format!("{error}");
+                let error_message = error_string
+                    .split(" at line ")
+                    .next()
+                    .map(str::to_string)
+                    .unwrap_or(error_string);
+                (error_span, error_message, None)
+            }
+            #[cfg(feature = "storage_format_message_pack")]
+            StorageFormatError::MessagePackEncode(_) | StorageFormatError::MessagePackDecode(_) => {
+                (None, format!("{error}"), None)
+            }
+            #[cfg(not(feature = "storage_format_message_pack"))]
+            StorageFormatError::MessagePackNotEnabled => (None, format!("{error}"), None),
+        }
+    }
+
+    /// Returns the error location and message of a YAML error.
+    ///
     /// TODO: Replace hack.
     ///
     /// The `location()` reported in the error is incorrect, due to
@@ -273,7 +284,7 @@ where
     /// In certain cases, we can reverse engineer the error from the
     /// `Display` string of the error.
     #[cfg(feature = "error_reporting")]
-    fn error_and_context(
+    fn yaml_error_and_context(
         file_contents: &str,
         error: &serde_yaml::Error,
     ) -> (
@@ -0,0 +1,71 @@
+use std::{fmt, marker::PhantomData};
+
+use peace_cfg::ItemSpecId;
+use peace_resources::{
+    states::{StateOrphaned, StateOrphanedValue, States},
+    type_reg::untagged::{BoxDtDisplay, TypeMap, TypeReg},
+};
+use serde::de::{DeserializeSeed, MapAccess, Visitor};
+
+/// Deserializes [`States`], retaining the states of unknown item specs.
+///
+/// States whose item spec ID is not registered in the type registry are
+/// deserialized as [`StateOrphaned`], instead of returning an error.
+pub(crate) struct StatesDeserializeSeed<'r, TS> {
+    /// Type registry with functions to deserialize each item spec state.
+    states_type_reg: &'r TypeReg<ItemSpecId, BoxDtDisplay>,
+    /// Marker.
+    marker: PhantomData<TS>,
+}
+
+impl<'r, TS> StatesDeserializeSeed<'r, TS> {
+    /// Returns a new `StatesDeserializeSeed`.
+    pub(crate) fn new(states_type_reg: &'r TypeReg<ItemSpecId, BoxDtDisplay>) -> Self {
+        Self {
+            states_type_reg,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, 'r, TS> DeserializeSeed<'de> for StatesDeserializeSeed<'r, TS> {
+    type Value = States<TS>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, 'r, TS> Visitor<'de> for StatesDeserializeSeed<'r, TS> {
+    type Value = States<TS>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a map of item spec IDs to states")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut type_map = match map.size_hint() {
+            Some(n) => TypeMap::with_capacity_typed(n),
+            None => TypeMap::new_typed(),
+        };
+
+        while let Some(item_spec_id) = map.next_key::<ItemSpecId>()? {
+            let state = match self.states_type_reg.get(&item_spec_id) {
+                Some(deserialize_seed) => map.next_value_seed(deserialize_seed)?,
+                None => {
+                    let value = map.next_value::<StateOrphanedValue>()?;
+                    BoxDtDisplay::new(StateOrphaned::new(value))
+                }
+            };
+            type_map.insert_raw(item_spec_id, state);
+        }
+
+        Ok(States::from(type_map))
+    }
+}
@@ -1,13 +1,18 @@
 use peace_cfg::ItemSpecId;
 use peace_resources::type_reg::untagged::{BoxDtDisplay, TypeReg};
 
-/// Type registries to deserialize `StatesSavedFile` and `StatesDesiredFile`.
+use crate::outcomes::ItemApplyBoxed;
+
+/// Type registries to deserialize `StatesSavedFile`, `StatesDesiredFile`,
+/// `EnsurePlanFile`, and history records.
 #[derive(Debug, Default)]
 pub struct StatesTypeRegs {
     /// Type registry for each item spec's `State<StateLogical, StatePhysical>`.
     states_current_type_reg: TypeReg<ItemSpecId, BoxDtDisplay>,
     /// Type registry for each item spec's `StateLogical`.
     states_desired_type_reg: TypeReg<ItemSpecId, BoxDtDisplay>,
+    /// Type registry for each item spec's `ItemApply<State, StateDiff>`.
+    item_apply_type_reg: TypeReg<ItemSpecId, ItemApplyBoxed>,
 }
 
 impl StatesTypeRegs {
@@ -45,4 +50,18 @@ impl StatesTypeRegs {
     pub fn states_desired_type_reg_mut(&mut self) -> &mut TypeReg<ItemSpecId, BoxDtDisplay> {
         &mut self.states_desired_type_reg
     }
+
+    /// Returns a reference to the item apply type registry.
+    ///
+    /// This maps from each item spec's ID to `ItemApply<State, StateDiff>`.
+    pub fn item_apply_type_reg(&self) -> &TypeReg<ItemSpecId, ItemApplyBoxed> {
+        &self.item_apply_type_reg
+    }
+
+    /// Returns a mutable reference to the item apply type registry.
+    ///
+    /// This maps from each item spec's ID to `ItemApply<State, StateDiff>`.
+    pub fn item_apply_type_reg_mut(&mut self) -> &mut TypeReg<ItemSpecId, ItemApplyBoxed> {
+        &mut self.item_apply_type_reg
+    }
 }
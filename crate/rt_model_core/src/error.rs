@@ -1,6 +1,9 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
-use peace_core::FlowId;
+use peace_core::{FlowId, ItemSpecId, Profile};
+use peace_data::params::ParamsResolveError;
+
+use crate::{params::ParamsLimitViolation, storage::StorageFormatError};
 
 cfg_if::cfg_if! {
     if #[cfg(not(target_arch = "wasm32"))] {
@@ -68,9 +71,11 @@ pub enum Error {
         /// Flow ID whose states are being deserialized.
         flow_id: FlowId,
         /// Source text to be deserialized.
+        ///
+        /// This is in an `Arc` to keep the size of `Error` small.
         #[cfg(feature = "error_reporting")]
         #[source_code]
-        states_file_source: miette::NamedSource,
+        states_file_source: std::sync::Arc<miette::NamedSource>,
         /// Offset within the source text that the error occurred.
         #[cfg(feature = "error_reporting")]
         #[label("{}", error_message)]
@@ -84,7 +89,7 @@ pub enum Error {
         context_span: Option<miette::SourceOffset>,
         /// Underlying error.
         #[source]
-        error: serde_yaml::Error,
+        error: StorageFormatError,
     },
 
     /// Failed to serialize states.
@@ -93,7 +98,7 @@ pub enum Error {
         feature = "error_reporting",
         diagnostic(code(peace_rt_model::states_serialize))
     )]
-    StatesSerialize(#[source] serde_yaml::Error),
+    StatesSerialize(#[source] StorageFormatError),
 
     /// Current states have not been discovered.
     ///
@@ -164,7 +169,7 @@ pub enum Error {
         feature = "error_reporting",
         diagnostic(code(peace_rt_model::workspace_init_params_serialize))
     )]
-    WorkspaceParamsSerialize(#[source] serde_yaml::Error),
+    WorkspaceParamsSerialize(#[source] StorageFormatError),
 
     /// Failed to deserialize workspace init params.
     #[error("Failed to deserialize workspace init params.")]
@@ -172,7 +177,7 @@ pub enum Error {
         feature = "error_reporting",
         diagnostic(code(peace_rt_model::workspace_init_params_deserialize))
     )]
-    WorkspaceParamsDeserialize(#[source] serde_yaml::Error),
+    WorkspaceParamsDeserialize(#[source] StorageFormatError),
 
     /// Workspace params does not exist, so cannot look up `Profile`.
     #[error("Workspace params does not exist, so cannot look up `Profile`.")]
@@ -196,7 +201,7 @@ pub enum Error {
         feature = "error_reporting",
         diagnostic(code(peace_rt_model::profile_init_params_serialize))
     )]
-    ProfileParamsSerialize(#[source] serde_yaml::Error),
+    ProfileParamsSerialize(#[source] StorageFormatError),
 
     /// Failed to deserialize profile init params.
     #[error("Failed to deserialize profile init params.")]
@@ -204,7 +209,7 @@ pub enum Error {
         feature = "error_reporting",
         diagnostic(code(peace_rt_model::profile_init_params_deserialize))
     )]
-    ProfileParamsDeserialize(#[source] serde_yaml::Error),
+    ProfileParamsDeserialize(#[source] StorageFormatError),
 
     /// Failed to serialize flow init params.
     #[error("Failed to serialize flow init params.")]
@@ -212,7 +217,7 @@ pub enum Error {
         feature = "error_reporting",
         diagnostic(code(peace_rt_model::flow_init_params_serialize))
     )]
-    FlowParamsSerialize(#[source] serde_yaml::Error),
+    FlowParamsSerialize(#[source] StorageFormatError),
 
     /// Failed to deserialize flow init params.
     #[error("Failed to deserialize flow init params.")]
@@ -220,7 +225,7 @@ pub enum Error {
         feature = "error_reporting",
         diagnostic(code(peace_rt_model::flow_init_params_deserialize))
     )]
-    FlowParamsDeserialize(#[source] serde_yaml::Error),
+    FlowParamsDeserialize(#[source] StorageFormatError),
 
     /// Item does not exist in storage.
     #[error("Item does not exist in storage: `{}`.", path.display())]
@@ -233,6 +238,472 @@ pub enum Error {
         path: PathBuf,
     },
 
+    /// Storage format recorded in the workspace is not supported.
+    #[error(
+        "Storage format recorded in `{}` is not supported: `{storage_format}`.",
+        path.display()
+    )]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::storage_format_unsupported),
+            help(
+                "Enable the `storage_format_json` or `storage_format_message_pack` feature\n\
+                if the workspace is in JSON or MessagePack."
+            )
+        )
+    )]
+    StorageFormatUnsupported {
+        /// Path to the storage format file.
+        path: PathBuf,
+        /// Storage format recorded in the file.
+        storage_format: String,
+    },
+
+    /// Failed to resolve an item spec's params from its params spec.
+    #[error("Failed to resolve `{item_spec_id}`'s `{params_type_name}`.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::params_resolve),
+            help(
+                "For `ValueSpec::Stored` fields, make sure the params are inserted, e.g. as a flow param.\n\
+                For `ValueSpec::FromMap` fields, make sure the state is of a predecessor of `{item_spec_id}`."
+            )
+        )
+    )]
+    ParamsResolve {
+        /// ID of the item spec whose params failed to be resolved.
+        item_spec_id: ItemSpecId,
+        /// Type name of the params.
+        params_type_name: &'static str,
+        /// Underlying error.
+        #[source]
+        error: ParamsResolveError,
+    },
+
+    /// Parameter values are outside their limits.
+    #[error("Parameter values are outside their limits.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::params_limits_exceeded),
+            help(
+                "Check that the parameter values are intended.\n\
+                If they are, override the limits using `ParamsLimits::with_override`."
+            )
+        )
+    )]
+    ParamsLimitsExceeded {
+        /// Each value that is outside its limits.
+        #[cfg_attr(feature = "error_reporting", related)]
+        violations: Vec<ParamsLimitViolation>,
+    },
+
+    /// Parameter type that is limited was not found in `Resources`.
+    #[error("Limited parameter type was not found: `{params_type_name}`.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::params_limit_params_not_found),
+            help(
+                "Make sure the parameter is inserted, e.g. as a flow param or item spec params,\n\
+                or remove its limit from the `ParamsLimits`."
+            )
+        )
+    )]
+    ParamsLimitParamsNotFound {
+        /// Type name of the parameter.
+        params_type_name: &'static str,
+    },
+
+    /// Item in storage was updated by another writer since it was read.
+    #[error("Item in storage was updated by another writer: `{}`.", path.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::storage_conflict),
+            help(
+                "Another command updated this item after it was read by this command.\n\
+                Run the command again to use the latest data."
+            )
+        )
+    )]
+    StorageConflict {
+        /// Path of the item.
+        path: PathBuf,
+    },
+
+    /// Failed to encrypt an item for storage.
+    #[cfg(feature = "storage_encryption")]
+    #[error("Failed to encrypt item for storage: `{}`.", path.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model::storage_encrypt))
+    )]
+    StorageEncrypt {
+        /// Path of the item.
+        path: PathBuf,
+    },
+
+    /// Failed to decrypt an item in storage.
+    ///
+    /// This happens when the key is not the key that the item was encrypted
+    /// with, the item was modified after it was encrypted, or the item was
+    /// moved from the path that it was encrypted at.
+    #[cfg(feature = "storage_encryption")]
+    #[error("Failed to decrypt item in storage: `{}`.", path.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::storage_decrypt),
+            help(
+                "Check that the encryption key is the key that the item was encrypted with.\n\
+                If the item was modified or moved outside of this tool, restore it or delete it."
+            )
+        )
+    )]
+    StorageDecrypt {
+        /// Path of the item.
+        path: PathBuf,
+    },
+
+    /// Failed to base64 decode the encryption key.
+    #[cfg(feature = "storage_encryption")]
+    #[error("Failed to base64 decode the encryption key from {key_source}.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::encryption_key_decode),
+            help("The encryption key must be a standard base64 encoded 32 byte key.")
+        )
+    )]
+    EncryptionKeyDecode {
+        /// Where the key was read from.
+        key_source: String,
+        /// Underlying base64 decode error.
+        #[source]
+        error: base64::DecodeError,
+    },
+
+    /// Encryption key is not 32 bytes long.
+    #[cfg(feature = "storage_encryption")]
+    #[error("Encryption key from {key_source} is {length} bytes long, but must be 32 bytes.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::encryption_key_length),
+            help("The encryption key must be a standard base64 encoded 32 byte key.")
+        )
+    )]
+    EncryptionKeyLength {
+        /// Where the key was read from.
+        key_source: String,
+        /// Number of bytes in the decoded key.
+        length: usize,
+    },
+
+    /// Item spec function did not complete within the item spec's timeout.
+    #[error("`{item_spec_id}` did not complete within {timeout:?}.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::item_spec_timeout),
+            help("Increase the timeout passed to `ItemSpecWrapper::with_timeout`.")
+        )
+    )]
+    ItemSpecTimeout {
+        /// ID of the item spec that timed out.
+        item_spec_id: ItemSpecId,
+        /// The item spec's timeout.
+        timeout: Duration,
+    },
+
+    /// Command did not complete within the command's timeout.
+    ///
+    /// This is returned for each item spec that was in progress when the
+    /// timeout elapsed.
+    #[error("`{item_spec_id}` was in progress when the command timed out after {timeout:?}.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::cmd_timeout),
+            help("Increase the timeout passed to `CmdCtx::with_timeout`.")
+        )
+    )]
+    CmdTimeout {
+        /// ID of the item spec that was in progress.
+        item_spec_id: ItemSpecId,
+        /// The command's timeout.
+        timeout: Duration,
+    },
+
+    /// Item spec selected to run is not in the flow.
+    #[error("Item spec `{item_spec_id}` was selected, but is not in the flow.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::item_spec_selection_id_not_found),
+            help("Make sure the item spec ID matches one of the item specs in the flow's graph.")
+        )
+    )]
+    ItemSpecSelectionIdNotFound {
+        /// ID of the item spec that was selected.
+        item_spec_id: ItemSpecId,
+    },
+
+    /// Desired state of an item spec is not the item spec's `State` type.
+    #[error("Desired state of item spec `{item_spec_id}` is not of type `{state_type}`.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::state_desired_type_mismatch),
+            help(
+                "Make sure the desired states were recorded by \
+                the item spec with ID `{item_spec_id}`."
+            )
+        )
+    )]
+    StateDesiredTypeMismatch {
+        /// ID of the item spec.
+        item_spec_id: ItemSpecId,
+        /// Name of the item spec's `State` type.
+        state_type: &'static str,
+    },
+
+    /// Failed to serialize ensure plan.
+    #[error("Failed to serialize ensure plan.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model::ensure_plan_serialize))
+    )]
+    EnsurePlanSerialize(#[source] StorageFormatError),
+
+    /// Failed to deserialize ensure plan.
+    #[error("Failed to deserialize ensure plan for `{flow_id}`.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::ensure_plan_deserialize),
+            help(
+                "Make sure that the ensure plan was generated using the same item spec graph \
+                as the `{flow_id}` flow."
+            )
+        )
+    )]
+    EnsurePlanDeserialize {
+        /// Flow ID whose ensure plan is being deserialized.
+        flow_id: FlowId,
+        /// Underlying error.
+        #[source]
+        error: StorageFormatError,
+    },
+
+    /// Ensure plan has not been written to disk.
+    ///
+    /// This is returned when `EnsurePlanFile` is attempted to be
+    /// deserialized but does not exist.
+    #[error("Ensure plan has not been written to disk.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::ensure_plan_required),
+            help("Ensure that `EnsureCmd::exec_dry_plan` has been called.")
+        )
+    )]
+    EnsurePlanRequired,
+
+    /// Item spec in the flow is not in the ensure plan.
+    #[error("Item spec `{item_spec_id}` is not in the ensure plan.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::ensure_plan_item_spec_not_found),
+            help(
+                "The item spec may have failed during the dry run, or been added to the flow \
+                after the plan was generated.\n\
+                Run `EnsureCmd::exec_dry_plan` again to generate a new plan."
+            )
+        )
+    )]
+    EnsurePlanItemSpecNotFound {
+        /// ID of the item spec that is not in the plan.
+        item_spec_id: ItemSpecId,
+    },
+
+    /// Current states, state diffs, or op check statuses no longer match the
+    /// ensure plan.
+    #[error("States of item specs no longer match the ensure plan: {item_spec_ids:?}.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::ensure_plan_stale),
+            help(
+                "The item specs' states have changed since the plan was generated.\n\
+                Run `EnsureCmd::exec_dry_plan` again and review the new plan."
+            )
+        )
+    )]
+    EnsurePlanStale {
+        /// IDs of the item specs whose states differ from the plan.
+        item_spec_ids: Vec<ItemSpecId>,
+    },
+
+    /// Failed to serialize history record.
+    #[error("Failed to serialize history record.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model::history_record_serialize))
+    )]
+    HistoryRecordSerialize(#[source] StorageFormatError),
+
+    /// Failed to deserialize history record.
+    #[error("Failed to deserialize history record: {}", path.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model::history_record_deserialize))
+    )]
+    HistoryRecordDeserialize {
+        /// Path to the history record file.
+        path: PathBuf,
+        /// Underlying error.
+        #[source]
+        error: StorageFormatError,
+    },
+
+    /// History record with the given index does not exist.
+    #[error("History record `{index}` does not exist.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::history_record_not_found),
+            help("Use `HistoryListCmd` to list the history records of the profile.")
+        )
+    )]
+    HistoryRecordNotFound {
+        /// Index of the history record.
+        index: u32,
+    },
+
+    /// History record was recorded for a different flow.
+    #[error(
+        "History record `{index}` was recorded for the `{flow_id}` flow, \
+        but the current flow is `{flow_id_current}`."
+    )]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::history_record_flow_id_mismatch),
+            help("Show the record using a `CmdCtx` with the `{flow_id}` flow.")
+        )
+    )]
+    HistoryRecordFlowIdMismatch {
+        /// Index of the history record.
+        index: u32,
+        /// ID of the flow that the record was recorded for.
+        flow_id: FlowId,
+        /// ID of the flow in the current `CmdCtx`.
+        flow_id_current: FlowId,
+    },
+
+    /// History record of a clean execution was used as a rollback target.
+    #[error("History record `{index}` was recorded for a clean execution.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::rollback_history_record_clean),
+            help(
+                "Roll back to the record of an ensure or rollback execution, \
+                or use `CleanCmd` to clean the items."
+            )
+        )
+    )]
+    RollbackHistoryRecordClean {
+        /// Index of the history record.
+        index: u32,
+    },
+
+    /// Profile is locked by another command.
+    #[error(
+        "Profile `{profile}` is locked by process `{pid}` on host `{hostname}`: {}",
+        profile_lock_file.display()
+    )]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::profile_locked),
+            help(
+                "Wait for the other command to complete, \
+                or use `ProfileLockMode::Wait` to wait for the lock."
+            )
+        )
+    )]
+    ProfileLocked {
+        /// The profile that is locked.
+        profile: Profile,
+        /// Path to the `ProfileLockFile`.
+        profile_lock_file: PathBuf,
+        /// ID of the process that holds the lock.
+        pid: u32,
+        /// Name of the host that the process runs on.
+        hostname: String,
+    },
+
+    /// Profile is not one of the profiles loaded by the `CmdCtx`.
+    #[error("Profile `{profile}` is not in the command context's profiles: {profiles_in_scope:?}.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::profile_not_in_scope),
+            help(
+                "Make sure the profile exists, \
+                and is not excluded by the `CmdCtx`'s profile filter."
+            )
+        )
+    )]
+    ProfileNotInScope {
+        /// The profile that is not in scope.
+        profile: Profile,
+        /// Profiles loaded by the `CmdCtx`.
+        profiles_in_scope: Vec<Profile>,
+    },
+
+    /// Flow is not one of the flows loaded by the `CmdCtx`.
+    #[error("Flow `{flow_id}` is not in the command context's flows: {flow_ids_in_scope:?}.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::flow_not_in_scope),
+            help("Make sure the flow is passed to the `CmdCtx` builder's `with_flows`.")
+        )
+    )]
+    FlowNotInScope {
+        /// ID of the flow that is not in scope.
+        flow_id: FlowId,
+        /// IDs of the flows loaded by the `CmdCtx`.
+        flow_ids_in_scope: Vec<FlowId>,
+    },
+
+    /// Current states have not been discovered for a profile.
+    ///
+    /// This is returned when the `StatesSavedFile` of a profile does not
+    /// exist.
+    #[error("Current states have not been discovered for profile `{profile}`.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model::profile_states_current_discover_required),
+            help(
+                "Ensure that `StatesDiscoverCmd` or `StatesCurrentDiscoverCmd` \
+                has been called for the profile."
+            )
+        )
+    )]
+    ProfileStatesCurrentDiscoverRequired {
+        /// The profile whose states have not been discovered.
+        profile: Profile,
+    },
+
     /// Native application error occurred.
     #[error("Native application error occurred.")]
     #[cfg(not(target_arch = "wasm32"))]
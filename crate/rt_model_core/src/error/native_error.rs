@@ -70,6 +70,20 @@ pub enum NativeError {
         error: std::io::Error,
     },
 
+    /// Failed to remove file.
+    #[error("Failed to remove file: `{path}`")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model_native::file_remove))
+    )]
+    FileRemove {
+        /// Path to the file.
+        path: PathBuf,
+        /// Underlying IO error.
+        #[source]
+        error: std::io::Error,
+    },
+
     /// Failed to write to file.
     #[error("Failed to write to file: `{path}`")]
     #[cfg_attr(
@@ -84,6 +98,130 @@ pub enum NativeError {
         error: std::io::Error,
     },
 
+    /// Failed to read the encryption key from an environment variable.
+    #[cfg(feature = "storage_encryption")]
+    #[error("Failed to read encryption key from environment variable: `{env_var_name}`")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model_native::encryption_key_env_var_read),
+            help("Set `{env_var_name}` to a standard base64 encoded 32 byte key.")
+        )
+    )]
+    EncryptionKeyEnvVarRead {
+        /// Name of the environment variable.
+        env_var_name: String,
+        /// Underlying environment variable error.
+        #[source]
+        error: std::env::VarError,
+    },
+
+    /// Failed to read the encryption key file.
+    #[cfg(feature = "storage_encryption")]
+    #[error("Failed to read encryption key file: `{path}`")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model_native::encryption_key_file_read))
+    )]
+    EncryptionKeyFileRead {
+        /// Path to the key file.
+        path: PathBuf,
+        /// Underlying IO error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// Base URL for HTTP storage cannot have paths appended to it.
+    #[error("Base URL for HTTP storage cannot have paths appended to it: `{base_url}`")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model_native::http_storage_base_url_invalid),
+            help("Use an `http` or `https` URL, such as `https://example.com/peace/`.")
+        )
+    )]
+    HttpStorageBaseUrlInvalid {
+        /// The base URL.
+        base_url: String,
+    },
+
+    /// Item path cannot be mapped to a URL in HTTP storage.
+    #[error("Item path cannot be mapped to a URL in HTTP storage: `{}`", path.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model_native::http_storage_item_path_invalid),
+            help(
+                "Item paths must be within the storage's root directory, and must not contain \
+                `.` or `..` components."
+            )
+        )
+    )]
+    HttpStorageItemPathInvalid {
+        /// Path of the item.
+        path: PathBuf,
+    },
+
+    /// Failed to send a request to HTTP storage.
+    #[error("Failed to send `{method}` request to HTTP storage: `{url}`")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model_native::http_storage_request))
+    )]
+    HttpStorageRequest {
+        /// HTTP method of the request.
+        method: &'static str,
+        /// URL of the request.
+        url: String,
+        /// Underlying HTTP client error.
+        #[source]
+        error: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+
+    /// HTTP storage responded with an unexpected status code.
+    #[error("HTTP storage responded to `{method}` with status {status}: `{url}`")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model_native::http_storage_response_status))
+    )]
+    HttpStorageResponseStatus {
+        /// HTTP method of the request.
+        method: &'static str,
+        /// URL of the request.
+        url: String,
+        /// Status code of the response.
+        status: u16,
+    },
+
+    /// Failed to serialize profile lock information.
+    #[error("Failed to serialize profile lock information.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model_native::profile_lock_info_serialize))
+    )]
+    ProfileLockInfoSerialize(#[source] serde_yaml::Error),
+
+
+    /// Failed to deserialize the `ProfileLockFile`.
+    #[error("Failed to deserialize profile lock file: {}", profile_lock_file.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model_native::profile_lock_file_deserialize),
+            help(
+                "Make sure no other command is running for this profile, \
+                then delete the lock file."
+            )
+        )
+    )]
+    ProfileLockFileDeserialize {
+        /// Path to the `ProfileLockFile`.
+        profile_lock_file: PathBuf,
+        /// Underlying deserialization error.
+        #[source]
+        error: serde_yaml::Error,
+    },
+
     /// Failed to list entries in `PeaceAppDir`.
     #[error("Failed to list entries in `PeaceAppDir`: {}", peace_app_dir.display())]
     PeaceAppDirRead {
@@ -137,6 +275,34 @@ pub enum NativeError {
     )]
     StdoutWrite(#[source] std::io::Error),
 
+    /// Failed to list entries in a storage directory.
+    #[error("Failed to list entries in directory: {}", path.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model_native::storage_dir_read))
+    )]
+    StorageDirRead {
+        /// Path to the directory.
+        path: PathBuf,
+        /// Underlying IO error.
+        #[source]
+        error: std::io::Error,
+    },
+
+    /// Failed to read entry in a storage directory.
+    #[error("Failed to read entry in directory: {}", path.display())]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model_native::storage_dir_entry_read))
+    )]
+    StorageDirEntryRead {
+        /// Path to the directory.
+        path: PathBuf,
+        /// Underlying IO error.
+        #[source]
+        error: std::io::Error,
+    },
+
     /// Storage synchronous thread failed to be joined.
     ///
     /// This variant is used for thread spawning errors for both reads and
@@ -53,6 +53,88 @@ pub enum WebError {
         diagnostic(code(peace_rt_model_web::session_storage_none))
     )]
     SessionStorageNone,
+    /// Browser web storage was accessed, but the workspace is stored in
+    /// IndexedDB.
+    ///
+    /// Items in IndexedDB are accessed asynchronously through the
+    /// `StorageBackend` methods.
+    #[error("Browser web storage was accessed, but the workspace is stored in IndexedDB.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(
+            code(peace_rt_model_web::web_storage_not_in_use),
+            help("Use the `StorageBackend` methods to access items in IndexedDB.")
+        )
+    )]
+    WebStorageNotInUse,
+
+    /// Browser IndexedDB unavailable.
+    #[error("Browser IndexedDB unavailable.")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model_web::indexed_db_unavailable))
+    )]
+    IndexedDbUnavailable,
+    /// Failed to get an item from browser IndexedDB.
+    ///
+    /// Note: The original `JsValue` error is converted to a `String` to allow
+    /// this type to be `Send`.
+    #[error("Failed to get an item in browser IndexedDB: `{path}`. Error: `{error}`")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model_web::indexed_db_get))
+    )]
+    IndexedDbGet {
+        /// Key to get.
+        path: PathBuf,
+        /// Stringified JS error.
+        error: String,
+    },
+    /// Failed to put an item into browser IndexedDB.
+    ///
+    /// Note: The original `JsValue` error is converted to a `String` to allow
+    /// this type to be `Send`.
+    #[error("Failed to put an item in browser IndexedDB: `{path}`. Error: `{error}`")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model_web::indexed_db_put))
+    )]
+    IndexedDbPut {
+        /// Key to put.
+        path: PathBuf,
+        /// Stringified JS error.
+        error: String,
+    },
+    /// Failed to delete an item from browser IndexedDB.
+    ///
+    /// Note: The original `JsValue` error is converted to a `String` to allow
+    /// this type to be `Send`.
+    #[error("Failed to delete an item from browser IndexedDB: `{path}`. Error: `{error}`")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model_web::indexed_db_delete))
+    )]
+    IndexedDbDelete {
+        /// Key to delete.
+        path: PathBuf,
+        /// Stringified JS error.
+        error: String,
+    },
+    /// Failed to list the item keys in browser IndexedDB.
+    ///
+    /// Note: The original `JsValue` error is converted to a `String` to allow
+    /// this type to be `Send`.
+    #[error("Failed to list items in browser IndexedDB within: `{dir}`. Error: `{error}`")]
+    #[cfg_attr(
+        feature = "error_reporting",
+        diagnostic(code(peace_rt_model_web::indexed_db_keys))
+    )]
+    IndexedDbKeys {
+        /// Directory whose entries were listed.
+        dir: PathBuf,
+        /// Stringified JS error.
+        error: String,
+    },
 
     /// Failed to base64 decode an item from browser storage.
     #[error(
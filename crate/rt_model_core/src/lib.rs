@@ -11,10 +11,12 @@ pub use indicatif;
 
 pub mod output;
 pub mod params;
+pub mod storage;
 
-pub use crate::error::Error;
+pub use crate::{error::Error, profile_lock_mode::ProfileLockMode};
 
 mod error;
+mod profile_lock_mode;
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "output_progress")] {
@@ -25,12 +25,17 @@
 //!
 //! * Server count: applicable to `deploy`
 //! * Force remove: applicable to `clean`
+//!
+//! [`ParamsLimits`] guard automation from executing with unusual parameter
+//! values, such as a server count of 500.
 
 pub use self::{
     cmd_params::CmdParams,
     cmd_params_builder::CmdParamsBuilder,
     flow_params::FlowParams,
     params_keys::{KeyKnown, KeyMaybe, KeyUnknown, ParamsKeys, ParamsKeysImpl},
+    params_limit_violation::ParamsLimitViolation,
+    params_limits::ParamsLimits,
     params_type_regs::ParamsTypeRegs,
     params_type_regs_builder::ParamsTypeRegsBuilder,
     profile_params::ProfileParams,
@@ -41,6 +46,8 @@ mod cmd_params;
 mod cmd_params_builder;
 mod flow_params;
 mod params_keys;
+mod params_limit_violation;
+mod params_limits;
 mod params_type_regs;
 mod params_type_regs_builder;
 mod profile_params;
@@ -0,0 +1,39 @@
+/// A parameter value that is outside of its limits.
+///
+/// This is returned by a limit registered with
+/// [`ParamsLimits::with_limit`], and rendered as a related diagnostic of
+/// [`Error::ParamsLimitsExceeded`].
+///
+/// [`ParamsLimits::with_limit`]: crate::params::ParamsLimits::with_limit
+/// [`Error::ParamsLimitsExceeded`]: crate::Error::ParamsLimitsExceeded
+#[cfg_attr(feature = "error_reporting", derive(miette::Diagnostic))]
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("`{params_type_name}`: {description}")]
+pub struct ParamsLimitViolation {
+    /// Type name of the parameter whose value is outside its limits.
+    params_type_name: &'static str,
+    /// Description of the violation, e.g. "instance count 500 exceeds limit
+    /// 10".
+    description: String,
+}
+
+impl ParamsLimitViolation {
+    /// Returns a new `ParamsLimitViolation`.
+    pub fn new(params_type_name: &'static str, description: String) -> Self {
+        Self {
+            params_type_name,
+            description,
+        }
+    }
+
+    /// Returns the type name of the parameter whose value is outside its
+    /// limits.
+    pub fn params_type_name(&self) -> &'static str {
+        self.params_type_name
+    }
+
+    /// Returns the description of the violation.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
@@ -0,0 +1,162 @@
+use std::{fmt, sync::Arc};
+
+use peace_data::{resman::Resource, Resources};
+
+use crate::{params::ParamsLimitViolation, Error};
+
+/// Type-erased function that checks a parameter's value in `Resources`.
+type ParamsLimitFn =
+    Arc<dyn Fn(&Resources) -> Result<Option<ParamsLimitViolation>, Error> + Send + Sync>;
+
+/// Limits on parameter values, to guard automation from executing with
+/// unusual values.
+///
+/// Each limit is registered against a parameter type, and is checked against
+/// the value of that type in [`Resources`]. This applies to workspace,
+/// profile, and flow params, as well as item spec params, as these are all
+/// inserted into `Resources`.
+///
+/// Limits are checked when the `CmdCtx` is built, after item specs are set
+/// up, and again before each item is applied, after its params are resolved.
+/// If any value is outside its limits, an [`Error::ParamsLimitsExceeded`] is
+/// returned, unless the limits are [overridden].
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let params_limits = ParamsLimits::new().with_limit(|instance_count: &InstanceCount| {
+///     if instance_count.0 > 10 {
+///         Err(format!("instance count {} exceeds limit 10", instance_count.0))
+///     } else {
+///         Ok(())
+///     }
+/// });
+/// ```
+///
+/// [overridden]: Self::with_override
+#[derive(Clone, Default)]
+pub struct ParamsLimits {
+    /// Type name of each limited parameter, and its limit function.
+    limits: Vec<(&'static str, ParamsLimitFn)>,
+    /// Whether execution proceeds even if values are outside their limits.
+    overridden: bool,
+}
+
+impl ParamsLimits {
+    /// Returns a new `ParamsLimits` with no limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a limit for parameters of type `T`.
+    ///
+    /// The function returns a description of the violation when the value is
+    /// outside its limits, e.g. "instance count 500 exceeds limit 10".
+    ///
+    /// Item spec params that are resolved from a params spec are checked in
+    /// their `Option<T>` resource once resolved, and are skipped before then.
+    ///
+    /// When the limits are checked, [`Error::ParamsLimitParamsNotFound`] is
+    /// returned if there is neither a `T` nor an `Option<T>` in `Resources`,
+    /// as the limit would otherwise never apply.
+    pub fn with_limit<T, F>(mut self, limit_fn: F) -> Self
+    where
+        T: Resource,
+        F: Fn(&T) -> Result<(), String> + Send + Sync + 'static,
+    {
+        let params_type_name = std::any::type_name::<T>();
+        let params_limit_fn = move |resources: &Resources| {
+            let result = match resources.try_borrow::<Option<T>>() {
+                Ok(value_resolved) => match value_resolved.as_ref() {
+                    Some(value) => limit_fn(value),
+                    None => Ok(()),
+                },
+                Err(_) => {
+                    let value = resources
+                        .try_borrow::<T>()
+                        .map_err(|_| Error::ParamsLimitParamsNotFound { params_type_name })?;
+                    limit_fn(&value)
+                }
+            };
+            Ok(result
+                .err()
+                .map(|description| ParamsLimitViolation::new(params_type_name, description)))
+        };
+        self.limits
+            .push((params_type_name, Arc::new(params_limit_fn)));
+        self
+    }
+
+    /// Sets whether execution proceeds even if values are outside their
+    /// limits.
+    ///
+    /// This is intended to be set when the user explicitly confirms that the
+    /// unusual values are intended, e.g. through a `--force` flag.
+    pub fn with_override(mut self, overridden: bool) -> Self {
+        self.overridden = overridden;
+        self
+    }
+
+    /// Sets whether execution proceeds even if values are outside their
+    /// limits.
+    pub fn set_overridden(&mut self, overridden: bool) {
+        self.overridden = overridden;
+    }
+
+    /// Returns whether execution proceeds even if values are outside their
+    /// limits.
+    pub fn overridden(&self) -> bool {
+        self.overridden
+    }
+
+    /// Returns the violations of the limits for the values in `Resources`.
+    ///
+    /// Violations are returned regardless of whether the limits are
+    /// overridden.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ParamsLimitParamsNotFound`] if a limited parameter type
+    /// is not in `Resources`.
+    pub fn violations(&self, resources: &Resources) -> Result<Vec<ParamsLimitViolation>, Error> {
+        self.limits
+            .iter()
+            .filter_map(|(_params_type_name, params_limit_fn)| {
+                params_limit_fn(resources).transpose()
+            })
+            .collect::<Result<Vec<ParamsLimitViolation>, Error>>()
+    }
+
+    /// Checks the values in `Resources` against their limits.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ParamsLimitsExceeded`] if any value is outside its
+    /// limits, and the limits are not overridden.
+    ///
+    /// Returns [`Error::ParamsLimitParamsNotFound`] if a limited parameter type
+    /// is not in `Resources`, even if the limits are overridden.
+    pub fn check(&self, resources: &Resources) -> Result<(), Error> {
+        let violations = self.violations(resources)?;
+        if self.overridden || violations.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::ParamsLimitsExceeded { violations })
+        }
+    }
+}
+
+impl fmt::Debug for ParamsLimits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let params_type_names = self
+            .limits
+            .iter()
+            .map(|(params_type_name, _params_limit_fn)| *params_type_name)
+            .collect::<Vec<&'static str>>();
+
+        f.debug_struct("ParamsLimits")
+            .field("limits", &params_type_names)
+            .field("overridden", &self.overridden)
+            .finish()
+    }
+}
@@ -0,0 +1,21 @@
+use std::time::Duration;
+
+/// Whether to wait for a `ProfileLock` held by another command.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProfileLockMode {
+    /// Return [`Error::ProfileLocked`] if the lock is held by another command.
+    ///
+    /// [`Error::ProfileLocked`]: crate::Error::ProfileLocked
+    #[default]
+    Fail,
+    /// Wait for the other command to release the lock.
+    Wait {
+        /// Maximum duration to wait, after which [`Error::ProfileLocked`] is
+        /// returned.
+        ///
+        /// If this is `None`, the lock is waited for indefinitely.
+        ///
+        /// [`Error::ProfileLocked`]: crate::Error::ProfileLocked
+        timeout: Option<Duration>,
+    },
+}
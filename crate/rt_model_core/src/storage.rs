@@ -0,0 +1,40 @@
+//! Backends that store serialized data for a workspace.
+//!
+//! The `Storage` type in `peace_rt_model` delegates reads and writes of
+//! serialized data -- params, states, plans, and history records -- to a
+//! [`StorageBackend`]. This allows the data to be stored on the file system,
+//! in browser storage, or in memory, such as for tests and for applications
+//! that embed `peace` and do not want to touch the disk.
+//!
+//! Items are serialized in the [`StorageFormat`] recorded in the workspace,
+//! and the format of each item is detected when it is read. The `Storage` for
+//! each target implements [`SerializedStorage`], so code that reads and writes
+//! serialized items is shared between native and WASM targets.
+//!
+//! With the `storage_encryption` feature, `EncryptedStorage` encrypts items
+//! before they are stored in another backend.
+
+pub use self::{
+    in_memory_storage::InMemoryStorage, serialized_storage::SerializedStorage,
+    storage_backend::StorageBackend, storage_format::StorageFormat,
+    storage_format_error::StorageFormatError,
+};
+
+mod in_memory_storage;
+mod serialized_storage;
+mod storage_backend;
+mod storage_format;
+mod storage_format_error;
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "storage_encryption")] {
+        pub use self::{
+            encrypted_storage::EncryptedStorage, encryption_key::EncryptionKey,
+            key_provider::KeyProvider,
+        };
+
+        mod encrypted_storage;
+        mod encryption_key;
+        mod key_provider;
+    }
+}
@@ -0,0 +1,244 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use aes_gcm::{
+    aead::{Aead, AeadCore, OsRng, Payload},
+    Aes256Gcm, KeyInit, Nonce,
+};
+use async_trait::async_trait;
+use base64::Engine;
+use peace_resources::paths::PeaceDir;
+
+use crate::{
+    storage::{KeyProvider, StorageBackend},
+    Error,
+};
+
+/// Prefix of encrypted items, which distinguishes them from unencrypted
+/// items.
+const ENCRYPTED_PREFIX: &[u8] = b"peace_encrypted_v1:";
+
+/// Length of the AES-GCM nonce that is stored before the ciphertext.
+const NONCE_LENGTH: usize = 12;
+
+/// Encrypts items before they are stored in another [`StorageBackend`].
+///
+/// Items are encrypted with AES-256-GCM, using the key from the
+/// [`KeyProvider`], and stored as base64 text so that the encrypted items may
+/// be stored in text-only backends such as browser web storage.
+///
+/// The path of each item within the `.peace` directory is authenticated along
+/// with its contents, so an encrypted item that is copied or moved to another
+/// path fails to decrypt. Items still decrypt when the workspace itself is
+/// moved, or is read on another machine.
+///
+/// Items that were written before encryption was enabled are read as is, and
+/// are encrypted the next time they are written.
+///
+/// # Examples
+///
+/// On native targets, the key may be read from an environment variable or a
+/// key file:
+///
+/// ```rust,ignore
+/// let storage = Storage::new(EncryptedStorage::new(
+///     FsStorage,
+///     EnvKeyProvider::new("ENVMAN_KEY"),
+/// ));
+/// let workspace = Workspace::new(app_name, workspace_spec)?.with_storage(storage);
+/// ```
+///
+/// On WASM targets, the key is provided by the application, and items may be
+/// stored in browser web storage:
+///
+/// ```rust,ignore
+/// let storage = Storage::new(EncryptedStorage::new(
+///     WebStorage::new(WorkspaceSpec::SessionStorage),
+///     EncryptionKey::from_base64("app key", &key_base64)?,
+/// ));
+/// let workspace = Workspace::new(app_name, WorkspaceSpec::SessionStorage)?
+///     .with_storage(storage);
+/// ```
+#[derive(Clone, Debug)]
+pub struct EncryptedStorage<B> {
+    /// Backend that stores the encrypted items.
+    backend: B,
+    /// Provides the key to encrypt and decrypt items with.
+    key_provider: Arc<dyn KeyProvider>,
+}
+
+impl<B> EncryptedStorage<B>
+where
+    B: StorageBackend,
+{
+    /// Returns a new `EncryptedStorage`.
+    ///
+    /// # Parameters
+    ///
+    /// * `backend`: Backend that stores the encrypted items.
+    /// * `key_provider`: Provides the key to encrypt and decrypt items with.
+    pub fn new<K>(backend: B, key_provider: K) -> Self
+    where
+        K: KeyProvider,
+    {
+        Self {
+            backend,
+            key_provider: Arc::new(key_provider),
+        }
+    }
+
+    /// Returns a reference to the backend that stores the encrypted items.
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// Returns the cipher for the key from the key provider.
+    fn cipher(&self) -> Result<Aes256Gcm, Error> {
+        let key = self.key_provider.key()?;
+        Ok(Aes256Gcm::new(key.as_bytes().into()))
+    }
+
+    /// Returns the additional authenticated data for the item at the given
+    /// path.
+    ///
+    /// This is the item's path relative to the `.peace` directory, with `/`
+    /// separated components. Paths that are not within a `.peace` directory
+    /// are used in full.
+    fn aad(path: &Path) -> Vec<u8> {
+        let mut components = path.components();
+        let path_relative =
+            if components.any(|component| component.as_os_str() == PeaceDir::NAME) {
+                components.as_path()
+            } else {
+                path
+            };
+
+        path_relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+            .into_bytes()
+    }
+
+    /// Encrypts the bytes of the item at the given path.
+    fn encrypt(&self, path: &Path, bytes: &[u8]) -> Result<Vec<u8>, Error> {
+        let cipher = self.cipher()?;
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let aad = Self::aad(path);
+        let payload = Payload {
+            msg: bytes,
+            aad: &aad,
+        };
+        let ciphertext = cipher
+            .encrypt(&nonce, payload)
+            .map_err(|_| Error::StorageEncrypt {
+                path: path.to_path_buf(),
+            })?;
+
+        let mut nonce_and_ciphertext = nonce.to_vec();
+        nonce_and_ciphertext.extend(ciphertext);
+
+        let nonce_and_ciphertext_base64 =
+            base64::engine::general_purpose::STANDARD.encode(nonce_and_ciphertext);
+        let mut encrypted = ENCRYPTED_PREFIX.to_vec();
+        encrypted.extend(nonce_and_ciphertext_base64.as_bytes());
+        encrypted.push(b'\n');
+
+        Ok(encrypted)
+    }
+
+    /// Decrypts the bytes of the item at the given path.
+    ///
+    /// Bytes that are not encrypted are returned as is.
+    fn decrypt(&self, path: &Path, bytes: Vec<u8>) -> Result<Vec<u8>, Error> {
+        let Some(encoded) = bytes.strip_prefix(ENCRYPTED_PREFIX) else {
+            return Ok(bytes);
+        };
+
+        let error_decrypt = || Error::StorageDecrypt {
+            path: path.to_path_buf(),
+        };
+        let nonce_and_ciphertext = std::str::from_utf8(encoded)
+            .ok()
+            .and_then(|encoded| {
+                base64::engine::general_purpose::STANDARD
+                    .decode(encoded.trim())
+                    .ok()
+            })
+            .ok_or_else(error_decrypt)?;
+        if nonce_and_ciphertext.len() < NONCE_LENGTH {
+            return Err(error_decrypt());
+        }
+        let (nonce, ciphertext) = nonce_and_ciphertext.split_at(NONCE_LENGTH);
+        let aad = Self::aad(path);
+        let payload = Payload {
+            msg: ciphertext,
+            aad: &aad,
+        };
+
+        self.cipher()?
+            .decrypt(Nonce::from_slice(nonce), payload)
+            .map_err(|_| error_decrypt())
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<B> StorageBackend for EncryptedStorage<B>
+where
+    B: StorageBackend,
+{
+    async fn contains(&self, path: &Path) -> Result<bool, Error> {
+        self.backend.contains(path).await
+    }
+
+    async fn read(&self, path: &Path) -> Result<Option<Vec<u8>>, Error> {
+        self.backend
+            .read(path)
+            .await?
+            .map(|bytes| self.decrypt(path, bytes))
+            .transpose()
+    }
+
+    async fn write(&self, path: &Path, bytes: &[u8]) -> Result<(), Error> {
+        let encrypted = self.encrypt(path, bytes)?;
+        self.backend.write(path, &encrypted).await
+    }
+
+    async fn write_new(&self, path: &Path, bytes: &[u8]) -> Result<bool, Error> {
+        let encrypted = self.encrypt(path, bytes)?;
+        self.backend.write_new(path, &encrypted).await
+    }
+
+    async fn remove(&self, path: &Path) -> Result<(), Error> {
+        self.backend.remove(path).await
+    }
+
+    async fn remove_if_unchanged(&self, path: &Path, bytes: &[u8]) -> Result<bool, Error> {
+        // Each encryption uses a new nonce, so the plaintext is compared, and the
+        // backend removes the item only if its ciphertext is unchanged.
+        let Some(encrypted) = self.backend.read(path).await? else {
+            return Ok(false);
+        };
+        if self.decrypt(path, encrypted.clone())? != bytes {
+            return Ok(false);
+        }
+
+        self.backend.remove_if_unchanged(path, &encrypted).await
+    }
+
+    async fn dir_create(&self, dir: &Path) -> Result<(), Error> {
+        self.backend.dir_create(dir).await
+    }
+
+    async fn dir_entries(&self, dir: &Path) -> Result<Vec<PathBuf>, Error> {
+        self.backend.dir_entries(dir).await
+    }
+
+    fn is_file_system(&self) -> bool {
+        self.backend.is_file_system()
+    }
+}
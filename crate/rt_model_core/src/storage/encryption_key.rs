@@ -0,0 +1,77 @@
+use std::fmt;
+
+use aes_gcm::{aead::OsRng, Aes256Gcm, KeyInit};
+use base64::Engine;
+
+use crate::{storage::KeyProvider, Error};
+
+/// Key that items are encrypted with by [`EncryptedStorage`].
+///
+/// This is a 256-bit AES-GCM key. Keys are exchanged as standard base64
+/// encoded strings, e.g. in an environment variable or key file.
+///
+/// The key is redacted when formatted with `Debug`.
+///
+/// [`EncryptedStorage`]: crate::storage::EncryptedStorage
+#[derive(Clone, PartialEq, Eq)]
+pub struct EncryptionKey([u8; 32]);
+
+impl EncryptionKey {
+    /// Returns an `EncryptionKey` from its raw bytes.
+    pub fn new(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns a new randomly generated `EncryptionKey`.
+    pub fn generate() -> Self {
+        Self(Aes256Gcm::generate_key(&mut OsRng).into())
+    }
+
+    /// Decodes an `EncryptionKey` from a standard base64 encoded string.
+    ///
+    /// Leading and trailing whitespace is ignored.
+    ///
+    /// # Parameters
+    ///
+    /// * `key_source`: Where the key was read from, used in error messages,
+    ///   e.g. "environment variable `ENVMAN_KEY`".
+    /// * `key_base64`: The base64 encoded key.
+    pub fn from_base64(key_source: &str, key_base64: &str) -> Result<Self, Error> {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(key_base64.trim())
+            .map_err(|error| Error::EncryptionKeyDecode {
+                key_source: key_source.to_string(),
+                error,
+            })?;
+        let bytes =
+            <[u8; 32]>::try_from(bytes.as_slice()).map_err(|_| Error::EncryptionKeyLength {
+                key_source: key_source.to_string(),
+                length: bytes.len(),
+            })?;
+
+        Ok(Self(bytes))
+    }
+
+    /// Returns the key as a standard base64 encoded string.
+    pub fn to_base64(&self) -> String {
+        base64::engine::general_purpose::STANDARD.encode(self.0)
+    }
+
+    /// Returns the raw bytes of the key.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("EncryptionKey").field(&"********").finish()
+    }
+}
+
+/// An `EncryptionKey` provides itself, for keys that are already in memory.
+impl KeyProvider for EncryptionKey {
+    fn key(&self) -> Result<EncryptionKey, Error> {
+        Ok(self.clone())
+    }
+}
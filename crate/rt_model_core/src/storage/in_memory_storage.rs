@@ -0,0 +1,107 @@
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use async_trait::async_trait;
+
+use crate::{storage::StorageBackend, Error};
+
+/// Stores serialized data in memory.
+///
+/// This is useful for tests, and for applications that embed `peace` and
+/// keep their data elsewhere.
+///
+/// Clones of an `InMemoryStorage` share the same data, so a test may keep a
+/// clone to inspect what was written by a command.
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryStorage {
+    /// Stored bytes, keyed by path.
+    items: Arc<Mutex<BTreeMap<PathBuf, Vec<u8>>>>,
+}
+
+impl InMemoryStorage {
+    /// Returns a new empty `InMemoryStorage`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the paths of all stored items, in sorted order.
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.items
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .keys()
+            .cloned()
+            .collect::<Vec<PathBuf>>()
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl StorageBackend for InMemoryStorage {
+    async fn contains(&self, path: &Path) -> Result<bool, Error> {
+        let items = self.items.lock().unwrap_or_else(PoisonError::into_inner);
+        Ok(items.contains_key(path))
+    }
+
+    async fn read(&self, path: &Path) -> Result<Option<Vec<u8>>, Error> {
+        let items = self.items.lock().unwrap_or_else(PoisonError::into_inner);
+        Ok(items.get(path).cloned())
+    }
+
+    async fn write(&self, path: &Path, bytes: &[u8]) -> Result<(), Error> {
+        let mut items = self.items.lock().unwrap_or_else(PoisonError::into_inner);
+        items.insert(path.to_path_buf(), bytes.to_vec());
+        Ok(())
+    }
+
+    async fn write_new(&self, path: &Path, bytes: &[u8]) -> Result<bool, Error> {
+        let mut items = self.items.lock().unwrap_or_else(PoisonError::into_inner);
+        match items.entry(path.to_path_buf()) {
+            Entry::Vacant(entry) => {
+                entry.insert(bytes.to_vec());
+                Ok(true)
+            }
+            Entry::Occupied(_) => Ok(false),
+        }
+    }
+
+    async fn remove(&self, path: &Path) -> Result<(), Error> {
+        let mut items = self.items.lock().unwrap_or_else(PoisonError::into_inner);
+        items.remove(path);
+        Ok(())
+    }
+
+    async fn remove_if_unchanged(&self, path: &Path, bytes: &[u8]) -> Result<bool, Error> {
+        let mut items = self.items.lock().unwrap_or_else(PoisonError::into_inner);
+        match items.entry(path.to_path_buf()) {
+            Entry::Occupied(entry) if entry.get() == bytes => {
+                entry.remove();
+                Ok(true)
+            }
+            Entry::Occupied(_) | Entry::Vacant(_) => Ok(false),
+        }
+    }
+
+    async fn dir_create(&self, _dir: &Path) -> Result<(), Error> {
+        // Directories are implied by the paths of stored items.
+        Ok(())
+    }
+
+    async fn dir_entries(&self, dir: &Path) -> Result<Vec<PathBuf>, Error> {
+        let items = self.items.lock().unwrap_or_else(PoisonError::into_inner);
+        let dir_entries = items
+            .keys()
+            .filter(|path| path.parent() == Some(dir))
+            .cloned()
+            .collect::<Vec<PathBuf>>();
+
+        Ok(dir_entries)
+    }
+
+    fn is_file_system(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,20 @@
+use std::fmt::Debug;
+
+use crate::{storage::EncryptionKey, Error};
+
+/// Provides the key that [`EncryptedStorage`] encrypts items with.
+///
+/// The key is requested each time an item is read or written, so that it is
+/// not held in memory by the framework for longer than necessary.
+///
+/// # Implementors
+///
+/// * [`EncryptionKey`]: A key that is already in memory.
+/// * `EnvKeyProvider`: Reads the key from an environment variable.
+/// * `FileKeyProvider`: Reads the key from a key file.
+///
+/// [`EncryptedStorage`]: crate::storage::EncryptedStorage
+pub trait KeyProvider: Debug + Send + Sync + 'static {
+    /// Returns the key to encrypt and decrypt items with.
+    fn key(&self) -> Result<EncryptionKey, Error>;
+}
@@ -0,0 +1,190 @@
+use std::{fmt::Debug, hash::Hash, path::Path};
+
+use async_trait::async_trait;
+use peace_resources::type_reg::untagged::{DataTypeWrapper, TypeMap, TypeReg};
+use serde::{
+    de::{DeserializeOwned, DeserializeSeed},
+    Serialize,
+};
+
+use crate::{
+    storage::{StorageBackend, StorageFormat, StorageFormatError},
+    Error,
+};
+
+/// Reads and writes serialized items through a [`StorageBackend`].
+///
+/// This is implemented by the `Storage` type for both native and WASM
+/// targets, so that serializers and initializers are written once for both.
+///
+/// Items are written in the [`format`] of the storage, and the format of each
+/// item is detected when it is read.
+///
+/// An item that is not found at the given path is read from the path with
+/// another format's extension, e.g. `states_saved.yaml` is read if
+/// `states_saved.json` does not exist. Writing an item does not change items
+/// at the paths with other formats' extensions, as those are only read if the
+/// item does not exist. The `StorageFormatMigrateCmd` removes them.
+///
+/// [`format`]: Self::format
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait SerializedStorage: Debug + Send + Sync {
+    /// Returns the backend that stores the serialized data.
+    fn backend(&self) -> &dyn StorageBackend;
+
+    /// Returns the format that items are written in.
+    fn format(&self) -> StorageFormat;
+
+    /// Reads the item at the given path, or at the path with another format's
+    /// extension if the item is not at the given path.
+    async fn item_read(&self, file_path: &Path) -> Result<Option<Vec<u8>>, Error> {
+        if let Some(bytes) = self.backend().read(file_path).await? {
+            return Ok(Some(bytes));
+        }
+
+        for file_path_other in StorageFormat::paths_other_formats(file_path) {
+            if let Some(bytes) = self.backend().read(&file_path_other).await? {
+                return Ok(Some(bytes));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Returns whether the item exists at the given path, or at the path with
+    /// another format's extension.
+    async fn item_contains(&self, file_path: &Path) -> Result<bool, Error> {
+        if self.backend().contains(file_path).await? {
+            return Ok(true);
+        }
+
+        for file_path_other in StorageFormat::paths_other_formats(file_path) {
+            if self.backend().contains(&file_path_other).await? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Reads a serializable item from the given path.
+    ///
+    /// # Parameters
+    ///
+    /// * `file_path`: Path to the file to read the serialized item.
+    /// * `f_map_err`: Maps the deserialization error (if any) to an [`Error`].
+    async fn serialized_read<T, F>(&self, file_path: &Path, f_map_err: F) -> Result<T, Error>
+    where
+        T: Serialize + DeserializeOwned + Send + Sync,
+        F: FnOnce(StorageFormatError) -> Error + Send,
+    {
+        match self.item_read(file_path).await? {
+            Some(bytes) => StorageFormat::deserialize::<T>(&bytes).map_err(f_map_err),
+            None => Err(Error::ItemNotExists {
+                path: file_path.to_path_buf(),
+            }),
+        }
+    }
+
+    /// Reads a serializable item from the given path if the file exists.
+    ///
+    /// # Parameters
+    ///
+    /// * `file_path`: Path to the file to read the serialized item.
+    /// * `f_map_err`: Maps the deserialization error (if any) to an [`Error`].
+    async fn serialized_read_opt<T, F>(
+        &self,
+        file_path: &Path,
+        f_map_err: F,
+    ) -> Result<Option<T>, Error>
+    where
+        T: DeserializeOwned + Send + Sync,
+        F: FnOnce(StorageFormatError) -> Error + Send,
+    {
+        self.item_read(file_path)
+            .await?
+            .map(|bytes| StorageFormat::deserialize::<T>(&bytes).map_err(f_map_err))
+            .transpose()
+    }
+
+    /// Deserializes a typemap from the given path if the file exists.
+    ///
+    /// # Parameters
+    ///
+    /// * `type_reg`: Type registry with the stateful deserialization mappings.
+    /// * `file_path`: Path to the file to read the serialized item.
+    /// * `f_map_err`: Maps the deserialization error (if any) to an [`Error`].
+    async fn serialized_typemap_read_opt<T, K, BoxDT, F>(
+        &self,
+        type_reg: &TypeReg<K, BoxDT>,
+        file_path: &Path,
+        f_map_err: F,
+    ) -> Result<Option<T>, Error>
+    where
+        T: From<TypeMap<K, BoxDT>> + Send + Sync,
+        K: Debug + DeserializeOwned + Eq + Hash + Send + Sync,
+        BoxDT: DataTypeWrapper + 'static,
+        F: FnOnce(StorageFormatError) -> Error + Send,
+    {
+        self.item_read(file_path)
+            .await?
+            .map(|bytes| {
+                let type_map =
+                    StorageFormat::deserialize_typemap(&bytes, type_reg).map_err(f_map_err)?;
+
+                Ok(T::from(type_map))
+            })
+            .transpose()
+    }
+
+    /// Deserializes an item from the given path using a `DeserializeSeed`, if
+    /// the file exists.
+    ///
+    /// # Parameters
+    ///
+    /// * `seed`: Stateful deserializer for the item.
+    /// * `file_path`: Path to the file to read the serialized item.
+    /// * `f_map_err`: Maps the deserialization error (if any) to an [`Error`].
+    ///   This is also passed the serialized contents, for error reporting.
+    async fn serialized_seed_read_opt<S, T, F>(
+        &self,
+        seed: S,
+        file_path: &Path,
+        f_map_err: F,
+    ) -> Result<Option<T>, Error>
+    where
+        S: for<'de> DeserializeSeed<'de, Value = T> + Send,
+        T: Send + Sync,
+        F: FnOnce(&[u8], StorageFormatError) -> Error + Send,
+    {
+        self.item_read(file_path)
+            .await?
+            .map(|bytes| {
+                StorageFormat::deserialize_seed(&bytes, seed)
+                    .map_err(|error| f_map_err(&bytes, error))
+            })
+            .transpose()
+    }
+
+    /// Writes a serializable item to the given path.
+    ///
+    /// # Parameters
+    ///
+    /// * `file_path`: Path to the file to store the serialized item.
+    /// * `t`: Item to serialize.
+    /// * `f_map_err`: Maps the serialization error (if any) to an [`Error`].
+    async fn serialized_write<T, F>(
+        &self,
+        file_path: &Path,
+        t: &T,
+        f_map_err: F,
+    ) -> Result<(), Error>
+    where
+        T: Serialize + Send + Sync,
+        F: FnOnce(StorageFormatError) -> Error + Send,
+    {
+        let serialized = self.format().serialize(t).map_err(f_map_err)?;
+        self.backend().write(file_path, &serialized).await
+    }
+}
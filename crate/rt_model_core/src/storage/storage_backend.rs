@@ -0,0 +1,76 @@
+use std::{
+    fmt::Debug,
+    path::{Path, PathBuf},
+};
+
+use async_trait::async_trait;
+
+use crate::Error;
+
+/// Reads and writes serialized data, keyed by path.
+///
+/// Paths are the same paths that would be used on the file system, e.g.
+/// `.peace/envman/dev/deploy/states_saved.yaml`, so that implementations that
+/// are not backed by the file system may use them as keys.
+///
+/// # Implementors
+///
+/// Implementations are expected to be cheap to clone, as `Storage` is cloned
+/// into each `CmdCtx`, and shared through `Resources`.
+///
+/// When compiling to WASM, the returned futures are not required to be
+/// `Send`, as browser APIs such as IndexedDB return `!Send` futures.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait StorageBackend: Debug + Send + Sync + 'static {
+    /// Returns whether an item exists at the given path.
+    async fn contains(&self, path: &Path) -> Result<bool, Error>;
+
+    /// Returns the bytes stored at the given path, if any.
+    async fn read(&self, path: &Path) -> Result<Option<Vec<u8>>, Error>;
+
+    /// Stores the bytes at the given path, replacing any existing item.
+    async fn write(&self, path: &Path, bytes: &[u8]) -> Result<(), Error>;
+
+    /// Stores the bytes at the given path, if there is no item at the path.
+    ///
+    /// Returns `false` and leaves the existing item unchanged if there is
+    /// already an item at the path.
+    ///
+    /// Implementations must check and store the item atomically, as this is
+    /// used to claim a path that concurrent writers may also try to claim.
+    async fn write_new(&self, path: &Path, bytes: &[u8]) -> Result<bool, Error>;
+
+    /// Removes the item at the given path.
+    ///
+    /// This returns `Ok(())` if there is no item at the path.
+    async fn remove(&self, path: &Path) -> Result<(), Error>;
+
+    /// Removes the item at the given path, if it holds the given bytes.
+    ///
+    /// Returns `false` and leaves the item unchanged if the item holds other
+    /// bytes, or if there is no item at the path.
+    ///
+    /// Implementations must compare and remove the item atomically, as this is
+    /// used to remove an item that was read earlier, only if no other writer
+    /// has replaced it since.
+    async fn remove_if_unchanged(&self, path: &Path, bytes: &[u8]) -> Result<bool, Error>;
+
+    /// Creates a directory, and all of its parent directories.
+    ///
+    /// Implementations that do not have a concept of directories may do
+    /// nothing.
+    async fn dir_create(&self, dir: &Path) -> Result<(), Error>;
+
+    /// Returns the paths of items directly within the given directory, in no
+    /// particular order.
+    ///
+    /// This returns an empty list if the directory does not exist.
+    async fn dir_entries(&self, dir: &Path) -> Result<Vec<PathBuf>, Error>;
+
+    /// Returns whether items are stored on the file system.
+    ///
+    /// When this is `false`, the framework does not change the current
+    /// directory to the workspace directory.
+    fn is_file_system(&self) -> bool;
+}
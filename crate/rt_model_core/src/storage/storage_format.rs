@@ -0,0 +1,235 @@
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    path::{Path, PathBuf},
+};
+
+use peace_resources::type_reg::untagged::{DataTypeWrapper, TypeMap, TypeReg};
+use serde::{
+    de::{DeserializeOwned, DeserializeSeed},
+    Serialize,
+};
+
+use crate::storage::StorageFormatError;
+
+/// Marker that MessagePack serialized items begin with.
+///
+/// `0xC1` is never used in MessagePack, and is not valid UTF-8, so it does
+/// not collide with YAML or JSON items.
+const MESSAGE_PACK_MAGIC: u8 = 0xC1;
+
+/// Format that params, states, plans, and history records are serialized in.
+///
+/// The format of a workspace is recorded in its `StorageFormatFile` when the
+/// workspace is first used, and items are written in that format. Items are
+/// named with the format's [`extension`], e.g. states are stored in
+/// `states_saved.json` in a JSON workspace.
+///
+/// When reading, the format is detected from the serialized bytes, and an item
+/// that is not found with the workspace's extension is read from a file with
+/// another format's extension. So a workspace may contain items in different
+/// formats, such as when the format is changed for an existing workspace.
+///
+/// YAML is always available. JSON and MessagePack are available with the
+/// `storage_format_json` and `storage_format_message_pack` features.
+///
+/// [`extension`]: Self::extension
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StorageFormat {
+    /// The YAML Ain't Markup Language™ ([YAML]) format.
+    ///
+    /// This is the default, as it is the easiest to read and edit by hand.
+    ///
+    /// [YAML]: https://yaml.org/
+    #[default]
+    Yaml,
+    /// The JavaScript Object Notation ([JSON]) format.
+    ///
+    /// [JSON]: https://www.json.org/
+    #[cfg(feature = "storage_format_json")]
+    Json,
+    /// The [MessagePack] binary format.
+    ///
+    /// This is the fastest to read and write, and is recommended for flows
+    /// with large states, such as thousands of file metadata entries.
+    ///
+    /// [MessagePack]: https://msgpack.org/
+    #[cfg(feature = "storage_format_message_pack")]
+    MessagePack,
+}
+
+impl StorageFormat {
+    /// File extensions of every format, including formats whose features are
+    /// not enabled, so that items in those formats are still found.
+    pub const EXTENSIONS: [&'static str; 3] = ["yaml", "json", "msgpack"];
+
+    /// Returns the file extension of items in this format, without the
+    /// leading `.`.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Yaml => Self::EXTENSIONS[0],
+            #[cfg(feature = "storage_format_json")]
+            Self::Json => Self::EXTENSIONS[1],
+            #[cfg(feature = "storage_format_message_pack")]
+            Self::MessagePack => Self::EXTENSIONS[2],
+        }
+    }
+
+    /// Returns the format with the given file extension, if its feature is
+    /// enabled.
+    ///
+    /// This is also used to read the format recorded in a
+    /// `StorageFormatFile`.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "yaml" => Some(Self::Yaml),
+            #[cfg(feature = "storage_format_json")]
+            "json" => Some(Self::Json),
+            #[cfg(feature = "storage_format_message_pack")]
+            "msgpack" => Some(Self::MessagePack),
+            _ => None,
+        }
+    }
+
+    /// Returns the paths that the item at the given path would be stored at
+    /// in other formats.
+    ///
+    /// This is empty if the path does not have a format's extension.
+    pub fn paths_other_formats(path: &Path) -> Vec<PathBuf> {
+        let Some(extension) = path.extension().and_then(|extension| extension.to_str()) else {
+            return Vec::new();
+        };
+        if !Self::EXTENSIONS.contains(&extension) {
+            return Vec::new();
+        }
+
+        Self::EXTENSIONS
+            .iter()
+            .filter(|extension_other| **extension_other != extension)
+            .map(|extension_other| path.with_extension(extension_other))
+            .collect()
+    }
+
+    /// Returns the format that the given serialized bytes are in, if its
+    /// feature is enabled.
+    ///
+    /// Bytes that begin with a `{` or `[` after leading whitespace are
+    /// detected as JSON. As JSON is a subset of YAML, these are detected as
+    /// YAML when the `storage_format_json` feature is not enabled, and YAML
+    /// items written in flow style are correctly read as JSON.
+    pub fn detect(bytes: &[u8]) -> Result<Self, StorageFormatError> {
+        if bytes.first() == Some(&MESSAGE_PACK_MAGIC) {
+            #[cfg(feature = "storage_format_message_pack")]
+            return Ok(Self::MessagePack);
+            #[cfg(not(feature = "storage_format_message_pack"))]
+            return Err(StorageFormatError::MessagePackNotEnabled);
+        }
+
+        #[cfg(feature = "storage_format_json")]
+        if let Some(b'{' | b'[') = bytes.iter().find(|byte| !byte.is_ascii_whitespace()) {
+            return Ok(Self::Json);
+        }
+
+        Ok(Self::Yaml)
+    }
+
+    /// Serializes the item in this format.
+    pub fn serialize<T>(self, t: &T) -> Result<Vec<u8>, StorageFormatError>
+    where
+        T: Serialize + ?Sized,
+    {
+        match self {
+            Self::Yaml => Ok(serde_yaml::to_string(t)?.into_bytes()),
+            #[cfg(feature = "storage_format_json")]
+            Self::Json => {
+                let mut serialized = serde_json::to_vec_pretty(t)?;
+                serialized.push(b'\n');
+                Ok(serialized)
+            }
+            #[cfg(feature = "storage_format_message_pack")]
+            Self::MessagePack => {
+                let mut serialized = vec![MESSAGE_PACK_MAGIC];
+                rmp_serde::encode::write_named(&mut serialized, t)?;
+                Ok(serialized)
+            }
+        }
+    }
+
+    /// Deserializes an item, detecting the format from the bytes.
+    pub fn deserialize<T>(bytes: &[u8]) -> Result<T, StorageFormatError>
+    where
+        T: DeserializeOwned,
+    {
+        Self::deserialize_seed(bytes, std::marker::PhantomData::<T>)
+    }
+
+    /// Deserializes a typemap, detecting the format from the bytes.
+    ///
+    /// # Parameters
+    ///
+    /// * `bytes`: The serialized typemap.
+    /// * `type_reg`: Type registry with the stateful deserialization mappings.
+    pub fn deserialize_typemap<K, BoxDT>(
+        bytes: &[u8],
+        type_reg: &TypeReg<K, BoxDT>,
+    ) -> Result<TypeMap<K, BoxDT>, StorageFormatError>
+    where
+        K: Debug + DeserializeOwned + Eq + Hash,
+        BoxDT: DataTypeWrapper + 'static,
+    {
+        Self::deserialize_seed(bytes, TypeMapSeed { type_reg })
+    }
+
+    /// Deserializes an item using a `DeserializeSeed`, detecting the format
+    /// from the bytes.
+    pub fn deserialize_seed<'de, S>(
+        bytes: &'de [u8],
+        seed: S,
+    ) -> Result<S::Value, StorageFormatError>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        match Self::detect(bytes)? {
+            Self::Yaml => {
+                let deserializer = serde_yaml::Deserializer::from_slice(bytes);
+                Ok(seed.deserialize(deserializer)?)
+            }
+            #[cfg(feature = "storage_format_json")]
+            Self::Json => {
+                let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+                let t = seed.deserialize(&mut deserializer)?;
+                deserializer.end()?;
+                Ok(t)
+            }
+            #[cfg(feature = "storage_format_message_pack")]
+            Self::MessagePack => {
+                let mut deserializer = rmp_serde::Deserializer::from_read_ref(&bytes[1..]);
+                Ok(seed.deserialize(&mut deserializer)?)
+            }
+        }
+    }
+}
+
+/// Deserializes a typemap using a type registry.
+struct TypeMapSeed<'r, K, BoxDT>
+where
+    K: Debug + Eq + Hash,
+{
+    /// Type registry with the stateful deserialization mappings.
+    type_reg: &'r TypeReg<K, BoxDT>,
+}
+
+impl<'de, 'r: 'de, K, BoxDT> DeserializeSeed<'de> for TypeMapSeed<'r, K, BoxDT>
+where
+    K: Debug + DeserializeOwned + Eq + Hash + 'de,
+    BoxDT: DataTypeWrapper + 'static,
+{
+    type Value = TypeMap<K, BoxDT>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        self.type_reg.deserialize_map(deserializer)
+    }
+}
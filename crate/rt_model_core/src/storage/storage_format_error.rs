@@ -0,0 +1,28 @@
+/// Failed to serialize or deserialize an item in a [`StorageFormat`].
+///
+/// [`StorageFormat`]: crate::storage::StorageFormat
+#[derive(Debug, thiserror::Error)]
+pub enum StorageFormatError {
+    /// Failed to serialize or deserialize YAML.
+    #[error(transparent)]
+    Yaml(#[from] serde_yaml::Error),
+    /// Failed to serialize or deserialize JSON.
+    #[cfg(feature = "storage_format_json")]
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// Failed to serialize MessagePack.
+    #[cfg(feature = "storage_format_message_pack")]
+    #[error(transparent)]
+    MessagePackEncode(#[from] rmp_serde::encode::Error),
+    /// Failed to deserialize MessagePack.
+    #[cfg(feature = "storage_format_message_pack")]
+    #[error(transparent)]
+    MessagePackDecode(#[from] rmp_serde::decode::Error),
+    /// Item is in MessagePack, but the `storage_format_message_pack` feature
+    /// is not enabled.
+    #[cfg(not(feature = "storage_format_message_pack"))]
+    #[error(
+        "Item is in MessagePack, but the `storage_format_message_pack` feature is not enabled."
+    )]
+    MessagePackNotEnabled,
+}
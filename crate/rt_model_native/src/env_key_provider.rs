@@ -0,0 +1,53 @@
+use peace_rt_model_core::{
+    storage::{EncryptionKey, KeyProvider},
+    Error, NativeError,
+};
+
+/// Reads the encryption key from an environment variable.
+///
+/// The environment variable's value is the standard base64 encoded 32 byte
+/// key, such as the value returned by [`EncryptionKey::to_base64`].
+///
+/// The variable is read each time the key is requested, so it must remain set
+/// for the lifetime of the command.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnvKeyProvider {
+    /// Name of the environment variable that holds the key.
+    env_var_name: String,
+}
+
+impl EnvKeyProvider {
+    /// Returns a new `EnvKeyProvider`.
+    ///
+    /// # Parameters
+    ///
+    /// * `env_var_name`: Name of the environment variable that holds the key,
+    ///   e.g. `"ENVMAN_KEY"`.
+    pub fn new(env_var_name: impl Into<String>) -> Self {
+        Self {
+            env_var_name: env_var_name.into(),
+        }
+    }
+
+    /// Returns the name of the environment variable that holds the key.
+    pub fn env_var_name(&self) -> &str {
+        &self.env_var_name
+    }
+}
+
+impl KeyProvider for EnvKeyProvider {
+    fn key(&self) -> Result<EncryptionKey, Error> {
+        let env_var_name = &self.env_var_name;
+        let key_base64 = std::env::var(env_var_name).map_err(|error| {
+            Error::Native(NativeError::EncryptionKeyEnvVarRead {
+                env_var_name: env_var_name.clone(),
+                error,
+            })
+        })?;
+
+        EncryptionKey::from_base64(
+            &format!("environment variable `{env_var_name}`"),
+            &key_base64,
+        )
+    }
+}
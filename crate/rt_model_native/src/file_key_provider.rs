@@ -0,0 +1,50 @@
+use std::path::{Path, PathBuf};
+
+use peace_rt_model_core::{
+    storage::{EncryptionKey, KeyProvider},
+    Error, NativeError,
+};
+
+/// Reads the encryption key from a key file.
+///
+/// The file contains the standard base64 encoded 32 byte key, such as the
+/// value returned by [`EncryptionKey::to_base64`]. Leading and trailing
+/// whitespace is ignored.
+///
+/// The key file should be stored outside of the workspace, so that it is not
+/// committed alongside the encrypted data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileKeyProvider {
+    /// Path to the key file.
+    path: PathBuf,
+}
+
+impl FileKeyProvider {
+    /// Returns a new `FileKeyProvider`.
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: Path to the key file.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Returns the path to the key file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl KeyProvider for FileKeyProvider {
+    fn key(&self) -> Result<EncryptionKey, Error> {
+        let path = &self.path;
+        let key_base64 = std::fs::read_to_string(path).map_err(|error| {
+            Error::Native(NativeError::EncryptionKeyFileRead {
+                path: path.clone(),
+                error,
+            })
+        })?;
+
+        EncryptionKey::from_base64(&format!("key file `{}`", path.display()), &key_base64)
+    }
+}
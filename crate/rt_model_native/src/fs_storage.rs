@@ -0,0 +1,155 @@
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use peace_rt_model_core::{async_trait, storage::StorageBackend, Error, NativeError};
+
+/// Counter to give each [`FsStorage::write_new`] and
+/// [`FsStorage::remove_if_unchanged`] call within this process a unique
+/// temporary file.
+static TMP_FILE_COUNT: AtomicU32 = AtomicU32::new(0);
+
+/// Stores serialized data on the file system.
+///
+/// This is the default storage backend for native applications.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FsStorage;
+
+impl FsStorage {
+    /// Returns a temporary path next to the given path, that is unique to this
+    /// call.
+    fn path_tmp(path: &Path) -> PathBuf {
+        let tmp_file_count = TMP_FILE_COUNT.fetch_add(1, Ordering::Relaxed);
+        let mut path_tmp = path.as_os_str().to_os_string();
+        path_tmp.push(format!(".{}.{tmp_file_count}.tmp", std::process::id()));
+        PathBuf::from(path_tmp)
+    }
+}
+
+#[async_trait]
+impl StorageBackend for FsStorage {
+    async fn contains(&self, path: &Path) -> Result<bool, Error> {
+        Ok(path.exists())
+    }
+
+    async fn read(&self, path: &Path) -> Result<Option<Vec<u8>>, Error> {
+        match tokio::fs::read(path).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => {
+                let path = path.to_path_buf();
+                Err(Error::Native(NativeError::FileRead { path, error }))
+            }
+        }
+    }
+
+    async fn write(&self, path: &Path, bytes: &[u8]) -> Result<(), Error> {
+        tokio::fs::write(path, bytes).await.map_err(|error| {
+            let path = path.to_path_buf();
+            Error::Native(NativeError::FileWrite { path, error })
+        })
+    }
+
+    async fn write_new(&self, path: &Path, bytes: &[u8]) -> Result<bool, Error> {
+        let map_err = |error| {
+            let path = path.to_path_buf();
+            Error::Native(NativeError::FileWrite { path, error })
+        };
+
+        // The bytes are written to a temporary file, which is then linked to the path.
+        // Linking fails if the path exists, so other processes never read a partially
+        // written file.
+        let path_tmp = Self::path_tmp(path);
+        tokio::fs::write(&path_tmp, bytes).await.map_err(map_err)?;
+
+        let link_result = tokio::fs::hard_link(&path_tmp, path).await;
+        tokio::fs::remove_file(&path_tmp).await.map_err(map_err)?;
+
+        match link_result {
+            Ok(()) => Ok(true),
+            Err(error) if error.kind() == ErrorKind::AlreadyExists => Ok(false),
+            Err(error) => Err(map_err(error)),
+        }
+    }
+
+    async fn remove(&self, path: &Path) -> Result<(), Error> {
+        match tokio::fs::remove_file(path).await {
+            Ok(()) => Ok(()),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(()),
+            Err(error) => {
+                let path = path.to_path_buf();
+                Err(Error::Native(NativeError::FileRemove { path, error }))
+            }
+        }
+    }
+
+    async fn remove_if_unchanged(&self, path: &Path, bytes: &[u8]) -> Result<bool, Error> {
+        let map_err = |error| {
+            let path = path.to_path_buf();
+            Error::Native(NativeError::FileRemove { path, error })
+        };
+
+        // The file is moved aside before it is compared, so that another process
+        // cannot replace it between the comparison and the removal.
+        let path_tmp = Self::path_tmp(path);
+        match tokio::fs::rename(path, &path_tmp).await {
+            Ok(()) => {}
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(false),
+            Err(error) => return Err(map_err(error)),
+        }
+
+        let unchanged = match tokio::fs::read(&path_tmp).await {
+            Ok(bytes_current) => bytes_current == bytes,
+            Err(_) => false,
+        };
+        if !unchanged {
+            // The file is moved back by linking it, so that a file written to the path
+            // in the meantime is not replaced.
+            let link_result = tokio::fs::hard_link(&path_tmp, path).await;
+            tokio::fs::remove_file(&path_tmp).await.map_err(map_err)?;
+
+            return match link_result {
+                Ok(()) => Ok(false),
+                Err(error) if error.kind() == ErrorKind::AlreadyExists => Ok(false),
+                Err(error) => Err(map_err(error)),
+            };
+        }
+
+        tokio::fs::remove_file(&path_tmp).await.map_err(map_err)?;
+        Ok(true)
+    }
+
+    async fn dir_create(&self, dir: &Path) -> Result<(), Error> {
+        tokio::fs::create_dir_all(dir).await.map_err(|error| {
+            let path = dir.to_path_buf();
+            Error::Native(NativeError::WorkspaceDirCreate { path, error })
+        })
+    }
+
+    async fn dir_entries(&self, dir: &Path) -> Result<Vec<PathBuf>, Error> {
+        let mut read_dir = match tokio::fs::read_dir(dir).await {
+            Ok(read_dir) => read_dir,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => {
+                let path = dir.to_path_buf();
+                return Err(Error::Native(NativeError::StorageDirRead { path, error }));
+            }
+        };
+
+        let mut dir_entries = Vec::new();
+        while let Some(entry) = read_dir.next_entry().await.map_err(|error| {
+            let path = dir.to_path_buf();
+            Error::Native(NativeError::StorageDirEntryRead { path, error })
+        })? {
+            dir_entries.push(entry.path());
+        }
+
+        Ok(dir_entries)
+    }
+
+    fn is_file_system(&self) -> bool {
+        true
+    }
+}
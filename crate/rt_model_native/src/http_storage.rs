@@ -0,0 +1,357 @@
+use std::{
+    collections::HashMap,
+    path::{Component, Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use peace_rt_model_core::{async_trait, storage::StorageBackend, Error, NativeError};
+use reqwest::{
+    header::{ETAG, IF_MATCH, IF_NONE_MATCH},
+    Client, Method, RequestBuilder, Response, StatusCode,
+};
+use url::Url;
+
+/// Stores serialized data in an HTTP object store.
+///
+/// Each item is an object whose URL is the item's path relative to the root
+/// directory, appended to the base URL. For example, with the base URL
+/// `https://example.com/peace/` and the root directory `/path/to/workspace`,
+/// the item at `/path/to/workspace/.peace/envman/dev/deploy/states_saved.yaml`
+/// is stored at
+/// `https://example.com/peace/.peace/envman/dev/deploy/states_saved.yaml`.
+///
+/// # Object API
+///
+/// The server is expected to support:
+///
+/// * `GET`: Responds with the object and its `ETag`, or `404` if it does not
+///   exist.
+/// * `HEAD`: Same as `GET`, without the body.
+/// * `PUT`: Stores the object and responds with its new `ETag`, honouring the
+///   `If-Match` and `If-None-Match: *` preconditions with `412`.
+/// * `DELETE`: Removes the object, honouring the same preconditions.
+/// * `GET` on a URL that ends with `/`: Responds with the names of objects
+///   directly within that directory, one per line, or `404` if there are none.
+///
+/// # Optimistic Locking
+///
+/// The `ETag` of each object that is read or written is remembered. When the
+/// object is written or removed, the request is conditional on the object not
+/// having changed since then. If another writer updated the object first,
+/// [`Error::StorageConflict`] is returned.
+///
+/// Objects that have not been read are written and removed with
+/// `If-None-Match: *`, so that an object created by another writer is not
+/// replaced. To replace an existing object, read it first.
+///
+/// Weak `ETag`s cannot be used in `If-Match` preconditions, so objects that
+/// are served with a weak `ETag` or without an `ETag` are replaced without a
+/// precondition once they have been read.
+///
+/// Item paths must be within the root directory, and must not contain `.` or
+/// `..` components.
+///
+/// [`Error::StorageConflict`]: peace_rt_model_core::Error::StorageConflict
+#[derive(Clone, Debug)]
+pub struct HttpStorage {
+    /// HTTP client to send requests with.
+    client: Client,
+    /// URL that item paths are appended to.
+    base_url: Url,
+    /// Directory that item paths are relative to.
+    root_dir: PathBuf,
+    /// Version of each object when it was last read or written.
+    versions: Arc<Mutex<HashMap<PathBuf, ObjectVersion>>>,
+}
+
+/// Version of an object when it was last read or written.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum ObjectVersion {
+    /// The object did not exist.
+    Absent,
+    /// The object existed with the given strong `ETag`.
+    ETag(String),
+    /// The object existed without a strong `ETag`, so changes to it cannot be
+    /// detected.
+    Unversioned,
+}
+
+impl HttpStorage {
+    /// Returns a new `HttpStorage`.
+    ///
+    /// # Parameters
+    ///
+    /// * `base_url`: URL that item paths are appended to.
+    /// * `root_dir`: Directory that item paths are relative to, usually the
+    ///   workspace directory.
+    pub fn new(base_url: Url, root_dir: PathBuf) -> Result<Self, Error> {
+        Self::new_with_client(Client::new(), base_url, root_dir)
+    }
+
+    /// Returns a new `HttpStorage` that sends requests with the given client.
+    ///
+    /// This allows the client to be configured, e.g. with authentication
+    /// headers or timeouts.
+    pub fn new_with_client(
+        client: Client,
+        base_url: Url,
+        root_dir: PathBuf,
+    ) -> Result<Self, Error> {
+        if base_url.cannot_be_a_base() {
+            return Err(Error::Native(NativeError::HttpStorageBaseUrlInvalid {
+                base_url: base_url.to_string(),
+            }));
+        }
+
+        Ok(Self {
+            client,
+            base_url,
+            root_dir,
+            versions: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    /// Returns the URL of the object for the given path.
+    ///
+    /// If `is_dir` is true, the URL ends with `/`.
+    ///
+    /// Returns an error if the path is outside the root directory, or has a
+    /// component that is not a plain name, such as `..`, so that items cannot
+    /// be read or written outside the base URL.
+    fn url(&self, path: &Path, is_dir: bool) -> Result<Url, Error> {
+        let relative_path = path.strip_prefix(&self.root_dir).unwrap_or(path);
+        let segments = relative_path
+            .components()
+            .map(|component| match component {
+                Component::Normal(segment) => Ok(segment.to_string_lossy()),
+                Component::Prefix(_)
+                | Component::RootDir
+                | Component::CurDir
+                | Component::ParentDir => {
+                    Err(Error::Native(NativeError::HttpStorageItemPathInvalid {
+                        path: path.to_path_buf(),
+                    }))
+                }
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let mut url = self.base_url.clone();
+        if let Ok(mut path_segments) = url.path_segments_mut() {
+            path_segments.pop_if_empty().extend(segments);
+            if is_dir {
+                path_segments.push("");
+            }
+        }
+
+        Ok(url)
+    }
+
+    /// Sends the request, mapping client errors to [`Error`]s.
+    async fn send(
+        request: RequestBuilder,
+        method: &'static str,
+        url: &Url,
+    ) -> Result<Response, Error> {
+        request.send().await.map_err(|error| {
+            Error::Native(NativeError::HttpStorageRequest {
+                method,
+                url: url.to_string(),
+                error: Box::new(error),
+            })
+        })
+    }
+
+    /// Returns the error for a response with an unexpected status.
+    fn status_error(method: &'static str, url: &Url, status: StatusCode) -> Error {
+        Error::Native(NativeError::HttpStorageResponseStatus {
+            method,
+            url: url.to_string(),
+            status: status.as_u16(),
+        })
+    }
+
+    /// Adds the precondition for writing over or removing the object's last
+    /// known version to the request.
+    ///
+    /// Objects with no known version must not exist.
+    fn precondition(&self, request: RequestBuilder, path: &Path) -> RequestBuilder {
+        let versions = self.versions.lock().unwrap_or_else(PoisonError::into_inner);
+        match versions.get(path) {
+            Some(ObjectVersion::Absent) | None => request.header(IF_NONE_MATCH, "*"),
+            Some(ObjectVersion::ETag(etag)) => request.header(IF_MATCH, etag.as_str()),
+            Some(ObjectVersion::Unversioned) => request,
+        }
+    }
+
+    /// Records the version of the object from the response.
+    ///
+    /// Weak `ETag`s are not recorded, as `If-Match` requires strong
+    /// comparison, so a weak `ETag` would never match.
+    fn version_record(&self, path: &Path, response: &Response) {
+        let object_version = response
+            .headers()
+            .get(ETAG)
+            .and_then(|etag| etag.to_str().ok())
+            .filter(|etag| !etag.starts_with("W/"))
+            .map(|etag| ObjectVersion::ETag(etag.to_string()))
+            .unwrap_or(ObjectVersion::Unversioned);
+
+        let mut versions = self.versions.lock().unwrap_or_else(PoisonError::into_inner);
+        versions.insert(path.to_path_buf(), object_version);
+    }
+
+    /// Records that the object does not exist.
+    fn version_record_absent(&self, path: &Path) {
+        let mut versions = self.versions.lock().unwrap_or_else(PoisonError::into_inner);
+        versions.insert(path.to_path_buf(), ObjectVersion::Absent);
+    }
+}
+
+#[async_trait]
+impl StorageBackend for HttpStorage {
+    async fn contains(&self, path: &Path) -> Result<bool, Error> {
+        let url = self.url(path, false)?;
+        let request = self.client.request(Method::HEAD, url.clone());
+        let response = Self::send(request, "HEAD", &url).await?;
+
+        // The `ETag` is not recorded, as the object is not read, so it should not
+        // be replaced without being read.
+        match response.status() {
+            status if status.is_success() => Ok(true),
+            StatusCode::NOT_FOUND => Ok(false),
+            status => Err(Self::status_error("HEAD", &url, status)),
+        }
+    }
+
+    async fn read(&self, path: &Path) -> Result<Option<Vec<u8>>, Error> {
+        let url = self.url(path, false)?;
+        let request = self.client.get(url.clone());
+        let response = Self::send(request, "GET", &url).await?;
+
+        match response.status() {
+            status if status.is_success() => {
+                self.version_record(path, &response);
+                let bytes = response.bytes().await.map_err(|error| {
+                    Error::Native(NativeError::HttpStorageRequest {
+                        method: "GET",
+                        url: url.to_string(),
+                        error: Box::new(error),
+                    })
+                })?;
+
+                Ok(Some(bytes.to_vec()))
+            }
+            StatusCode::NOT_FOUND => {
+                self.version_record_absent(path);
+                Ok(None)
+            }
+            status => Err(Self::status_error("GET", &url, status)),
+        }
+    }
+
+    async fn write(&self, path: &Path, bytes: &[u8]) -> Result<(), Error> {
+        let url = self.url(path, false)?;
+        let request = self.client.put(url.clone()).body(bytes.to_vec());
+        let request = self.precondition(request, path);
+        let response = Self::send(request, "PUT", &url).await?;
+
+        match response.status() {
+            status if status.is_success() => {
+                self.version_record(path, &response);
+                Ok(())
+            }
+            StatusCode::PRECONDITION_FAILED => Err(Error::StorageConflict {
+                path: path.to_path_buf(),
+            }),
+            status => Err(Self::status_error("PUT", &url, status)),
+        }
+    }
+
+    async fn write_new(&self, path: &Path, bytes: &[u8]) -> Result<bool, Error> {
+        let url = self.url(path, false)?;
+        let request = self
+            .client
+            .put(url.clone())
+            .header(IF_NONE_MATCH, "*")
+            .body(bytes.to_vec());
+        let response = Self::send(request, "PUT", &url).await?;
+
+        match response.status() {
+            status if status.is_success() => {
+                self.version_record(path, &response);
+                Ok(true)
+            }
+            StatusCode::PRECONDITION_FAILED => Ok(false),
+            status => Err(Self::status_error("PUT", &url, status)),
+        }
+    }
+
+    async fn remove(&self, path: &Path) -> Result<(), Error> {
+        let url = self.url(path, false)?;
+        let request = self.client.delete(url.clone());
+        let request = self.precondition(request, path);
+        let response = Self::send(request, "DELETE", &url).await?;
+
+        match response.status() {
+            status if status.is_success() || status == StatusCode::NOT_FOUND => {
+                self.version_record_absent(path);
+                Ok(())
+            }
+            StatusCode::PRECONDITION_FAILED => Err(Error::StorageConflict {
+                path: path.to_path_buf(),
+            }),
+            status => Err(Self::status_error("DELETE", &url, status)),
+        }
+    }
+
+    async fn remove_if_unchanged(&self, path: &Path, bytes: &[u8]) -> Result<bool, Error> {
+        // Reading the object records its `ETag`, so the removal is conditional on
+        // the object not changing after it is compared.
+        if self.read(path).await?.as_deref() != Some(bytes) {
+            return Ok(false);
+        }
+
+        match self.remove(path).await {
+            Ok(()) => Ok(true),
+            Err(Error::StorageConflict { .. }) => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn dir_create(&self, _dir: &Path) -> Result<(), Error> {
+        // Directories are implied by the URLs of stored objects.
+        Ok(())
+    }
+
+    async fn dir_entries(&self, dir: &Path) -> Result<Vec<PathBuf>, Error> {
+        let url = self.url(dir, true)?;
+        let request = self.client.get(url.clone());
+        let response = Self::send(request, "GET", &url).await?;
+
+        match response.status() {
+            status if status.is_success() => {
+                let names = response.text().await.map_err(|error| {
+                    Error::Native(NativeError::HttpStorageRequest {
+                        method: "GET",
+                        url: url.to_string(),
+                        error: Box::new(error),
+                    })
+                })?;
+                let dir_entries = names
+                    .lines()
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(|name| dir.join(name))
+                    .collect::<Vec<PathBuf>>();
+
+                Ok(dir_entries)
+            }
+            StatusCode::NOT_FOUND => Ok(Vec::new()),
+            status => Err(Self::status_error("GET", &url, status)),
+        }
+    }
+
+    fn is_file_system(&self) -> bool {
+        false
+    }
+}
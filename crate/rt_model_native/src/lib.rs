@@ -7,14 +7,39 @@
 pub use tokio_util::io::SyncIoBridge;
 
 pub use crate::{
-    storage::Storage, workspace::Workspace, workspace_dirs_builder::WorkspaceDirsBuilder,
-    workspace_initializer::WorkspaceInitializer, workspace_spec::WorkspaceSpec,
+    fs_storage::FsStorage,
+    profile_lock::{ProfileLock, ProfileLockInfo},
+    storage::Storage,
+    workspace::Workspace,
+    workspace_dirs_builder::WorkspaceDirsBuilder,
+    workspace_initializer::WorkspaceInitializer,
+    workspace_spec::WorkspaceSpec,
 };
 
 pub mod output;
+pub mod time;
 pub mod workspace;
 
+mod fs_storage;
+mod profile_lock;
 mod storage;
 mod workspace_dirs_builder;
 mod workspace_initializer;
 mod workspace_spec;
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "storage_encryption")] {
+        pub use crate::{env_key_provider::EnvKeyProvider, file_key_provider::FileKeyProvider};
+
+        mod env_key_provider;
+        mod file_key_provider;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "storage_http")] {
+        pub use crate::http_storage::HttpStorage;
+
+        mod http_storage;
+    }
+}
@@ -658,7 +658,10 @@ where
     {
         match self.outcome_format {
             OutputFormat::Text => self.output_presentable(presentable).await,
-            OutputFormat::Yaml => self.output_yaml(&presentable, Error::StatesSerialize).await,
+            OutputFormat::Yaml => {
+                self.output_yaml(&presentable, |error| Error::StatesSerialize(error.into()))
+                    .await
+            }
             #[cfg(feature = "output_json")]
             OutputFormat::Json => {
                 self.output_json(&presentable, Error::StatesSerializeJson)
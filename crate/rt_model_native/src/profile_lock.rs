@@ -0,0 +1,228 @@
+use std::time::Duration;
+
+use peace_core::Profile;
+use peace_resources::paths::ProfileLockFile;
+use peace_rt_model_core::{storage::SerializedStorage, Error, NativeError, ProfileLockMode};
+use serde::{Deserialize, Serialize};
+use sysinfo::{Pid, ProcessRefreshKind, System};
+
+use crate::Storage;
+
+/// Interval between attempts to acquire a lock in [`ProfileLockMode::Wait`].
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Advisory lock on a profile, held while a command mutates its states.
+///
+/// The lock is a [`ProfileLockFile`] that records the process ID, process start
+/// time, and host name of the command holding the lock. It is created through the [`Storage`]'s
+/// backend, so commands that share a storage backend, such as an HTTP object
+/// store, also share locks.
+///
+/// The lock is released when this is dropped. For backends that are not on the
+/// file system, removing the lock file on drop is done in the background, so
+/// use [`ProfileLock::release`] to wait for the lock to be released.
+///
+/// # Stale Locks
+///
+/// A lock is stale when it was taken by a process on this host that is no
+/// longer running. The process start time is compared as well as its ID, so a
+/// lock is still stale if its process ID has been reused by another process.
+///
+/// Stale locks are removed when acquiring the lock, only if the lock file is
+/// unchanged since it was read, so that a lock acquired by another command in
+/// the meantime is not removed.
+///
+/// Locks taken on other hosts are never treated as stale, so the lock file has
+/// to be deleted manually if the process that took it did not release it.
+#[derive(Debug)]
+pub struct ProfileLock {
+    /// Storage that the lock file is written to.
+    storage: Storage,
+    /// Path to the lock file.
+    profile_lock_file: ProfileLockFile,
+    /// Whether the lock file has been removed.
+    released: bool,
+}
+
+impl ProfileLock {
+    /// Acquires the lock for the given profile.
+    ///
+    /// # Parameters
+    ///
+    /// * `storage`: Storage to write the lock file to.
+    /// * `profile`: Profile to lock.
+    /// * `profile_lock_file`: Path to the profile's lock file.
+    /// * `profile_lock_mode`: Whether to wait for the lock if it is held by
+    ///   another command.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ProfileLocked`] if the lock is held by another command,
+    /// and is not released within the wait timeout.
+    pub async fn acquire(
+        storage: &Storage,
+        profile: &Profile,
+        profile_lock_file: ProfileLockFile,
+        profile_lock_mode: ProfileLockMode,
+    ) -> Result<Self, Error> {
+        let profile_lock_info_current = ProfileLockInfo::current();
+        let profile_lock_info_bytes = serde_yaml::to_string(&profile_lock_info_current)
+            .map_err(NativeError::ProfileLockInfoSerialize)
+            .map_err(Error::Native)?;
+        let wait_start = tokio::time::Instant::now();
+
+        loop {
+            if storage
+                .backend()
+                .write_new(&profile_lock_file, profile_lock_info_bytes.as_bytes())
+                .await?
+            {
+                return Ok(Self {
+                    storage: storage.clone(),
+                    profile_lock_file,
+                    released: false,
+                });
+            }
+
+            let (profile_lock_info, profile_lock_bytes) =
+                match Self::lock_info_read(storage, &profile_lock_file).await? {
+                    Some(profile_lock_info_and_bytes) => profile_lock_info_and_bytes,
+                    // Lock was released between the attempt and the read.
+                    None => continue,
+                };
+
+            if profile_lock_info.is_stale(&profile_lock_info_current.hostname) {
+                storage
+                    .backend()
+                    .remove_if_unchanged(&profile_lock_file, &profile_lock_bytes)
+                    .await?;
+                continue;
+            }
+
+            let wait_elapsed = match profile_lock_mode {
+                ProfileLockMode::Fail => true,
+                ProfileLockMode::Wait { timeout } => {
+                    matches!(timeout, Some(timeout) if wait_start.elapsed() >= timeout)
+                }
+            };
+            if wait_elapsed {
+                let ProfileLockInfo {
+                    pid,
+                    start_time: _,
+                    hostname,
+                } = profile_lock_info;
+                return Err(Error::ProfileLocked {
+                    profile: profile.clone(),
+                    profile_lock_file: profile_lock_file.to_path_buf(),
+                    pid,
+                    hostname,
+                });
+            }
+
+            tokio::time::sleep(LOCK_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Returns the path to the lock file.
+    pub fn profile_lock_file(&self) -> &ProfileLockFile {
+        &self.profile_lock_file
+    }
+
+    /// Releases the lock, waiting for the lock file to be removed.
+    pub async fn release(mut self) -> Result<(), Error> {
+        self.released = true;
+        self.storage.backend().remove(&self.profile_lock_file).await
+    }
+
+    /// Returns the lock information in the lock file, and the file's bytes, if
+    /// it exists.
+    async fn lock_info_read(
+        storage: &Storage,
+        profile_lock_file: &ProfileLockFile,
+    ) -> Result<Option<(ProfileLockInfo, Vec<u8>)>, Error> {
+        let Some(profile_lock_bytes) = storage.backend().read(profile_lock_file).await? else {
+            return Ok(None);
+        };
+
+        serde_yaml::from_slice(&profile_lock_bytes)
+            .map(|profile_lock_info| Some((profile_lock_info, profile_lock_bytes)))
+            .map_err(|error| {
+                Error::Native(NativeError::ProfileLockFileDeserialize {
+                    profile_lock_file: profile_lock_file.to_path_buf(),
+                    error,
+                })
+            })
+    }
+}
+
+impl Drop for ProfileLock {
+    fn drop(&mut self) {
+        if self.released {
+            return;
+        }
+
+        // Failing to remove the file leaves a stale lock, which is removed by
+        // the next command on this host.
+        if self.storage.backend().is_file_system() {
+            let _ = std::fs::remove_file(&self.profile_lock_file);
+        } else if let Ok(runtime_handle) = tokio::runtime::Handle::try_current() {
+            let storage = self.storage.clone();
+            let profile_lock_file = self.profile_lock_file.clone();
+            runtime_handle.spawn(async move {
+                let _ = storage.backend().remove(&profile_lock_file).await;
+            });
+        }
+    }
+}
+
+/// Information about the command that holds a [`ProfileLock`].
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ProfileLockInfo {
+    /// ID of the process that holds the lock.
+    pub pid: u32,
+    /// Time that the process started, in seconds since the Unix epoch.
+    ///
+    /// This distinguishes the process from a later process that is given the
+    /// same ID. This is `0` on platforms where processes cannot be listed.
+    pub start_time: u64,
+    /// Name of the host that the process runs on.
+    pub hostname: String,
+}
+
+impl ProfileLockInfo {
+    /// Returns the lock information for the current process.
+    pub fn current() -> Self {
+        let pid = std::process::id();
+        Self {
+            pid,
+            start_time: process_start_time(pid).unwrap_or(0),
+            hostname: whoami::fallible::hostname().unwrap_or_else(|_| String::from("localhost")),
+        }
+    }
+
+    /// Returns whether the process that took the lock is known to no longer be
+    /// running.
+    fn is_stale(&self, hostname_current: &str) -> bool {
+        if self.hostname != hostname_current || !sysinfo::IS_SUPPORTED_SYSTEM {
+            return false;
+        }
+
+        // A process with the same ID that started at a different time is a different
+        // process.
+        process_start_time(self.pid) != Some(self.start_time)
+    }
+}
+
+/// Returns the start time of the process with the given ID, if it is running.
+///
+/// This returns `None` on platforms where processes cannot be listed.
+fn process_start_time(pid: u32) -> Option<u64> {
+    if !sysinfo::IS_SUPPORTED_SYSTEM {
+        return None;
+    }
+
+    let pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_process_specifics(pid, ProcessRefreshKind::new());
+    system.process(pid).map(|process| process.start_time())
+}
@@ -1,148 +1,74 @@
-use std::{fmt::Debug, hash::Hash, io::Write, path::Path, sync::Mutex};
+use std::{
+    io::Write,
+    path::Path,
+    sync::{Arc, Mutex},
+};
 
-use peace_resources::type_reg::untagged::{DataTypeWrapper, TypeMap, TypeReg};
-use peace_rt_model_core::{Error, NativeError};
-use serde::{de::DeserializeOwned, Serialize};
+use peace_rt_model_core::{
+    storage::{SerializedStorage, StorageBackend, StorageFormat},
+    Error, NativeError,
+};
 use tokio::{
     fs::File,
     io::{BufReader, BufWriter},
 };
 use tokio_util::io::SyncIoBridge;
 
-/// Wrapper around file system operations.
+use crate::FsStorage;
+
+/// Reads and writes serialized data through a [`StorageBackend`].
+///
+/// Serialized items are read and written through the [`SerializedStorage`]
+/// methods.
+///
+/// By default, data is stored on the file system. Use [`Storage::new`] with
+/// an [`InMemoryStorage`] to keep data in memory instead, and pass it to
+/// [`Workspace::with_storage`].
+///
+/// Items are written in the [`StorageFormat`] recorded in the workspace, which
+/// is YAML for new workspaces unless [`Storage::with_format`] is used. Items
+/// in any format are read, as the format is detected from each item.
+///
+/// [`InMemoryStorage`]: peace_rt_model_core::storage::InMemoryStorage
+/// [`Workspace::with_storage`]: crate::Workspace::with_storage
 #[derive(Clone, Debug)]
-pub struct Storage;
+pub struct Storage {
+    /// Backend that stores the serialized data.
+    backend: Arc<dyn StorageBackend>,
+    /// Format that items are written in.
+    format: StorageFormat,
+}
 
 impl Storage {
-    /// Reads a serializable item from the given path.
-    ///
-    /// # Parameters
-    ///
-    /// * `thread_name`: Name of the thread to use to do the read operation.
-    /// * `file_path`: Path to the file to read the serialized item.
-    /// * `f_map_err`: Maps the deserialization error (if any) to an [`Error`].
-    pub async fn serialized_read<T, F>(
-        &self,
-        thread_name: String,
-        file_path: &Path,
-        f_map_err: F,
-    ) -> Result<T, Error>
+    /// Returns a new `Storage` that stores data in the given backend.
+    pub fn new<B>(backend: B) -> Self
     where
-        T: Serialize + DeserializeOwned + Send + Sync,
-        F: FnOnce(serde_yaml::Error) -> Error + Send,
+        B: StorageBackend,
     {
-        if file_path.exists() {
-            let t = self
-                .read_with_sync_api(thread_name, file_path, |file| {
-                    serde_yaml::from_reader::<_, T>(file).map_err(f_map_err)
-                })
-                .await?;
-
-            Ok(t)
-        } else {
-            Err(Error::ItemNotExists {
-                path: file_path.to_path_buf(),
-            })
+        Self {
+            backend: Arc::new(backend),
+            format: StorageFormat::default(),
         }
     }
 
-    /// Reads a serializable item from the given path if the file exists.
+    /// Sets the format that items are written in.
     ///
-    /// # Parameters
-    ///
-    /// * `thread_name`: Name of the thread to use to do the read operation.
-    /// * `file_path`: Path to the file to read the serialized item.
-    /// * `f_map_err`: Maps the deserialization error (if any) to an [`Error`].
-    pub async fn serialized_read_opt<T, F>(
-        &self,
-        thread_name: String,
-        file_path: &Path,
-        f_map_err: F,
-    ) -> Result<Option<T>, Error>
-    where
-        T: DeserializeOwned + Send + Sync,
-        F: FnOnce(serde_yaml::Error) -> Error + Send,
-    {
-        if file_path.exists() {
-            let t = self
-                .read_with_sync_api(thread_name, file_path, |file| {
-                    serde_yaml::from_reader::<_, T>(file).map_err(f_map_err)
-                })
-                .await?;
-
-            Ok(Some(t))
-        } else {
-            Ok(None)
-        }
-    }
-
-    /// Deserializes a typemap from the given path if the file exists.
-    ///
-    /// # Parameters
-    ///
-    /// * `thread_name`: Name of the thread to use to do the read operation.
-    /// * `type_reg`: Type registry with the stateful deserialization mappings.
-    /// * `file_path`: Path to the file to read the serialized item.
-    /// * `f_map_err`: Maps the deserialization error (if any) to an [`Error`].
-    pub async fn serialized_typemap_read_opt<T, K, BoxDT, F>(
-        &self,
-        thread_name: String,
-        type_reg: &TypeReg<K, BoxDT>,
-        file_path: &Path,
-        f_map_err: F,
-    ) -> Result<Option<T>, Error>
-    where
-        T: From<TypeMap<K, BoxDT>> + Send + Sync,
-        K: Debug + DeserializeOwned + Eq + Hash + Sync,
-        BoxDT: DataTypeWrapper + 'static,
-        F: FnOnce(serde_yaml::Error) -> Error + Send,
-    {
-        if file_path.exists() {
-            let t = self
-                .read_with_sync_api(thread_name, file_path, |file| {
-                    let deserializer = serde_yaml::Deserializer::from_reader(file);
-                    let type_map = type_reg.deserialize_map(deserializer).map_err(f_map_err)?;
-
-                    Result::<_, Error>::Ok(T::from(type_map))
-                })
-                .await?;
-
-            Ok(Some(t))
-        } else {
-            Ok(None)
-        }
-    }
-
-    /// Writes a serializable item to the given path.
-    ///
-    /// # Parameters
-    ///
-    /// * `thread_name`: Name of the thread to use to do the write operation.
-    /// * `file_path`: Path to the file to store the serialized item.
-    /// * `t`: Item to serialize.
-    /// * `f_map_err`: Maps the serialization error (if any) to an [`Error`].
-    pub async fn serialized_write<T, F>(
-        &self,
-        thread_name: String,
-        file_path: &Path,
-        t: &T,
-        f_map_err: F,
-    ) -> Result<(), Error>
-    where
-        T: Serialize + Send + Sync,
-        F: FnOnce(serde_yaml::Error) -> Error + Send,
-    {
-        self.write_with_sync_api(thread_name, file_path, |file| {
-            serde_yaml::to_writer(file, t).map_err(f_map_err)
-        })
-        .await?;
-
-        Ok(())
+    /// For a workspace's storage, this is the format of new workspaces. The
+    /// format is recorded in the workspace when it is first used, and each
+    /// `CmdCtx` uses a `Storage` with the recorded format. Use the
+    /// `StorageFormatMigrateCmd` to change the format of an existing
+    /// workspace.
+    pub fn with_format(mut self, format: StorageFormat) -> Self {
+        self.format = format;
+        self
     }
 
     /// Reads from a file, bridging to libraries that take a synchronous `Write`
     /// type.
     ///
+    /// This always reads from the file system, regardless of the storage
+    /// backend, as it is intended for files that item specs manage.
+    ///
     /// This method buffers the write, and calls flush on the buffer when the
     /// passed in closure returns.
     pub async fn read_with_sync_api<'f, F, T, E>(
@@ -182,6 +108,9 @@ impl Storage {
     /// Writes to a file, bridging to libraries that take a synchronous `Write`
     /// type.
     ///
+    /// This always writes to the file system, regardless of the storage
+    /// backend, as it is intended for files that item specs manage.
+    ///
     /// This method buffers the write, and calls flush on the buffer when the
     /// passed in closure returns.
     ///
@@ -233,3 +162,19 @@ impl Storage {
         Ok(t)
     }
 }
+
+impl SerializedStorage for Storage {
+    fn backend(&self) -> &dyn StorageBackend {
+        self.backend.as_ref()
+    }
+
+    fn format(&self) -> StorageFormat {
+        self.format
+    }
+}
+
+impl Default for Storage {
+    fn default() -> Self {
+        Self::new(FsStorage)
+    }
+}
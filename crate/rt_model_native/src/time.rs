@@ -0,0 +1,8 @@
+//! Timer functions for the native runtime.
+
+use std::time::Duration;
+
+/// Waits until `duration` has elapsed.
+pub async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
@@ -30,7 +30,7 @@ pub struct Workspace {
     app_name: AppName,
     /// Convention-based directories in this workspace.
     dirs: WorkspaceDirs,
-    /// File system storage access.
+    /// Storage for serialized data.
     storage: Storage,
 }
 
@@ -43,7 +43,7 @@ impl Workspace {
     /// * `workspace_spec`: Defines how to discover the workspace.
     pub fn new(app_name: AppName, workspace_spec: WorkspaceSpec) -> Result<Self, Error> {
         let dirs = WorkspaceDirsBuilder::build(&app_name, workspace_spec)?;
-        let storage = Storage;
+        let storage = Storage::default();
 
         Ok(Self {
             app_name,
@@ -52,6 +52,21 @@ impl Workspace {
         })
     }
 
+    /// Sets the storage for serialized data in this workspace.
+    ///
+    /// By default, data is stored on the file system.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let workspace = Workspace::new(app_name, WorkspaceSpec::Path(workspace_dir))?
+    ///     .with_storage(Storage::new(InMemoryStorage::new()));
+    /// ```
+    pub fn with_storage(mut self, storage: Storage) -> Self {
+        self.storage = storage;
+        self
+    }
+
     /// Returns the underlying data.
     pub fn into_inner(self) -> (AppName, WorkspaceDirs, Storage) {
         let Self {
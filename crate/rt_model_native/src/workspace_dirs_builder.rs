@@ -25,13 +25,16 @@ impl WorkspaceDirsBuilder {
         use peace_resources::paths::WorkspaceDir;
 
         let workspace_dir = {
-            let working_dir = std::env::current_dir()
-                .map_err(NativeError::WorkingDirRead)
-                .map_err(Error::Native)?;
+            let working_dir = || {
+                std::env::current_dir()
+                    .map_err(NativeError::WorkingDirRead)
+                    .map_err(Error::Native)
+            };
             let workspace_dir = match workspace_spec {
-                WorkspaceSpec::WorkingDir => working_dir,
+                WorkspaceSpec::WorkingDir => working_dir()?,
                 WorkspaceSpec::Path(path) => path,
                 WorkspaceSpec::FirstDirWithFile(file_name) => {
+                    let working_dir = working_dir()?;
                     Self::first_dir_with_file(&working_dir, &file_name).ok_or({
                         Error::Native(NativeError::WorkspaceFileNotFound {
                             working_dir,
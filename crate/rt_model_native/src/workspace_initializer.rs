@@ -3,17 +3,16 @@ use std::{fmt::Debug, hash::Hash, path::Path};
 use futures::{stream, StreamExt, TryStreamExt};
 
 use peace_resources::{
-    internal::{FlowParamsFile, ProfileParamsFile, WorkspaceParamsFile},
+    internal::{FlowParamsFile, ProfileParamsFile, StorageFormatFile, WorkspaceParamsFile},
     type_reg::untagged::TypeReg,
 };
 use peace_rt_model_core::{
     params::{FlowParams, ProfileParams, WorkspaceParams},
-    Error, NativeError,
+    storage::{SerializedStorage, StorageFormat},
+    Error,
 };
 use serde::{de::DeserializeOwned, Serialize};
 
-use crate::Storage;
-
 /// Logic to create peace directories and reads/writes initialization params.
 ///
 /// # Type Parameters
@@ -49,32 +48,70 @@ pub struct WorkspaceInitializer;
 
 impl WorkspaceInitializer {
     /// Creates directories used by the peace framework.
-    pub async fn dirs_create<'f, I>(dirs: I) -> Result<(), Error>
+    pub async fn dirs_create<'f, S, I>(storage: &S, dirs: I) -> Result<(), Error>
     where
+        S: SerializedStorage,
         I: IntoIterator<Item = &'f Path>,
     {
         stream::iter(dirs.into_iter())
             .map(Result::<_, Error>::Ok)
-            .try_for_each(|dir| async move {
-                tokio::fs::create_dir_all(dir).await.map_err(|error| {
-                    let path = dir.to_path_buf();
-                    Error::Native(NativeError::WorkspaceDirCreate { path, error })
-                })
+            .try_for_each(|dir| storage.backend().dir_create(dir))
+            .await
+    }
+
+    /// Returns the storage format recorded in the workspace, if any.
+    ///
+    /// Use [`storage_format_serialize`] to record the storage's format for a
+    /// new workspace.
+    ///
+    /// [`storage_format_serialize`]: Self::storage_format_serialize
+    pub async fn storage_format_deserialize<S>(
+        storage: &S,
+        storage_format_file: &StorageFormatFile,
+    ) -> Result<Option<StorageFormat>, Error>
+    where
+        S: SerializedStorage,
+    {
+        let Some(bytes) = storage.backend().read(storage_format_file).await? else {
+            return Ok(None);
+        };
+        let storage_format = String::from_utf8_lossy(&bytes);
+        let storage_format = storage_format.trim();
+
+        StorageFormat::from_extension(storage_format)
+            .map(Some)
+            .ok_or_else(|| Error::StorageFormatUnsupported {
+                path: storage_format_file.to_path_buf(),
+                storage_format: storage_format.to_string(),
             })
+    }
+
+    /// Records the storage's format in the workspace.
+    pub async fn storage_format_serialize<S>(
+        storage: &S,
+        storage_format_file: &StorageFormatFile,
+    ) -> Result<(), Error>
+    where
+        S: SerializedStorage,
+    {
+        let storage_format = format!("{}\n", storage.format().extension());
+        storage
+            .backend()
+            .write(storage_format_file, storage_format.as_bytes())
             .await
     }
 
-    pub async fn workspace_params_serialize<K>(
-        storage: &Storage,
+    pub async fn workspace_params_serialize<S, K>(
+        storage: &S,
         workspace_params: &WorkspaceParams<K>,
         workspace_params_file: &WorkspaceParamsFile,
     ) -> Result<(), Error>
     where
+        S: SerializedStorage,
         K: Eq + Hash + Serialize + Send + Sync,
     {
         storage
             .serialized_write(
-                "workspace_params_serialize".to_string(),
                 workspace_params_file,
                 workspace_params,
                 Error::WorkspaceParamsSerialize,
@@ -82,17 +119,17 @@ impl WorkspaceInitializer {
             .await
     }
 
-    pub async fn workspace_params_deserialize<K>(
-        storage: &Storage,
+    pub async fn workspace_params_deserialize<S, K>(
+        storage: &S,
         type_reg: &TypeReg<K>,
         workspace_params_file: &WorkspaceParamsFile,
     ) -> Result<Option<WorkspaceParams<K>>, Error>
     where
+        S: SerializedStorage,
         K: Debug + Eq + Hash + DeserializeOwned + Send + Sync,
     {
         storage
             .serialized_typemap_read_opt(
-                "workspace_params_deserialize".to_string(),
                 type_reg,
                 workspace_params_file,
                 Error::WorkspaceParamsDeserialize,
@@ -100,17 +137,17 @@ impl WorkspaceInitializer {
             .await
     }
 
-    pub async fn profile_params_serialize<K>(
-        storage: &Storage,
+    pub async fn profile_params_serialize<S, K>(
+        storage: &S,
         profile_params: &ProfileParams<K>,
         profile_params_file: &ProfileParamsFile,
     ) -> Result<(), Error>
     where
+        S: SerializedStorage,
         K: Eq + Hash + Serialize + Send + Sync,
     {
         storage
             .serialized_write(
-                "profile_params_serialize".to_string(),
                 profile_params_file,
                 profile_params,
                 Error::ProfileParamsSerialize,
@@ -118,17 +155,17 @@ impl WorkspaceInitializer {
             .await
     }
 
-    pub async fn profile_params_deserialize<K>(
-        storage: &Storage,
+    pub async fn profile_params_deserialize<S, K>(
+        storage: &S,
         type_reg: &TypeReg<K>,
         profile_params_file: &ProfileParamsFile,
     ) -> Result<Option<ProfileParams<K>>, Error>
     where
+        S: SerializedStorage,
         K: Debug + Eq + Hash + DeserializeOwned + Send + Sync,
     {
         storage
             .serialized_typemap_read_opt(
-                "profile_params_deserialize".to_string(),
                 type_reg,
                 profile_params_file,
                 Error::ProfileParamsDeserialize,
@@ -136,39 +173,31 @@ impl WorkspaceInitializer {
             .await
     }
 
-    pub async fn flow_params_serialize<K>(
-        storage: &Storage,
+    pub async fn flow_params_serialize<S, K>(
+        storage: &S,
         flow_params: &FlowParams<K>,
         flow_params_file: &FlowParamsFile,
     ) -> Result<(), Error>
     where
+        S: SerializedStorage,
         K: Eq + Hash + Serialize + Send + Sync,
     {
         storage
-            .serialized_write(
-                "flow_params_serialize".to_string(),
-                flow_params_file,
-                flow_params,
-                Error::FlowParamsSerialize,
-            )
+            .serialized_write(flow_params_file, flow_params, Error::FlowParamsSerialize)
             .await
     }
 
-    pub async fn flow_params_deserialize<K>(
-        storage: &Storage,
+    pub async fn flow_params_deserialize<S, K>(
+        storage: &S,
         type_reg: &TypeReg<K>,
         flow_params_file: &FlowParamsFile,
     ) -> Result<Option<FlowParams<K>>, Error>
     where
+        S: SerializedStorage,
         K: Debug + Eq + Hash + DeserializeOwned + Send + Sync,
     {
         storage
-            .serialized_typemap_read_opt(
-                "flow_params_deserialize".to_string(),
-                type_reg,
-                flow_params_file,
-                Error::FlowParamsDeserialize,
-            )
+            .serialized_typemap_read_opt(type_reg, flow_params_file, Error::FlowParamsDeserialize)
             .await
     }
 }
@@ -0,0 +1,134 @@
+/**
+ * Name of the object store that items are stored in.
+ */
+const STORE_NAME = 'items';
+
+/**
+ * Returns whether IndexedDB is available.
+ */
+export function indexedDbAvailable() /* -> bool */ {
+    return typeof indexedDB !== 'undefined' && indexedDB !== null;
+}
+
+/**
+ * Opens the database, creating the object store if it does not exist.
+ */
+function dbOpen(dbName) /* -> Promise<IDBDatabase> */ {
+    return new Promise((resolve, reject) => {
+        const request = indexedDB.open(dbName, 1);
+        request.onupgradeneeded = () => {
+            const db = request.result;
+            if (!db.objectStoreNames.contains(STORE_NAME)) {
+                db.createObjectStore(STORE_NAME);
+            }
+        };
+        request.onsuccess = () => resolve(request.result);
+        request.onerror = () => reject(request.error);
+    });
+}
+
+/**
+ * Runs `f` with the object store in a transaction, resolving with the result
+ * of the request returned by `f` once the transaction completes.
+ */
+async function storeRequest(dbName, mode, f) /* -> Promise<any> */ {
+    const db = await dbOpen(dbName);
+    try {
+        return await new Promise((resolve, reject) => {
+            const transaction = db.transaction(STORE_NAME, mode);
+            const request = f(transaction.objectStore(STORE_NAME));
+            transaction.oncomplete = () => resolve(request.result);
+            transaction.onerror = () => reject(transaction.error);
+            transaction.onabort = () => reject(transaction.error);
+        });
+    } finally {
+        db.close();
+    }
+}
+
+/**
+ * Returns the bytes stored for the key, or `undefined` if there is no item.
+ */
+export function indexedDbGet(dbName, key) /* -> Promise<Uint8Array | undefined> */ {
+    return storeRequest(dbName, 'readonly', (store) => store.get(key));
+}
+
+/**
+ * Stores the bytes for the key, replacing any existing item.
+ */
+export function indexedDbPut(dbName, key, bytes) /* -> Promise<undefined> */ {
+    return storeRequest(dbName, 'readwrite', (store) => store.put(bytes, key))
+        .then(() => undefined);
+}
+
+/**
+ * Stores the bytes for the key if there is no item for the key, resolving to
+ * whether the bytes were stored.
+ */
+export async function indexedDbAdd(dbName, key, bytes) /* -> Promise<bool> */ {
+    try {
+        await storeRequest(dbName, 'readwrite', (store) => store.add(bytes, key));
+        return true;
+    } catch (error) {
+        if (error && error.name === 'ConstraintError') {
+            return false;
+        }
+        throw error;
+    }
+}
+
+/**
+ * Deletes the item for the key, if any.
+ */
+export function indexedDbDelete(dbName, key) /* -> Promise<undefined> */ {
+    return storeRequest(dbName, 'readwrite', (store) => store.delete(key));
+}
+
+/**
+ * Deletes the item for the key if it holds the bytes, resolving to whether the
+ * item was deleted.
+ *
+ * The item is read and deleted in the same transaction, so it cannot be
+ * replaced in between.
+ */
+export async function indexedDbDeleteIfEq(dbName, key, bytes) /* -> Promise<bool> */ {
+    const db = await dbOpen(dbName);
+    try {
+        return await new Promise((resolve, reject) => {
+            const transaction = db.transaction(STORE_NAME, 'readwrite');
+            const store = transaction.objectStore(STORE_NAME);
+            let deleted = false;
+            const request = store.get(key);
+            request.onsuccess = () => {
+                if (bytesEq(request.result, bytes)) {
+                    store.delete(key);
+                    deleted = true;
+                }
+            };
+            transaction.oncomplete = () => resolve(deleted);
+            transaction.onerror = () => reject(transaction.error);
+            transaction.onabort = () => reject(transaction.error);
+        });
+    } finally {
+        db.close();
+    }
+}
+
+/**
+ * Returns whether the stored value holds the same bytes.
+ */
+function bytesEq(value, bytes) /* -> bool */ {
+    if (value === undefined || value === null) {
+        return false;
+    }
+    const valueBytes = new Uint8Array(value);
+    return valueBytes.length === bytes.length
+        && valueBytes.every((byte, index) => byte === bytes[index]);
+}
+
+/**
+ * Returns all keys in the object store.
+ */
+export function indexedDbKeys(dbName) /* -> Promise<Array<string>> */ {
+    return storeRequest(dbName, 'readonly', (store) => store.getAllKeys());
+}
@@ -0,0 +1,193 @@
+use std::path::{Path, PathBuf};
+
+use peace_rt_model_core::{async_trait, storage::StorageBackend, Error, WebError};
+use wasm_bindgen::prelude::*;
+
+/// Stores serialized data as binary items in browser IndexedDB.
+///
+/// Unlike web storage, IndexedDB stores bytes natively, so binary items do
+/// not need to be base64 encoded, and is not limited to around 5MB.
+///
+/// Items are keyed by their path in a single object store. Directories are
+/// implied by the keys of stored items.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IndexedDbStorage;
+
+#[wasm_bindgen(module = "/js/indexed_db.js")]
+extern "C" {
+    /// Returns whether IndexedDB is available.
+    fn indexedDbAvailable() -> bool;
+    /// Returns the bytes stored for the key, or `undefined` if there is no
+    /// item.
+    #[wasm_bindgen(catch)]
+    async fn indexedDbGet(db_name: &str, key: &str) -> Result<JsValue, JsValue>;
+    /// Stores the bytes for the key, replacing any existing item.
+    #[wasm_bindgen(catch)]
+    async fn indexedDbPut(
+        db_name: &str,
+        key: &str,
+        bytes: js_sys::Uint8Array,
+    ) -> Result<JsValue, JsValue>;
+    /// Stores the bytes for the key if there is no item for the key, resolving
+    /// to whether the bytes were stored.
+    #[wasm_bindgen(catch)]
+    async fn indexedDbAdd(
+        db_name: &str,
+        key: &str,
+        bytes: js_sys::Uint8Array,
+    ) -> Result<JsValue, JsValue>;
+    /// Deletes the item for the key, if any.
+    #[wasm_bindgen(catch)]
+    async fn indexedDbDelete(db_name: &str, key: &str) -> Result<JsValue, JsValue>;
+    /// Deletes the item for the key if it holds the bytes, resolving to whether
+    /// the item was deleted.
+    #[wasm_bindgen(catch)]
+    async fn indexedDbDeleteIfEq(
+        db_name: &str,
+        key: &str,
+        bytes: js_sys::Uint8Array,
+    ) -> Result<JsValue, JsValue>;
+    /// Returns all keys in the object store.
+    #[wasm_bindgen(catch)]
+    async fn indexedDbKeys(db_name: &str) -> Result<JsValue, JsValue>;
+}
+
+impl IndexedDbStorage {
+    /// Name of the IndexedDB database that items are stored in.
+    pub const DB_NAME: &'static str = "peace";
+
+    /// Returns a new `IndexedDbStorage`.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns an error if IndexedDB is not available in this browser.
+    fn available_ensure() -> Result<(), Error> {
+        if indexedDbAvailable() {
+            Ok(())
+        } else {
+            Err(Error::Web(WebError::IndexedDbUnavailable))
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl StorageBackend for IndexedDbStorage {
+    async fn contains(&self, path: &Path) -> Result<bool, Error> {
+        self.read(path).await.map(|bytes| bytes.is_some())
+    }
+
+    async fn read(&self, path: &Path) -> Result<Option<Vec<u8>>, Error> {
+        Self::available_ensure()?;
+
+        let key = path.to_string_lossy();
+        let value = indexedDbGet(Self::DB_NAME, key.as_ref())
+            .await
+            .map_err(|js_value| {
+                Error::Web(WebError::IndexedDbGet {
+                    path: path.to_path_buf(),
+                    error: crate::stringify_js_value(js_value),
+                })
+            })?;
+
+        if value.is_undefined() || value.is_null() {
+            Ok(None)
+        } else {
+            Ok(Some(js_sys::Uint8Array::new(&value).to_vec()))
+        }
+    }
+
+    async fn write(&self, path: &Path, bytes: &[u8]) -> Result<(), Error> {
+        Self::available_ensure()?;
+
+        // Copies the bytes out of WASM memory, as the JS side stores them after
+        // the database is opened asynchronously.
+        let bytes = js_sys::Uint8Array::from(bytes);
+        let key = path.to_string_lossy();
+        indexedDbPut(Self::DB_NAME, key.as_ref(), bytes)
+            .await
+            .map(|_| ())
+            .map_err(|js_value| {
+                Error::Web(WebError::IndexedDbPut {
+                    path: path.to_path_buf(),
+                    error: crate::stringify_js_value(js_value),
+                })
+            })
+    }
+
+    async fn write_new(&self, path: &Path, bytes: &[u8]) -> Result<bool, Error> {
+        Self::available_ensure()?;
+
+        let bytes = js_sys::Uint8Array::from(bytes);
+        let key = path.to_string_lossy();
+        indexedDbAdd(Self::DB_NAME, key.as_ref(), bytes)
+            .await
+            .map(|added| added.as_bool().unwrap_or(false))
+            .map_err(|js_value| {
+                Error::Web(WebError::IndexedDbPut {
+                    path: path.to_path_buf(),
+                    error: crate::stringify_js_value(js_value),
+                })
+            })
+    }
+
+    async fn remove(&self, path: &Path) -> Result<(), Error> {
+        Self::available_ensure()?;
+
+        let key = path.to_string_lossy();
+        indexedDbDelete(Self::DB_NAME, key.as_ref())
+            .await
+            .map(|_| ())
+            .map_err(|js_value| {
+                Error::Web(WebError::IndexedDbDelete {
+                    path: path.to_path_buf(),
+                    error: crate::stringify_js_value(js_value),
+                })
+            })
+    }
+
+    async fn remove_if_unchanged(&self, path: &Path, bytes: &[u8]) -> Result<bool, Error> {
+        Self::available_ensure()?;
+
+        let bytes = js_sys::Uint8Array::from(bytes);
+        let key = path.to_string_lossy();
+        indexedDbDeleteIfEq(Self::DB_NAME, key.as_ref(), bytes)
+            .await
+            .map(|deleted| deleted.as_bool().unwrap_or(false))
+            .map_err(|js_value| {
+                Error::Web(WebError::IndexedDbDelete {
+                    path: path.to_path_buf(),
+                    error: crate::stringify_js_value(js_value),
+                })
+            })
+    }
+
+    async fn dir_create(&self, _dir: &Path) -> Result<(), Error> {
+        // Directories are implied by the keys of stored items.
+        Ok(())
+    }
+
+    async fn dir_entries(&self, dir: &Path) -> Result<Vec<PathBuf>, Error> {
+        Self::available_ensure()?;
+
+        let keys = indexedDbKeys(Self::DB_NAME).await.map_err(|js_value| {
+            Error::Web(WebError::IndexedDbKeys {
+                dir: dir.to_path_buf(),
+                error: crate::stringify_js_value(js_value),
+            })
+        })?;
+
+        let dir_entries = js_sys::Array::from(&keys)
+            .iter()
+            .filter_map(|key| key.as_string())
+            .map(PathBuf::from)
+            .filter(|path| path.parent() == Some(dir))
+            .collect::<Vec<PathBuf>>();
+
+        Ok(dir_entries)
+    }
+
+    fn is_file_system(&self) -> bool {
+        false
+    }
+}
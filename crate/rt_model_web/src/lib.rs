@@ -7,13 +7,17 @@
 //! **This crate is intended to be used with `#[cfg(target_arch = "wasm32")]`.**
 
 pub use crate::{
-    storage::Storage, workspace::Workspace, workspace_dirs_builder::WorkspaceDirsBuilder,
+    indexed_db_storage::IndexedDbStorage, storage::Storage, web_storage::WebStorage,
+    workspace::Workspace, workspace_dirs_builder::WorkspaceDirsBuilder,
     workspace_initializer::WorkspaceInitializer, workspace_spec::WorkspaceSpec,
 };
 
+pub mod time;
 pub mod workspace;
 
+mod indexed_db_storage;
 mod storage;
+mod web_storage;
 mod workspace_dirs_builder;
 mod workspace_initializer;
 mod workspace_spec;
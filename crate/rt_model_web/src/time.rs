@@ -0,0 +1,31 @@
+//! Timer functions for the web runtime.
+
+use std::time::Duration;
+
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+#[wasm_bindgen]
+extern "C" {
+    /// `setTimeout` on the global object.
+    ///
+    /// This is bound to the global function instead of `window.setTimeout`,
+    /// so that it is available in both windows and web workers.
+    #[wasm_bindgen(js_name = "setTimeout", catch)]
+    fn set_timeout(handler: &js_sys::Function, timeout: i32) -> Result<JsValue, JsValue>;
+}
+
+/// Waits until `duration` has elapsed.
+///
+/// This uses the global `setTimeout`, which is available in windows and web
+/// workers. If `setTimeout` fails, this returns immediately.
+pub async fn sleep(duration: Duration) {
+    let millis = i32::try_from(duration.as_millis()).unwrap_or(i32::MAX);
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        if set_timeout(&resolve, millis).is_err() {
+            let _resolve_result = resolve.call0(&JsValue::NULL);
+        }
+    });
+
+    let _timeout_result = JsFuture::from(promise).await;
+}
@@ -0,0 +1,458 @@
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use peace_rt_model_core::{async_trait, storage::StorageBackend, Error, WebError};
+use wasm_bindgen::prelude::*;
+
+use crate::WorkspaceSpec;
+
+/// Prefix of binary items in web storage, such as items serialized as
+/// MessagePack, which are stored as base64 text.
+const BINARY_PREFIX: &str = "peace_binary_v1:";
+
+/// Stores serialized data as text items in browser local or session storage.
+///
+/// `web_sys::Storage` is retrieved on demand, as it is `!Send`.
+///
+/// When the `WorkspaceSpec` is [`WorkspaceSpec::IndexedDb`], methods return
+/// [`WebError::WebStorageNotInUse`]; use [`IndexedDbStorage`] instead.
+///
+/// [`IndexedDbStorage`]: crate::IndexedDbStorage
+#[derive(Clone, Copy, Debug)]
+pub struct WebStorage {
+    /// Describes which browser storage to use.
+    workspace_spec: WorkspaceSpec,
+}
+
+#[wasm_bindgen(module = "/js/workspace.js")]
+extern "C" {
+    /// Returns whether local storage is available.
+    fn localStorageAvailable() -> bool;
+    /// Returns whether session storage is available.
+    fn sessionStorageAvailable() -> bool;
+}
+
+impl WebStorage {
+    /// Returns a new `WebStorage`.
+    pub fn new(workspace_spec: WorkspaceSpec) -> Self {
+        Self { workspace_spec }
+    }
+
+    /// Returns the browser storage used for the workspace.
+    ///
+    /// This is the local or session storage depending on the `WorkspaceSpec`
+    /// passed into `Workspace::new`.
+    ///
+    /// `web_sys::Storage` is `!Send`, so cannot be inserted into `Resources`.
+    /// As a compromise, we provide this function to fetch the storage when it
+    /// needs to be accessed.
+    ///
+    /// This returns [`WebError::WebStorageNotInUse`] if the workspace is
+    /// stored in IndexedDB.
+    pub fn get(&self) -> Result<web_sys::Storage, Error> {
+        let window = web_sys::window().ok_or(WebError::WindowNone)?;
+        let storage = match self.workspace_spec {
+            WorkspaceSpec::LocalStorage => {
+                if !localStorageAvailable() {
+                    return Err(Error::Web(WebError::LocalStorageUnavailable));
+                }
+                window
+                    .local_storage()
+                    .map_err(crate::stringify_js_value)
+                    .map_err(WebError::LocalStorageGet)
+                    .map_err(Error::Web)?
+                    .ok_or(Error::Web(WebError::LocalStorageNone))?
+            }
+            WorkspaceSpec::SessionStorage => {
+                if !sessionStorageAvailable() {
+                    return Err(Error::Web(WebError::SessionStorageUnavailable));
+                }
+                window
+                    .session_storage()
+                    .map_err(crate::stringify_js_value)
+                    .map_err(WebError::SessionStorageGet)
+                    .map_err(Error::Web)?
+                    .ok_or(Error::Web(WebError::SessionStorageNone))?
+            }
+            WorkspaceSpec::IndexedDb => return Err(Error::Web(WebError::WebStorageNotInUse)),
+        };
+
+        Ok(storage)
+    }
+
+    /// Returns whether an item exists in the web storage.
+    pub fn contains_item(&self, path: &Path) -> Result<bool, Error> {
+        self.get_item_opt(path).map(|item| item.is_some())
+    }
+
+    /// Gets an optional item in the web storage.
+    ///
+    /// * Use [`get_item_opt`] if you would like to fetch an item that may not
+    ///   exist.
+    /// * Use [`get_items_opt`] if you would like to fetch multiple optional
+    ///   items.
+    /// * Use [`get_item`] if you would like to fetch an item that must exist.
+    /// * Use [`get_items`] if you would like to fetch multiple items that must
+    ///   exist.
+    ///
+    /// [`get_items_opt`]: Self::get_items
+    pub fn get_item_opt(&self, path: &Path) -> Result<Option<String>, Error> {
+        let storage = self.get()?;
+        let key = path.to_string_lossy();
+        storage.get_item(key.as_ref()).map_err(|js_value| {
+            Error::Web(WebError::StorageGetItem {
+                path: path.to_path_buf(),
+                error: crate::stringify_js_value(js_value),
+            })
+        })
+    }
+
+    /// Gets multiple items in the web storage.
+    ///
+    /// * Use [`get_item_opt`] if you would like to fetch an item that may not
+    ///   exist.
+    /// * Use [`get_items_opt`] if you would like to fetch multiple optional
+    ///   items.
+    /// * Use [`get_item`] if you would like to fetch an item that must exist.
+    /// * Use [`get_items`] if you would like to fetch multiple items that must
+    ///   exist.
+    ///
+    /// [`get_item`]: Self::get_item
+    pub fn get_items_opt<'f, I>(
+        &self,
+        iter: I,
+    ) -> Result<impl Iterator<Item = Result<(&'f Path, Option<String>), Error>>, Error>
+    where
+        I: Iterator<Item = &'f Path>,
+    {
+        let storage = self.get()?;
+
+        let iter = iter.map(move |path| {
+            let key = path.to_string_lossy();
+            storage
+                .get_item(key.as_ref())
+                .map(|value| (path, value))
+                .map_err(|js_value| {
+                    Error::Web(WebError::StorageGetItem {
+                        path: path.to_path_buf(),
+                        error: crate::stringify_js_value(js_value),
+                    })
+                })
+        });
+
+        Ok(iter)
+    }
+
+    /// Gets an item in the web storage.
+    ///
+    /// * Use [`get_item_opt`] if you would like to fetch an item that may not
+    ///   exist.
+    /// * Use [`get_items_opt`] if you would like to fetch multiple optional
+    ///   items.
+    /// * Use [`get_item`] if you would like to fetch an item that must exist.
+    /// * Use [`get_items`] if you would like to fetch multiple items that must
+    ///   exist.
+    ///
+    /// [`get_items`]: Self::get_items
+    pub fn get_item(&self, path: &Path) -> Result<String, Error> {
+        let storage = self.get()?;
+        let key = path.to_string_lossy();
+        storage
+            .get_item(key.as_ref())
+            .map_err(|js_value| {
+                Error::Web(WebError::StorageGetItem {
+                    path: path.to_path_buf(),
+                    error: crate::stringify_js_value(js_value),
+                })
+            })
+            .and_then(|value| {
+                value.ok_or_else(|| Error::ItemNotExists {
+                    path: path.to_path_buf(),
+                })
+            })
+    }
+
+    /// Gets a base64 encoded item in the web storage.
+    ///
+    /// * Use [`get_item_b64_opt`] if you would like to fetch an item that may
+    ///   not exist.
+    ///
+    /// [`get_items`]: Self::get_items
+    pub fn get_item_b64_opt(&self, path: &Path) -> Result<Option<Vec<u8>>, Error> {
+        self.get_item_opt(path).and_then(|value| {
+            value
+                .map(|value| {
+                    base64::engine::general_purpose::STANDARD
+                        .decode(&value)
+                        .map_err(|error| {
+                            Error::Web(WebError::StorageB64Decode {
+                                path: path.to_path_buf(),
+                                value,
+                                error,
+                            })
+                        })
+                })
+                .transpose()
+        })
+    }
+
+    /// Gets a base64 encoded item in the web storage.
+    ///
+    /// * Use [`get_item_b64_opt`] if you would like to fetch an item that may
+    ///   not exist.
+    ///
+    /// [`get_items`]: Self::get_items
+    pub fn get_item_b64(&self, path: &Path) -> Result<Vec<u8>, Error> {
+        self.get_item(path).and_then(|value| {
+            base64::engine::general_purpose::STANDARD
+                .decode(&value)
+                .map_err(|error| {
+                    Error::Web(WebError::StorageB64Decode {
+                        path: path.to_path_buf(),
+                        value,
+                        error,
+                    })
+                })
+        })
+    }
+
+    /// Gets multiple items in the web storage.
+    ///
+    /// * Use [`get_item_opt`] if you would like to fetch an item that may not
+    ///   exist.
+    /// * Use [`get_items_opt`] if you would like to fetch multiple optional
+    ///   items.
+    /// * Use [`get_item`] if you would like to fetch an item that must exist.
+    /// * Use [`get_items`] if you would like to fetch multiple items that must
+    ///   exist.
+    ///
+    /// [`get_item`]: Self::get_item
+    pub fn get_items<'f, I>(
+        &self,
+        iter: I,
+    ) -> Result<impl Iterator<Item = Result<(&'f Path, String), Error>>, Error>
+    where
+        I: Iterator<Item = &'f Path>,
+    {
+        let storage = self.get()?;
+
+        let iter = iter.map(move |path| {
+            let key = path.to_string_lossy();
+            storage
+                .get_item(key.as_ref())
+                .map_err(|js_value| {
+                    Error::Web(WebError::StorageGetItem {
+                        path: path.to_path_buf(),
+                        error: crate::stringify_js_value(js_value),
+                    })
+                })
+                .and_then(|value| {
+                    value.ok_or_else(|| Error::ItemNotExists {
+                        path: path.to_path_buf(),
+                    })
+                })
+                .map(|value| (path, value))
+        });
+
+        Ok(iter)
+    }
+
+    /// Sets an item in the web storage.
+    ///
+    /// See [`set_items`] if you would like to set multiple items.
+    ///
+    /// [`set_items`]: Self::set_items
+    pub fn set_item(&self, path: &Path, value: &str) -> Result<(), Error> {
+        let storage = self.get()?;
+        let key = path.to_string_lossy();
+        storage.set_item(key.as_ref(), value).map_err(|js_value| {
+            Error::Web(WebError::StorageSetItem {
+                path: path.to_path_buf(),
+                value: value.to_string(),
+                error: crate::stringify_js_value(js_value),
+            })
+        })
+    }
+
+    /// Base64 encodes and sets a value in the web storage.
+    pub fn set_item_b64<B>(&self, path: &Path, bytes: &B) -> Result<(), Error>
+    where
+        B: AsRef<[u8]>,
+    {
+        let value = base64::engine::general_purpose::STANDARD.encode(bytes);
+        self.set_item(path, &value)
+    }
+
+    /// Sets multiple items in the web storage.
+    ///
+    /// See [`set_item`] if you would like to set a single item.
+    ///
+    /// [`set_item`]: Self::set_item
+    pub fn set_items<'f, I>(&self, mut iter: I) -> Result<(), Error>
+    where
+        I: Iterator<Item = (&'f Path, &'f str)>,
+    {
+        let storage = self.get()?;
+
+        iter.try_for_each(|(path, value)| {
+            let key = path.to_string_lossy();
+            storage.set_item(key.as_ref(), value).map_err(|js_value| {
+                Error::Web(WebError::StorageSetItem {
+                    path: path.to_path_buf(),
+                    value: value.to_string(),
+                    error: crate::stringify_js_value(js_value),
+                })
+            })
+        })
+    }
+
+    /// Runs the provided closure for each item produced by the iterator,
+    /// augmented with the web storage.
+    ///
+    /// Note that the `storage` passed to the closure is the browser storage, so
+    /// `set_item` takes a `&str` for the key.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use std::path::PathBuf;
+    /// # use peace_rt_model_web::{WebStorage, WorkspaceSpec, Error};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// let storage = WebStorage::new(WorkspaceSpec::SessionStorage);
+    /// let keys = ["abc", "def"];
+    ///
+    /// storage.iter_with_storage(keys.into_iter(), |storage, key| {
+    ///     let value = "something";
+    ///     storage
+    ///         .set_item(key, value)
+    ///         .map_err(|js_value| (PathBuf::from(key), value.to_string(), js_value))
+    /// })?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter_with_storage<F, I, T>(&self, mut iter: I, mut f: F) -> Result<(), Error>
+    where
+        F: for<'f> FnMut(&'f web_sys::Storage, T) -> Result<(), (PathBuf, String, JsValue)>,
+        I: Iterator<Item = T>,
+    {
+        let storage = self.get()?;
+
+        iter.try_for_each(|t| {
+            f(&storage, t).map_err(|(path, value, js_value)| {
+                Error::Web(WebError::StorageSetItem {
+                    path,
+                    value,
+                    error: crate::stringify_js_value(js_value),
+                })
+            })
+        })
+    }
+
+    /// Deletes an item from the web storage.
+    pub fn remove_item(&self, path: &Path) -> Result<(), Error> {
+        let storage = self.get()?;
+        let key = path.to_string_lossy();
+        storage.remove_item(key.as_ref()).map_err(|js_value| {
+            Error::Web(WebError::StorageRemoveItem {
+                path: path.to_path_buf(),
+                error: crate::stringify_js_value(js_value),
+            })
+        })
+    }
+}
+
+/// Directories are stored as items with empty values, matching
+/// [`WorkspaceInitializer::dirs_create`]. Items that are not valid UTF-8, such
+/// as MessagePack serialized items, are stored as base64 text with a prefix.
+///
+/// [`WorkspaceInitializer::dirs_create`]: crate::WorkspaceInitializer::dirs_create
+#[async_trait(?Send)]
+impl StorageBackend for WebStorage {
+    async fn contains(&self, path: &Path) -> Result<bool, Error> {
+        self.contains_item(path)
+    }
+
+    async fn read(&self, path: &Path) -> Result<Option<Vec<u8>>, Error> {
+        self.get_item_opt(path)?
+            .map(|value| match value.strip_prefix(BINARY_PREFIX) {
+                Some(value_b64) => base64::engine::general_purpose::STANDARD
+                    .decode(value_b64)
+                    .map_err(|error| {
+                        Error::Web(WebError::StorageB64Decode {
+                            path: path.to_path_buf(),
+                            value: value_b64.to_string(),
+                            error,
+                        })
+                    }),
+                None => Ok(value.into_bytes()),
+            })
+            .transpose()
+    }
+
+    async fn write(&self, path: &Path, bytes: &[u8]) -> Result<(), Error> {
+        match std::str::from_utf8(bytes) {
+            Ok(value) => self.set_item(path, value),
+            Err(_) => {
+                let value_b64 = base64::engine::general_purpose::STANDARD.encode(bytes);
+                self.set_item(path, &format!("{BINARY_PREFIX}{value_b64}"))
+            }
+        }
+    }
+
+    async fn write_new(&self, path: &Path, bytes: &[u8]) -> Result<bool, Error> {
+        // Web storage calls are synchronous, so nothing else on this page writes
+        // the item between the check and the write.
+        if self.contains_item(path)? {
+            Ok(false)
+        } else {
+            self.write(path, bytes).await.map(|()| true)
+        }
+    }
+
+    async fn remove(&self, path: &Path) -> Result<(), Error> {
+        self.remove_item(path)
+    }
+
+    async fn remove_if_unchanged(&self, path: &Path, bytes: &[u8]) -> Result<bool, Error> {
+        // Web storage calls are synchronous, so nothing else on this page writes
+        // the item between the comparison and the removal.
+        if self.read(path).await?.as_deref() == Some(bytes) {
+            self.remove_item(path).map(|()| true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    async fn dir_create(&self, dir: &Path) -> Result<(), Error> {
+        self.set_item(dir, "")
+    }
+
+    async fn dir_entries(&self, dir: &Path) -> Result<Vec<PathBuf>, Error> {
+        let storage = self.get()?;
+        let map_err = |js_value| {
+            Error::Web(WebError::StorageGetItem {
+                path: dir.to_path_buf(),
+                error: crate::stringify_js_value(js_value),
+            })
+        };
+
+        let length = storage.length().map_err(map_err)?;
+        let mut dir_entries = Vec::new();
+        for index in 0..length {
+            if let Some(key) = storage.key(index).map_err(map_err)? {
+                let path = PathBuf::from(key);
+                if path.parent() == Some(dir) {
+                    dir_entries.push(path);
+                }
+            }
+        }
+
+        Ok(dir_entries)
+    }
+
+    fn is_file_system(&self) -> bool {
+        false
+    }
+}
@@ -21,7 +21,7 @@ use peace_core::AppName;
 use peace_resources::internal::WorkspaceDirs;
 use peace_rt_model_core::Error;
 
-use crate::{Storage, WorkspaceDirsBuilder, WorkspaceSpec};
+use crate::{IndexedDbStorage, Storage, WebStorage, WorkspaceDirsBuilder, WorkspaceSpec};
 
 /// Workspace that the `peace` tool runs in.
 #[derive(Clone, Debug)]
@@ -30,7 +30,7 @@ pub struct Workspace {
     app_name: AppName,
     /// `Resources` in this workspace.
     dirs: WorkspaceDirs,
-    /// Wrapper to retrieve `web_sys::Storage` on demand.
+    /// Reads and writes serialized data in the browser.
     storage: Storage,
 }
 
@@ -45,7 +45,12 @@ impl Workspace {
     /// * `flow_id`: ID of the flow that is being executed.
     pub fn new(app_name: AppName, workspace_spec: WorkspaceSpec) -> Result<Self, Error> {
         let dirs = WorkspaceDirsBuilder::build(&app_name, workspace_spec)?;
-        let storage = Storage::new(workspace_spec);
+        let storage = match workspace_spec {
+            WorkspaceSpec::LocalStorage | WorkspaceSpec::SessionStorage => {
+                Storage::new(WebStorage::new(workspace_spec))
+            }
+            WorkspaceSpec::IndexedDb => Storage::new(IndexedDbStorage),
+        };
 
         Ok(Self {
             app_name,
@@ -54,6 +59,24 @@ impl Workspace {
         })
     }
 
+    /// Sets the storage for serialized data in this workspace.
+    ///
+    /// By default, data is stored in the browser storage of the
+    /// `WorkspaceSpec`, in YAML. The format is only used for new workspaces,
+    /// as existing workspaces are read and written in their recorded format.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let workspace = Workspace::new(app_name, WorkspaceSpec::IndexedDb)?.with_storage(
+    ///     Storage::new(IndexedDbStorage).with_format(StorageFormat::MessagePack),
+    /// );
+    /// ```
+    pub fn with_storage(mut self, storage: Storage) -> Self {
+        self.storage = storage;
+        self
+    }
+
     /// Returns the underlying data.
     pub fn into_inner(self) -> (AppName, WorkspaceDirs, Storage) {
         let Self {
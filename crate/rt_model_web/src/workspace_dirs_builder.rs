@@ -22,7 +22,9 @@ impl WorkspaceDirsBuilder {
         // Written this way so that if we want to add a prefix, this would compile
         // error.
         let workspace_dir = match workspace_spec {
-            WorkspaceSpec::LocalStorage | WorkspaceSpec::SessionStorage => PathBuf::from("").into(),
+            WorkspaceSpec::LocalStorage
+            | WorkspaceSpec::SessionStorage
+            | WorkspaceSpec::IndexedDb => PathBuf::from("").into(),
         };
 
         let peace_dir = PeaceDir::from(&workspace_dir);
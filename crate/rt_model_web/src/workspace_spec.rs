@@ -1,6 +1,9 @@
 /// Describes how to store peace automation data.
 ///
-/// See <https://developer.mozilla.org/en-US/docs/Web/API/Web_Storage_API>.
+/// See:
+///
+/// * <https://developer.mozilla.org/en-US/docs/Web/API/Web_Storage_API>
+/// * <https://developer.mozilla.org/en-US/docs/Web/API/IndexedDB_API>
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum WorkspaceSpec {
     /// Use browser local storage to store peace data.
@@ -22,4 +25,19 @@ pub enum WorkspaceSpec {
     /// * Data is never transferred to the server.
     /// * Storage limit is larger than a cookie (at most 5MB).
     SessionStorage,
+    /// Use browser IndexedDB to store peace data.
+    ///
+    /// Persists even when the browser is closed and reopened.
+    ///
+    /// * Stores binary data natively, so items such as downloaded files do not
+    ///   need to be base64 encoded.
+    /// * Storage limit is a proportion of the available disk space, instead of
+    ///   around 5MB.
+    /// * Items are read and written asynchronously through
+    ///   [`IndexedDbStorage`], instead of the synchronous [`WebStorage`]
+    ///   methods.
+    ///
+    /// [`IndexedDbStorage`]: crate::IndexedDbStorage
+    /// [`WebStorage`]: crate::WebStorage
+    IndexedDb,
 }
@@ -166,6 +166,45 @@ pub fn flow_id(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     ensure_valid_id(input, "FlowId", None)
 }
 
+/// Returns a `const ConcurrencyGroupId` validated at compile time.
+///
+/// # Examples
+///
+/// Instantiate a valid `ConcurrencyGroupId` at compile time:
+///
+/// ```rust
+/// # use peace_static_check_macros::concurrency_group_id;
+/// // use peace::cfg::{concurrency_group_id, ConcurrencyGroupId};
+///
+/// let _my_group: ConcurrencyGroupId = concurrency_group_id!("valid_id"); // Ok!
+///
+/// # struct ConcurrencyGroupId(&'static str);
+/// # impl ConcurrencyGroupId {
+/// #     fn new_unchecked(s: &'static str) -> Self { Self(s) }
+/// # }
+/// ```
+///
+/// If the ID is invalid, a compilation error is produced:
+///
+/// ```rust,compile_fail
+/// # use peace_static_check_macros::concurrency_group_id;
+/// // use peace::cfg::{concurrency_group_id, ConcurrencyGroupId};
+///
+/// let _my_group: ConcurrencyGroupId = concurrency_group_id!("-invalid_id"); // Compile error
+/// //                                  ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+/// // error: "-invalid_id" is not a valid `ConcurrencyGroupId`.
+/// //        `ConcurrencyGroupId`s must begin with a letter or underscore, and contain only letters, numbers, or underscores.
+/// #
+/// # struct ConcurrencyGroupId(&'static str);
+/// # impl ConcurrencyGroupId {
+/// #     fn new_unchecked(s: &'static str) -> Self { Self(s) }
+/// # }
+/// ```
+#[proc_macro]
+pub fn concurrency_group_id(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    ensure_valid_id(input, "ConcurrencyGroupId", None)
+}
+
 fn ensure_valid_id(
     input: proc_macro::TokenStream,
     ty_name: &str,
@@ -1,17 +1,10 @@
 use futures::future::LocalBoxFuture;
 use peace::{
     cfg::{app_name, AppName, Profile},
-    cmd::{
-        ctx::CmdCtx,
-        scopes::{SingleProfileSingleFlow, SingleProfileSingleFlowView},
-    },
+    cmd::{ctx::CmdCtx, scopes::SingleProfileSingleFlowView},
     fmt::{presentln, Presentable},
     resources::resources::ts::SetUp,
-    rt_model::{
-        output::OutputWrite,
-        params::{KeyKnown, ParamsKeysImpl},
-        Workspace, WorkspaceSpec,
-    },
+    rt_model::{output::OutputWrite, Workspace, WorkspaceSpec},
 };
 
 use crate::{
@@ -36,18 +29,49 @@ impl EnvCmd {
     where
         O: OutputWrite<EnvManError>,
         for<'fn_once> F: FnOnce(
-            &'fn_once mut CmdCtx<
-                SingleProfileSingleFlow<
-                    '_,
-                    EnvManError,
-                    O,
-                    ParamsKeysImpl<KeyKnown<String>, KeyKnown<String>, KeyKnown<String>>,
-                    SetUp,
-                >,
-            >,
+            &'fn_once mut EnvManCmdCtx<'_, O, SetUp>,
+        ) -> LocalBoxFuture<'fn_once, Result<T, EnvManError>>,
+    {
+        Self::run_internal(output, profile_print, false, f).await
+    }
+
+    /// Runs a command that changes the environment.
+    ///
+    /// The profile is locked while the command runs, so that other commands
+    /// that change the environment cannot run at the same time.
+    ///
+    /// # Parameters
+    ///
+    /// * `output`: Output to write the execution outcome.
+    /// * `profile_print`: Whether to print the profile used.
+    /// * `f`: The command to run.
+    pub async fn run_locked<O, T, F>(
+        output: &mut O,
+        profile_print: bool,
+        f: F,
+    ) -> Result<T, EnvManError>
+    where
+        O: OutputWrite<EnvManError>,
+        for<'fn_once> F: FnOnce(
+            &'fn_once mut EnvManCmdCtx<'_, O, SetUp>,
         ) -> LocalBoxFuture<'fn_once, Result<T, EnvManError>>,
     {
-        cmd_ctx_init!(output, cmd_ctx);
+        Self::run_internal(output, profile_print, true, f).await
+    }
+
+    async fn run_internal<O, T, F>(
+        output: &mut O,
+        profile_print: bool,
+        lock: bool,
+        f: F,
+    ) -> Result<T, EnvManError>
+    where
+        O: OutputWrite<EnvManError>,
+        for<'fn_once> F: FnOnce(
+            &'fn_once mut EnvManCmdCtx<'_, O, SetUp>,
+        ) -> LocalBoxFuture<'fn_once, Result<T, EnvManError>>,
+    {
+        cmd_ctx_init!(output, cmd_ctx, lock);
 
         if profile_print {
             Self::profile_print(&mut cmd_ctx).await?;
@@ -77,7 +101,7 @@ impl EnvCmd {
         ) -> LocalBoxFuture<'fn_once, Result<T, EnvManError>>,
         T: Presentable,
     {
-        cmd_ctx_init!(output, cmd_ctx);
+        cmd_ctx_init!(output, cmd_ctx, false);
 
         if profile_print {
             Self::profile_print(&mut cmd_ctx).await?;
@@ -91,6 +115,29 @@ impl EnvCmd {
         Ok(())
     }
 
+    /// Interrupts the command's execution when the user presses `Ctrl-C`.
+    ///
+    /// Item specs that are already in progress are allowed to complete, and
+    /// their states are still saved. The returned task should be aborted once
+    /// the command completes.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn interrupt_on_ctrl_c<O>(
+        cmd_ctx: &EnvManCmdCtx<'_, O, SetUp>,
+    ) -> tokio::task::JoinHandle<()>
+    where
+        O: OutputWrite<EnvManError>,
+    {
+        use peace::cfg::InterruptSignal;
+
+        let interrupt_signal =
+            InterruptSignal::clone(&cmd_ctx.resources().borrow::<InterruptSignal>());
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                interrupt_signal.interrupt();
+            }
+        })
+    }
+
     async fn profile_print<O>(cmd_ctx: &mut EnvManCmdCtx<'_, O, SetUp>) -> Result<(), EnvManError>
     where
         O: OutputWrite<EnvManError>,
@@ -117,7 +164,7 @@ impl EnvCmd {
 }
 
 macro_rules! cmd_ctx_init {
-    ($output:ident, $cmd_ctx:ident) => {
+    ($output:ident, $cmd_ctx:ident, $lock:expr) => {
         let workspace = Workspace::new(
             app_name!(),
             #[cfg(not(target_arch = "wasm32"))]
@@ -133,10 +180,19 @@ macro_rules! cmd_ctx_init {
                 CmdCtx::builder_single_profile_single_flow::<EnvManError, _>($output, &workspace);
             crate::cmds::ws_profile_and_flow_params_augment!(cmd_ctx_builder);
 
-            cmd_ctx_builder
+            let cmd_ctx_builder = cmd_ctx_builder
                 .with_profile_from_workspace_param(&profile_key)
-                .with_flow(&flow)
-                .await?
+                .with_flow(&flow);
+            #[cfg(not(target_arch = "wasm32"))]
+            let cmd_ctx_builder = if $lock {
+                cmd_ctx_builder.with_lock(peace::rt_model::ProfileLockMode::Fail)
+            } else {
+                cmd_ctx_builder
+            };
+            #[cfg(target_arch = "wasm32")]
+            let _ = $lock;
+
+            cmd_ctx_builder.await?
         };
     };
 }
@@ -25,17 +25,22 @@ impl EnvDeployCmd {
     where
         O: OutputWrite<EnvManError> + Send,
     {
-        let states_saved = EnvCmd::run(output, true, |ctx| {
-            StatesSavedReadCmd::exec(ctx).boxed_local()
-        })
-        .await?;
-        EnvCmd::run(output, false, |ctx| {
+        EnvCmd::run_locked(output, true, |ctx| {
             async move {
-                let states_saved_ref = &states_saved;
-                let states_ensured_outcome = EnsureCmd::exec(ctx, states_saved_ref).await?;
+                let states_saved = StatesSavedReadCmd::exec(ctx).await?;
+                #[cfg(not(target_arch = "wasm32"))]
+                let ctrl_c_task = EnvCmd::interrupt_on_ctrl_c(ctx);
+                let states_ensured_outcome = EnsureCmd::exec(ctx, &states_saved).await;
+                #[cfg(not(target_arch = "wasm32"))]
+                ctrl_c_task.abort();
+                let states_ensured_outcome = states_ensured_outcome?;
                 let CmdOutcome {
                     value: states_ensured,
                     errors,
+                    retries: _,
+                    item_statuses: _,
+                    interrupted: _,
+                    history_write_error: _,
                 } = &states_ensured_outcome;
                 let SingleProfileSingleFlowView { output, flow, .. } = ctx.view();
 
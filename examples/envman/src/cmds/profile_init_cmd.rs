@@ -91,7 +91,6 @@ impl ProfileInitCmd {
             iam_role_params,
             instance_profile_params,
             s3_bucket_params,
-            s3_object_params,
         } = EnvDeployFlow::params(&profile_to_create, slug, version, url)?;
         let flow = EnvDeployFlow::flow().await?;
         let profile_key = String::from("profile");
@@ -116,7 +115,6 @@ impl ProfileInitCmd {
                     Some(instance_profile_params),
                 )
                 .with_flow_param_value(String::from("s3_bucket_params"), Some(s3_bucket_params))
-                .with_flow_param_value(String::from("s3_object_params"), Some(s3_object_params))
                 .await?
         };
 
@@ -1,11 +1,14 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use peace::{
     cfg::{app_name, flow_id, item_spec_id, AppName, FlowId, ItemSpecId, Profile},
-    rt_model::{Flow, ItemSpecGraphBuilder},
+    data::params::ValueSpec,
+    rt_model::{Flow, ItemSpecGraphBuilder, ItemSpecWrapper, RetryPolicy},
 };
 use peace_item_specs::{
-    file_download::{FileDownloadItemSpec, FileDownloadParams},
+    file_download::{
+        FileDownloadError, FileDownloadItemSpec, FileDownloadParams, FileDownloadState,
+    },
     tar_x::{TarXItemSpec, TarXParams},
 };
 use semver::Version;
@@ -16,8 +19,8 @@ use crate::{
         peace_aws_iam_policy::{IamPolicyItemSpec, IamPolicyParams},
         peace_aws_iam_role::{IamRoleItemSpec, IamRoleParams},
         peace_aws_instance_profile::{InstanceProfileItemSpec, InstanceProfileParams},
-        peace_aws_s3_bucket::{S3BucketItemSpec, S3BucketParams},
-        peace_aws_s3_object::{S3ObjectItemSpec, S3ObjectParams},
+        peace_aws_s3_bucket::{S3BucketItemSpec, S3BucketParams, S3BucketState},
+        peace_aws_s3_object::{S3ObjectItemSpec, S3ObjectParamsSpec},
     },
     model::{EnvManError, RepoSlug, WebAppFileId},
 };
@@ -34,8 +37,20 @@ impl EnvDeployFlow {
             let graph = {
                 let mut graph_builder = ItemSpecGraphBuilder::<EnvManError>::new();
 
+                // Retry the download when the connection fails.
+                let app_download_retry_policy = RetryPolicy::new(3).with_retryable(|error| {
+                    matches!(
+                        error,
+                        FileDownloadError::SrcGet { .. }
+                            | FileDownloadError::ResponseBytesStream(_)
+                    )
+                });
                 let app_download_id = graph_builder.add_fn(
-                    FileDownloadItemSpec::<WebAppFileId>::new(item_spec_id!("app_download")).into(),
+                    ItemSpecWrapper::from(FileDownloadItemSpec::<WebAppFileId>::new(
+                        item_spec_id!("app_download"),
+                    ))
+                    .with_retry_policy(app_download_retry_policy)
+                    .into(),
                 );
                 let app_extract_id = graph_builder
                     .add_fn(TarXItemSpec::<WebAppFileId>::new(item_spec_id!("app_extract")).into());
@@ -55,8 +70,28 @@ impl EnvDeployFlow {
                 let s3_bucket_id = graph_builder.add_fn(
                     S3BucketItemSpec::<WebAppFileId>::new(item_spec_id!("s3_bucket")).into(),
                 );
+                // Upload the downloaded file to the bucket that the `s3_bucket` item spec
+                // manages.
+                let s3_object_params_spec = S3ObjectParamsSpec::<WebAppFileId>::new(
+                    ValueSpec::from_map(|file_download_state: &FileDownloadState| {
+                        Some(file_download_path(file_download_state).to_path_buf())
+                    }),
+                    ValueSpec::from_map(|s3_bucket_state: &S3BucketState| match s3_bucket_state {
+                        S3BucketState::None => None,
+                        S3BucketState::Some { name, .. } => Some(name.clone()),
+                    }),
+                    ValueSpec::from_map(|file_download_state: &FileDownloadState| {
+                        file_download_path(file_download_state)
+                            .file_name()
+                            .map(|file_name| file_name.to_string_lossy().to_string())
+                    }),
+                );
                 let s3_object_id = graph_builder.add_fn(
-                    S3ObjectItemSpec::<WebAppFileId>::new(item_spec_id!("s3_object")).into(),
+                    ItemSpecWrapper::from(S3ObjectItemSpec::<WebAppFileId>::new(item_spec_id!(
+                        "s3_object"
+                    )))
+                    .with_params_spec(s3_object_params_spec)
+                    .into(),
                 );
 
                 graph_builder.add_edges([
@@ -153,17 +188,7 @@ impl EnvDeployFlow {
         let instance_profile_params =
             InstanceProfileParams::<WebAppFileId>::new(instance_profile_name, path, true);
 
-        let s3_bucket_params = S3BucketParams::<WebAppFileId>::new(bucket_name.clone());
-        let s3_object_params = {
-            let web_app_path_local = app_download_params.dest().to_path_buf();
-            let object_key = web_app_path_local
-                .file_name()
-                .expect("Expected web app file name to exist.")
-                .to_string_lossy()
-                .to_string();
-
-            S3ObjectParams::<WebAppFileId>::new(web_app_path_local, bucket_name, object_key)
-        };
+        let s3_bucket_params = S3BucketParams::<WebAppFileId>::new(bucket_name);
 
         Ok(EnvDeployFlowParams {
             app_download_params,
@@ -172,11 +197,20 @@ impl EnvDeployFlow {
             iam_role_params,
             instance_profile_params,
             s3_bucket_params,
-            s3_object_params,
         })
     }
 }
 
+/// Returns the path of the downloaded file.
+fn file_download_path(file_download_state: &FileDownloadState) -> &Path {
+    match file_download_state {
+        FileDownloadState::None { path }
+        | FileDownloadState::StringContents { path, .. }
+        | FileDownloadState::Length { path, .. }
+        | FileDownloadState::Unknown { path } => path,
+    }
+}
+
 #[derive(Debug)]
 pub struct EnvDeployFlowParams {
     pub app_download_params: FileDownloadParams<WebAppFileId>,
@@ -185,5 +219,4 @@ pub struct EnvDeployFlowParams {
     pub iam_role_params: IamRoleParams<WebAppFileId>,
     pub instance_profile_params: InstanceProfileParams<WebAppFileId>,
     pub s3_bucket_params: S3BucketParams<WebAppFileId>,
-    pub s3_object_params: S3ObjectParams<WebAppFileId>,
 }
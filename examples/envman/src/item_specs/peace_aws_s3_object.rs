@@ -3,7 +3,8 @@
 pub use self::{
     s3_object_apply_op_spec::S3ObjectApplyOpSpec, s3_object_data::S3ObjectData,
     s3_object_error::S3ObjectError, s3_object_item_spec::S3ObjectItemSpec,
-    s3_object_params::S3ObjectParams, s3_object_state::S3ObjectState,
+    s3_object_params::{S3ObjectParams, S3ObjectParamsSpec},
+    s3_object_state::S3ObjectState,
     s3_object_state_current_fn_spec::S3ObjectStateCurrentFnSpec,
     s3_object_state_desired_fn_spec::S3ObjectStateDesiredFnSpec,
     s3_object_state_diff::S3ObjectStateDiff, s3_object_state_diff_fn_spec::S3ObjectStateDiffFnSpec,
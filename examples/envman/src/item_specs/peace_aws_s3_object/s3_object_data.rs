@@ -1,5 +1,5 @@
 use peace::data::{
-    accessors::{ROpt, R, W},
+    accessors::{ROpt, WOpt, R},
     Data,
 };
 
@@ -16,8 +16,8 @@ pub struct S3ObjectData<'op, Id>
 where
     Id: Send + Sync + 'static,
 {
-    /// S3Object state parameters.
-    params: W<'op, S3ObjectParams<Id>>,
+    /// S3Object state parameters, resolved from the `S3ObjectParamsSpec`.
+    params: WOpt<'op, S3ObjectParams<Id>>,
     /// IAM client to communicate with AWS.
     client: R<'op, aws_sdk_s3::Client>,
     /// Region to use to constrain S3 object.
@@ -29,11 +29,15 @@ where
     Id: Send + Sync + 'static,
 {
     pub fn params(&self) -> &S3ObjectParams<Id> {
-        &self.params
+        self.params
+            .as_ref()
+            .expect("Expected `S3ObjectParams` to be resolved before the item spec is run.")
     }
 
     pub fn params_mut(&mut self) -> &mut S3ObjectParams<Id> {
-        &mut self.params
+        self.params
+            .as_mut()
+            .expect("Expected `S3ObjectParams` to be resolved before the item spec is run.")
     }
 
     pub fn client(&self) -> &R<'op, aws_sdk_s3::Client> {
@@ -21,6 +21,12 @@ use crate::item_specs::peace_aws_s3_object::{
 /// The `Id` type parameter is needed for each S3 object params to be a
 /// distinct type.
 ///
+/// The params are resolved from an [`S3ObjectParamsSpec`], which must be set
+/// through [`ItemSpecWrapper::with_params_spec`].
+///
+/// [`ItemSpecWrapper::with_params_spec`]: peace::rt_model::ItemSpecWrapper::with_params_spec
+/// [`S3ObjectParamsSpec`]: crate::item_specs::peace_aws_s3_object::S3ObjectParamsSpec
+///
 /// # Type Parameters
 ///
 /// * `Id`: A zero-sized type used to distinguish different S3 object parameters
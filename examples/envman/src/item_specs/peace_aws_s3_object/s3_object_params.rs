@@ -4,6 +4,7 @@ use std::{
 };
 
 use derivative::Derivative;
+use peace::data::params::Params;
 use serde::{Deserialize, Serialize};
 
 /// S3Object item parameters.
@@ -11,11 +12,15 @@ use serde::{Deserialize, Serialize};
 /// The `Id` type parameter is needed for each S3 object params to be a
 /// distinct type.
 ///
+/// The derived [`S3ObjectParamsSpec`] allows the file path to be mapped from
+/// the `FileDownloadState`, and the bucket name to be mapped from the
+/// `S3BucketState`, so these params do not need to be stored.
+///
 /// # Type Parameters
 ///
 /// * `Id`: A zero-sized type used to distinguish different S3 object parameters
 ///   from each other.
-#[derive(Clone, Derivative, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Clone, Derivative, Params, PartialEq, Eq, Deserialize, Serialize)]
 #[derivative(Debug)]
 pub struct S3ObjectParams<Id> {
     /// Path to the file to upload.
@@ -11,7 +11,7 @@ cfg_if::cfg_if! {
     } else if #[cfg(target_arch = "wasm32")] {
         use std::path::Path;
 
-        use peace::rt_model::Storage;
+        use peace::rt_model::{storage::StorageBackend, Storage};
     }
 }
 
@@ -215,14 +215,14 @@ where
                     .text()
                     .await
                     .map_err(FileDownloadError::ResponseTextRead)?;
-                storage.set_item(dest_path, &value)?;
+                storage.write(dest_path, value.as_bytes()).await?;
             }
             StorageForm::Base64 => {
                 let bytes = response
                     .bytes()
                     .await
                     .map_err(FileDownloadError::ResponseBytesRead)?;
-                storage.set_item_b64(dest_path, &bytes)?;
+                storage.binary_write(dest_path, &bytes).await?;
             }
         }
 
@@ -358,7 +358,7 @@ where
                     .map_err(FileDownloadError::DestFileRemove)?;
 
                 #[cfg(target_arch = "wasm32")]
-                file_download_data.storage().remove_item(path)?;
+                file_download_data.storage().remove(path).await?;
 
                 Ok(file_download_state_desired.clone())
             }
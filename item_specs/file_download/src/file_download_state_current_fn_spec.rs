@@ -5,7 +5,7 @@ use peace::cfg::{async_trait, state::FetchedOpt, OpCtx, State, TryFnSpec};
 use tokio::{fs::File, io::AsyncReadExt};
 
 #[cfg(target_arch = "wasm32")]
-use peace::rt_model::Storage;
+use peace::rt_model::{storage::StorageBackend, Storage};
 
 use crate::{ETag, FileDownloadData, FileDownloadError, FileDownloadState};
 
@@ -49,7 +49,9 @@ impl<Id> FileDownloadStateCurrentFnSpec<Id> {
         storage: &Storage,
     ) -> Result<FileDownloadState, FileDownloadError> {
         let file_state = storage
-            .get_item_opt(dest)?
+            .read(dest)
+            .await?
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
             .map(|contents| {
                 contents
                     .bytes()
@@ -105,7 +107,7 @@ where
         #[cfg(not(target_arch = "wasm32"))]
         let file_exists = dest.exists();
         #[cfg(target_arch = "wasm32")]
-        let file_exists = file_download_data.storage().get_item_opt(dest)?.is_some();
+        let file_exists = file_download_data.storage().contains(dest).await?;
         if !file_exists {
             let path = dest.to_path_buf();
             return Ok(State::new(
@@ -10,5 +10,8 @@ pub enum StorageForm {
     /// Base64 encode the response bytes.
     ///
     /// This must be used if the response is binary.
+    ///
+    /// When the workspace is stored in IndexedDB, the bytes are stored as is
+    /// instead of being base64 encoded.
     Base64,
 }
@@ -16,23 +16,33 @@ impl<Id> ShCmdExecutor<Id> {
         sh_cmd: &ShCmd,
     ) -> Result<State<ShCmdState<Id>, ShCmdExecutionRecord>, ShCmdError> {
         let start_datetime = Utc::now();
+        let cmd_exec_fail = |error| {
+            #[cfg(feature = "error_reporting")]
+            let sh_cmd_string = format!("{sh_cmd}");
+
+            ShCmdError::CmdExecFail {
+                sh_cmd: sh_cmd.clone(),
+                #[cfg(feature = "error_reporting")]
+                sh_cmd_string,
+                error,
+            }
+        };
+
+        // The command stays in the terminal's foreground process group, so that
+        // `Ctrl-C` is also delivered to it.
+        //
+        // If execution is cancelled, e.g. when a timeout elapses, the command's
+        // process is killed.
         let mut command: Command = sh_cmd.into();
-        let output = command
+        let child = command
             .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
             .kill_on_drop(true)
-            .output()
-            .await
-            .map_err(|error| {
-                #[cfg(feature = "error_reporting")]
-                let sh_cmd_string = format!("{sh_cmd}");
+            .spawn()
+            .map_err(cmd_exec_fail)?;
 
-                ShCmdError::CmdExecFail {
-                    sh_cmd: sh_cmd.clone(),
-                    #[cfg(feature = "error_reporting")]
-                    sh_cmd_string,
-                    error,
-                }
-            })?;
+        let output = child.wait_with_output().await.map_err(cmd_exec_fail)?;
         let end_datetime = Utc::now();
 
         let stdout = String::from_utf8(output.stdout).map_err(|from_utf8_error| {
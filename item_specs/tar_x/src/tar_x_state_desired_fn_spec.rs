@@ -30,13 +30,13 @@ impl<Id> TarXStateDesiredFnSpec<Id> {
     }
 
     #[cfg(target_arch = "wasm32")]
-    pub fn files_in_tar(
+    pub async fn files_in_tar(
         storage: &Storage,
         tar_path: &Path,
     ) -> Result<Vec<FileMetadata>, TarXError> {
         use std::io::Cursor;
 
-        let bytes = storage.get_item_b64(tar_path)?;
+        let bytes = storage.binary_read(tar_path).await?;
         Self::tar_file_metadata(tar_path, Archive::new(Cursor::new(bytes)))
     }
 
@@ -107,9 +107,11 @@ where
         let tar_file_exists = tar_x_data.tar_x_params().tar_path().exists();
         #[cfg(target_arch = "wasm32")]
         let tar_file_exists = {
+            use peace::rt_model::storage::StorageBackend;
+
             let storage = tar_x_data.storage();
             let tar_path = tar_x_data.tar_x_params().tar_path();
-            storage.contains_item(tar_path)?
+            storage.contains(tar_path).await?
         };
 
         if tar_file_exists {
@@ -130,13 +132,14 @@ where
         #[cfg(not(target_arch = "wasm32"))]
         let tar_file_exists = tar_x_data.tar_x_params().tar_path().exists();
         #[cfg(target_arch = "wasm32")]
-        let tar_file_exists = storage.contains_item(tar_path)?;
+        let tar_file_exists = {
+            use peace::rt_model::storage::StorageBackend;
+
+            storage.contains(tar_path).await?
+        };
 
         if tar_file_exists {
-            #[cfg(not(target_arch = "wasm32"))]
             let files_in_tar = Self::files_in_tar(storage, tar_path).await?;
-            #[cfg(target_arch = "wasm32")]
-            let files_in_tar = Self::files_in_tar(storage, tar_path)?;
 
             Ok(FileMetadatas::from(files_in_tar))
         } else {
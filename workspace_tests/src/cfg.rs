@@ -1,3 +1,4 @@
+mod concurrency_group_id;
 mod flow_id;
 mod item_spec_id;
 mod item_spec_id_invalid_fmt;
@@ -6,4 +7,5 @@ mod profile_invalid_fmt;
 #[cfg(feature = "output_progress")]
 mod progress;
 mod saved;
+mod sensitive;
 mod state;
@@ -0,0 +1,129 @@
+use std::{borrow::Cow, str::FromStr};
+
+use peace::{
+    cfg::{ConcurrencyGroupId, ConcurrencyGroupIdInvalidFmt},
+    fmt::Presentable,
+};
+
+use crate::{FnInvocation, FnTrackerPresenter};
+
+#[test]
+fn from_str_returns_ok_owned_for_valid_id() -> Result<(), ConcurrencyGroupIdInvalidFmt<'static>> {
+    let concurrency_group_id = ConcurrencyGroupId::from_str("good_id")?;
+
+    assert_eq!("good_id", *concurrency_group_id);
+    Ok(())
+}
+
+#[test]
+fn underscore_is_valid_first_character() -> Result<(), ConcurrencyGroupIdInvalidFmt<'static>> {
+    let concurrency_group_id = ConcurrencyGroupId::new("_good_id")?;
+
+    assert_eq!("_good_id", *concurrency_group_id);
+    Ok(())
+}
+
+#[test]
+fn new_unchecked_does_not_validate_id() -> Result<(), ConcurrencyGroupIdInvalidFmt<'static>> {
+    let concurrency_group_id = ConcurrencyGroupId::new_unchecked("!valid");
+
+    assert_eq!("!valid", *concurrency_group_id);
+    Ok(())
+}
+
+#[test]
+fn try_from_str_returns_ok_borrowed_for_valid_id()
+-> Result<(), ConcurrencyGroupIdInvalidFmt<'static>> {
+    let concurrency_group_id = ConcurrencyGroupId::try_from("good_id")?;
+
+    assert_eq!("good_id", *concurrency_group_id);
+    Ok(())
+}
+
+#[test]
+fn try_from_string_returns_ok_owned_for_valid_id()
+-> Result<(), ConcurrencyGroupIdInvalidFmt<'static>> {
+    let concurrency_group_id = ConcurrencyGroupId::try_from(String::from("good_id"))?;
+
+    assert_eq!("good_id", *concurrency_group_id);
+    Ok(())
+}
+
+#[test]
+fn from_str_returns_err_owned_for_invalid_id() {
+    let error = ConcurrencyGroupId::from_str("has space").unwrap_err();
+
+    assert!(matches!(error.value(), Cow::Owned(_)));
+    assert_eq!("has space", error.value());
+}
+
+#[test]
+fn try_from_str_returns_err_borrowed_for_invalid_id() {
+    let error = ConcurrencyGroupId::try_from("has space").unwrap_err();
+
+    assert!(matches!(error.value(), Cow::Borrowed(_)));
+    assert_eq!("has space", error.value());
+}
+
+#[test]
+fn try_from_string_returns_err_owned_for_invalid_id() {
+    let error = ConcurrencyGroupId::try_from(String::from("has space")).unwrap_err();
+
+    assert!(matches!(error.value(), Cow::Owned(_)));
+    assert_eq!("has space", error.value());
+}
+
+#[test]
+fn display_returns_inner_str() -> Result<(), ConcurrencyGroupIdInvalidFmt<'static>> {
+    let concurrency_group_id = ConcurrencyGroupId::try_from("good_id")?;
+
+    assert_eq!("good_id", concurrency_group_id.to_string());
+    Ok(())
+}
+
+#[tokio::test]
+async fn present_uses_code_inline() -> Result<(), Box<dyn std::error::Error>> {
+    let mut presenter = FnTrackerPresenter::new();
+    let concurrency_group_id = ConcurrencyGroupId::try_from("concurrency_group_id")?;
+
+    concurrency_group_id.present(&mut presenter).await?;
+
+    assert_eq!(
+        vec![FnInvocation::new(
+            "code_inline",
+            vec![Some(r#""concurrency_group_id""#.to_string())]
+        )],
+        presenter.fn_invocations()
+    );
+    Ok(())
+}
+
+#[test]
+fn clone() -> Result<(), ConcurrencyGroupIdInvalidFmt<'static>> {
+    let concurrency_group_id_0 = ConcurrencyGroupId::new("id")?;
+    #[allow(clippy::redundant_clone)] // https://github.com/rust-lang/rust-clippy/issues/9011
+    let concurrency_group_id_1 = concurrency_group_id_0.clone();
+
+    assert_eq!(concurrency_group_id_0, concurrency_group_id_1);
+    Ok(())
+}
+
+#[test]
+fn debug() -> Result<(), ConcurrencyGroupIdInvalidFmt<'static>> {
+    let concurrency_group_id = ConcurrencyGroupId::new("id")?;
+
+    assert_eq!(
+        r#"ConcurrencyGroupId("id")"#,
+        format!("{concurrency_group_id:?}")
+    );
+    Ok(())
+}
+
+#[test]
+fn partial_eq_ne() -> Result<(), ConcurrencyGroupIdInvalidFmt<'static>> {
+    let concurrency_group_id_0 = ConcurrencyGroupId::new("id0")?;
+    let concurrency_group_id_1 = ConcurrencyGroupId::new("id1")?;
+
+    assert!(concurrency_group_id_0 != concurrency_group_id_1);
+    Ok(())
+}
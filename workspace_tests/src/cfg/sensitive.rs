@@ -0,0 +1,263 @@
+use peace::{
+    cfg::{
+        app_name, flow_id, item_spec_id, profile, AppName, FlowId, ItemSpecId, Profile, Sensitive,
+        SensitiveSource,
+    },
+    cmd::ctx::CmdCtx,
+    resources::{
+        internal::{FlowParamsFile, StatesMut},
+        paths::StatesSavedFile,
+        states::StatesSaved,
+    },
+    rt_model::{
+        output::{CliOutputBuilder, OutputFormat, OutputWrite},
+        Error, Flow, ItemSpecGraphBuilder, StatesSerializer, Storage, Workspace, WorkspaceSpec,
+    },
+};
+
+use crate::{no_op_output::NoOpOutput, PeaceTestError};
+
+#[test]
+fn value_returns_raw_value() {
+    let sensitive = Sensitive::new(String::from("s3cr3t"));
+
+    assert_eq!(Some("s3cr3t"), sensitive.value().map(String::as_str));
+    assert_eq!(Some(String::from("s3cr3t")), sensitive.into_value());
+}
+
+#[test]
+fn display_is_redacted() {
+    let sensitive = Sensitive::new(String::from("s3cr3t"));
+
+    assert_eq!("********", sensitive.to_string());
+}
+
+#[test]
+fn debug_is_redacted() {
+    let sensitive = Sensitive::new(String::from("s3cr3t"));
+
+    assert_eq!(r#"Sensitive("********")"#, format!("{sensitive:?}"));
+}
+
+#[test]
+fn clone() {
+    let sensitive = Sensitive::new(String::from("s3cr3t"));
+    let sensitive_clone = sensitive.clone();
+
+    assert_eq!(sensitive, sensitive_clone);
+    assert_eq!(Some("s3cr3t"), sensitive_clone.value().map(String::as_str));
+}
+
+#[test]
+fn eq_compares_source_and_value() {
+    let sensitive = Sensitive::new(String::from("s3cr3t"));
+
+    assert_eq!(sensitive, Sensitive::new(String::from("s3cr3t")));
+    assert_ne!(sensitive, Sensitive::new(String::from("other")));
+    assert_ne!(
+        sensitive,
+        Sensitive::<String>::from_env("PEACE_TEST_SENSITIVE_EQ")
+    );
+}
+
+#[test]
+fn from_env_reads_value_from_env_var() {
+    std::env::set_var("PEACE_TEST_SENSITIVE_FROM_ENV", "s3cr3t");
+
+    let sensitive = Sensitive::<String>::from_env("PEACE_TEST_SENSITIVE_FROM_ENV");
+
+    assert_eq!(
+        &SensitiveSource::Env(String::from("PEACE_TEST_SENSITIVE_FROM_ENV")),
+        sensitive.source()
+    );
+    assert_eq!(Some("s3cr3t"), sensitive.value().map(String::as_str));
+}
+
+#[test]
+fn from_env_value_is_none_when_env_var_not_set() {
+    let sensitive = Sensitive::<String>::from_env("PEACE_TEST_SENSITIVE_NOT_SET");
+
+    assert_eq!(None, sensitive.value());
+}
+
+#[test]
+fn serialize_writes_redacted_source_instead_of_value() -> Result<(), serde_yaml::Error> {
+    let sensitive = Sensitive::new(String::from("s3cr3t"));
+
+    let serialized = serde_yaml::to_string(&sensitive)?;
+
+    assert_eq!("Redacted\n", serialized);
+    Ok(())
+}
+
+#[test]
+fn serialize_writes_env_source_instead_of_value() -> Result<(), serde_yaml::Error> {
+    std::env::set_var("PEACE_TEST_SENSITIVE_SERIALIZE", "s3cr3t");
+    let sensitive = Sensitive::<String>::from_env("PEACE_TEST_SENSITIVE_SERIALIZE");
+
+    let serialized = serde_yaml::to_string(&sensitive)?;
+
+    assert_eq!("!Env PEACE_TEST_SENSITIVE_SERIALIZE\n", serialized);
+    Ok(())
+}
+
+#[test]
+fn deserialize_reads_value_from_env_source() -> Result<(), serde_yaml::Error> {
+    std::env::set_var("PEACE_TEST_SENSITIVE_DESERIALIZE", "s3cr3t");
+
+    let deserialized =
+        serde_yaml::from_str::<Sensitive<String>>("!Env PEACE_TEST_SENSITIVE_DESERIALIZE")?;
+
+    assert_eq!(Some("s3cr3t"), deserialized.value().map(String::as_str));
+    Ok(())
+}
+
+#[test]
+fn deserialize_redacted_source_without_value() -> Result<(), serde_yaml::Error> {
+    let sensitive = Sensitive::new(String::from("s3cr3t"));
+    let serialized = serde_yaml::to_string(&sensitive)?;
+
+    let deserialized = serde_yaml::from_str::<Sensitive<String>>(&serialized)?;
+
+    assert_eq!(&SensitiveSource::Redacted, deserialized.source());
+    assert_eq!(None, deserialized.value());
+    Ok(())
+}
+
+#[test]
+fn deserialize_returns_error_when_source_invalid() {
+    let error = serde_yaml::from_str::<Sensitive<String>>("s3cr3t").unwrap_err();
+
+    assert!(
+        error.to_string().contains("unknown variant `s3cr3t`"),
+        "Expected invalid source error, but was: {error}"
+    );
+}
+
+#[tokio::test]
+async fn present_is_redacted() -> Result<(), Box<dyn std::error::Error>> {
+    let mut buffer = Vec::new();
+    let mut cli_output = CliOutputBuilder::new_with_writer(&mut buffer)
+        .with_outcome_format(OutputFormat::Text)
+        .build();
+    let sensitive = Sensitive::new(String::from("s3cr3t"));
+
+    <_ as OutputWrite<Error>>::present(&mut cli_output, &sensitive).await?;
+
+    assert_eq!("********", String::from_utf8(buffer)?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn present_yaml_is_redacted() -> Result<(), Box<dyn std::error::Error>> {
+    let mut buffer = Vec::new();
+    let mut cli_output = CliOutputBuilder::new_with_writer(&mut buffer)
+        .with_outcome_format(OutputFormat::Yaml)
+        .build();
+    let sensitive = Sensitive::new(String::from("s3cr3t"));
+
+    <_ as OutputWrite<Error>>::present(&mut cli_output, &sensitive).await?;
+
+    let output = String::from_utf8(buffer)?;
+    assert!(
+        !output.contains("s3cr3t"),
+        "Expected output to be redacted, but was: {output}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn states_file_is_redacted() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = Storage::default();
+    let states_saved_file = StatesSavedFile::new(tempdir.path().join("states_saved.yaml"));
+    let sensitive = Sensitive::new(String::from("s3cr3t"));
+
+    let states = {
+        let mut states = StatesMut::new();
+        states.insert(item_spec_id!("a"), sensitive.clone());
+        StatesSaved::from(states)
+    };
+    StatesSerializer::<Error>::serialize(&storage, &states, &states_saved_file).await?;
+
+    let serialized = tokio::fs::read_to_string(states_saved_file).await?;
+    assert_eq!("a: Redacted\n", serialized);
+    Ok(())
+}
+
+#[tokio::test]
+async fn params_file_is_redacted() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let flow =
+        Flow::<PeaceTestError>::new(flow_id!("test_flow"), ItemSpecGraphBuilder::new().build());
+    std::env::set_var("PEACE_TEST_SENSITIVE_PARAMS_FILE", "s3cr3t");
+    let sensitive = Sensitive::<String>::from_env("PEACE_TEST_SENSITIVE_PARAMS_FILE");
+
+    let mut output = NoOpOutput;
+    let cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .with_flow_param_value(String::from("token"), Some(sensitive))
+        .build()
+        .await?;
+
+    let resources = cmd_ctx.resources();
+    let flow_params_file = resources.borrow::<FlowParamsFile>();
+    let serialized = tokio::fs::read_to_string(&*flow_params_file).await?;
+    assert_eq!("token: !Env PEACE_TEST_SENSITIVE_PARAMS_FILE\n", serialized);
+    // Item specs still have access to the raw value.
+    assert_eq!(
+        Some("s3cr3t"),
+        resources
+            .borrow::<Sensitive<String>>()
+            .value()
+            .map(String::as_str)
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn params_file_value_is_read_again_by_later_commands()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let flow =
+        Flow::<PeaceTestError>::new(flow_id!("test_flow"), ItemSpecGraphBuilder::new().build());
+    std::env::set_var("PEACE_TEST_SENSITIVE_PARAMS_RELOAD", "s3cr3t");
+
+    let mut output = NoOpOutput;
+    CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .with_flow_param_value(
+            String::from("token"),
+            Some(Sensitive::<String>::from_env(
+                "PEACE_TEST_SENSITIVE_PARAMS_RELOAD",
+            )),
+        )
+        .build()
+        .await?;
+    let cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .with_flow_param_value(String::from("token"), None::<Sensitive<String>>)
+        .build()
+        .await?;
+
+    let resources = cmd_ctx.resources();
+    assert_eq!(
+        Some("s3cr3t"),
+        resources
+            .borrow::<Sensitive<String>>()
+            .value()
+            .map(String::as_str)
+    );
+    Ok(())
+}
@@ -1,10 +1,18 @@
 use peace::{
-    cfg::{app_name, flow_id, profile, AppName, FlowId, Profile},
+    cfg::{app_name, flow_id, profile, AppName, FlowId, InterruptSignal, ItemSpec, Profile},
     cmd::ctx::CmdCtxBuilder,
-    rt_model::{Flow, ItemSpecGraphBuilder, Workspace},
+    data::{accessors::RMaybe, Data},
+    resources::{
+        paths::{ProfileDir, ProfileLockFile},
+        states::{FlowsStatesSaved, StatesSaved},
+    },
+    rt::cmds::{EnsureCmd, StatesDiscoverCmd},
+    rt_model::{
+        params::ParamsLimits, Error, Flow, ItemSpecGraphBuilder, ProfileLockMode, Workspace,
+    },
 };
 
-use crate::{no_op_output::NoOpOutput, PeaceTestError};
+use crate::{no_op_output::NoOpOutput, PeaceTestError, VecCopyItemSpec, VecCopyState};
 
 #[tokio::test]
 async fn single_profile_single_flow_getters() -> Result<(), Box<dyn std::error::Error>> {
@@ -33,6 +41,497 @@ async fn single_profile_single_flow_getters() -> Result<(), Box<dyn std::error::
     Ok(())
 }
 
+#[tokio::test]
+async fn with_concurrency_limit_sets_concurrency_limit() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(tempdir, app_name!("test_single_profile_single_flow"))?;
+    let profile = profile!("test_profile");
+    let flow_id = flow_id!("test_flow_id");
+    let flow = Flow::<PeaceTestError>::new(flow_id, ItemSpecGraphBuilder::new().build());
+
+    let mut output = NoOpOutput;
+    let cmd_ctx = CmdCtxBuilder::single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile)
+        .with_flow(&flow)
+        .build()
+        .await?;
+    assert_eq!(None, cmd_ctx.concurrency_limit());
+
+    let cmd_ctx = cmd_ctx.with_concurrency_limit(4);
+    assert_eq!(Some(4), cmd_ctx.concurrency_limit());
+
+    let cmd_ctx = cmd_ctx.with_concurrency_limit(0);
+    assert_eq!(Some(1), cmd_ctx.concurrency_limit());
+    Ok(())
+}
+
+#[tokio::test]
+async fn with_lock_locks_profile_until_cmd_ctx_is_dropped() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tempdir = tempfile::tempdir()?;
+    let workspace = {
+        let workspace_spec = peace::rt_model::WorkspaceSpec::Path(tempdir.path().to_path_buf());
+        Workspace::new(app_name!("test_single_profile_single_flow"), workspace_spec)?
+    };
+    let profile = profile!("test_profile");
+    let flow_id = flow_id!("test_flow_id");
+    let flow = Flow::<PeaceTestError>::new(flow_id, ItemSpecGraphBuilder::new().build());
+
+    let mut output = NoOpOutput;
+    let cmd_ctx = CmdCtxBuilder::single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile.clone())
+        .with_flow(&flow)
+        .with_lock(ProfileLockMode::Fail)
+        .build()
+        .await?;
+    let profile_lock_file = ProfileLockFile::from(cmd_ctx.profile_dir());
+    assert_eq!(
+        Some(&profile_lock_file),
+        cmd_ctx
+            .profile_lock()
+            .map(|profile_lock| profile_lock.profile_lock_file())
+    );
+
+    let mut output_other = NoOpOutput;
+    let error = CmdCtxBuilder::single_profile_single_flow(&mut output_other, &workspace)
+        .with_profile(profile.clone())
+        .with_flow(&flow)
+        .with_lock(ProfileLockMode::Fail)
+        .build()
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            &error,
+            PeaceTestError::PeaceRtError(Error::ProfileLocked { profile: profile_locked, .. })
+            if profile_locked == &profile
+        ),
+        "Expected error to be `ProfileLocked`, but was {error:?}"
+    );
+
+    drop(cmd_ctx);
+    assert!(!profile_lock_file.exists());
+
+    let mut output_other = NoOpOutput;
+    let cmd_ctx_other = CmdCtxBuilder::single_profile_single_flow(&mut output_other, &workspace)
+        .with_profile(profile)
+        .with_flow(&flow)
+        .build()
+        .await?;
+    assert!(cmd_ctx_other.profile_lock().is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn with_profile_concurrency_limit_sets_profile_concurrency_limit()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(tempdir, app_name!("test_multi_profile_single_flow"))?;
+    let flow_id = flow_id!("test_flow_id");
+    let flow = Flow::<PeaceTestError>::new(flow_id, ItemSpecGraphBuilder::new().build());
+
+    let mut output = NoOpOutput;
+    CmdCtxBuilder::single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .build()
+        .await?;
+    let cmd_ctx = CmdCtxBuilder::multi_profile_single_flow(&mut output, &workspace)
+        .with_flow(&flow)
+        .build()
+        .await?;
+    assert_eq!(None, cmd_ctx.profile_concurrency_limit());
+
+    let cmd_ctx = cmd_ctx.with_profile_concurrency_limit(4);
+    assert_eq!(Some(4), cmd_ctx.profile_concurrency_limit());
+
+    let cmd_ctx = cmd_ctx.with_profile_concurrency_limit(0);
+    assert_eq!(Some(1), cmd_ctx.profile_concurrency_limit());
+    Ok(())
+}
+
+#[tokio::test]
+async fn profile_cmd_ctx_returns_single_profile_single_flow_cmd_ctx_for_profile()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(tempdir, app_name!("test_multi_profile_single_flow"))?;
+    let profile = profile!("test_profile");
+    let flow_id = flow_id!("test_flow_id");
+    let flow = Flow::<PeaceTestError>::new(flow_id, ItemSpecGraphBuilder::new().build());
+
+    let mut output = NoOpOutput;
+    CmdCtxBuilder::single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile.clone())
+        .with_flow(&flow)
+        .build()
+        .await?;
+    let cmd_ctx = CmdCtxBuilder::multi_profile_single_flow(&mut output, &workspace)
+        .with_flow(&flow)
+        .build()
+        .await?;
+
+    let mut profile_output = NoOpOutput;
+    let profile_cmd_ctx = cmd_ctx
+        .profile_cmd_ctx(&mut profile_output, &profile)
+        .await?;
+
+    assert_eq!(&profile, profile_cmd_ctx.profile());
+    assert_eq!(
+        cmd_ctx.profile_dirs().get(&profile),
+        Some(profile_cmd_ctx.profile_dir())
+    );
+    assert_eq!(
+        cmd_ctx.flow_dirs().get(&profile),
+        Some(profile_cmd_ctx.flow_dir())
+    );
+    assert_eq!(&profile, &*profile_cmd_ctx.resources().borrow::<Profile>());
+    Ok(())
+}
+
+#[tokio::test]
+async fn profile_cmd_ctx_returns_error_when_profile_not_in_scope()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(tempdir, app_name!("test_multi_profile_single_flow"))?;
+    let flow_id = flow_id!("test_flow_id");
+    let flow = Flow::<PeaceTestError>::new(flow_id, ItemSpecGraphBuilder::new().build());
+
+    let mut output = NoOpOutput;
+    CmdCtxBuilder::single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .build()
+        .await?;
+    let cmd_ctx = CmdCtxBuilder::multi_profile_single_flow(&mut output, &workspace)
+        .with_flow(&flow)
+        .build()
+        .await?;
+
+    let mut profile_output = NoOpOutput;
+    let result = cmd_ctx
+        .profile_cmd_ctx(&mut profile_output, &profile!("other_profile"))
+        .await;
+
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceTestError::PeaceRtError(Error::ProfileNotInScope {
+                profile,
+                profiles_in_scope,
+            }))
+            if profile == &profile!("other_profile")
+            && profiles_in_scope == &[profile!("test_profile")]
+        ),
+        "Expected `Error::ProfileNotInScope`, but was {:?}",
+        result.map(|_| ())
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn profile_resources_are_set_up_for_profile() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(tempdir, app_name!("test_multi_profile_single_flow"))?;
+    let flow_id = flow_id!("test_flow_id");
+    let flow = Flow::<PeaceTestError>::new(flow_id, ItemSpecGraphBuilder::new().build());
+
+    let mut output = NoOpOutput;
+    for profile in [profile!("profile_0"), profile!("profile_1")] {
+        CmdCtxBuilder::single_profile_single_flow(&mut output, &workspace)
+            .with_profile(profile)
+            .with_flow(&flow)
+            .build()
+            .await?;
+    }
+    let cmd_ctx = CmdCtxBuilder::multi_profile_single_flow(&mut output, &workspace)
+        .with_flow(&flow)
+        .build()
+        .await?;
+
+    let resources = cmd_ctx.profile_resources(&profile!("profile_1")).await?;
+
+    assert_eq!(profile!("profile_1"), *resources.borrow::<Profile>());
+    assert_eq!(
+        cmd_ctx.profile_dirs().get(&profile!("profile_1")),
+        Some(&*resources.borrow::<ProfileDir>())
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn profile_cmd_ctx_shares_interrupt_signal_and_params_limits()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(tempdir, app_name!("test_multi_profile_single_flow"))?;
+    let profile = profile!("test_profile");
+    let flow_id = flow_id!("test_flow_id");
+    let flow = Flow::<PeaceTestError>::new(flow_id, ItemSpecGraphBuilder::new().build());
+
+    let mut output = NoOpOutput;
+    CmdCtxBuilder::single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile.clone())
+        .with_flow(&flow)
+        .build()
+        .await?;
+    let cmd_ctx = CmdCtxBuilder::multi_profile_single_flow(&mut output, &workspace)
+        .with_flow(&flow)
+        .with_params_limits(ParamsLimits::new().with_override(true))
+        .build()
+        .await?;
+
+    let mut profile_output = NoOpOutput;
+    let profile_cmd_ctx = cmd_ctx
+        .profile_cmd_ctx(&mut profile_output, &profile)
+        .await?;
+    cmd_ctx.interrupt_signal().interrupt();
+
+    let resources = profile_cmd_ctx.resources();
+    assert!(resources.borrow::<InterruptSignal>().is_interrupted());
+    assert!(resources.borrow::<ParamsLimits>().overridden());
+    Ok(())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[tokio::test]
+async fn profile_cmd_ctx_locks_profile_when_built_with_lock()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(tempdir, app_name!("test_multi_profile_single_flow"))?;
+    let profile = profile!("test_profile");
+    let flow_id = flow_id!("test_flow_id");
+    let flow = Flow::<PeaceTestError>::new(flow_id, ItemSpecGraphBuilder::new().build());
+
+    let mut output = NoOpOutput;
+    CmdCtxBuilder::single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile.clone())
+        .with_flow(&flow)
+        .build()
+        .await?;
+    let cmd_ctx = CmdCtxBuilder::multi_profile_single_flow(&mut output, &workspace)
+        .with_flow(&flow)
+        .with_lock(ProfileLockMode::Fail)
+        .build()
+        .await?;
+
+    let mut profile_output = NoOpOutput;
+    let profile_cmd_ctx = cmd_ctx
+        .profile_cmd_ctx(&mut profile_output, &profile)
+        .await?;
+    let profile_lock_file = ProfileLockFile::from(profile_cmd_ctx.profile_dir());
+    assert!(profile_lock_file.exists());
+
+    let mut profile_output_other = NoOpOutput;
+    let result = cmd_ctx
+        .profile_cmd_ctx(&mut profile_output_other, &profile)
+        .await;
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceTestError::PeaceRtError(Error::ProfileLocked { profile: profile_locked, .. }))
+            if profile_locked == &profile
+        ),
+        "Expected error to be `ProfileLocked`, but was {:?}",
+        result.map(|_| ())
+    );
+
+    drop(profile_cmd_ctx);
+    assert!(!profile_lock_file.exists());
+    Ok(())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[tokio::test]
+async fn single_profile_multi_flow_with_lock_locks_profile_until_cmd_ctx_is_dropped()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(tempdir, app_name!("test_single_profile_multi_flow"))?;
+    let profile = profile!("test_profile");
+    let flow_network = vec_copy_flow(flow_id!("network"));
+    let flow_app = vec_copy_flow(flow_id!("app"));
+
+    let mut output = NoOpOutput;
+    let cmd_ctx = CmdCtxBuilder::single_profile_multi_flow(&mut output, &workspace)
+        .with_profile(profile.clone())
+        .with_flows([&flow_network, &flow_app])
+        .with_lock(ProfileLockMode::Fail)
+        .build()
+        .await?;
+    let profile_lock_file = ProfileLockFile::from(cmd_ctx.profile_dir());
+    assert_eq!(
+        Some(&profile_lock_file),
+        cmd_ctx
+            .profile_lock()
+            .map(|profile_lock| profile_lock.profile_lock_file())
+    );
+
+    // Each flow's `CmdCtx` runs under this command's lock, instead of taking its own.
+    let mut flow_output = NoOpOutput;
+    let flow_cmd_ctx = cmd_ctx
+        .flow_cmd_ctx(&mut flow_output, flow_app.flow_id())
+        .await?;
+    assert!(flow_cmd_ctx.profile_lock().is_none());
+    drop(flow_cmd_ctx);
+    assert!(profile_lock_file.exists());
+
+    let mut output_other = NoOpOutput;
+    let error = CmdCtxBuilder::single_profile_single_flow(&mut output_other, &workspace)
+        .with_profile(profile.clone())
+        .with_flow(&flow_app)
+        .with_lock(ProfileLockMode::Fail)
+        .build()
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            &error,
+            PeaceTestError::PeaceRtError(Error::ProfileLocked { profile: profile_locked, .. })
+            if profile_locked == &profile
+        ),
+        "Expected error to be `ProfileLocked`, but was {error:?}"
+    );
+
+    drop(cmd_ctx);
+    assert!(!profile_lock_file.exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn flow_cmd_ctx_returns_single_profile_single_flow_cmd_ctx_with_flows_states_saved()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(tempdir, app_name!("test_single_profile_multi_flow"))?;
+    let profile = profile!("test_profile");
+    let flow_network = vec_copy_flow(flow_id!("network"));
+    let flow_app = vec_copy_flow(flow_id!("app"));
+
+    // Only ensure the `network` flow.
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtxBuilder::single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile.clone())
+        .with_flow(&flow_network)
+        .build()
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx, &StatesSaved::from(states_current)).await?;
+    drop(cmd_ctx);
+
+    let cmd_ctx = CmdCtxBuilder::single_profile_multi_flow(&mut output, &workspace)
+        .with_profile(profile.clone())
+        .with_flows([&flow_network, &flow_app])
+        .build()
+        .await?;
+
+    let mut flow_output = NoOpOutput;
+    let flow_cmd_ctx = cmd_ctx
+        .flow_cmd_ctx(&mut flow_output, flow_app.flow_id())
+        .await?;
+
+    assert_eq!(&profile, flow_cmd_ctx.profile());
+    assert_eq!(flow_app.flow_id(), flow_cmd_ctx.flow().flow_id());
+    assert_eq!(
+        cmd_ctx.flow_dirs().get(flow_app.flow_id()),
+        Some(flow_cmd_ctx.flow_dir())
+    );
+    let resources = flow_cmd_ctx.resources();
+    assert!(resources.try_borrow::<StatesSaved>().is_err());
+    let flows_states_saved = RMaybe::<FlowsStatesSaved>::borrow(VecCopyItemSpec.id(), resources);
+    assert_eq!(
+        Some(VecCopyState::from(vec![0, 1, 2, 3, 4, 5, 6, 7])).as_ref(),
+        flows_states_saved
+            .as_ref()
+            .and_then(|flows_states_saved| flows_states_saved.get(flow_network.flow_id()))
+            .and_then(|states_saved| states_saved.get::<VecCopyState, _>(VecCopyItemSpec.id()))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn flow_cmd_ctx_shares_interrupt_signal_and_inserts_flow_params()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(tempdir, app_name!("test_single_profile_multi_flow"))?;
+    let profile = profile!("test_profile");
+    let flow_network = vec_copy_flow(flow_id!("network"));
+    let flow_app = vec_copy_flow(flow_id!("app"));
+
+    let mut output = NoOpOutput;
+    CmdCtxBuilder::single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile.clone())
+        .with_flow(&flow_app)
+        .with_flow_param_value(String::from("flow_param_0"), Some(456u16))
+        .build()
+        .await?;
+    let cmd_ctx = CmdCtxBuilder::single_profile_multi_flow(&mut output, &workspace)
+        .with_profile(profile.clone())
+        .with_flows([&flow_network, &flow_app])
+        .with_flow_params_k::<String>()
+        .with_flow_param::<u16>(String::from("flow_param_0"))
+        .build()
+        .await?;
+
+    let mut flow_output = NoOpOutput;
+    let flow_cmd_ctx = cmd_ctx
+        .flow_cmd_ctx(&mut flow_output, flow_app.flow_id())
+        .await?;
+    cmd_ctx.interrupt_signal().interrupt();
+
+    assert_eq!(
+        Some(456u16),
+        flow_cmd_ctx
+            .flow_params()
+            .get::<u16, _>("flow_param_0")
+            .copied()
+    );
+    let resources = flow_cmd_ctx.resources();
+    assert!(resources.borrow::<InterruptSignal>().is_interrupted());
+    Ok(())
+}
+
+#[tokio::test]
+async fn flow_cmd_ctx_returns_error_when_flow_not_in_scope()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(tempdir, app_name!("test_single_profile_multi_flow"))?;
+    let flow_network = vec_copy_flow(flow_id!("network"));
+
+    let mut output = NoOpOutput;
+    let cmd_ctx = CmdCtxBuilder::single_profile_multi_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flows([&flow_network])
+        .build()
+        .await?;
+
+    let mut flow_output = NoOpOutput;
+    let result = cmd_ctx
+        .flow_cmd_ctx(&mut flow_output, &flow_id!("app"))
+        .await;
+
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceTestError::PeaceRtError(Error::FlowNotInScope {
+                flow_id,
+                flow_ids_in_scope,
+            }))
+            if flow_id == &flow_id!("app")
+            && flow_ids_in_scope == &[flow_id!("network")]
+        ),
+        "Expected `Error::FlowNotInScope`, but was {:?}",
+        result.map(|_| ())
+    );
+    Ok(())
+}
+
+fn vec_copy_flow(flow_id: FlowId) -> Flow<PeaceTestError> {
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    Flow::new(flow_id, graph)
+}
+
 fn workspace(
     tempdir: tempfile::TempDir,
     app_name: AppName,
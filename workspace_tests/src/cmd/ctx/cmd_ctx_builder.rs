@@ -9,6 +9,7 @@ use peace::{
     },
     rt_model::{
         params::{FlowParams, ProfileParams, WorkspaceParams},
+        storage::SerializedStorage,
         Error, Storage, Workspace, WorkspaceSpec,
     },
 };
@@ -16,6 +17,7 @@ use peace::{
 mod multi_profile_no_flow;
 mod multi_profile_single_flow_builder;
 mod no_profile_no_flow_builder;
+mod single_profile_multi_flow_builder;
 mod single_profile_no_flow_builder;
 mod single_profile_single_flow_builder;
 
@@ -50,9 +52,8 @@ async fn workspace_with(
     workspace_params.insert(String::from("profile"), profiles_existing[0].clone());
     workspace_params.insert(String::from("ws_param_1"), String::from("ws_param_1_value"));
 
-    Storage
+    Storage::default()
         .serialized_write(
-            crate::fn_name_short!().to_string(),
             &workspace_params_file,
             &workspace_params,
             Error::WorkspaceParamsSerialize,
@@ -72,9 +73,8 @@ async fn workspace_with(
             profile_params.insert(String::from("profile_param_0"), 1u32);
             profile_params.insert(String::from("profile_param_1"), 2u64);
 
-            Storage
+            Storage::default()
                 .serialized_write(
-                    crate::fn_name_short!().to_string(),
                     &profile_params_file,
                     &profile_params,
                     Error::ProfileParamsSerialize,
@@ -90,13 +90,8 @@ async fn workspace_with(
                 flow_params.insert(String::from("flow_param_0"), true);
                 flow_params.insert(String::from("flow_param_1"), 456u16);
 
-                Storage
-                    .serialized_write(
-                        crate::fn_name_short!().to_string(),
-                        &flow_params_file,
-                        &flow_params,
-                        Error::FlowParamsSerialize,
-                    )
+                Storage::default()
+                    .serialized_write(&flow_params_file, &flow_params, Error::FlowParamsSerialize)
                     .await?;
             }
 
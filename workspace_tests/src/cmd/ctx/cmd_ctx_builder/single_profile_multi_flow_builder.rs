@@ -0,0 +1,190 @@
+use peace::{
+    cfg::{app_name, flow_id, profile, AppName, FlowId, ItemSpec, Profile},
+    cmd::ctx::CmdCtx,
+    resources::{
+        paths::{FlowDir, ProfileDir, ProfileHistoryDir},
+        states::StatesSaved,
+    },
+    rt::cmds::{EnsureCmd, StatesDiscoverCmd},
+    rt_model::{Flow, ItemSpecGraphBuilder},
+};
+
+use crate::{
+    cmd::ctx::cmd_ctx_builder::workspace, no_op_output::NoOpOutput, PeaceTestError,
+    VecCopyItemSpec, VecCopyState,
+};
+
+#[tokio::test]
+async fn build() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(&tempdir, app_name!("test_single_profile_multi_flow"))?;
+    let profile = profile!("test_profile");
+    let flow_network =
+        Flow::<PeaceTestError>::new(flow_id!("network"), ItemSpecGraphBuilder::new().build());
+    let flow_app =
+        Flow::<PeaceTestError>::new(flow_id!("app"), ItemSpecGraphBuilder::new().build());
+
+    let mut output = NoOpOutput;
+    let cmd_ctx = CmdCtx::builder_single_profile_multi_flow(&mut output, &workspace)
+        .with_profile(profile.clone())
+        .with_flows([&flow_network, &flow_app])
+        .build()
+        .await?;
+
+    let peace_app_dir = workspace.dirs().peace_app_dir();
+    let profile_dir = ProfileDir::from((peace_app_dir, &profile));
+    let profile_history_dir = ProfileHistoryDir::from(&profile_dir);
+    let flow_dir_network = FlowDir::from((&profile_dir, flow_network.flow_id()));
+    let flow_dir_app = FlowDir::from((&profile_dir, flow_app.flow_id()));
+
+    let scope = cmd_ctx.scope();
+    assert!(std::ptr::eq(&workspace, cmd_ctx.workspace()));
+    assert_eq!(peace_app_dir, cmd_ctx.workspace().dirs().peace_app_dir());
+    assert_eq!(&profile, scope.profile());
+    assert_eq!(&profile_dir, scope.profile_dir());
+    assert_eq!(&profile_history_dir, scope.profile_history_dir());
+    assert_eq!(
+        vec![flow_network.flow_id(), flow_app.flow_id()],
+        scope
+            .flows()
+            .iter()
+            .map(|flow| flow.flow_id())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Some(&flow_dir_network),
+        scope.flow_dirs().get(flow_network.flow_id())
+    );
+    assert_eq!(
+        Some(&flow_dir_app),
+        scope.flow_dirs().get(flow_app.flow_id())
+    );
+    assert!(flow_dir_network.exists());
+    assert!(flow_dir_app.exists());
+    assert!(scope.flows_states_saved().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn build_with_workspace_params_and_profile_params() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(&tempdir, app_name!("test_single_profile_multi_flow"))?;
+    let profile = profile!("test_profile");
+    let flow_network =
+        Flow::<PeaceTestError>::new(flow_id!("network"), ItemSpecGraphBuilder::new().build());
+    let flow_app =
+        Flow::<PeaceTestError>::new(flow_id!("app"), ItemSpecGraphBuilder::new().build());
+
+    let mut output = NoOpOutput;
+    let cmd_ctx = CmdCtx::builder_single_profile_multi_flow(&mut output, &workspace)
+        .with_workspace_param_value(String::from("profile"), Some(profile.clone()))
+        .with_profile_param_value(String::from("profile_param_0"), Some(1u32))
+        .with_profile_from_workspace_param(&String::from("profile"))
+        .with_flows([&flow_network, &flow_app])
+        .build()
+        .await?;
+
+    let scope = cmd_ctx.scope();
+    assert_eq!(&profile, scope.profile());
+    assert_eq!(Some(&profile), scope.workspace_params().get("profile"));
+    assert_eq!(Some(&1u32), scope.profile_params().get("profile_param_0"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn build_reads_saved_states_of_each_flow() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(&tempdir, app_name!("test_single_profile_multi_flow"))?;
+    let profile = profile!("test_profile");
+    let flow_network = vec_copy_flow(flow_id!("network"));
+    let flow_app = vec_copy_flow(flow_id!("app"));
+
+    // Only ensure the `network` flow.
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile.clone())
+        .with_flow(&flow_network)
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx, &StatesSaved::from(states_current)).await?;
+    drop(cmd_ctx);
+
+    let cmd_ctx = CmdCtx::builder_single_profile_multi_flow(&mut output, &workspace)
+        .with_profile(profile.clone())
+        .with_flows([&flow_network, &flow_app])
+        .await?;
+
+    let flows_states_saved = cmd_ctx.scope().flows_states_saved();
+    assert_eq!(
+        Some(VecCopyState::from(vec![0, 1, 2, 3, 4, 5, 6, 7])).as_ref(),
+        flows_states_saved
+            .get(flow_network.flow_id())
+            .and_then(|states_saved| states_saved.get::<VecCopyState, _>(VecCopyItemSpec.id()))
+    );
+    assert!(flows_states_saved.get(flow_app.flow_id()).is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn build_with_flow_params_reads_flow_params_of_each_flow()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(&tempdir, app_name!("test_single_profile_multi_flow"))?;
+    let profile = profile!("test_profile");
+    let flow_network =
+        Flow::<PeaceTestError>::new(flow_id!("network"), ItemSpecGraphBuilder::new().build());
+    let flow_app =
+        Flow::<PeaceTestError>::new(flow_id!("app"), ItemSpecGraphBuilder::new().build());
+
+    // Only store flow params for the `network` flow.
+    let mut output = NoOpOutput;
+    CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile.clone())
+        .with_flow(&flow_network)
+        .with_flow_param_value(String::from("flow_param_0"), Some(true))
+        .with_flow_param_value(String::from("flow_param_1"), Some(456u16))
+        .build()
+        .await?;
+
+    let cmd_ctx = CmdCtx::builder_single_profile_multi_flow(&mut output, &workspace)
+        .with_profile(profile.clone())
+        .with_flows([&flow_network, &flow_app])
+        .with_flow_params_k::<String>()
+        .with_flow_param::<bool>(String::from("flow_param_0"))
+        .with_flow_param::<u16>(String::from("flow_param_1"))
+        .build()
+        .await?;
+
+    let flow_id_to_flow_params = cmd_ctx.scope().flow_id_to_flow_params();
+    let flow_params_network = flow_id_to_flow_params.get(flow_network.flow_id());
+    assert_eq!(
+        Some(true),
+        flow_params_network
+            .and_then(|flow_params| flow_params.get::<bool, _>("flow_param_0"))
+            .copied()
+    );
+    assert_eq!(
+        Some(456u16),
+        flow_params_network
+            .and_then(|flow_params| flow_params.get::<u16, _>("flow_param_1"))
+            .copied()
+    );
+    assert_eq!(
+        Some(true),
+        flow_id_to_flow_params
+            .get(flow_app.flow_id())
+            .map(|flow_params| flow_params.is_empty())
+    );
+    Ok(())
+}
+
+fn vec_copy_flow(flow_id: FlowId) -> Flow<PeaceTestError> {
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    Flow::new(flow_id, graph)
+}
@@ -1,8 +1,15 @@
 use peace::{
     cfg::{app_name, flow_id, profile, AppName, FlowId, Profile},
     cmd::ctx::CmdCtx,
-    resources::paths::{FlowDir, ProfileDir, ProfileHistoryDir},
-    rt_model::{Flow, ItemSpecGraphBuilder},
+    resources::{
+        internal::WorkspaceParamsFile,
+        paths::{FlowDir, ProfileDir, ProfileHistoryDir},
+    },
+    rt_model::{
+        params::{ParamsLimitViolation, ParamsLimits},
+        storage::InMemoryStorage,
+        Error, Flow, ItemSpecGraphBuilder, Storage,
+    },
 };
 
 use crate::{
@@ -387,3 +394,107 @@ async fn build_with_workspace_params_with_profile_params_with_profile_from_param
     assert_flow_params(resources).await?;
     Ok(())
 }
+
+#[tokio::test]
+async fn build_with_in_memory_storage() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let in_memory_storage = InMemoryStorage::new();
+    let workspace = workspace(&tempdir, app_name!("test_single_profile_single_flow"))?
+        .with_storage(Storage::new(in_memory_storage.clone()));
+    let profile = profile!("test_profile");
+    let flow_id = flow_id!("test_flow_id");
+    let flow = Flow::<PeaceTestError>::new(flow_id, ItemSpecGraphBuilder::new().build());
+
+    let mut output = NoOpOutput;
+    CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile.clone())
+        .with_flow(&flow)
+        .with_workspace_param_value(String::from("profile"), Some(profile.clone()))
+        .build()
+        .await?;
+
+    let peace_app_dir = workspace.dirs().peace_app_dir();
+    let workspace_params_file = WorkspaceParamsFile::from(peace_app_dir);
+    assert!(!peace_app_dir.exists());
+    assert!(in_memory_storage
+        .paths()
+        .contains(&workspace_params_file.to_path_buf()));
+
+    // Workspace params are read back from the in-memory storage.
+    let cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_workspace_params_k::<String>()
+        .with_workspace_param::<Profile>(String::from("profile"))
+        .with_profile_from_workspace_param(&String::from("profile"))
+        .with_flow(&flow)
+        .build()
+        .await?;
+
+    assert_eq!(&profile, cmd_ctx.scope().profile());
+    Ok(())
+}
+
+#[tokio::test]
+async fn build_returns_error_when_params_exceed_limits() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(&tempdir, app_name!("test_single_profile_single_flow"))?;
+    let profile = profile!("test_profile");
+    let flow_id = flow_id!("test_flow_id");
+    let flow = Flow::<PeaceTestError>::new(flow_id, ItemSpecGraphBuilder::new().build());
+
+    let mut output = NoOpOutput;
+    let cmd_ctx_result = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile.clone())
+        .with_flow_param_value(String::from("flow_param_1"), Some(456u16))
+        .with_flow(&flow)
+        .with_params_limits(params_limits())
+        .build()
+        .await;
+
+    assert!(
+        matches!(
+            &cmd_ctx_result,
+            Err(PeaceTestError::PeaceRtError(Error::ParamsLimitsExceeded { violations }))
+            if violations == &[ParamsLimitViolation::new(
+                "u16",
+                String::from("value 456 exceeds limit 100"),
+            )]
+        ),
+        "Expected `Error::ParamsLimitsExceeded`, but was {:?}",
+        cmd_ctx_result.map(|_| ())
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn build_with_params_limits_overridden() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = workspace(&tempdir, app_name!("test_single_profile_single_flow"))?;
+    let profile = profile!("test_profile");
+    let flow_id = flow_id!("test_flow_id");
+    let flow = Flow::<PeaceTestError>::new(flow_id, ItemSpecGraphBuilder::new().build());
+
+    let mut output = NoOpOutput;
+    let cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile.clone())
+        .with_flow_param_value(String::from("flow_param_1"), Some(456u16))
+        .with_flow(&flow)
+        .with_params_limits(params_limits().with_override(true))
+        .build()
+        .await?;
+
+    let resources = cmd_ctx.resources();
+    let params_limits = resources.borrow::<ParamsLimits>();
+    assert!(params_limits.overridden());
+    assert_eq!(1, params_limits.violations(resources)?.len());
+    Ok(())
+}
+
+fn params_limits() -> ParamsLimits {
+    ParamsLimits::new().with_limit(|value: &u16| {
+        if *value > 100 {
+            Err(format!("value {value} exceeds limit 100"))
+        } else {
+            Ok(())
+        }
+    })
+}
@@ -1,3 +1,4 @@
 mod derive;
+mod params;
 mod r_maybe;
 mod w_maybe;
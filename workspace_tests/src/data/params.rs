@@ -0,0 +1,2 @@
+mod params_derive;
+mod value_spec;
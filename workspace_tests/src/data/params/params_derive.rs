@@ -0,0 +1,94 @@
+use std::marker::PhantomData;
+
+use peace::data::{
+    marker::Current,
+    params::{Params, ParamsResolveError, ParamsSpec, ValueResolutionMode, ValueSpec},
+    Resources,
+};
+
+#[test]
+fn spec_resolves_stored_value_and_mapped_values() {
+    let mut resources = Resources::new();
+    resources.insert(ObjectParams::<ObjectId>::new(
+        String::from("stored_bucket"),
+        String::from("stored_key"),
+    ));
+    resources.insert(Current(Some(BucketState(String::from("bucket")))));
+
+    let params_spec = ObjectParamsSpec::<ObjectId>::new(
+        ValueSpec::from_map(|bucket_state: &BucketState| Some(bucket_state.0.clone())),
+        ValueSpec::Stored,
+    );
+    let params = params_spec.resolve(&resources, ValueResolutionMode::Current);
+
+    assert_eq!(
+        Ok(ObjectParams::new(
+            String::from("bucket"),
+            String::from("stored_key")
+        )),
+        params
+    );
+}
+
+#[test]
+fn spec_from_params_resolves_to_params() {
+    let resources = Resources::new();
+    let params = ObjectParams::<ObjectId>::new(String::from("bucket"), String::from("key"));
+
+    let params_spec = <ObjectParams<ObjectId> as Params>::Spec::from(params.clone());
+
+    assert_eq!(
+        Ok(params),
+        params_spec.resolve(&resources, ValueResolutionMode::Current)
+    );
+}
+
+#[test]
+fn spec_resolve_returns_error_when_params_not_stored() {
+    let resources = Resources::new();
+
+    let params_spec = ObjectParamsSpec::<ObjectId>::new(String::from("bucket"), ValueSpec::Stored);
+
+    assert_eq!(
+        Err(ParamsResolveError::Stored {
+            field_name: "object_key"
+        }),
+        params_spec.resolve(&resources, ValueResolutionMode::Current)
+    );
+}
+
+#[test]
+fn spec_clone_and_debug() {
+    let params_spec = ObjectParamsSpec::<ObjectId>::new(String::from("bucket"), ValueSpec::Stored);
+
+    assert_eq!(
+        "ObjectParamsSpec { bucket_name: Value(\"bucket\"), object_key: Stored }",
+        format!("{:?}", params_spec.clone())
+    );
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Params)]
+pub struct ObjectParams<Id> {
+    /// Name of the bucket to insert the object into.
+    bucket_name: String,
+    /// Key for the object.
+    object_key: String,
+    /// Marker for unique object parameters type.
+    marker: PhantomData<Id>,
+}
+
+impl<Id> ObjectParams<Id> {
+    fn new(bucket_name: String, object_key: String) -> Self {
+        Self {
+            bucket_name,
+            object_key,
+            marker: PhantomData,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ObjectId;
+
+#[derive(Debug)]
+struct BucketState(String);
@@ -0,0 +1,154 @@
+use peace::data::{
+    marker::{ApplyDry, Current, Desired},
+    params::{ParamsResolveError, ValueResolutionMode, ValueSpec},
+    Resources,
+};
+
+#[test]
+fn resolve_stored_returns_stored_value() {
+    let resources = Resources::new();
+    let value_spec = ValueSpec::<String>::Stored;
+
+    let value = value_spec.resolve(
+        &resources,
+        ValueResolutionMode::Current,
+        "name",
+        Some(&String::from("stored")),
+    );
+
+    assert_eq!(Ok(String::from("stored")), value);
+}
+
+#[test]
+fn resolve_stored_returns_error_when_not_stored() {
+    let resources = Resources::new();
+    let value_spec = ValueSpec::<String>::Stored;
+
+    let value = value_spec.resolve(&resources, ValueResolutionMode::Current, "name", None);
+
+    assert_eq!(
+        Err(ParamsResolveError::Stored { field_name: "name" }),
+        value
+    );
+}
+
+#[test]
+fn resolve_value_returns_value() {
+    let resources = Resources::new();
+    let value_spec = ValueSpec::from(String::from("value"));
+
+    let value = value_spec.resolve(
+        &resources,
+        ValueResolutionMode::Current,
+        "name",
+        Some(&String::from("stored")),
+    );
+
+    assert_eq!(Ok(String::from("value")), value);
+}
+
+#[test]
+fn resolve_from_map_current_maps_current_state() {
+    let mut resources = Resources::new();
+    resources.insert(Current(Some(BucketState::from("current"))));
+    resources.insert(Desired(Some(BucketState::from("desired"))));
+
+    let value = bucket_name_spec().resolve(&resources, ValueResolutionMode::Current, "name", None);
+
+    assert_eq!(Ok(String::from("current")), value);
+}
+
+#[test]
+fn resolve_from_map_current_does_not_fall_back_to_desired_state() {
+    let mut resources = Resources::new();
+    resources.insert(Current::<BucketState>(None));
+    resources.insert(Desired(Some(BucketState::from("desired"))));
+
+    let value = bucket_name_spec().resolve(&resources, ValueResolutionMode::Current, "name", None);
+
+    assert_eq!(
+        Err(ParamsResolveError::FromMap {
+            field_name: "name",
+            from_type_name: std::any::type_name::<BucketState>(),
+            value_resolution_mode: ValueResolutionMode::Current,
+        }),
+        value
+    );
+}
+
+#[test]
+fn resolve_from_map_desired_maps_desired_state() {
+    let mut resources = Resources::new();
+    resources.insert(Current(Some(BucketState::from("current"))));
+    resources.insert(Desired(Some(BucketState::from("desired"))));
+
+    let value = bucket_name_spec().resolve(&resources, ValueResolutionMode::Desired, "name", None);
+
+    assert_eq!(Ok(String::from("desired")), value);
+}
+
+#[test]
+fn resolve_from_map_apply_dry_falls_back_to_current_state() {
+    let mut resources = Resources::new();
+    resources.insert(ApplyDry::<BucketState>(None));
+    resources.insert(Current(Some(BucketState::from("current"))));
+
+    let value = bucket_name_spec().resolve(&resources, ValueResolutionMode::ApplyDry, "name", None);
+
+    assert_eq!(Ok(String::from("current")), value);
+}
+
+#[test]
+fn resolve_from_map_uses_fallback_when_mapping_fn_returns_none() {
+    let mut resources = Resources::new();
+    resources.insert(Desired(Some(BucketState(None))));
+    resources.insert(Current(Some(BucketState::from("current"))));
+
+    let value = bucket_name_spec().resolve(&resources, ValueResolutionMode::Desired, "name", None);
+
+    assert_eq!(Ok(String::from("current")), value);
+}
+
+#[test]
+fn resolve_from_map_returns_error_when_state_not_available() {
+    let mut resources = Resources::new();
+    resources.insert(Current::<BucketState>(None));
+
+    let value = bucket_name_spec().resolve(&resources, ValueResolutionMode::Desired, "name", None);
+
+    assert_eq!(
+        Err(ParamsResolveError::FromMap {
+            field_name: "name",
+            from_type_name: std::any::type_name::<BucketState>(),
+            value_resolution_mode: ValueResolutionMode::Desired,
+        }),
+        value
+    );
+}
+
+#[test]
+fn params_resolve_error_display() {
+    let error = ParamsResolveError::FromMap {
+        field_name: "name",
+        from_type_name: "BucketState",
+        value_resolution_mode: ValueResolutionMode::ApplyDry,
+    };
+
+    assert_eq!(
+        "`name` could not be mapped from the apply dry `BucketState`.",
+        error.to_string()
+    );
+}
+
+fn bucket_name_spec() -> ValueSpec<String> {
+    ValueSpec::from_map(|bucket_state: &BucketState| bucket_state.0.clone())
+}
+
+#[derive(Debug)]
+struct BucketState(Option<String>);
+
+impl From<&str> for BucketState {
+    fn from(name: &str) -> Self {
+        Self(Some(String::from(name)))
+    }
+}
@@ -1,15 +1,23 @@
+use std::time::Duration;
+
 use peace::{
-    cfg::{app_name, item_spec_id, profile, AppName, FlowId, ItemSpecId, Profile, State},
+    cfg::{
+        app_name, concurrency_group_id, item_spec_id, profile, AppName, ConcurrencyGroupId, FlowId,
+        InterruptSignal, ItemSpecId, Profile, State,
+    },
     cmd::ctx::CmdCtx,
     data::marker::Clean,
     resources::states::StatesSaved,
     rt::cmds::{
-        sub::{StatesCurrentDiscoverCmd, StatesDesiredDiscoverCmd, StatesSavedReadCmd},
-        CleanCmd, DiffCmd, EnsureCmd, StatesDiscoverCmd,
+        sub::{
+            ApplyErrorMode, StatesCurrentDiscoverCmd, StatesDesiredDiscoverCmd, StatesSavedReadCmd,
+        },
+        CleanCmd, DiffCmd, EnsureCmd, HistoryListCmd, StatesDiscoverCmd,
     },
     rt_model::{
-        outcomes::CmdOutcome, Flow, InMemoryTextOutput, ItemSpecGraphBuilder, Workspace,
-        WorkspaceSpec,
+        outcomes::{CmdOutcome, ItemApplyStatus},
+        Flow, InMemoryTextOutput, ItemSpecGraphBuilder, ItemSpecSelection, ItemSpecWrapper,
+        RetryPolicy, Workspace, WorkspaceSpec,
     },
 };
 use peace_item_specs::sh_cmd::{
@@ -107,6 +115,204 @@ impl TestFileCreationShCmdItemSpec {
 
         ShCmdItemSpec::new(Self::ID, Some(sh_cmd_params))
     }
+
+    /// Returns a new `TestFileCreationShCmdItemSpec` whose apply exec shell
+    /// command does not complete in a reasonable time.
+    #[cfg(unix)]
+    pub fn new_apply_exec_hang() -> ShCmdItemSpec<Self> {
+        test_file_creation_sh_cmd_item_spec(
+            Self::ID,
+            include_str!("sh_cmd_item_spec/unix/test_file_creation_apply_check.sh"),
+            "sleep 30",
+        )
+    }
+}
+
+/// Fails to apply, using the `test_file_creation` state shell commands.
+#[derive(Clone, Copy, Debug)]
+pub struct TestApplyFailShCmdItemSpec;
+
+impl TestApplyFailShCmdItemSpec {
+    /// ID
+    pub const ID: ItemSpecId = item_spec_id!("test_apply_fail");
+
+    /// Returns a new `TestApplyFailShCmdItemSpec`.
+    ///
+    /// The apply check outputs a non-boolean value, so applying this item spec
+    /// fails regardless of whether another item spec has created `test_file`.
+    #[cfg(unix)]
+    pub fn new() -> ShCmdItemSpec<Self> {
+        test_file_creation_sh_cmd_item_spec(Self::ID, "printf 'maybe'", "true")
+    }
+}
+
+/// Depends on `TestApplyFailShCmdItemSpec`, using the `test_file_creation`
+/// shell commands.
+#[derive(Clone, Copy, Debug)]
+pub struct TestApplyFailDependentShCmdItemSpec;
+
+impl TestApplyFailDependentShCmdItemSpec {
+    /// ID
+    pub const ID: ItemSpecId = item_spec_id!("test_apply_fail_dependent");
+
+    /// Returns a new `TestApplyFailDependentShCmdItemSpec`.
+    #[cfg(unix)]
+    pub fn new() -> ShCmdItemSpec<Self> {
+        test_file_creation_sh_cmd_item_spec(
+            Self::ID,
+            include_str!("sh_cmd_item_spec/unix/test_file_creation_apply_check.sh"),
+            include_str!("sh_cmd_item_spec/unix/test_file_creation_apply_exec.sh"),
+        )
+    }
+}
+
+/// Sleeps when applied, using the `test_file_creation` state shell commands.
+///
+/// `N` distinguishes the state types of multiple instances of this item spec.
+#[derive(Clone, Copy, Debug)]
+pub struct TestApplySleepShCmdItemSpec<const N: u8>;
+
+impl<const N: u8> TestApplySleepShCmdItemSpec<N> {
+    /// Returns a new `TestApplySleepShCmdItemSpec` whose apply exec shell
+    /// command sleeps for the given number of seconds.
+    #[cfg(unix)]
+    pub fn new(item_spec_id: ItemSpecId, seconds: &str) -> ShCmdItemSpec<Self> {
+        test_file_creation_sh_cmd_item_spec(
+            item_spec_id,
+            "printf 'true'",
+            &format!("sleep {seconds}"),
+        )
+    }
+}
+
+/// Fails to discover its current state on the first attempt, using the
+/// `test_file_creation` shell commands.
+#[derive(Clone, Copy, Debug)]
+pub struct TestStateCurrentFlakyShCmdItemSpec;
+
+impl TestStateCurrentFlakyShCmdItemSpec {
+    /// ID
+    pub const ID: ItemSpecId = item_spec_id!("test_state_current_flaky");
+
+    /// Returns a new `TestStateCurrentFlakyShCmdItemSpec`.
+    ///
+    /// The state current shell command outputs non-UTF-8 bytes, which fails
+    /// discovery, if the `state_current_attempted` file does not exist, and
+    /// creates it.
+    #[cfg(unix)]
+    pub fn new() -> ShCmdItemSpec<Self> {
+        let state_clean_sh_cmd = ShCmd::new("bash").arg("-c").arg(include_str!(
+            "sh_cmd_item_spec/unix/test_file_creation_state_clean.sh"
+        ));
+        let state_current_sh_cmd = ShCmd::new("bash").arg("-c").arg(concat!(
+            "if ! test -f 'state_current_attempted'; then ",
+            "touch 'state_current_attempted'; printf '\\xff'; exit; ",
+            "fi\n",
+            include_str!("sh_cmd_item_spec/unix/test_file_creation_state_current.sh")
+        ));
+        let state_desired_sh_cmd = ShCmd::new("bash").arg("-c").arg(include_str!(
+            "sh_cmd_item_spec/unix/test_file_creation_state_desired.sh"
+        ));
+        let state_diff_sh_cmd = ShCmd::new("bash").arg("-c").arg(include_str!(
+            "sh_cmd_item_spec/unix/test_file_creation_state_diff.sh"
+        ));
+        let apply_check_sh_cmd = ShCmd::new("bash").arg("-c").arg(include_str!(
+            "sh_cmd_item_spec/unix/test_file_creation_apply_check.sh"
+        ));
+        let apply_exec_sh_cmd = ShCmd::new("bash").arg("-c").arg(include_str!(
+            "sh_cmd_item_spec/unix/test_file_creation_apply_exec.sh"
+        ));
+        let sh_cmd_params = ShCmdParams::<Self>::new(
+            state_clean_sh_cmd,
+            state_current_sh_cmd,
+            state_desired_sh_cmd,
+            state_diff_sh_cmd,
+            apply_check_sh_cmd,
+            apply_exec_sh_cmd,
+        );
+
+        ShCmdItemSpec::new(Self::ID, Some(sh_cmd_params))
+    }
+}
+
+/// Does not complete discovering its current state in a reasonable time,
+/// using the `test_file_creation` shell commands.
+#[derive(Clone, Copy, Debug)]
+pub struct TestStateCurrentHangShCmdItemSpec;
+
+impl TestStateCurrentHangShCmdItemSpec {
+    /// ID
+    pub const ID: ItemSpecId = item_spec_id!("test_state_current_hang");
+
+    /// Returns a new `TestStateCurrentHangShCmdItemSpec`.
+    ///
+    /// The state current shell command creates the
+    /// `state_current_completed` file after a second.
+    #[cfg(unix)]
+    pub fn new() -> ShCmdItemSpec<Self> {
+        let state_clean_sh_cmd = ShCmd::new("bash").arg("-c").arg(include_str!(
+            "sh_cmd_item_spec/unix/test_file_creation_state_clean.sh"
+        ));
+        let state_current_sh_cmd = ShCmd::new("bash")
+            .arg("-c")
+            .arg("sleep 1; touch 'state_current_completed'");
+        let state_desired_sh_cmd = ShCmd::new("bash").arg("-c").arg(include_str!(
+            "sh_cmd_item_spec/unix/test_file_creation_state_desired.sh"
+        ));
+        let state_diff_sh_cmd = ShCmd::new("bash").arg("-c").arg(include_str!(
+            "sh_cmd_item_spec/unix/test_file_creation_state_diff.sh"
+        ));
+        let apply_check_sh_cmd = ShCmd::new("bash").arg("-c").arg(include_str!(
+            "sh_cmd_item_spec/unix/test_file_creation_apply_check.sh"
+        ));
+        let apply_exec_sh_cmd = ShCmd::new("bash").arg("-c").arg(include_str!(
+            "sh_cmd_item_spec/unix/test_file_creation_apply_exec.sh"
+        ));
+        let sh_cmd_params = ShCmdParams::<Self>::new(
+            state_clean_sh_cmd,
+            state_current_sh_cmd,
+            state_desired_sh_cmd,
+            state_diff_sh_cmd,
+            apply_check_sh_cmd,
+            apply_exec_sh_cmd,
+        );
+
+        ShCmdItemSpec::new(Self::ID, Some(sh_cmd_params))
+    }
+}
+
+/// Returns a `ShCmdItemSpec` that uses the `test_file_creation` state shell
+/// commands, with the given apply check and exec shell scripts.
+#[cfg(unix)]
+fn test_file_creation_sh_cmd_item_spec<Id>(
+    item_spec_id: ItemSpecId,
+    apply_check_script: &str,
+    apply_exec_script: &str,
+) -> ShCmdItemSpec<Id> {
+    let state_clean_sh_cmd = ShCmd::new("bash").arg("-c").arg(include_str!(
+        "sh_cmd_item_spec/unix/test_file_creation_state_clean.sh"
+    ));
+    let state_current_sh_cmd = ShCmd::new("bash").arg("-c").arg(include_str!(
+        "sh_cmd_item_spec/unix/test_file_creation_state_current.sh"
+    ));
+    let state_desired_sh_cmd = ShCmd::new("bash").arg("-c").arg(include_str!(
+        "sh_cmd_item_spec/unix/test_file_creation_state_desired.sh"
+    ));
+    let state_diff_sh_cmd = ShCmd::new("bash").arg("-c").arg(include_str!(
+        "sh_cmd_item_spec/unix/test_file_creation_state_diff.sh"
+    ));
+    let apply_check_sh_cmd = ShCmd::new("bash").arg("-c").arg(apply_check_script);
+    let apply_exec_sh_cmd = ShCmd::new("bash").arg("-c").arg(apply_exec_script);
+    let sh_cmd_params = ShCmdParams::<Id>::new(
+        state_clean_sh_cmd,
+        state_current_sh_cmd,
+        state_desired_sh_cmd,
+        state_diff_sh_cmd,
+        apply_check_sh_cmd,
+        apply_exec_sh_cmd,
+    );
+
+    ShCmdItemSpec::new(item_spec_id, Some(sh_cmd_params))
 }
 
 #[tokio::test]
@@ -301,6 +507,10 @@ async fn ensure_when_creation_required_executes_apply_exec_shell_command()
     let CmdOutcome {
         value: states_ensured,
         errors: _,
+        retries: _,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
     } = EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
 
     let state_ensured = states_ensured
@@ -363,6 +573,10 @@ async fn ensure_when_exists_sync_does_not_reexecute_apply_exec_shell_command()
     let CmdOutcome {
         value: states_ensured,
         errors: _,
+        retries: _,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
     } = EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
 
     let state_ensured = states_ensured
@@ -423,6 +637,10 @@ async fn clean_when_exists_sync_executes_shell_command() -> Result<(), Box<dyn s
     let CmdOutcome {
         value: states_cleaned,
         errors: _,
+        retries: _,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
     } = CleanCmd::exec(&mut cmd_ctx, &states_saved).await?;
 
     let state_cleaned = states_cleaned
@@ -442,3 +660,560 @@ async fn clean_when_exists_sync_executes_shell_command() -> Result<(), Box<dyn s
 
     Ok(())
 }
+
+#[cfg(unix)]
+#[tokio::test]
+async fn ensure_when_item_spec_timeout_elapses_returns_item_spec_timeout_error()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<ShCmdError>::new();
+        graph_builder.add_fn(
+            ItemSpecWrapper::from(TestFileCreationShCmdItemSpec::new_apply_exec_hang())
+                .with_timeout(Duration::from_millis(200))
+                .into(),
+        );
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = InMemoryTextOutput::new();
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+
+    // Discover states current and desired
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    let states_saved = StatesSaved::from(states_current);
+
+    let CmdOutcome {
+        value: _,
+        errors,
+        retries: _,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
+    } = EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
+
+    let error = errors.get(&TestFileCreationShCmdItemSpec::ID);
+    assert!(
+        matches!(
+            error,
+            Some(ShCmdError::PeaceRtError(
+                peace::rt_model::Error::ItemSpecTimeout {
+                    item_spec_id,
+                    timeout,
+                }
+            ))
+            if item_spec_id == &TestFileCreationShCmdItemSpec::ID
+                && *timeout == Duration::from_millis(200)
+        ),
+        "Expected error to be `ItemSpecTimeout`, but was {error:?}"
+    );
+    assert!(!tempdir.path().join("test_file").exists());
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn ensure_when_cmd_timeout_elapses_completes_in_progress_item_specs_as_timed_out()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<ShCmdError>::new();
+        let sleep_0_id = graph_builder.add_fn(
+            TestApplySleepShCmdItemSpec::<0>::new(item_spec_id!("sleep_0"), "0.5").into(),
+        );
+        let sleep_1_id = graph_builder.add_fn(
+            TestApplySleepShCmdItemSpec::<1>::new(item_spec_id!("sleep_1"), "0.5").into(),
+        );
+        graph_builder.add_edge(sleep_0_id, sleep_1_id)?;
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = InMemoryTextOutput::new();
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?
+        .with_timeout(Duration::from_millis(200));
+
+    // Discover states current and desired
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    let states_saved = StatesSaved::from(states_current);
+
+    let CmdOutcome {
+        value: _,
+        errors,
+        retries: _,
+        item_statuses,
+        interrupted,
+        history_write_error: _,
+    } = EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
+
+    let error = errors.get(&item_spec_id!("sleep_0"));
+    assert!(
+        matches!(
+            error,
+            Some(ShCmdError::PeaceRtError(
+                peace::rt_model::Error::CmdTimeout {
+                    item_spec_id,
+                    timeout,
+                }
+            ))
+            if item_spec_id == &item_spec_id!("sleep_0")
+                && *timeout == Duration::from_millis(200)
+        ),
+        "Expected error to be `CmdTimeout`, but was {error:?}"
+    );
+    assert_eq!(1, errors.len());
+    assert!(!interrupted);
+    assert_eq!(
+        Some(&ItemApplyStatus::TimedOut),
+        item_statuses.get(&item_spec_id!("sleep_0"))
+    );
+    assert_eq!(
+        Some(&ItemApplyStatus::NotReached),
+        item_statuses.get(&item_spec_id!("sleep_1"))
+    );
+
+    // The history record also records the item spec as timed out.
+    let history_summaries = HistoryListCmd::exec(&mut cmd_ctx).await?;
+    let history_summary_ensure = history_summaries
+        .last()
+        .expect("Expected `EnsureCmd` to append a history record.");
+    assert_eq!(
+        Some(&ItemApplyStatus::TimedOut),
+        history_summary_ensure
+            .item_statuses
+            .get(&item_spec_id!("sleep_0"))
+    );
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn discover_when_cmd_timeout_elapses_returns_cmd_timeout_error_and_kills_processes()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<ShCmdError>::new();
+        graph_builder.add_fn(TestStateCurrentHangShCmdItemSpec::new().into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = InMemoryTextOutput::new();
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?
+        .with_timeout(Duration::from_millis(200));
+
+    let CmdOutcome {
+        value: (states_current, _states_desired),
+        errors,
+        retries: _,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
+    } = StatesDiscoverCmd::current_and_desired_with_outcome(&mut cmd_ctx).await?;
+
+    let error = errors.get(&TestStateCurrentHangShCmdItemSpec::ID);
+    assert!(
+        matches!(
+            error,
+            Some(ShCmdError::PeaceRtError(
+                peace::rt_model::Error::CmdTimeout {
+                    item_spec_id,
+                    timeout,
+                }
+            ))
+            if item_spec_id == &TestStateCurrentHangShCmdItemSpec::ID
+                && *timeout == Duration::from_millis(200)
+        ),
+        "Expected error to be `CmdTimeout`, but was {error:?}"
+    );
+    assert!(
+        states_current
+            .get_raw(&TestStateCurrentHangShCmdItemSpec::ID)
+            .is_none()
+    );
+
+    // The shell command is killed, so it does not complete.
+    peace::rt_model::time::sleep(Duration::from_millis(1500)).await;
+    assert!(!tempdir.path().join("state_current_completed").exists());
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn ensure_with_stop_on_error_does_not_reach_item_specs_after_failure()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<ShCmdError>::new();
+        let fail_id = graph_builder.add_fn(TestApplyFailShCmdItemSpec::new().into());
+        let fail_dependent_id =
+            graph_builder.add_fn(TestApplyFailDependentShCmdItemSpec::new().into());
+        graph_builder.add_edge(fail_id, fail_dependent_id)?;
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = InMemoryTextOutput::new();
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+
+    // Discover states current and desired
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    let states_saved = StatesSaved::from(states_current);
+
+    let CmdOutcome {
+        value: _,
+        errors,
+        retries: _,
+        item_statuses,
+        interrupted,
+        history_write_error: _,
+    } = EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
+
+    assert!(errors.contains_key(&TestApplyFailShCmdItemSpec::ID));
+    assert_eq!(1, errors.len());
+    assert!(!interrupted);
+    assert_eq!(
+        Some(&ItemApplyStatus::Fail),
+        item_statuses.get(&TestApplyFailShCmdItemSpec::ID)
+    );
+    assert_eq!(
+        Some(&ItemApplyStatus::NotReached),
+        item_statuses.get(&TestApplyFailDependentShCmdItemSpec::ID)
+    );
+    assert!(!tempdir.path().join("test_file").exists());
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn ensure_with_continue_on_error_applies_item_specs_independent_of_failure()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<ShCmdError>::new();
+        let fail_id = graph_builder.add_fn(TestApplyFailShCmdItemSpec::new().into());
+        let fail_dependent_id =
+            graph_builder.add_fn(TestApplyFailDependentShCmdItemSpec::new().into());
+        graph_builder.add_fn(TestFileCreationShCmdItemSpec::new().into());
+        graph_builder.add_edge(fail_id, fail_dependent_id)?;
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = InMemoryTextOutput::new();
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+
+    // Discover states current and desired
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    let states_saved = StatesSaved::from(states_current);
+
+    let CmdOutcome {
+        value: _,
+        errors,
+        retries: _,
+        item_statuses,
+        interrupted: _,
+        history_write_error: _,
+    } = EnsureCmd::exec_with_error_mode(
+        &mut cmd_ctx,
+        &states_saved,
+        ApplyErrorMode::ContinueOnError,
+    )
+    .await?;
+
+    assert!(errors.contains_key(&TestApplyFailShCmdItemSpec::ID));
+    assert_eq!(1, errors.len());
+    assert_eq!(
+        Some(&ItemApplyStatus::Fail),
+        item_statuses.get(&TestApplyFailShCmdItemSpec::ID)
+    );
+    assert_eq!(
+        Some(&ItemApplyStatus::UpstreamFailed),
+        item_statuses.get(&TestApplyFailDependentShCmdItemSpec::ID)
+    );
+    assert_eq!(
+        Some(&ItemApplyStatus::Success),
+        item_statuses.get(&TestFileCreationShCmdItemSpec::ID)
+    );
+    assert!(tempdir.path().join("test_file").exists());
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn ensure_applies_item_specs_in_concurrency_group_up_to_group_limit()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<ShCmdError>::new();
+        graph_builder.add_concurrency_group(concurrency_group_id!("sleepers"), 1);
+        graph_builder.add_fn(
+            ItemSpecWrapper::from(TestApplySleepShCmdItemSpec::<0>::new(
+                item_spec_id!("sleep_0"),
+                "0.3",
+            ))
+            .with_concurrency_group(concurrency_group_id!("sleepers"))
+            .into(),
+        );
+        graph_builder.add_fn(
+            ItemSpecWrapper::from(TestApplySleepShCmdItemSpec::<1>::new(
+                item_spec_id!("sleep_1"),
+                "0.3",
+            ))
+            .with_concurrency_group(concurrency_group_id!("sleepers"))
+            .into(),
+        );
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = InMemoryTextOutput::new();
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+
+    // Discover states current and desired
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    let states_saved = StatesSaved::from(states_current);
+
+    let start = std::time::Instant::now();
+    let CmdOutcome {
+        value: _,
+        errors,
+        retries: _,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
+    } = EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
+    let elapsed = start.elapsed();
+
+    // Both item specs sleep for 0.3 seconds, and are not applied concurrently.
+    assert!(errors.is_empty());
+    assert!(
+        elapsed >= Duration::from_millis(600),
+        "Expected item specs to be applied sequentially, but took {elapsed:?}."
+    );
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn ensure_when_interrupted_while_applying_completes_in_progress_item_specs_only()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<ShCmdError>::new();
+        let sleep_0_id = graph_builder.add_fn(
+            TestApplySleepShCmdItemSpec::<0>::new(item_spec_id!("sleep_0"), "0.3").into(),
+        );
+        let sleep_1_id = graph_builder.add_fn(
+            TestApplySleepShCmdItemSpec::<1>::new(item_spec_id!("sleep_1"), "0.3").into(),
+        );
+        graph_builder.add_edge(sleep_0_id, sleep_1_id)?;
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = InMemoryTextOutput::new();
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+
+    // Discover states current and desired
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    let states_saved = StatesSaved::from(states_current);
+
+    // Interrupt while `sleep_0` is being applied.
+    let interrupt_signal = InterruptSignal::clone(&cmd_ctx.resources().borrow::<InterruptSignal>());
+    let interrupt_task = async {
+        peace::rt_model::time::sleep(Duration::from_millis(100)).await;
+        interrupt_signal.interrupt();
+    };
+    let (cmd_outcome, ()) = futures::join!(
+        EnsureCmd::exec(&mut cmd_ctx, &states_saved),
+        interrupt_task
+    );
+    let CmdOutcome {
+        value: _,
+        errors,
+        retries: _,
+        item_statuses,
+        interrupted,
+        history_write_error: _,
+    } = cmd_outcome?;
+
+    assert!(errors.is_empty());
+    assert!(interrupted);
+    assert_eq!(
+        Some(&ItemApplyStatus::Success),
+        item_statuses.get(&item_spec_id!("sleep_0"))
+    );
+    assert_eq!(
+        Some(&ItemApplyStatus::Interrupted),
+        item_statuses.get(&item_spec_id!("sleep_1"))
+    );
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn discover_with_outcome_returns_retries_made_while_discovering()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<ShCmdError>::new();
+        graph_builder.add_fn(
+            ItemSpecWrapper::from(TestStateCurrentFlakyShCmdItemSpec::new())
+                .with_retry_policy(RetryPolicy::new(2).with_backoff(Duration::ZERO, Duration::ZERO))
+                .into(),
+        );
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = InMemoryTextOutput::new();
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+
+    let CmdOutcome {
+        value: (states_current, _states_desired),
+        errors,
+        retries,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
+    } = StatesDiscoverCmd::current_and_desired_with_outcome(&mut cmd_ctx).await?;
+
+    assert!(errors.is_empty(), "Expected no errors, but was {errors:?}");
+    assert_eq!(
+        Some(&1),
+        retries.get(&TestStateCurrentFlakyShCmdItemSpec::ID)
+    );
+    assert!(
+        states_current
+            .get_raw(&TestStateCurrentFlakyShCmdItemSpec::ID)
+            .is_some()
+    );
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn ensure_with_item_spec_selection_applies_selected_item_specs_only()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<ShCmdError>::new();
+        graph_builder.add_fn(TestApplyFailShCmdItemSpec::new().into());
+        graph_builder.add_fn(TestFileCreationShCmdItemSpec::new().into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = InMemoryTextOutput::new();
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+
+    // Discover states current and desired of all item specs.
+    StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+
+    let item_spec_selection = ItemSpecSelection::new([TestFileCreationShCmdItemSpec::ID]);
+    let mut cmd_ctx = cmd_ctx.with_item_spec_selection(&item_spec_selection)?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    assert!(
+        states_current
+            .get::<TestFileCreationShCmdState, _>(&TestFileCreationShCmdItemSpec::ID)
+            .is_some()
+    );
+    assert_eq!(1, states_current.len());
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+
+    let CmdOutcome {
+        value: _,
+        errors,
+        retries: _,
+        item_statuses,
+        interrupted: _,
+        history_write_error: _,
+    } = EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
+
+    // `TestApplyFailShCmdItemSpec` would fail if it were applied.
+    assert!(errors.is_empty());
+    assert_eq!(1, item_statuses.len());
+    assert_eq!(
+        Some(&ItemApplyStatus::Success),
+        item_statuses.get(&TestFileCreationShCmdItemSpec::ID)
+    );
+    assert!(tempdir.path().join("test_file").exists());
+
+    // States of the unselected item spec are retained.
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+    assert!(states_saved.contains_key(&TestApplyFailShCmdItemSpec::ID));
+    assert!(states_saved.contains_key(&TestFileCreationShCmdItemSpec::ID));
+
+    Ok(())
+}
@@ -549,6 +549,10 @@ async fn ensure_unpacks_tar_when_files_not_exists() -> Result<(), Box<dyn std::e
     let CmdOutcome {
         value: states_ensured,
         errors: _,
+        retries: _,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
     } = EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
 
     let state_ensured = states_ensured
@@ -608,6 +612,10 @@ async fn ensure_removes_other_files_and_is_idempotent() -> Result<(), Box<dyn st
     let CmdOutcome {
         value: states_ensured,
         errors: _,
+        retries: _,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
     } = EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
 
     let state_ensured = states_ensured
@@ -627,6 +635,10 @@ async fn ensure_removes_other_files_and_is_idempotent() -> Result<(), Box<dyn st
     let CmdOutcome {
         value: states_ensured,
         errors: _,
+        retries: _,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
     } = EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
 
     let state_ensured = states_ensured
@@ -673,6 +685,10 @@ async fn clean_removes_files_in_dest_directory() -> Result<(), Box<dyn std::erro
     let CmdOutcome {
         value: states_cleaned,
         errors: _,
+        retries: _,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
     } = CleanCmd::exec(&mut cmd_ctx, &states_saved).await?;
 
     let state_cleaned = states_cleaned
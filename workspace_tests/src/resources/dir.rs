@@ -1,6 +1,7 @@
 mod peace_dir;
 mod profile_dir;
 mod profile_history_dir;
+mod profile_lock_file;
 mod states_desired_file;
 mod states_saved_file;
 mod workspace_dir;
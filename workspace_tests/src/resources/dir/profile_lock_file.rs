@@ -0,0 +1,29 @@
+use std::path::Path;
+
+use peace::{
+    cfg::{app_name, profile, AppName, Profile},
+    resources::paths::{PeaceAppDir, PeaceDir, ProfileDir, ProfileLockFile},
+};
+
+#[test]
+pub fn debug() {
+    let profile_lock_file = ProfileLockFile::from(Path::new(".").to_path_buf());
+
+    assert_eq!(r#"ProfileLockFile(".")"#, format!("{profile_lock_file:?}"));
+}
+
+#[test]
+pub fn from_profile_dir_relative() {
+    let app_name = app_name!();
+    let peace_dir = PeaceDir::from(Path::new(".").to_path_buf());
+    let profile = profile!("test_profile");
+    let peace_app_dir = PeaceAppDir::from((&peace_dir, &app_name));
+    let profile_dir = ProfileDir::from((&peace_app_dir, &profile));
+    let profile_lock_file = ProfileLockFile::from(&profile_dir);
+
+    let mut path = Path::new(".").to_path_buf();
+    path.push(&**app_name!());
+    path.push("test_profile");
+    path.push(".lock.yaml");
+    assert_eq!(path, &*profile_lock_file);
+}
@@ -1,7 +1,15 @@
 mod clean_cmd;
 mod diff_cmd;
 mod ensure_cmd;
+mod history_list_cmd;
+mod history_show_cmd;
+mod orphans_cmd;
+mod profiles_diff_cmd;
+mod rollback_cmd;
 mod states_desired_display_cmd;
 mod states_discover_cmd;
+mod states_drift_cmd;
 mod states_saved_display_cmd;
+#[cfg(all(feature = "storage_format_json", feature = "storage_format_message_pack"))]
+mod storage_format_migrate_cmd;
 mod sub;
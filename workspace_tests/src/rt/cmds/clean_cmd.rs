@@ -39,6 +39,10 @@ async fn resources_cleaned_dry_does_not_alter_state_when_state_not_ensured()
     let CmdOutcome {
         value: states_cleaned_dry,
         errors,
+        retries: _,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
     } = CleanCmd::exec_dry(&mut cmd_ctx, &states_saved).await?;
 
     assert_eq!(
@@ -83,6 +87,10 @@ async fn resources_cleaned_dry_does_not_alter_state_when_state_ensured()
     let CmdOutcome {
         value: states_ensured,
         errors: _,
+        retries: _,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
     } = EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
 
     // Clean states.
@@ -137,6 +145,10 @@ async fn resources_cleaned_contains_state_cleaned_for_each_item_spec_when_state_
     let CmdOutcome {
         value: cleaned_states_cleaned,
         errors: _,
+        retries: _,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
     } = CleanCmd::exec(&mut cmd_ctx, &states_saved).await?;
 
     // Re-read states from disk.
@@ -183,12 +195,20 @@ async fn resources_cleaned_contains_state_cleaned_for_each_item_spec_when_state_
     let CmdOutcome {
         value: states_ensured,
         errors: _,
+        retries: _,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
     } = EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
 
     // Clean states.
     let CmdOutcome {
         value: cleaned_states_cleaned,
         errors: _,
+        retries: _,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
     } = CleanCmd::exec(&mut cmd_ctx, &states_saved).await?;
 
     // Re-read states from disk.
@@ -210,6 +230,60 @@ async fn resources_cleaned_contains_state_cleaned_for_each_item_spec_when_state_
     Ok(())
 }
 
+#[tokio::test]
+async fn exec_multi_profile_cleans_each_profile() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+
+    // Create the profile directories.
+    for profile in [profile!("dev_a"), profile!("dev_b")] {
+        CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+            .with_profile(profile)
+            .with_flow(&flow)
+            .await?;
+    }
+
+    let mut cmd_ctx = CmdCtx::builder_multi_profile_single_flow(&mut output, &workspace)
+        .with_flow(&flow)
+        .build()
+        .await?;
+    StatesDiscoverCmd::current_and_desired_multi_profile(&mut cmd_ctx).await?;
+    EnsureCmd::exec_multi_profile(&mut cmd_ctx).await?;
+    let profiles_cmd_outcome = CleanCmd::exec_multi_profile(&mut cmd_ctx).await?;
+
+    assert!(profiles_cmd_outcome.is_ok());
+    assert_eq!(
+        vec![&profile!("dev_a"), &profile!("dev_b")],
+        profiles_cmd_outcome
+            .profile_to_cmd_outcome
+            .keys()
+            .collect::<Vec<_>>()
+    );
+    profiles_cmd_outcome
+        .profile_to_cmd_outcome
+        .values()
+        .for_each(|cmd_outcome| {
+            assert_eq!(
+                Some(VecCopyState::new()).as_ref(),
+                cmd_outcome
+                    .value
+                    .get::<VecCopyState, _>(VecCopyItemSpec.id())
+            );
+        });
+
+    Ok(())
+}
+
 #[test]
 fn debug() {
     let debug_str = format!("{:?}", CleanCmd::<VecCopyError, NoOpOutput, ()>::default());
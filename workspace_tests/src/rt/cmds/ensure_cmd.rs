@@ -1,12 +1,22 @@
 use peace::{
-    cfg::{app_name, profile, AppName, FlowId, ItemSpec, Profile},
+    cfg::{app_name, profile, AppName, FlowId, InterruptSignal, ItemSpec, Profile},
     cmd::ctx::CmdCtx,
-    resources::states::StatesSaved,
-    rt::cmds::{sub::StatesSavedReadCmd, EnsureCmd, StatesDiscoverCmd},
-    rt_model::{outcomes::CmdOutcome, Flow, ItemSpecGraphBuilder, Workspace, WorkspaceSpec},
+    resources::{states::StatesSaved, type_reg::untagged::BoxDataTypeDowncast},
+    rt::cmds::{
+        sub::{EnsurePlanReadCmd, StatesSavedReadCmd},
+        EnsureCmd, StatesDiscoverCmd,
+    },
+    rt_model::{
+        outcomes::{CmdOutcome, EnsurePlan, ItemApply, ItemApplyStatus},
+        params::ParamsLimits,
+        Error, Flow, ItemSpecGraphBuilder, Workspace, WorkspaceSpec,
+    },
 };
 
-use crate::{NoOpOutput, PeaceTestError, VecCopyError, VecCopyItemSpec, VecCopyState};
+use crate::{
+    NoOpOutput, PeaceTestError, VecA, VecB, VecCopyDiff, VecCopyError, VecCopyItemSpec,
+    VecCopyState,
+};
 
 #[tokio::test]
 async fn resources_ensured_dry_does_not_alter_state() -> Result<(), Box<dyn std::error::Error>> {
@@ -38,6 +48,10 @@ async fn resources_ensured_dry_does_not_alter_state() -> Result<(), Box<dyn std:
     let CmdOutcome {
         value: states_ensured_dry,
         errors: _,
+        retries: _,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
     } = EnsureCmd::exec_dry(&mut cmd_ctx, &states_saved).await?;
 
     // TODO: When EnsureCmd returns the execution report, assert on the state that
@@ -100,6 +114,10 @@ async fn resources_ensured_contains_state_ensured_for_each_item_spec_when_state_
     let CmdOutcome {
         value: ensured_states_ensured,
         errors: _,
+        retries: _,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
     } = EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
 
     // Re-read states from disk.
@@ -169,6 +187,10 @@ async fn resources_ensured_contains_state_ensured_for_each_item_spec_when_state_
     let CmdOutcome {
         value: ensured_states_ensured,
         errors: _,
+        retries: _,
+        item_statuses: ensured_item_statuses,
+        interrupted: _,
+        history_write_error: _,
     } = EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
     let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
 
@@ -181,6 +203,10 @@ async fn resources_ensured_contains_state_ensured_for_each_item_spec_when_state_
     let CmdOutcome {
         value: ensured_states_ensured_dry,
         errors: _,
+        retries: _,
+        item_statuses: ensured_dry_item_statuses,
+        interrupted: _,
+        history_write_error: _,
     } = EnsureCmd::exec_dry(&mut cmd_ctx, &states_saved).await?;
 
     // Re-read states from disk.
@@ -220,6 +246,639 @@ async fn resources_ensured_contains_state_ensured_for_each_item_spec_when_state_
         Some(VecCopyState::from(vec![0u8, 1, 2, 3, 4, 5, 6, 7])).as_ref(),
         states_saved.get::<VecCopyState, _>(VecCopyItemSpec.id())
     );
+    assert_eq!(
+        Some(&ItemApplyStatus::Success),
+        ensured_item_statuses.get(VecCopyItemSpec.id())
+    );
+    assert_eq!(
+        Some(&ItemApplyStatus::ExecNotRequired),
+        ensured_dry_item_statuses.get(VecCopyItemSpec.id())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_does_not_apply_item_specs_when_interrupted() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+
+    // Write current and desired states to disk.
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    let states_saved = StatesSaved::from(states_current);
+
+    // Interrupt before ensuring.
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    cmd_ctx.resources().borrow::<InterruptSignal>().interrupt();
+    let CmdOutcome {
+        value: ensured_states_ensured,
+        errors,
+        retries: _,
+        item_statuses,
+        interrupted,
+        history_write_error: _,
+    } = EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
+
+    // Re-read states from disk.
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+
+    assert!(errors.is_empty());
+    assert!(interrupted);
+    assert_eq!(
+        Some(&ItemApplyStatus::Interrupted),
+        item_statuses.get(VecCopyItemSpec.id())
+    );
+    assert_eq!(
+        Some(VecCopyState::new()).as_ref(),
+        ensured_states_ensured.get::<VecCopyState, _>(VecCopyItemSpec.id())
+    );
+    assert_eq!(
+        Some(VecCopyState::new()).as_ref(),
+        states_saved.get::<VecCopyState, _>(VecCopyItemSpec.id())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_returns_error_when_item_spec_params_exceed_limits()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let params_limits = || {
+        ParamsLimits::new().with_limit(|vec_a: &VecA| {
+            if vec_a.0.len() > 4 {
+                Err(format!("{} bytes exceeds limit 4", vec_a.0.len()))
+            } else {
+                Ok(())
+            }
+        })
+    };
+
+    // `VecA` is inserted by the item spec's setup, and is checked when building the
+    // `CmdCtx`.
+    let mut output = NoOpOutput;
+    let cmd_ctx_result = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .with_params_limits(params_limits())
+        .await;
+    assert!(
+        matches!(
+            &cmd_ctx_result,
+            Err(PeaceTestError::PeaceRtError(Error::ParamsLimitsExceeded { violations }))
+            if violations.len() == 1
+        ),
+        "Expected `Error::ParamsLimitsExceeded`, but was {:?}",
+        cmd_ctx_result.map(|_| ())
+    );
+
+    // The limits are checked again before items are applied.
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .with_params_limits(params_limits().with_override(true))
+        .await?;
+    cmd_ctx
+        .resources_mut()
+        .borrow_mut::<ParamsLimits>()
+        .set_overridden(false);
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    let states_saved = StatesSaved::from(states_current);
+
+    let result = EnsureCmd::exec(&mut cmd_ctx, &states_saved).await;
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceTestError::PeaceRtError(Error::ParamsLimitsExceeded { violations }))
+            if violations.len() == 1
+            && violations[0].description() == "8 bytes exceeds limit 4"
+        ),
+        "Expected `Error::ParamsLimitsExceeded`, but was {:?}",
+        result.map(|_| ())
+    );
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+    assert_eq!(
+        Some(VecCopyState::new()).as_ref(),
+        states_saved.get::<VecCopyState, _>(VecCopyItemSpec.id())
+    );
+
+    // Overriding the limits allows the item spec to be applied.
+    cmd_ctx
+        .resources_mut()
+        .borrow_mut::<ParamsLimits>()
+        .set_overridden(true);
+    let CmdOutcome {
+        value: states_ensured,
+        errors,
+        retries: _,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
+    } = EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
+    assert!(errors.is_empty());
+    assert_eq!(
+        Some(VecCopyState::from(vec![0u8, 1, 2, 3, 4, 5, 6, 7])).as_ref(),
+        states_ensured.get::<VecCopyState, _>(VecCopyItemSpec.id())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_dry_plan_returns_and_writes_plan() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    let states_saved = StatesSaved::from(states_current);
+
+    let CmdOutcome {
+        value: ensure_plan,
+        errors,
+        retries: _,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
+    } = EnsureCmd::exec_dry_plan(&mut cmd_ctx, &states_saved).await?;
+
+    assert!(errors.is_empty());
+    let item_apply = ensure_plan
+        .get_raw(VecCopyItemSpec.id())
+        .and_then(BoxDataTypeDowncast::<ItemApply<VecCopyState, VecCopyDiff>>::downcast_ref)
+        .expect("Expected `VecCopyItemSpec` to be in the ensure plan.");
+    assert_eq!(VecCopyState::new(), item_apply.state_current);
+    assert_eq!(
+        VecCopyState::from(vec![0u8, 1, 2, 3, 4, 5, 6, 7]),
+        item_apply.state_target
+    );
+    assert!(EnsurePlanReadCmd::exec(&mut cmd_ctx).await.is_ok());
+
+    // States are not altered.
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+    assert_eq!(
+        Some(VecCopyState::new()).as_ref(),
+        states_saved.get::<VecCopyState, _>(VecCopyItemSpec.id())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_dry_plan_does_not_write_plan_when_interrupted()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    let states_saved = StatesSaved::from(states_current);
+
+    cmd_ctx.resources().borrow::<InterruptSignal>().interrupt();
+    let CmdOutcome {
+        value: ensure_plan,
+        errors,
+        retries: _,
+        item_statuses: _,
+        interrupted,
+        history_write_error: _,
+    } = EnsureCmd::exec_dry_plan(&mut cmd_ctx, &states_saved).await?;
+
+    assert!(errors.is_empty());
+    assert!(interrupted);
+    assert!(ensure_plan.get_raw(VecCopyItemSpec.id()).is_none());
+    assert!(EnsurePlanReadCmd::exec(&mut cmd_ctx).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_with_plan_ensures_states_when_states_match_plan()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    let states_saved = StatesSaved::from(states_current);
+    EnsureCmd::exec_dry_plan(&mut cmd_ctx, &states_saved).await?;
+
+    // Ensure using the plan read from disk.
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let ensure_plan = EnsurePlanReadCmd::exec(&mut cmd_ctx).await?;
+    let CmdOutcome {
+        value: states_ensured,
+        errors,
+        retries: _,
+        item_statuses,
+        interrupted: _,
+        history_write_error: _,
+    } = EnsureCmd::exec_with_plan(&mut cmd_ctx, &states_saved, &ensure_plan).await?;
+
+    assert!(errors.is_empty());
+    assert_eq!(
+        Some(&ItemApplyStatus::Success),
+        item_statuses.get(VecCopyItemSpec.id())
+    );
+    assert_eq!(
+        Some(VecCopyState::from(vec![0u8, 1, 2, 3, 4, 5, 6, 7])).as_ref(),
+        states_ensured.get::<VecCopyState, _>(VecCopyItemSpec.id())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_with_plan_ensures_planned_target_states_when_desired_states_change()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    let states_saved = StatesSaved::from(states_current);
+    let CmdOutcome {
+        value: ensure_plan, ..
+    } = EnsureCmd::exec_dry_plan(&mut cmd_ctx, &states_saved).await?;
+
+    // Change the desired state after the plan is made.
+    cmd_ctx.resources_mut().insert(VecA(vec![9, 9]));
+    let CmdOutcome {
+        value: states_ensured,
+        errors,
+        ..
+    } = EnsureCmd::exec_with_plan(&mut cmd_ctx, &states_saved, &ensure_plan).await?;
+
+    assert!(errors.is_empty(), "Expected no errors, but was {errors:?}");
+    assert_eq!(
+        Some(VecCopyState::from(vec![0u8, 1, 2, 3, 4, 5, 6, 7])).as_ref(),
+        states_ensured.get::<VecCopyState, _>(VecCopyItemSpec.id())
+    );
+    assert_eq!(
+        vec![0u8, 1, 2, 3, 4, 5, 6, 7],
+        &*cmd_ctx.resources().borrow::<VecB>().0
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_with_plan_returns_error_when_state_current_differs_from_plan()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    let states_saved = StatesSaved::from(states_current);
+    let CmdOutcome {
+        value: ensure_plan, ..
+    } = EnsureCmd::exec_dry_plan(&mut cmd_ctx, &states_saved).await?;
+
+    // Change the state after the plan is made.
+    cmd_ctx.resources_mut().insert(VecB(vec![0, 1, 2]));
+    let exec_result = EnsureCmd::exec_with_plan(&mut cmd_ctx, &states_saved, &ensure_plan).await;
+
+    assert!(
+        matches!(
+            &exec_result,
+            Err(PeaceTestError::PeaceRtError(Error::EnsurePlanStale { item_spec_ids }))
+            if item_spec_ids == &[VecCopyItemSpec.id().clone()]
+        ),
+        "Expected error to be `EnsurePlanStale`, but was {exec_result:?}"
+    );
+    assert_eq!(vec![0u8, 1, 2], &*cmd_ctx.resources().borrow::<VecB>().0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_with_plan_returns_error_when_item_spec_not_in_plan()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    let states_saved = StatesSaved::from(states_current);
+
+    let exec_result =
+        EnsureCmd::exec_with_plan(&mut cmd_ctx, &states_saved, &EnsurePlan::new()).await;
+
+    assert!(
+        matches!(
+            &exec_result,
+            Err(PeaceTestError::PeaceRtError(Error::EnsurePlanItemSpecNotFound { item_spec_id }))
+            if item_spec_id == VecCopyItemSpec.id()
+        ),
+        "Expected error to be `EnsurePlanItemSpecNotFound`, but was {exec_result:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_multi_profile_ensures_each_profile() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+
+    // Create the profile directories.
+    for profile in [profile!("dev_a"), profile!("dev_b")] {
+        CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+            .with_profile(profile)
+            .with_flow(&flow)
+            .await?;
+    }
+
+    let mut cmd_ctx = CmdCtx::builder_multi_profile_single_flow(&mut output, &workspace)
+        .with_flow(&flow)
+        .build()
+        .await?
+        .with_profile_concurrency_limit(1);
+    StatesDiscoverCmd::current_and_desired_multi_profile(&mut cmd_ctx).await?;
+    let profiles_cmd_outcome = EnsureCmd::exec_multi_profile(&mut cmd_ctx).await?;
+
+    assert!(profiles_cmd_outcome.is_ok());
+    assert_eq!(
+        vec![&profile!("dev_a"), &profile!("dev_b")],
+        profiles_cmd_outcome
+            .profile_to_cmd_outcome
+            .keys()
+            .collect::<Vec<_>>()
+    );
+    profiles_cmd_outcome
+        .profile_to_cmd_outcome
+        .values()
+        .for_each(|cmd_outcome| {
+            assert_eq!(
+                Some(&ItemApplyStatus::Success),
+                cmd_outcome.item_statuses.get(VecCopyItemSpec.id())
+            );
+            assert_eq!(
+                Some(VecCopyState::from(vec![0u8, 1, 2, 3, 4, 5, 6, 7])).as_ref(),
+                cmd_outcome
+                    .value
+                    .get::<VecCopyState, _>(VecCopyItemSpec.id())
+            );
+        });
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_multi_profile_only_ensures_filtered_profiles()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+
+    // Create the profile directories.
+    for profile in [profile!("dev_a"), profile!("dev_b")] {
+        CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+            .with_profile(profile)
+            .with_flow(&flow)
+            .await?;
+    }
+
+    let mut cmd_ctx = CmdCtx::builder_multi_profile_single_flow(&mut output, &workspace)
+        .with_flow(&flow)
+        .build()
+        .await?;
+    StatesDiscoverCmd::current_and_desired_multi_profile(&mut cmd_ctx).await?;
+    drop(cmd_ctx);
+
+    let mut cmd_ctx = CmdCtx::builder_multi_profile_single_flow(&mut output, &workspace)
+        .with_profile_filter(|profile| **profile == "dev_a")
+        .with_flow(&flow)
+        .build()
+        .await?;
+    let profiles_cmd_outcome = EnsureCmd::exec_multi_profile(&mut cmd_ctx).await?;
+    drop(cmd_ctx);
+
+    assert!(profiles_cmd_outcome.is_ok());
+    assert_eq!(
+        vec![&profile!("dev_a")],
+        profiles_cmd_outcome
+            .profile_to_cmd_outcome
+            .keys()
+            .collect::<Vec<_>>()
+    );
+
+    let cmd_ctx = CmdCtx::builder_multi_profile_single_flow(&mut output, &workspace)
+        .with_flow(&flow)
+        .build()
+        .await?;
+    let profile_to_states_saved = cmd_ctx.profile_to_states_saved();
+    assert_eq!(
+        Some(VecCopyState::from(vec![0u8, 1, 2, 3, 4, 5, 6, 7])).as_ref(),
+        profile_to_states_saved
+            .get(&profile!("dev_a"))
+            .and_then(Option::as_ref)
+            .and_then(|states_saved| states_saved.get::<VecCopyState, _>(VecCopyItemSpec.id()))
+    );
+    assert_eq!(
+        Some(VecCopyState::new()).as_ref(),
+        profile_to_states_saved
+            .get(&profile!("dev_b"))
+            .and_then(Option::as_ref)
+            .and_then(|states_saved| states_saved.get::<VecCopyState, _>(VecCopyItemSpec.id()))
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_multi_profile_records_error_for_profile_whose_states_are_not_discovered()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+
+    // Only discover `dev_a`'s states.
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("dev_a"))
+        .with_flow(&flow)
+        .await?;
+    StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    drop(cmd_ctx);
+    CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("dev_b"))
+        .with_flow(&flow)
+        .await?;
+
+    let mut cmd_ctx = CmdCtx::builder_multi_profile_single_flow(&mut output, &workspace)
+        .with_flow(&flow)
+        .build()
+        .await?;
+    let profiles_cmd_outcome = EnsureCmd::exec_multi_profile(&mut cmd_ctx).await?;
+
+    assert!(profiles_cmd_outcome.is_err());
+    assert_eq!(
+        vec![&profile!("dev_a")],
+        profiles_cmd_outcome
+            .profile_to_cmd_outcome
+            .keys()
+            .collect::<Vec<_>>()
+    );
+    let error = profiles_cmd_outcome
+        .profile_to_error
+        .get(&profile!("dev_b"));
+    assert!(
+        matches!(
+            error,
+            Some(PeaceTestError::PeaceRtError(
+                Error::StatesCurrentDiscoverRequired
+            ))
+        ),
+        "Expected `Error::StatesCurrentDiscoverRequired`, but was {error:?}"
+    );
 
     Ok(())
 }
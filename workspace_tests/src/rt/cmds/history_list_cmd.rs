@@ -0,0 +1,282 @@
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
+
+use peace::{
+    cfg::{app_name, async_trait, profile, AppName, FlowId, ItemSpec, Profile},
+    cmd::ctx::CmdCtx,
+    resources::states::StatesSaved,
+    rt::cmds::{
+        sub::StatesSavedReadCmd, CleanCmd, EnsureCmd, HistoryListCmd, StatesDiscoverCmd,
+    },
+    rt_model::{
+        outcomes::{HistoryCmd, ItemApplyStatus},
+        storage::{InMemoryStorage, StorageBackend},
+        Error, Flow, ItemSpecGraphBuilder, Storage, Workspace, WorkspaceSpec,
+    },
+};
+
+use crate::{
+    FnInvocation, FnTrackerOutput, NoOpOutput, PeaceTestError, VecCopyError, VecCopyItemSpec,
+    VecCopyState,
+};
+
+#[tokio::test]
+async fn lists_summaries_of_ensure_and_clean_executions() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    let states_saved = StatesSaved::from(states_current);
+    EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+    CleanCmd::exec(&mut cmd_ctx, &states_saved).await?;
+
+    let mut fn_tracker_output = FnTrackerOutput::new();
+    let mut cmd_ctx =
+        CmdCtx::builder_single_profile_single_flow(&mut fn_tracker_output, &workspace)
+            .with_profile(profile!("test_profile"))
+            .with_flow(&flow)
+            .await?;
+    let history_summaries = HistoryListCmd::exec(&mut cmd_ctx).await?;
+
+    assert_eq!(2, history_summaries.len());
+    let history_summary_ensure = &history_summaries[0];
+    assert_eq!(0, history_summary_ensure.index);
+    assert_eq!(HistoryCmd::Ensure, history_summary_ensure.cmd);
+    assert_eq!(flow.flow_id(), &history_summary_ensure.flow_id);
+    assert!(history_summary_ensure.start <= history_summary_ensure.end);
+    assert_eq!(
+        Some(&ItemApplyStatus::Success),
+        history_summary_ensure
+            .item_statuses
+            .get(VecCopyItemSpec.id())
+    );
+    let history_summary_clean = &history_summaries[1];
+    assert_eq!(1, history_summary_clean.index);
+    assert_eq!(HistoryCmd::Clean, history_summary_clean.cmd);
+    assert!(history_summary_ensure.end <= history_summary_clean.start);
+    assert_eq!(
+        vec![FnInvocation::new(
+            "present",
+            vec![Some(serde_yaml::to_string(&history_summaries)?)],
+        )],
+        cmd_ctx.output().fn_invocations()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn returns_empty_list_when_no_commands_executed() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+
+    let history_summaries = HistoryListCmd::exec(&mut cmd_ctx).await?;
+
+    assert!(history_summaries.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn records_concurrent_executions_with_different_indices()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    // Listing no history records is what a command sees if another command
+    // records its history between this command listing and writing records.
+    let history_storage = HistoryStorage {
+        in_memory_storage: InMemoryStorage::new(),
+        history_listing_stale: true,
+        history_write_fails: false,
+    };
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?
+    .with_storage(Storage::new(history_storage.clone()));
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    let states_saved = StatesSaved::from(states_current);
+    EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+    CleanCmd::exec(&mut cmd_ctx, &states_saved).await?;
+
+    let history_storage = HistoryStorage {
+        history_listing_stale: false,
+        ..history_storage
+    };
+    let workspace = workspace.with_storage(Storage::new(history_storage));
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let history_summaries = HistoryListCmd::exec(&mut cmd_ctx).await?;
+
+    assert_eq!(2, history_summaries.len());
+    assert_eq!(0, history_summaries[0].index);
+    assert_eq!(HistoryCmd::Ensure, history_summaries[0].cmd);
+    assert_eq!(1, history_summaries[1].index);
+    assert_eq!(HistoryCmd::Clean, history_summaries[1].cmd);
+    Ok(())
+}
+
+#[tokio::test]
+async fn history_write_error_does_not_fail_execution() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let history_storage = HistoryStorage {
+        in_memory_storage: InMemoryStorage::new(),
+        history_listing_stale: false,
+        history_write_fails: true,
+    };
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?
+    .with_storage(Storage::new(history_storage));
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    let states_saved = StatesSaved::from(states_current);
+    let cmd_outcome = EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
+
+    assert!(cmd_outcome.is_ok());
+    assert!(matches!(
+        cmd_outcome.history_write_error,
+        Some(PeaceTestError::PeaceRtError(Error::StorageConflict { .. }))
+    ));
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+    assert_eq!(
+        Some(VecCopyState::from(vec![0u8, 1, 2, 3, 4, 5, 6, 7])).as_ref(),
+        states_saved.get::<VecCopyState, _>(VecCopyItemSpec.id())
+    );
+    let history_summaries = HistoryListCmd::exec(&mut cmd_ctx).await?;
+    assert!(history_summaries.is_empty());
+    Ok(())
+}
+
+#[test]
+fn debug() {
+    let debug_str = format!(
+        "{:?}",
+        HistoryListCmd::<VecCopyError, NoOpOutput, ()>::default()
+    );
+    assert!(
+        debug_str
+            == r#"HistoryListCmd(PhantomData<(workspace_tests::vec_copy_item_spec::VecCopyError, workspace_tests::no_op_output::NoOpOutput, ())>)"#
+            || debug_str == r#"HistoryListCmd(PhantomData)"#
+    );
+}
+
+/// Storage whose history records may be listed as if none were written, or
+/// fail to be written.
+#[derive(Clone, Debug)]
+struct HistoryStorage {
+    in_memory_storage: InMemoryStorage,
+    history_listing_stale: bool,
+    history_write_fails: bool,
+}
+
+impl HistoryStorage {
+    fn is_history_path(path: &Path) -> bool {
+        path.components()
+            .any(|component| component.as_os_str() == OsStr::new(".history"))
+    }
+}
+
+#[async_trait]
+impl StorageBackend for HistoryStorage {
+    async fn contains(&self, path: &Path) -> Result<bool, Error> {
+        self.in_memory_storage.contains(path).await
+    }
+
+    async fn read(&self, path: &Path) -> Result<Option<Vec<u8>>, Error> {
+        self.in_memory_storage.read(path).await
+    }
+
+    async fn write(&self, path: &Path, bytes: &[u8]) -> Result<(), Error> {
+        self.in_memory_storage.write(path, bytes).await
+    }
+
+    async fn write_new(&self, path: &Path, bytes: &[u8]) -> Result<bool, Error> {
+        if self.history_write_fails && Self::is_history_path(path) {
+            return Err(Error::StorageConflict {
+                path: path.to_path_buf(),
+            });
+        }
+        self.in_memory_storage.write_new(path, bytes).await
+    }
+
+    async fn remove(&self, path: &Path) -> Result<(), Error> {
+        self.in_memory_storage.remove(path).await
+    }
+
+    async fn remove_if_unchanged(&self, path: &Path, bytes: &[u8]) -> Result<bool, Error> {
+        self.in_memory_storage.remove_if_unchanged(path, bytes).await
+    }
+
+    async fn dir_create(&self, dir: &Path) -> Result<(), Error> {
+        self.in_memory_storage.dir_create(dir).await
+    }
+
+    async fn dir_entries(&self, dir: &Path) -> Result<Vec<PathBuf>, Error> {
+        if self.history_listing_stale && Self::is_history_path(dir) {
+            return Ok(Vec::new());
+        }
+        self.in_memory_storage.dir_entries(dir).await
+    }
+
+    fn is_file_system(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,162 @@
+use peace::{
+    cfg::{app_name, profile, AppName, FlowId, ItemSpec, Profile},
+    cmd::ctx::CmdCtx,
+    resources::{states::StatesSaved, type_reg::untagged::BoxDataTypeDowncast},
+    rt::cmds::{EnsureCmd, HistoryShowCmd, StatesDiscoverCmd},
+    rt_model::{
+        outcomes::{HistoryCmd, ItemApply},
+        Error, Flow, ItemSpecGraphBuilder, Workspace, WorkspaceSpec,
+    },
+};
+
+use crate::{
+    FnInvocation, FnTrackerOutput, NoOpOutput, PeaceTestError, VecCopyDiff, VecCopyError,
+    VecCopyItemSpec, VecCopyState,
+};
+
+#[tokio::test]
+async fn returns_record_with_item_applies() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx, &StatesSaved::from(states_current)).await?;
+
+    let mut fn_tracker_output = FnTrackerOutput::new();
+    let mut cmd_ctx =
+        CmdCtx::builder_single_profile_single_flow(&mut fn_tracker_output, &workspace)
+            .with_profile(profile!("test_profile"))
+            .with_flow(&flow)
+            .await?;
+    let history_record = HistoryShowCmd::exec(&mut cmd_ctx, 0).await?;
+
+    assert_eq!(0, history_record.summary.index);
+    assert_eq!(HistoryCmd::Ensure, history_record.summary.cmd);
+    assert!(history_record.errors.is_empty());
+    let item_apply = history_record
+        .item_applies
+        .get_raw(VecCopyItemSpec.id())
+        .and_then(BoxDataTypeDowncast::<ItemApply<VecCopyState, VecCopyDiff>>::downcast_ref)
+        .expect("Expected `VecCopyItemSpec` to be in the history record.");
+    assert_eq!(VecCopyState::new(), item_apply.state_current);
+    assert_eq!(
+        Some(VecCopyState::from(vec![0u8, 1, 2, 3, 4, 5, 6, 7])),
+        item_apply.state_applied
+    );
+    assert_eq!(
+        vec![FnInvocation::new(
+            "present",
+            vec![Some(serde_yaml::to_string(&history_record)?)],
+        )],
+        cmd_ctx.output().fn_invocations()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn returns_error_when_record_not_found() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut fn_tracker_output = FnTrackerOutput::new();
+    let mut cmd_ctx =
+        CmdCtx::builder_single_profile_single_flow(&mut fn_tracker_output, &workspace)
+            .with_profile(profile!("test_profile"))
+            .with_flow(&flow)
+            .await?;
+
+    let exec_result = HistoryShowCmd::exec(&mut cmd_ctx, 0).await;
+
+    assert!(matches!(
+        exec_result,
+        Err(PeaceTestError::PeaceRtError(Error::HistoryRecordNotFound {
+            index: 0
+        }))
+    ));
+    let err = PeaceTestError::PeaceRtError(Error::HistoryRecordNotFound { index: 0 });
+    assert_eq!(
+        vec![FnInvocation::new(
+            "write_err",
+            vec![Some(format!("{err:?}"))],
+        )],
+        cmd_ctx.output().fn_invocations()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn returns_error_when_record_is_for_different_flow() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph.clone());
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx, &StatesSaved::from(states_current)).await?;
+
+    let flow_other = Flow::new(FlowId::new("other_flow")?, graph);
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow_other)
+        .await?;
+    let exec_result = HistoryShowCmd::exec(&mut cmd_ctx, 0).await;
+
+    assert!(matches!(
+        exec_result,
+        Err(PeaceTestError::PeaceRtError(Error::HistoryRecordFlowIdMismatch {
+            index: 0,
+            flow_id,
+            flow_id_current,
+        }))
+        if &flow_id == flow.flow_id() && &flow_id_current == flow_other.flow_id()
+    ));
+    Ok(())
+}
+
+#[test]
+fn debug() {
+    let debug_str = format!(
+        "{:?}",
+        HistoryShowCmd::<VecCopyError, NoOpOutput, ()>::default()
+    );
+    assert!(
+        debug_str
+            == r#"HistoryShowCmd(PhantomData<(workspace_tests::vec_copy_item_spec::VecCopyError, workspace_tests::no_op_output::NoOpOutput, ())>)"#
+            || debug_str == r#"HistoryShowCmd(PhantomData)"#
+    );
+}
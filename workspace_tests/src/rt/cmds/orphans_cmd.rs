@@ -0,0 +1,204 @@
+use peace::{
+    cfg::{app_name, profile, AppName, FlowId, ItemSpec, Profile},
+    cmd::ctx::CmdCtx,
+    resources::states::{StateOrphaned, StateOrphanedValue, StatesSaved},
+    rt::cmds::{sub::StatesSavedReadCmd, EnsureCmd, OrphansCmd, StatesDiscoverCmd},
+    rt_model::{
+        outcomes::{CmdOutcome, ItemApplyStatus},
+        Flow, ItemSpecGraphBuilder, Workspace, WorkspaceSpec,
+    },
+};
+
+use crate::{NoOpOutput, PeaceTestError, VecCopyError, VecCopyItemSpec, VecCopyState};
+
+#[tokio::test]
+async fn list_returns_states_of_item_specs_removed_from_flow()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let flow_id = FlowId::new(crate::fn_name_short!())?;
+    let mut output = NoOpOutput;
+    vec_copy_ensure(&mut output, &workspace, &flow_id).await?;
+
+    // Remove the item spec from the flow.
+    let flow = Flow::new(
+        flow_id,
+        ItemSpecGraphBuilder::<PeaceTestError>::new().build(),
+    );
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let states_orphaned = OrphansCmd::list(&mut cmd_ctx).await?;
+
+    let state_orphaned_expected = StateOrphaned::new(StateOrphanedValue::Seq(
+        (0..8).map(StateOrphanedValue::U64).collect(),
+    ));
+    assert_eq!(
+        vec![(VecCopyItemSpec.id(), &state_orphaned_expected)],
+        states_orphaned.orphans().collect::<Vec<_>>()
+    );
+    assert_eq!(1, states_orphaned.len());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_returns_empty_states_when_no_item_specs_removed()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let flow_id = FlowId::new(crate::fn_name_short!())?;
+    let mut output = NoOpOutput;
+    vec_copy_ensure(&mut output, &workspace, &flow_id).await?;
+
+    let flow = vec_copy_flow(flow_id);
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let states_orphaned = OrphansCmd::list(&mut cmd_ctx).await?;
+
+    assert!(states_orphaned.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn clean_dry_does_not_remove_orphaned_states() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let flow_id = FlowId::new(crate::fn_name_short!())?;
+    let mut output = NoOpOutput;
+    vec_copy_ensure(&mut output, &workspace, &flow_id).await?;
+
+    // Retired flow with the item spec that was removed.
+    let flow_retired = vec_copy_flow(flow_id);
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow_retired)
+        .await?;
+    let CmdOutcome {
+        value: states_cleaned_dry,
+        errors,
+        ..
+    } = OrphansCmd::clean_dry(&mut cmd_ctx).await?;
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+
+    assert!(errors.is_empty());
+    assert_eq!(
+        Some(VecCopyState::new()).as_ref(),
+        states_cleaned_dry.get::<VecCopyState, _>(VecCopyItemSpec.id())
+    );
+    assert_eq!(
+        Some(VecCopyState::from(vec![0, 1, 2, 3, 4, 5, 6, 7])).as_ref(),
+        states_saved.get::<VecCopyState, _>(VecCopyItemSpec.id())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn clean_cleans_and_removes_orphaned_states() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let flow_id = FlowId::new(crate::fn_name_short!())?;
+    let mut output = NoOpOutput;
+    vec_copy_ensure(&mut output, &workspace, &flow_id).await?;
+
+    // Retired flow with the item spec that was removed.
+    let flow_retired = vec_copy_flow(flow_id.clone());
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow_retired)
+        .await?;
+    let CmdOutcome {
+        value: states_cleaned,
+        errors,
+        item_statuses,
+        interrupted: _,
+        history_write_error: _,
+        ..
+    } = OrphansCmd::clean(&mut cmd_ctx).await?;
+    drop(cmd_ctx);
+
+    assert!(errors.is_empty());
+    assert_eq!(
+        Some(&ItemApplyStatus::Success),
+        item_statuses.get(VecCopyItemSpec.id())
+    );
+    assert_eq!(
+        Some(VecCopyState::new()).as_ref(),
+        states_cleaned.get::<VecCopyState, _>(VecCopyItemSpec.id())
+    );
+
+    // The cleaned item is no longer listed as an orphan.
+    let flow = Flow::new(
+        flow_id,
+        ItemSpecGraphBuilder::<PeaceTestError>::new().build(),
+    );
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let states_orphaned = OrphansCmd::list(&mut cmd_ctx).await?;
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+
+    assert!(states_orphaned.is_empty());
+    assert!(states_saved.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn debug() {
+    let debug_str = format!(
+        "{:?}",
+        OrphansCmd::<VecCopyError, NoOpOutput, ()>::default()
+    );
+    assert!(
+        debug_str
+            == r#"OrphansCmd(PhantomData<(workspace_tests::vec_copy_item_spec::VecCopyError, workspace_tests::no_op_output::NoOpOutput, ())>)"#
+            || debug_str == r#"OrphansCmd(PhantomData)"#
+    );
+}
+
+fn vec_copy_flow(flow_id: FlowId) -> Flow<PeaceTestError> {
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    Flow::new(flow_id, graph)
+}
+
+/// Ensures the `VecCopyItemSpec`, so that its state is saved.
+async fn vec_copy_ensure(
+    output: &mut NoOpOutput,
+    workspace: &Workspace,
+    flow_id: &FlowId,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let flow = vec_copy_flow(flow_id.clone());
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(output, workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    let states_saved = StatesSaved::from(states_current);
+    EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
+
+    Ok(())
+}
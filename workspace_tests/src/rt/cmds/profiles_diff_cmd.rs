@@ -0,0 +1,225 @@
+use diff::{VecDiff, VecDiffType};
+use peace::{
+    cfg::{app_name, profile, AppName, FlowId, ItemSpec, Profile},
+    cmd::ctx::CmdCtx,
+    resources::{
+        internal::StatesMut,
+        states::{ts::Saved, StatesSaved},
+    },
+    rt::cmds::{sub::StatesCurrentDiscoverCmd, EnsureCmd, ProfilesDiffCmd, StatesDiscoverCmd},
+    rt_model::{
+        outcomes::ProfilesStateDiff, Error, Flow, ItemSpecGraphBuilder, Workspace, WorkspaceSpec,
+    },
+};
+
+use crate::{NoOpOutput, PeaceTestError, VecCopyDiff, VecCopyError, VecCopyItemSpec};
+
+#[tokio::test]
+async fn contains_state_diff_between_profiles_saved_states()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let flow = vec_copy_flow(FlowId::new(crate::fn_name_short!())?);
+    let mut output = NoOpOutput;
+
+    // Only ensure `prod`.
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("staging"))
+        .with_flow(&flow)
+        .await?;
+    StatesCurrentDiscoverCmd::exec(&mut cmd_ctx).await?;
+    drop(cmd_ctx);
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("prod"))
+        .with_flow(&flow)
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx, &StatesSaved::from(states_current)).await?;
+    drop(cmd_ctx);
+
+    let mut cmd_ctx = CmdCtx::builder_multi_profile_single_flow(&mut output, &workspace)
+        .with_flow(&flow)
+        .build()
+        .await?;
+    let state_diffs =
+        ProfilesDiffCmd::exec(&mut cmd_ctx, &profile!("staging"), &profile!("prod")).await?;
+
+    assert_eq!(
+        Some(VecCopyDiff::from(VecDiff(vec![VecDiffType::Inserted {
+            index: 0,
+            changes: vec![0u8, 1, 2, 3, 4, 5, 6, 7]
+        }])))
+        .as_ref(),
+        state_diffs.get::<VecCopyDiff, _>(VecCopyItemSpec.id())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reports_item_with_state_saved_in_one_profile_as_added_or_removed()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let flow = vec_copy_flow(FlowId::new(crate::fn_name_short!())?);
+    let mut output = NoOpOutput;
+
+    for profile in [profile!("staging"), profile!("prod")] {
+        let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+            .with_profile(profile)
+            .with_flow(&flow)
+            .await?;
+        StatesCurrentDiscoverCmd::exec(&mut cmd_ctx).await?;
+    }
+
+    let mut cmd_ctx = CmdCtx::builder_multi_profile_single_flow(&mut output, &workspace)
+        .with_flow(&flow)
+        .build()
+        .await?;
+    let states_saved = cmd_ctx
+        .profile_to_states_saved()
+        .get(&profile!("staging"))
+        .cloned()
+        .flatten()
+        .expect("Expected `staging` states to be saved.");
+    let states_saved_none = StatesSaved::from(StatesMut::<Saved>::new());
+
+    let state_diffs = ProfilesDiffCmd::exec_with_states_saved(
+        &mut cmd_ctx,
+        &profile!("prod"),
+        &states_saved_none,
+        &states_saved,
+    )
+    .await?;
+    let state_diff = state_diffs.get::<ProfilesStateDiff, _>(VecCopyItemSpec.id());
+    assert!(
+        matches!(state_diff, Some(ProfilesStateDiff::Added { .. })),
+        "Expected `ProfilesStateDiff::Added`, but was {state_diff:?}"
+    );
+
+    let state_diffs = ProfilesDiffCmd::exec_with_states_saved(
+        &mut cmd_ctx,
+        &profile!("prod"),
+        &states_saved,
+        &states_saved_none,
+    )
+    .await?;
+    let state_diff = state_diffs.get::<ProfilesStateDiff, _>(VecCopyItemSpec.id());
+    assert!(
+        matches!(state_diff, Some(ProfilesStateDiff::Removed { .. })),
+        "Expected `ProfilesStateDiff::Removed`, but was {state_diff:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn returns_error_when_profile_not_in_scope() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let flow = vec_copy_flow(FlowId::new(crate::fn_name_short!())?);
+    let mut output = NoOpOutput;
+
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("staging"))
+        .with_flow(&flow)
+        .await?;
+    StatesCurrentDiscoverCmd::exec(&mut cmd_ctx).await?;
+    drop(cmd_ctx);
+
+    let mut cmd_ctx = CmdCtx::builder_multi_profile_single_flow(&mut output, &workspace)
+        .with_flow(&flow)
+        .build()
+        .await?;
+    let result = ProfilesDiffCmd::exec(&mut cmd_ctx, &profile!("staging"), &profile!("prod")).await;
+
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceTestError::PeaceRtError(Error::ProfileNotInScope {
+                profile,
+                profiles_in_scope,
+            }))
+            if profile == &profile!("prod")
+            && profiles_in_scope == &[profile!("staging")]
+        ),
+        "Expected `Error::ProfileNotInScope`, but was {result:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn returns_error_when_profile_states_not_discovered()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let flow = vec_copy_flow(FlowId::new(crate::fn_name_short!())?);
+    let mut output = NoOpOutput;
+
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("staging"))
+        .with_flow(&flow)
+        .await?;
+    StatesCurrentDiscoverCmd::exec(&mut cmd_ctx).await?;
+    drop(cmd_ctx);
+    // Creates the `prod` profile directory without discovering states.
+    CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("prod"))
+        .with_flow(&flow)
+        .await?;
+
+    let mut cmd_ctx = CmdCtx::builder_multi_profile_single_flow(&mut output, &workspace)
+        .with_flow(&flow)
+        .build()
+        .await?;
+    let result = ProfilesDiffCmd::exec(&mut cmd_ctx, &profile!("staging"), &profile!("prod")).await;
+
+    assert!(
+        matches!(
+            &result,
+            Err(PeaceTestError::PeaceRtError(
+                Error::ProfileStatesCurrentDiscoverRequired { profile }
+            ))
+            if profile == &profile!("prod")
+        ),
+        "Expected `Error::ProfileStatesCurrentDiscoverRequired`, but was {result:?}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn debug() {
+    let debug_str = format!(
+        "{:?}",
+        ProfilesDiffCmd::<VecCopyError, NoOpOutput, ()>::default()
+    );
+    assert!(
+        debug_str
+            == r#"ProfilesDiffCmd(PhantomData<(workspace_tests::vec_copy_item_spec::VecCopyError, workspace_tests::no_op_output::NoOpOutput, ())>)"#
+            || debug_str == r#"ProfilesDiffCmd(PhantomData)"#
+    );
+}
+
+fn vec_copy_flow(flow_id: FlowId) -> Flow<PeaceTestError> {
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    Flow::new(flow_id, graph)
+}
@@ -0,0 +1,339 @@
+use diff::{VecDiff, VecDiffType};
+use peace::{
+    cfg::{app_name, profile, AppName, FlowId, ItemSpec, Profile},
+    cmd::ctx::CmdCtx,
+    resources::{
+        internal::StatesMut,
+        states::{ts::Desired, StatesDesired, StatesSaved},
+        type_reg::untagged::BoxDataTypeDowncast,
+    },
+    rt::cmds::{
+        sub::{StatesDesiredReadCmd, StatesSavedReadCmd},
+        CleanCmd, EnsureCmd, HistoryListCmd, RollbackCmd, StatesDiscoverCmd,
+    },
+    rt_model::{
+        outcomes::{CmdOutcome, HistoryCmd},
+        Error, Flow, ItemSpecGraphBuilder, Workspace, WorkspaceSpec,
+    },
+};
+
+use crate::{
+    FnInvocation, FnTrackerOutput, NoOpOutput, PeaceTestError, VecA, VecB, VecCopyDiff,
+    VecCopyError, VecCopyItemSpec, VecCopyState,
+};
+
+#[tokio::test]
+async fn exec_dry_presents_diff_to_history_record_states_without_applying()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut fn_tracker_output = FnTrackerOutput::new();
+    let mut cmd_ctx =
+        CmdCtx::builder_single_profile_single_flow(&mut fn_tracker_output, &workspace)
+            .with_profile(profile!("test_profile"))
+            .with_flow(&flow)
+            .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx, &StatesSaved::from(states_current)).await?;
+
+    // Ensure a different desired state.
+    cmd_ctx.resources_mut().insert(VecA(vec![0, 1, 2]));
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
+
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+    let CmdOutcome {
+        value: state_diffs,
+        errors,
+        retries: _,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
+    } = RollbackCmd::exec_dry(&mut cmd_ctx, &states_saved, 0).await?;
+
+    assert!(errors.is_empty());
+    assert_eq!(
+        Some(VecCopyDiff::from(VecDiff(vec![VecDiffType::Inserted {
+            index: 3,
+            changes: vec![3u8, 4, 5, 6, 7]
+        }])))
+        .as_ref(),
+        state_diffs
+            .get_raw(VecCopyItemSpec.id())
+            .and_then(BoxDataTypeDowncast::<VecCopyDiff>::downcast_ref)
+    );
+    // Nothing is applied.
+    assert_eq!(
+        &[0u8, 1, 2],
+        cmd_ctx.resources().borrow::<VecB>().0.as_slice()
+    );
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+    assert_eq!(
+        Some(VecCopyState::from(vec![0u8, 1, 2])).as_ref(),
+        states_saved.get::<VecCopyState, _>(VecCopyItemSpec.id())
+    );
+    assert_eq!(
+        Some(&FnInvocation::new(
+            "present",
+            vec![Some(serde_yaml::to_string(&state_diffs)?)],
+        )),
+        cmd_ctx.output().fn_invocations().last()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_ensures_history_record_states() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx, &StatesSaved::from(states_current)).await?;
+
+    // Ensure a different desired state.
+    cmd_ctx.resources_mut().insert(VecA(vec![0, 1, 2]));
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
+
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+    let CmdOutcome {
+        value: states_rolled_back,
+        errors,
+        retries: _,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
+    } = RollbackCmd::exec(&mut cmd_ctx, &states_saved, 0).await?;
+
+    assert!(errors.is_empty());
+    assert_eq!(
+        Some(VecCopyState::from(vec![0u8, 1, 2, 3, 4, 5, 6, 7])).as_ref(),
+        states_rolled_back.get::<VecCopyState, _>(VecCopyItemSpec.id())
+    );
+    assert_eq!(
+        &[0u8, 1, 2, 3, 4, 5, 6, 7],
+        cmd_ctx.resources().borrow::<VecB>().0.as_slice()
+    );
+    let states_desired = StatesDesiredReadCmd::exec(&mut cmd_ctx).await?;
+    assert_eq!(
+        Some(VecCopyState::from(vec![0u8, 1, 2, 3, 4, 5, 6, 7])).as_ref(),
+        states_desired.get::<VecCopyState, _>(VecCopyItemSpec.id())
+    );
+    let history_summaries = HistoryListCmd::exec(&mut cmd_ctx).await?;
+    assert_eq!(
+        vec![HistoryCmd::Ensure, HistoryCmd::Ensure, HistoryCmd::Rollback],
+        history_summaries
+            .iter()
+            .map(|history_summary| history_summary.cmd)
+            .collect::<Vec<_>>()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_with_states_desired_ensures_states_desired_snapshot()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx, &StatesSaved::from(states_current)).await?;
+    let states_desired_snapshot = StatesDesiredReadCmd::exec(&mut cmd_ctx).await?;
+
+    // Ensure a different desired state.
+    cmd_ctx.resources_mut().insert(VecA(vec![0, 1, 2]));
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx, &states_saved).await?;
+
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+    let CmdOutcome {
+        value: states_rolled_back,
+        errors,
+        retries: _,
+        item_statuses: _,
+        interrupted: _,
+        history_write_error: _,
+    } = RollbackCmd::exec_with_states_desired(
+        &mut cmd_ctx,
+        &states_saved,
+        &states_desired_snapshot,
+    )
+    .await?;
+
+    assert!(errors.is_empty());
+    assert_eq!(
+        Some(VecCopyState::from(vec![0u8, 1, 2, 3, 4, 5, 6, 7])).as_ref(),
+        states_rolled_back.get::<VecCopyState, _>(VecCopyItemSpec.id())
+    );
+    assert_eq!(
+        &[0u8, 1, 2, 3, 4, 5, 6, 7],
+        cmd_ctx.resources().borrow::<VecB>().0.as_slice()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_returns_error_when_history_record_not_found() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+
+    let exec_result = RollbackCmd::exec(&mut cmd_ctx, &StatesSaved::from(states_current), 1).await;
+
+    assert!(matches!(
+        exec_result,
+        Err(PeaceTestError::PeaceRtError(Error::HistoryRecordNotFound {
+            index: 1
+        }))
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_returns_error_when_history_record_is_clean() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx, &StatesSaved::from(states_current)).await?;
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+    CleanCmd::exec(&mut cmd_ctx, &states_saved).await?;
+
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+    let exec_result = RollbackCmd::exec(&mut cmd_ctx, &states_saved, 1).await;
+
+    assert!(matches!(
+        exec_result,
+        Err(PeaceTestError::PeaceRtError(
+            Error::RollbackHistoryRecordClean { index: 1 }
+        ))
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_with_states_desired_returns_error_and_does_not_apply_when_dry_run_fails()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    let states_saved = StatesSaved::from(states_current);
+
+    // The dry run fails, as the desired state is not a `VecCopyState`.
+    let states_desired = {
+        let mut states_desired_mut = StatesMut::<Desired>::new();
+        states_desired_mut.insert(VecCopyItemSpec.id().clone(), 1u32);
+        StatesDesired::from(states_desired_mut)
+    };
+    let exec_result =
+        RollbackCmd::exec_with_states_desired(&mut cmd_ctx, &states_saved, &states_desired).await;
+
+    assert!(matches!(
+        exec_result,
+        Err(PeaceTestError::PeaceRtError(Error::StateDesiredTypeMismatch {
+            item_spec_id,
+            state_type: _,
+        })) if &item_spec_id == VecCopyItemSpec.id()
+    ));
+    assert!(cmd_ctx.resources().borrow::<VecB>().0.is_empty());
+    let history_summaries = HistoryListCmd::exec(&mut cmd_ctx).await?;
+    assert!(history_summaries.is_empty());
+    Ok(())
+}
+
+#[test]
+fn debug() {
+    let debug_str = format!(
+        "{:?}",
+        RollbackCmd::<VecCopyError, NoOpOutput, ()>::default()
+    );
+    assert!(
+        debug_str
+            == r#"RollbackCmd(PhantomData<(workspace_tests::vec_copy_item_spec::VecCopyError, workspace_tests::no_op_output::NoOpOutput, ())>)"#
+            || debug_str == r#"RollbackCmd(PhantomData)"#
+    );
+}
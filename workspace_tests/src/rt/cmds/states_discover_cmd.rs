@@ -1,13 +1,19 @@
 use peace::{
-    cfg::{app_name, profile, AppName, FlowId, ItemSpec, ItemSpecId, Profile},
+    cfg::{
+        app_name, item_spec_id, profile, AppName, FlowId, InterruptSignal, ItemSpec, ItemSpecId,
+        Profile,
+    },
     cmd::ctx::CmdCtx,
     resources::{
         paths::{StatesDesiredFile, StatesSavedFile},
-        states::{StatesCurrent, StatesDesired},
+        states::{StateOrphaned, StateOrphanedValue, StatesCurrent, StatesDesired},
         type_reg::untagged::{BoxDtDisplay, TypeReg},
     },
-    rt::cmds::StatesDiscoverCmd,
-    rt_model::{Flow, ItemSpecGraphBuilder, Workspace, WorkspaceSpec},
+    rt::cmds::{sub::StatesSavedReadCmd, StatesDiscoverCmd},
+    rt_model::{
+        outcomes::CmdOutcome,
+        Error, Flow, ItemSpecGraphBuilder, StatesSerializer, Storage, Workspace, WorkspaceSpec,
+    },
 };
 
 use crate::{NoOpOutput, PeaceTestError, VecCopyError, VecCopyItemSpec, VecCopyState};
@@ -74,6 +80,212 @@ async fn runs_state_current_and_state_desired() -> Result<(), Box<dyn std::error
     Ok(())
 }
 
+#[tokio::test]
+async fn current_and_desired_does_not_discover_states_when_interrupted()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+
+    // Interrupt before discovering.
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    cmd_ctx.resources().borrow::<InterruptSignal>().interrupt();
+    let CmdOutcome {
+        value: (states_current, states_desired),
+        errors,
+        retries: _,
+        item_statuses: _,
+        interrupted,
+        history_write_error: _,
+    } = StatesDiscoverCmd::current_and_desired_with_outcome(&mut cmd_ctx).await?;
+
+    assert!(errors.is_empty());
+    assert!(interrupted);
+    assert!(states_current.is_empty());
+    assert!(states_desired.is_empty());
+
+    // Previously saved states are retained.
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+    assert_eq!(
+        Some(VecCopyState::new()).as_ref(),
+        states_saved.get::<VecCopyState, _>(VecCopyItemSpec.id())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn current_retains_orphaned_states() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let states_saved_file = StatesSavedFile::from(cmd_ctx.flow_dir());
+    tokio::fs::write(&states_saved_file, "item_spec_removed: 123\n").await?;
+
+    StatesDiscoverCmd::current(&mut cmd_ctx).await?;
+
+    let states_saved = StatesSerializer::<PeaceTestError>::deserialize_saved(
+        flow.flow_id(),
+        &Storage::default(),
+        cmd_ctx.states_type_regs().states_current_type_reg(),
+        &states_saved_file,
+    )
+    .await?;
+    let state_orphaned_expected = StateOrphaned::new(StateOrphanedValue::U64(123));
+    assert_eq!(
+        vec![(
+            &item_spec_id!("item_spec_removed"),
+            &state_orphaned_expected
+        )],
+        states_saved.orphans().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Some(VecCopyState::new()).as_ref(),
+        states_saved.get::<VecCopyState, _>(VecCopyItemSpec.id())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn current_returns_error_when_states_saved_cannot_be_read()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let states_saved_file = StatesSavedFile::from(cmd_ctx.flow_dir());
+    tokio::fs::write(&states_saved_file, "vec_copy: not_a_list\n").await?;
+
+    let error = StatesDiscoverCmd::current(&mut cmd_ctx).await.unwrap_err();
+
+    assert!(
+        matches!(
+            &error,
+            PeaceTestError::PeaceRtError(Error::StatesDeserialize { .. })
+        ),
+        "Expected error to be `StatesDeserialize`, but was {error:?}"
+    );
+    assert_eq!(
+        "vec_copy: not_a_list\n",
+        tokio::fs::read_to_string(&states_saved_file).await?
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn current_and_desired_multi_profile_discovers_states_for_each_profile()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+
+    // Create the profile directories.
+    for profile in [profile!("dev_a"), profile!("dev_b")] {
+        CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+            .with_profile(profile)
+            .with_flow(&flow)
+            .await?;
+    }
+
+    let mut cmd_ctx = CmdCtx::builder_multi_profile_single_flow(&mut output, &workspace)
+        .with_flow(&flow)
+        .build()
+        .await?;
+    let profiles_cmd_outcome =
+        StatesDiscoverCmd::current_and_desired_multi_profile(&mut cmd_ctx).await?;
+    drop(cmd_ctx);
+
+    assert!(profiles_cmd_outcome.is_ok());
+    assert_eq!(
+        vec![&profile!("dev_a"), &profile!("dev_b")],
+        profiles_cmd_outcome
+            .profile_to_cmd_outcome
+            .keys()
+            .collect::<Vec<_>>()
+    );
+    profiles_cmd_outcome
+        .profile_to_cmd_outcome
+        .values()
+        .for_each(|cmd_outcome| {
+            let (states_current, states_desired) = &cmd_outcome.value;
+            assert_eq!(
+                Some(VecCopyState::new()).as_ref(),
+                states_current.get::<VecCopyState, _>(VecCopyItemSpec.id())
+            );
+            assert_eq!(
+                Some(VecCopyState::from(vec![0u8, 1, 2, 3, 4, 5, 6, 7])).as_ref(),
+                states_desired.get::<VecCopyState, _>(VecCopyItemSpec.id())
+            );
+        });
+
+    // States are saved for each profile.
+    let cmd_ctx = CmdCtx::builder_multi_profile_single_flow(&mut output, &workspace)
+        .with_flow(&flow)
+        .build()
+        .await?;
+    assert!(
+        cmd_ctx
+            .profile_to_states_saved()
+            .values()
+            .all(Option::is_some)
+    );
+
+    Ok(())
+}
+
 #[test]
 fn debug() {
     let debug_str = format!(
@@ -0,0 +1,191 @@
+use diff::{VecDiff, VecDiffType};
+use peace::{
+    cfg::{app_name, profile, AppName, FlowId, ItemSpec, Profile},
+    cmd::ctx::CmdCtx,
+    resources::type_reg::untagged::BoxDataTypeDowncast,
+    rt::cmds::{
+        sub::{StatesCurrentDiscoverCmd, StatesSavedReadCmd},
+        StatesDriftCmd,
+    },
+    rt_model::{outcomes::StateDrift, Error, Flow, ItemSpecGraphBuilder, Workspace, WorkspaceSpec},
+};
+
+use crate::{
+    FnInvocation, FnTrackerOutput, NoOpOutput, PeaceTestError, VecB, VecCopyDiff, VecCopyError,
+    VecCopyItemSpec, VecCopyState,
+};
+
+#[tokio::test]
+async fn returns_in_sync_when_current_state_matches_saved_state()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut fn_tracker_output = FnTrackerOutput::new();
+    let mut cmd_ctx =
+        CmdCtx::builder_single_profile_single_flow(&mut fn_tracker_output, &workspace)
+            .with_profile(profile!("test_profile"))
+            .with_flow(&flow)
+            .await?;
+    StatesCurrentDiscoverCmd::exec(&mut cmd_ctx).await?;
+
+    let state_drifts = StatesDriftCmd::exec(&mut cmd_ctx).await?;
+    let fn_tracker_output = cmd_ctx.output();
+
+    assert!(!state_drifts.is_drifted());
+    assert!(matches!(
+        state_drifts.get(VecCopyItemSpec.id()),
+        Some(StateDrift::InSync)
+    ));
+    assert_eq!(
+        vec![FnInvocation::new(
+            "present",
+            vec![Some(serde_yaml::to_string(&state_drifts)?)],
+        )],
+        fn_tracker_output.fn_invocations()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn returns_drifted_when_current_state_differs_from_saved_state()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    StatesCurrentDiscoverCmd::exec(&mut cmd_ctx).await?;
+
+    // Change the state outside of an `ApplyCmd`.
+    cmd_ctx.resources_mut().insert(VecB(vec![0, 1, 2]));
+    let state_drifts = StatesDriftCmd::exec(&mut cmd_ctx).await?;
+
+    assert!(state_drifts.is_drifted());
+    let Some(StateDrift::Drifted { state_diff }) = state_drifts.get(VecCopyItemSpec.id()) else {
+        panic!(
+            "Expected state drift to be `Drifted`, but was {:?}",
+            state_drifts.get(VecCopyItemSpec.id())
+        );
+    };
+    assert_eq!(
+        Some(&VecCopyDiff::from(VecDiff(vec![VecDiffType::Inserted {
+            index: 0,
+            changes: vec![0u8, 1, 2]
+        }]))),
+        BoxDataTypeDowncast::<VecCopyDiff>::downcast_ref(state_diff)
+    );
+
+    // Saved states are not overwritten.
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+    assert_eq!(
+        Some(VecCopyState::new()).as_ref(),
+        states_saved.get::<VecCopyState, _>(VecCopyItemSpec.id())
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_and_save_saves_current_states() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    StatesCurrentDiscoverCmd::exec(&mut cmd_ctx).await?;
+
+    cmd_ctx.resources_mut().insert(VecB(vec![0, 1, 2]));
+    let state_drifts = StatesDriftCmd::exec_and_save(&mut cmd_ctx).await?;
+    assert!(state_drifts.is_drifted());
+
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+    assert_eq!(
+        Some(VecCopyState::from(vec![0u8, 1, 2])).as_ref(),
+        states_saved.get::<VecCopyState, _>(VecCopyItemSpec.id())
+    );
+    let state_drifts = StatesDriftCmd::exec(&mut cmd_ctx).await?;
+    assert!(!state_drifts.is_drifted());
+    Ok(())
+}
+
+#[tokio::test]
+async fn returns_error_when_states_not_on_disk() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut fn_tracker_output = FnTrackerOutput::new();
+    let mut cmd_ctx =
+        CmdCtx::builder_single_profile_single_flow(&mut fn_tracker_output, &workspace)
+            .with_profile(profile!("test_profile"))
+            .with_flow(&flow)
+            .await?;
+
+    let exec_result = StatesDriftCmd::exec(&mut cmd_ctx).await;
+
+    assert!(matches!(
+        exec_result,
+        Err(PeaceTestError::PeaceRtError(
+            Error::StatesCurrentDiscoverRequired
+        ))
+    ));
+    let err = PeaceTestError::PeaceRtError(Error::StatesCurrentDiscoverRequired);
+    assert_eq!(
+        vec![FnInvocation::new(
+            "write_err",
+            vec![Some(format!("{err:?}"))],
+        )],
+        cmd_ctx.output().fn_invocations()
+    );
+    Ok(())
+}
+
+#[test]
+fn debug() {
+    let debug_str = format!(
+        "{:?}",
+        StatesDriftCmd::<VecCopyError, NoOpOutput, ()>::default()
+    );
+    assert!(
+        debug_str
+            == r#"StatesDriftCmd(PhantomData<(workspace_tests::vec_copy_item_spec::VecCopyError, workspace_tests::no_op_output::NoOpOutput, ())>)"#
+            || debug_str == r#"StatesDriftCmd(PhantomData)"#
+    );
+}
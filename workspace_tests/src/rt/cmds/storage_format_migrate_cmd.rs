@@ -0,0 +1,213 @@
+use peace::{
+    cfg::{app_name, profile, AppName, FlowId, ItemSpec, Profile},
+    cmd::ctx::CmdCtx,
+    resources::{
+        internal::{StorageFormatFile, WorkspaceParamsFile},
+        paths::{StatesDesiredFile, StatesSavedFile},
+        states::StatesSaved,
+        type_reg::untagged::BoxDataTypeDowncast,
+    },
+    rt::cmds::{
+        sub::StatesSavedReadCmd, EnsureCmd, HistoryShowCmd, StatesDiscoverCmd,
+        StorageFormatMigrateCmd,
+    },
+    rt_model::{
+        outcomes::ItemApply,
+        storage::{SerializedStorage, StorageFormat},
+        Flow, ItemSpecGraphBuilder, Storage, Workspace, WorkspaceSpec,
+    },
+};
+
+use crate::{NoOpOutput, PeaceTestError, VecCopyDiff, VecCopyItemSpec, VecCopyState};
+
+#[tokio::test]
+async fn records_storage_format_and_rewrites_params_states_and_history_in_storage_format()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let profile_key = String::from("profile");
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .with_workspace_param_value(profile_key.clone(), Some(profile!("test_profile")))
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx, &StatesSaved::from(states_current)).await?;
+
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_workspace_params_k::<String>()
+        .with_workspace_param::<Profile>(profile_key.clone())
+        .with_profile_from_workspace_param(&profile_key)
+        .with_flow(&flow)
+        .await?;
+    let paths_migrated =
+        StorageFormatMigrateCmd::exec(&mut cmd_ctx, StorageFormat::MessagePack).await?;
+
+    let peace_app_dir = workspace.dirs().peace_app_dir();
+    let flow_dir = cmd_ctx.scope().flow_dir();
+    let workspace_params_file = WorkspaceParamsFile::from((peace_app_dir, "msgpack"));
+    let states_saved_file = StatesSavedFile::from((flow_dir, "msgpack"));
+    let states_desired_file = StatesDesiredFile::from((flow_dir, "msgpack"));
+    let history_record_file = cmd_ctx
+        .scope()
+        .profile_history_dir()
+        .join("00000000.msgpack");
+    let storage_format_file = StorageFormatFile::from(peace_app_dir);
+    assert_eq!(
+        vec![
+            workspace_params_file.to_path_buf(),
+            states_saved_file.to_path_buf(),
+            states_desired_file.to_path_buf(),
+            history_record_file,
+            storage_format_file.to_path_buf(),
+        ],
+        paths_migrated
+    );
+    for path in paths_migrated[..4].iter() {
+        let bytes = tokio::fs::read(path).await?;
+        assert_eq!(StorageFormat::MessagePack, StorageFormat::detect(&bytes)?);
+        assert!(!path.with_extension("yaml").exists());
+    }
+    assert_eq!(
+        "msgpack\n",
+        tokio::fs::read_to_string(&storage_format_file).await?
+    );
+    // Only this `CmdCtx` uses the new format, the workspace's storage is unchanged.
+    assert_eq!(
+        StorageFormat::MessagePack,
+        cmd_ctx.resources().borrow::<Storage>().format()
+    );
+    assert_eq!(StorageFormat::Yaml, workspace.storage().format());
+
+    let states_saved = StatesSavedReadCmd::exec(&mut cmd_ctx).await?;
+    assert_eq!(
+        Some(VecCopyState::from(vec![0u8, 1, 2, 3, 4, 5, 6, 7])).as_ref(),
+        states_saved.get::<VecCopyState, _>(VecCopyItemSpec.id())
+    );
+    let history_record = HistoryShowCmd::exec(&mut cmd_ctx, 0).await?;
+    let item_apply = history_record
+        .item_applies
+        .get_raw(VecCopyItemSpec.id())
+        .and_then(BoxDataTypeDowncast::<ItemApply<VecCopyState, VecCopyDiff>>::downcast_ref)
+        .expect("Expected `VecCopyItemSpec` to be in the history record.");
+    assert_eq!(
+        Some(VecCopyState::from(vec![0u8, 1, 2, 3, 4, 5, 6, 7])),
+        item_apply.state_applied
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn does_not_rewrite_history_records_of_other_flows() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let (states_current, _states_desired) =
+        StatesDiscoverCmd::current_and_desired(&mut cmd_ctx).await?;
+    EnsureCmd::exec(&mut cmd_ctx, &StatesSaved::from(states_current)).await?;
+    let profile_history_dir = cmd_ctx.scope().profile_history_dir().clone();
+
+    let flow_other = Flow::new(
+        FlowId::new("other_flow")?,
+        ItemSpecGraphBuilder::<PeaceTestError>::new().build(),
+    );
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow_other)
+        .await?;
+    let paths_migrated = StorageFormatMigrateCmd::exec(&mut cmd_ctx, StorageFormat::Json).await?;
+
+    assert_eq!(
+        vec![StorageFormatFile::from(workspace.dirs().peace_app_dir()).to_path_buf()],
+        paths_migrated
+    );
+    let record_paths = Storage::default()
+        .backend()
+        .dir_entries(&profile_history_dir)
+        .await?;
+    assert_eq!(1, record_paths.len());
+    assert_eq!(profile_history_dir.join("00000000.yaml"), record_paths[0]);
+    let bytes = tokio::fs::read(&record_paths[0]).await?;
+    assert_eq!(StorageFormat::Yaml, StorageFormat::detect(&bytes)?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn build_uses_storage_format_recorded_in_workspace() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?
+    .with_storage(Storage::default().with_format(StorageFormat::Json));
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    StatesDiscoverCmd::current(&mut cmd_ctx).await?;
+    let storage_format_file = StorageFormatFile::from(workspace.dirs().peace_app_dir());
+    let states_saved_file = StatesSavedFile::from((cmd_ctx.scope().flow_dir(), "json"));
+    assert_eq!(
+        "json\n",
+        tokio::fs::read_to_string(&storage_format_file).await?
+    );
+    assert!(states_saved_file.exists());
+
+    // The format configured on the storage is only used for new workspaces.
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?
+    .with_storage(Storage::default().with_format(StorageFormat::MessagePack));
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    StatesDiscoverCmd::current(&mut cmd_ctx).await?;
+
+    assert_eq!(
+        StorageFormat::Json,
+        cmd_ctx.resources().borrow::<Storage>().format()
+    );
+    assert_eq!(StorageFormat::MessagePack, workspace.storage().format());
+    let bytes = tokio::fs::read(&states_saved_file).await?;
+    assert_eq!(StorageFormat::Json, StorageFormat::detect(&bytes)?);
+    assert!(!states_saved_file.with_extension("msgpack").exists());
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+mod ensure_plan_read_cmd;
 mod states_current_discover_cmd;
 mod states_desired_discover_cmd;
 mod states_desired_read_cmd;
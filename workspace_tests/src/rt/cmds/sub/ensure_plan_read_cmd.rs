@@ -0,0 +1,99 @@
+use peace::{
+    cfg::{app_name, profile, AppName, FlowId, ItemSpec, Profile},
+    cmd::ctx::CmdCtx,
+    resources::{states::StatesSaved, type_reg::untagged::BoxDataTypeDowncast},
+    rt::cmds::{
+        sub::{EnsurePlanReadCmd, StatesCurrentDiscoverCmd},
+        EnsureCmd,
+    },
+    rt_model::{outcomes::ItemApply, Error, Flow, ItemSpecGraphBuilder, Workspace, WorkspaceSpec},
+};
+
+use crate::{NoOpOutput, PeaceTestError, VecCopyDiff, VecCopyError, VecCopyItemSpec, VecCopyState};
+
+#[tokio::test]
+async fn reads_ensure_plan_from_disk_when_present() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+    let mut output = NoOpOutput;
+
+    // Write ensure plan to disk.
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let states_current = StatesCurrentDiscoverCmd::exec(&mut cmd_ctx).await?;
+    let ensure_plan_from_dry_run =
+        EnsureCmd::exec_dry_plan(&mut cmd_ctx, &StatesSaved::from(states_current))
+            .await?
+            .value;
+
+    // Re-read ensure plan from disk.
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let ensure_plan_from_read = EnsurePlanReadCmd::exec(&mut cmd_ctx).await?;
+
+    let item_apply_from_dry_run = ensure_plan_from_dry_run
+        .get_raw(VecCopyItemSpec.id())
+        .and_then(BoxDataTypeDowncast::<ItemApply<VecCopyState, VecCopyDiff>>::downcast_ref);
+    let item_apply_from_read = ensure_plan_from_read
+        .get_raw(VecCopyItemSpec.id())
+        .and_then(BoxDataTypeDowncast::<ItemApply<VecCopyState, VecCopyDiff>>::downcast_ref);
+    assert!(item_apply_from_dry_run.is_some());
+    assert_eq!(item_apply_from_dry_run, item_apply_from_read);
+    Ok(())
+}
+
+#[tokio::test]
+async fn returns_error_when_ensure_plan_not_on_disk() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let workspace = Workspace::new(
+        app_name!(),
+        WorkspaceSpec::Path(tempdir.path().to_path_buf()),
+    )?;
+    let graph = {
+        let mut graph_builder = ItemSpecGraphBuilder::<PeaceTestError>::new();
+        graph_builder.add_fn(VecCopyItemSpec.into());
+        graph_builder.build()
+    };
+    let flow = Flow::new(FlowId::new(crate::fn_name_short!())?, graph);
+
+    // Try and read ensure plan from disk.
+    let mut output = NoOpOutput;
+    let mut cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &workspace)
+        .with_profile(profile!("test_profile"))
+        .with_flow(&flow)
+        .await?;
+    let exec_result = EnsurePlanReadCmd::exec(&mut cmd_ctx).await;
+
+    assert!(matches!(
+        exec_result,
+        Err(PeaceTestError::PeaceRtError(Error::EnsurePlanRequired))
+    ));
+    Ok(())
+}
+
+#[test]
+fn debug() {
+    let debug_str = format!(
+        "{:?}",
+        EnsurePlanReadCmd::<VecCopyError, NoOpOutput, ()>::default()
+    );
+    assert!(
+        debug_str
+            == r#"EnsurePlanReadCmd(PhantomData<(workspace_tests::vec_copy_item_spec::VecCopyError, workspace_tests::no_op_output::NoOpOutput, ())>)"#
+            || debug_str == r#"EnsurePlanReadCmd(PhantomData)"#
+    );
+}
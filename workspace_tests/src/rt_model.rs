@@ -1,9 +1,20 @@
+#[cfg(feature = "storage_http")]
+mod http_storage;
+#[cfg(feature = "storage_encryption")]
+mod encrypted_storage;
 mod item_spec_boxed;
 mod item_spec_graph;
 mod item_spec_graph_builder;
+#[cfg(feature = "item_specs")]
+mod item_spec_selection;
 mod item_spec_wrapper;
 mod outcomes;
 mod output;
+mod params_limits;
+mod profile_lock;
+mod retry_policy;
 mod states_serializer;
 mod storage;
+#[cfg(all(feature = "storage_format_json", feature = "storage_format_message_pack"))]
+mod storage_format;
 mod workspace_dirs_builder;
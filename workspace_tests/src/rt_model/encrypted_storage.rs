@@ -0,0 +1,311 @@
+use std::path::PathBuf;
+
+use peace::{
+    cfg::{app_name, flow_id, item_spec_id, profile, AppName, FlowId, ItemSpecId, Profile},
+    cmd::ctx::CmdCtx,
+    resources::{
+        internal::{StatesMut, WorkspaceParamsFile},
+        paths::StatesSavedFile,
+        states::StatesSaved,
+        type_reg::untagged::TypeReg,
+    },
+    rt_model::{
+        storage::{
+            EncryptedStorage, EncryptionKey, EnvKeyProvider, FileKeyProvider, FsStorage,
+            InMemoryStorage, KeyProvider, StorageBackend,
+        },
+        Error, Flow, ItemSpecGraphBuilder, NativeError, StatesSerializer, Storage, Workspace,
+        WorkspaceSpec,
+    },
+};
+
+use crate::{no_op_output::NoOpOutput, PeaceTestError};
+
+#[tokio::test]
+async fn write_then_read_round_trips_and_stores_ciphertext()
+-> Result<(), Box<dyn std::error::Error>> {
+    let in_memory_storage = InMemoryStorage::new();
+    let encrypted_storage =
+        EncryptedStorage::new(in_memory_storage.clone(), EncryptionKey::generate());
+    let path = PathBuf::from("states_saved.yaml");
+
+    encrypted_storage.write(&path, b"a: 123\n").await?;
+
+    let stored = in_memory_storage.read(&path).await?.unwrap();
+    assert!(stored.starts_with(b"peace_encrypted_v1:"));
+    assert!(!String::from_utf8(stored)?.contains("a: 123"));
+    assert_eq!(
+        Some(b"a: 123\n".to_vec()),
+        encrypted_storage.read(&path).await?
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_returns_item_as_is_when_item_not_encrypted() -> Result<(), Box<dyn std::error::Error>>
+{
+    let in_memory_storage = InMemoryStorage::new();
+    let encrypted_storage =
+        EncryptedStorage::new(in_memory_storage.clone(), EncryptionKey::generate());
+    let path = PathBuf::from("states_saved.yaml");
+    in_memory_storage.write(&path, b"a: 123\n").await?;
+
+    assert_eq!(
+        Some(b"a: 123\n".to_vec()),
+        encrypted_storage.read(&path).await?
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_returns_decrypt_error_when_key_is_different() -> Result<(), Box<dyn std::error::Error>>
+{
+    let in_memory_storage = InMemoryStorage::new();
+    let path = PathBuf::from("states_saved.yaml");
+    EncryptedStorage::new(in_memory_storage.clone(), EncryptionKey::generate())
+        .write(&path, b"a: 123\n")
+        .await?;
+
+    let error = EncryptedStorage::new(in_memory_storage, EncryptionKey::generate())
+        .read(&path)
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(&error, Error::StorageDecrypt { path: error_path } if error_path == &path),
+        "Expected `error` to be `Error::StorageDecrypt`, but was {error:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_returns_decrypt_error_when_item_is_moved() -> Result<(), Box<dyn std::error::Error>> {
+    let in_memory_storage = InMemoryStorage::new();
+    let encrypted_storage =
+        EncryptedStorage::new(in_memory_storage.clone(), EncryptionKey::generate());
+    let path = PathBuf::from("states_saved.yaml");
+    let moved_path = PathBuf::from("states_desired.yaml");
+    encrypted_storage.write(&path, b"a: 123\n").await?;
+    let stored = in_memory_storage.read(&path).await?.unwrap();
+    in_memory_storage.write(&moved_path, &stored).await?;
+
+    let error = encrypted_storage.read(&moved_path).await.unwrap_err();
+
+    assert!(
+        matches!(&error, Error::StorageDecrypt { path: error_path } if error_path == &moved_path),
+        "Expected `error` to be `Error::StorageDecrypt`, but was {error:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_decrypts_item_when_workspace_is_moved() -> Result<(), Box<dyn std::error::Error>> {
+    let in_memory_storage = InMemoryStorage::new();
+    let encrypted_storage =
+        EncryptedStorage::new(in_memory_storage.clone(), EncryptionKey::generate());
+    let path = PathBuf::from("/home/laptop/workspace/.peace/app/dev/states_saved.yaml");
+    let moved_path = PathBuf::from("/builds/ci/workspace/.peace/app/dev/states_saved.yaml");
+    encrypted_storage.write(&path, b"a: 123\n").await?;
+    let stored = in_memory_storage.read(&path).await?.unwrap();
+    in_memory_storage.write(&moved_path, &stored).await?;
+
+    assert_eq!(
+        Some(b"a: 123\n".to_vec()),
+        encrypted_storage.read(&moved_path).await?
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_returns_decrypt_error_when_item_is_moved_within_peace_dir()
+-> Result<(), Box<dyn std::error::Error>> {
+    let in_memory_storage = InMemoryStorage::new();
+    let encrypted_storage =
+        EncryptedStorage::new(in_memory_storage.clone(), EncryptionKey::generate());
+    let path = PathBuf::from("/workspace/.peace/app/dev/states_saved.yaml");
+    let moved_path = PathBuf::from("/workspace/.peace/app/prod/states_saved.yaml");
+    encrypted_storage.write(&path, b"a: 123\n").await?;
+    let stored = in_memory_storage.read(&path).await?.unwrap();
+    in_memory_storage.write(&moved_path, &stored).await?;
+
+    let error = encrypted_storage.read(&moved_path).await.unwrap_err();
+
+    assert!(
+        matches!(&error, Error::StorageDecrypt { path: error_path } if error_path == &moved_path),
+        "Expected `error` to be `Error::StorageDecrypt`, but was {error:?}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn encryption_key_from_base64_round_trips() -> Result<(), Box<dyn std::error::Error>> {
+    let encryption_key = EncryptionKey::generate();
+
+    let encryption_key_decoded =
+        EncryptionKey::from_base64("test", &format!("{}\n", encryption_key.to_base64()))?;
+
+    assert_eq!(encryption_key, encryption_key_decoded);
+    assert_eq!(
+        "EncryptionKey(\"********\")",
+        format!("{encryption_key_decoded:?}")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn encryption_key_from_base64_returns_error_when_length_is_not_32_bytes() {
+    let error = EncryptionKey::from_base64("test", "YWJj").unwrap_err();
+
+    assert!(
+        matches!(
+            &error,
+            Error::EncryptionKeyLength { key_source, length: 3 } if key_source == "test"
+        ),
+        "Expected `error` to be `Error::EncryptionKeyLength`, but was {error:?}"
+    );
+}
+
+#[test]
+fn env_key_provider_reads_key_from_env_var() -> Result<(), Box<dyn std::error::Error>> {
+    let encryption_key = EncryptionKey::generate();
+    let env_var_name = "PEACE_TEST_ENV_KEY_PROVIDER_READS_KEY_FROM_ENV_VAR";
+    std::env::set_var(env_var_name, encryption_key.to_base64());
+
+    let encryption_key_read = EnvKeyProvider::new(env_var_name).key()?;
+
+    assert_eq!(encryption_key, encryption_key_read);
+
+    Ok(())
+}
+
+#[test]
+fn env_key_provider_returns_error_when_env_var_not_set() {
+    let env_var_name = "PEACE_TEST_ENV_KEY_PROVIDER_RETURNS_ERROR_WHEN_ENV_VAR_NOT_SET";
+
+    let error = EnvKeyProvider::new(env_var_name).key().unwrap_err();
+
+    assert!(
+        matches!(
+            &error,
+            Error::Native(NativeError::EncryptionKeyEnvVarRead {
+                env_var_name: error_env_var_name,
+                error: std::env::VarError::NotPresent,
+            }) if error_env_var_name == env_var_name
+        ),
+        "Expected `error` to be `EncryptionKeyEnvVarRead`, but was {error:?}"
+    );
+}
+
+#[test]
+fn file_key_provider_reads_key_from_file() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let key_file_path = tempdir.path().join("peace.key");
+    let encryption_key = EncryptionKey::generate();
+    std::fs::write(&key_file_path, format!("{}\n", encryption_key.to_base64()))?;
+
+    let encryption_key_read = FileKeyProvider::new(key_file_path).key()?;
+
+    assert_eq!(encryption_key, encryption_key_read);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn states_serializer_serializes_encrypted_states() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let flow_id = flow_id!("test_flow");
+    let encryption_key = EncryptionKey::generate();
+    let storage = Storage::new(EncryptedStorage::new(FsStorage, encryption_key));
+    let item_spec_id = item_spec_id!("a");
+    let mut states_type_reg = TypeReg::new_typed();
+    states_type_reg.register::<u32>(item_spec_id.clone());
+    let states_saved_file = StatesSavedFile::new(tempdir.path().join("states_saved.yaml"));
+
+    let states = {
+        let mut states = StatesMut::new();
+        states.insert(item_spec_id.clone(), 123u32);
+        StatesSaved::from(states)
+    };
+    StatesSerializer::<Error>::serialize(&storage, &states, &states_saved_file).await?;
+
+    let serialized = tokio::fs::read_to_string(&states_saved_file).await?;
+    assert!(serialized.starts_with("peace_encrypted_v1:"));
+    let states_deserialized = StatesSerializer::<Error>::deserialize_saved(
+        &flow_id,
+        &storage,
+        &states_type_reg,
+        &states_saved_file,
+    )
+    .await?;
+    assert_eq!(
+        Some(123),
+        states_deserialized.get::<u32, _>(&item_spec_id).copied()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn cmd_ctx_build_encrypts_workspace_params_and_reads_unencrypted_params()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let app_name = app_name!("test_encrypted_storage");
+    let profile = profile!("test_profile");
+    let flow = Flow::<PeaceTestError>::new(
+        flow_id!("test_flow_id"),
+        ItemSpecGraphBuilder::new().build(),
+    );
+    let encryption_key = EncryptionKey::generate();
+
+    // Params written before encryption is enabled.
+    let unencrypted_workspace = workspace(&tempdir, app_name.clone(), None)?;
+    let mut output = NoOpOutput;
+    CmdCtx::builder_single_profile_single_flow(&mut output, &unencrypted_workspace)
+        .with_profile(profile.clone())
+        .with_flow(&flow)
+        .with_workspace_param_value(String::from("profile"), Some(profile.clone()))
+        .build()
+        .await?;
+
+    let encrypted_workspace = workspace(&tempdir, app_name, Some(encryption_key))?;
+    let cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &encrypted_workspace)
+        .with_workspace_params_k::<String>()
+        .with_workspace_param::<Profile>(String::from("profile"))
+        .with_profile_from_workspace_param(&String::from("profile"))
+        .with_flow(&flow)
+        .build()
+        .await?;
+
+    assert_eq!(&profile, cmd_ctx.scope().profile());
+    let workspace_params_file =
+        WorkspaceParamsFile::from(encrypted_workspace.dirs().peace_app_dir());
+    let workspace_params_serialized = tokio::fs::read_to_string(&workspace_params_file).await?;
+    assert!(workspace_params_serialized.starts_with("peace_encrypted_v1:"));
+    assert!(!workspace_params_serialized.contains("test_profile"));
+
+    Ok(())
+}
+
+fn workspace(
+    tempdir: &tempfile::TempDir,
+    app_name: AppName,
+    encryption_key: Option<EncryptionKey>,
+) -> Result<Workspace, Box<dyn std::error::Error>> {
+    let workspace = Workspace::new(app_name, WorkspaceSpec::Path(tempdir.path().to_path_buf()))?;
+    let workspace = match encryption_key {
+        Some(encryption_key) => workspace.with_storage(Storage::new(EncryptedStorage::new(
+            FsStorage,
+            encryption_key,
+        ))),
+        None => workspace,
+    };
+
+    Ok(workspace)
+}
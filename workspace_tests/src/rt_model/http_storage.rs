@@ -0,0 +1,450 @@
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    net::SocketAddr,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use hyper::{
+    header::{ETAG, IF_MATCH, IF_NONE_MATCH},
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use peace::{
+    cfg::{app_name, flow_id, profile, AppName, FlowId, Profile},
+    cmd::ctx::CmdCtx,
+    rt_model::{
+        storage::{HttpStorage, SerializedStorage, StorageBackend},
+        Error, Flow, ItemSpecGraphBuilder, NativeError, Storage, Workspace, WorkspaceSpec,
+    },
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{no_op_output::NoOpOutput, PeaceTestError};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+struct TestStruct {
+    a: u32,
+}
+
+#[tokio::test]
+async fn serialized_write_then_read_round_trips() -> Result<(), Box<dyn std::error::Error>> {
+    let (base_url, objects) = object_server_start();
+    let root_dir = PathBuf::from("/workspace");
+    let storage = Storage::new(HttpStorage::new(base_url, root_dir.clone())?);
+    let file_path = root_dir.join(".peace/app/t.yaml");
+
+    storage
+        .serialized_write(&file_path, &TestStruct { a: 1 }, |_error| {
+            panic!("Expected `test_struct` to be serialized.")
+        })
+        .await?;
+    let test_struct = storage
+        .serialized_read::<TestStruct, _>(&file_path, |_error| {
+            panic!("Expected `test_struct` to be deserialized.")
+        })
+        .await?;
+
+    assert_eq!(TestStruct { a: 1 }, test_struct);
+    assert_eq!(
+        vec![String::from("/.peace/app/t.yaml")],
+        objects.lock().unwrap().keys().cloned().collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_returns_none_when_object_not_exists() -> Result<(), Box<dyn std::error::Error>> {
+    let (base_url, _objects) = object_server_start();
+    let http_storage = HttpStorage::new(base_url, PathBuf::from("/workspace"))?;
+    let path = PathBuf::from("/workspace/t.yaml");
+
+    assert_eq!(None, http_storage.read(&path).await?);
+    assert!(!http_storage.contains(&path).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn write_returns_conflict_when_object_updated_by_another_writer()
+-> Result<(), Box<dyn std::error::Error>> {
+    let (base_url, _objects) = object_server_start();
+    let root_dir = PathBuf::from("/workspace");
+    let laptop_storage = HttpStorage::new(base_url.clone(), root_dir.clone())?;
+    let ci_storage = HttpStorage::new(base_url, root_dir.clone())?;
+    let path = root_dir.join("states_saved.yaml");
+    laptop_storage.write(&path, b"a: 1").await?;
+
+    laptop_storage.read(&path).await?;
+    ci_storage.read(&path).await?;
+    ci_storage.write(&path, b"a: 2").await?;
+    let error = laptop_storage.write(&path, b"a: 3").await.unwrap_err();
+
+    assert!(
+        matches!(&error, Error::StorageConflict { path: conflict_path } if conflict_path == &path),
+        "Expected `error` to be `Error::StorageConflict`, but was {error:?}"
+    );
+    assert_eq!(Some(b"a: 2".to_vec()), ci_storage.read(&path).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn write_returns_conflict_when_object_created_by_another_writer()
+-> Result<(), Box<dyn std::error::Error>> {
+    let (base_url, _objects) = object_server_start();
+    let root_dir = PathBuf::from("/workspace");
+    let laptop_storage = HttpStorage::new(base_url.clone(), root_dir.clone())?;
+    let ci_storage = HttpStorage::new(base_url, root_dir.clone())?;
+    let path = root_dir.join("states_saved.yaml");
+
+    assert_eq!(None, laptop_storage.read(&path).await?);
+    ci_storage.write(&path, b"a: 1").await?;
+    let error = laptop_storage.write(&path, b"a: 2").await.unwrap_err();
+
+    assert!(
+        matches!(&error, Error::StorageConflict { path: conflict_path } if conflict_path == &path),
+        "Expected `error` to be `Error::StorageConflict`, but was {error:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn write_succeeds_after_own_write() -> Result<(), Box<dyn std::error::Error>> {
+    let (base_url, _objects) = object_server_start();
+    let http_storage = HttpStorage::new(base_url, PathBuf::from("/workspace"))?;
+    let path = PathBuf::from("/workspace/states_saved.yaml");
+
+    assert_eq!(None, http_storage.read(&path).await?);
+    http_storage.write(&path, b"a: 1").await?;
+    http_storage.write(&path, b"a: 2").await?;
+
+    assert_eq!(Some(b"a: 2".to_vec()), http_storage.read(&path).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn write_replaces_object_after_read() -> Result<(), Box<dyn std::error::Error>> {
+    let (base_url, _objects) = object_server_start();
+    let root_dir = PathBuf::from("/workspace");
+    let laptop_storage = HttpStorage::new(base_url.clone(), root_dir.clone())?;
+    let ci_storage = HttpStorage::new(base_url, root_dir.clone())?;
+    let path = root_dir.join("states_desired.yaml");
+    laptop_storage.write(&path, b"a: 1").await?;
+
+    ci_storage.read(&path).await?;
+    ci_storage.write(&path, b"a: 2").await?;
+
+    assert_eq!(Some(b"a: 2".to_vec()), laptop_storage.read(&path).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn write_returns_conflict_when_object_has_not_been_read()
+-> Result<(), Box<dyn std::error::Error>> {
+    let (base_url, _objects) = object_server_start();
+    let root_dir = PathBuf::from("/workspace");
+    let laptop_storage = HttpStorage::new(base_url.clone(), root_dir.clone())?;
+    let ci_storage = HttpStorage::new(base_url, root_dir.clone())?;
+    let path = root_dir.join("states_desired.yaml");
+    laptop_storage.write(&path, b"a: 1").await?;
+
+    // Checking whether the object exists does not read it.
+    assert!(ci_storage.contains(&path).await?);
+    let error = ci_storage.write(&path, b"a: 2").await.unwrap_err();
+
+    assert!(
+        matches!(&error, Error::StorageConflict { path: conflict_path } if conflict_path == &path),
+        "Expected `error` to be `Error::StorageConflict`, but was {error:?}"
+    );
+    assert_eq!(Some(b"a: 1".to_vec()), ci_storage.read(&path).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn remove_returns_conflict_when_object_has_not_been_read()
+-> Result<(), Box<dyn std::error::Error>> {
+    let (base_url, _objects) = object_server_start();
+    let root_dir = PathBuf::from("/workspace");
+    let laptop_storage = HttpStorage::new(base_url.clone(), root_dir.clone())?;
+    let ci_storage = HttpStorage::new(base_url, root_dir.clone())?;
+    let path = root_dir.join("states_saved.yaml");
+    laptop_storage.write(&path, b"a: 1").await?;
+
+    let error = ci_storage.remove(&path).await.unwrap_err();
+
+    assert!(
+        matches!(&error, Error::StorageConflict { path: conflict_path } if conflict_path == &path),
+        "Expected `error` to be `Error::StorageConflict`, but was {error:?}"
+    );
+    assert_eq!(Some(b"a: 1".to_vec()), ci_storage.read(&path).await?);
+    ci_storage.remove(&path).await?;
+    assert!(!laptop_storage.contains(&path).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn write_replaces_object_with_weak_etag_after_read() -> Result<(), Box<dyn std::error::Error>>
+{
+    let (base_url, _objects) = object_server_start_with(EtagStrength::Weak);
+    let http_storage = HttpStorage::new(base_url, PathBuf::from("/workspace"))?;
+    let path = PathBuf::from("/workspace/states_saved.yaml");
+
+    http_storage.write(&path, b"a: 1").await?;
+    http_storage.read(&path).await?;
+    http_storage.write(&path, b"a: 2").await?;
+
+    assert_eq!(Some(b"a: 2".to_vec()), http_storage.read(&path).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn write_returns_error_when_path_is_outside_root_dir()
+-> Result<(), Box<dyn std::error::Error>> {
+    let (base_url, objects) = object_server_start();
+    let root_dir = PathBuf::from("/workspace");
+    let http_storage = HttpStorage::new(base_url, root_dir.clone())?;
+
+    for path in [
+        root_dir.join("../secrets.yaml"),
+        root_dir.join("a/../../secrets.yaml"),
+        PathBuf::from("/elsewhere/t.yaml"),
+    ] {
+        let error = http_storage.write(&path, b"a: 1").await.unwrap_err();
+
+        assert!(
+            matches!(
+                &error,
+                Error::Native(NativeError::HttpStorageItemPathInvalid { path: error_path })
+                if error_path == &path
+            ),
+            "Expected `error` to be `HttpStorageItemPathInvalid`, but was {error:?}"
+        );
+    }
+    assert!(objects.lock().unwrap().is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn dir_entries_returns_objects_directly_within_dir() -> Result<(), Box<dyn std::error::Error>>
+{
+    let (base_url, _objects) = object_server_start();
+    let root_dir = PathBuf::from("/workspace");
+    let http_storage = HttpStorage::new(base_url, root_dir.clone())?;
+    let dir = root_dir.join(".history");
+    http_storage
+        .write(&dir.join("00000000_a.yaml"), b"")
+        .await?;
+    http_storage
+        .write(&dir.join("00000001_b.yaml"), b"")
+        .await?;
+    http_storage.write(&dir.join("sub/c.yaml"), b"").await?;
+
+    let mut dir_entries = http_storage.dir_entries(&dir).await?;
+    dir_entries.sort();
+
+    assert_eq!(
+        vec![dir.join("00000000_a.yaml"), dir.join("00000001_b.yaml")],
+        dir_entries
+    );
+    assert!(http_storage
+        .dir_entries(&root_dir.join("missing"))
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn cmd_ctx_build_reads_workspace_params_written_from_another_workspace()
+-> Result<(), Box<dyn std::error::Error>> {
+    let (base_url, _objects) = object_server_start();
+    let app_name = app_name!("test_http_storage");
+    let profile = profile!("test_profile");
+    let flow = Flow::<PeaceTestError>::new(
+        flow_id!("test_flow_id"),
+        ItemSpecGraphBuilder::new().build(),
+    );
+
+    let laptop_tempdir = tempfile::tempdir()?;
+    let laptop_workspace = workspace(&laptop_tempdir, app_name.clone(), base_url.clone())?;
+    let mut output = NoOpOutput;
+    CmdCtx::builder_single_profile_single_flow(&mut output, &laptop_workspace)
+        .with_profile(profile.clone())
+        .with_flow(&flow)
+        .with_workspace_param_value(String::from("profile"), Some(profile.clone()))
+        .build()
+        .await?;
+
+    let ci_tempdir = tempfile::tempdir()?;
+    let ci_workspace = workspace(&ci_tempdir, app_name, base_url)?;
+    let cmd_ctx = CmdCtx::builder_single_profile_single_flow(&mut output, &ci_workspace)
+        .with_workspace_params_k::<String>()
+        .with_workspace_param::<Profile>(String::from("profile"))
+        .with_profile_from_workspace_param(&String::from("profile"))
+        .with_flow(&flow)
+        .build()
+        .await?;
+
+    assert_eq!(&profile, cmd_ctx.scope().profile());
+    assert!(!ci_workspace.dirs().peace_app_dir().exists());
+
+    Ok(())
+}
+
+fn workspace(
+    tempdir: &tempfile::TempDir,
+    app_name: AppName,
+    base_url: Url,
+) -> Result<Workspace, Box<dyn std::error::Error>> {
+    let workspace_dir = tempdir.path().to_path_buf();
+    let http_storage = HttpStorage::new(base_url, workspace_dir.clone())?;
+    let workspace = Workspace::new(app_name, WorkspaceSpec::Path(workspace_dir))?
+        .with_storage(Storage::new(http_storage));
+
+    Ok(workspace)
+}
+
+/// Objects stored by the stand-in server, keyed by URL path, with their
+/// version.
+type Objects = Arc<Mutex<BTreeMap<String, (Vec<u8>, u64)>>>;
+
+/// Whether the stand-in server responds with strong or weak `ETag`s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum EtagStrength {
+    Strong,
+    Weak,
+}
+
+/// Starts a stand-in HTTP object server, returning its base URL and objects.
+fn object_server_start() -> (Url, Objects) {
+    object_server_start_with(EtagStrength::Strong)
+}
+
+/// Starts a stand-in HTTP object server that responds with `ETag`s of the
+/// given strength, returning its base URL and objects.
+fn object_server_start_with(etag_strength: EtagStrength) -> (Url, Objects) {
+    let objects = Objects::default();
+    let objects_for_server = objects.clone();
+    let make_service = make_service_fn(move |_connection| {
+        let objects = objects_for_server.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                object_request_handle(objects.clone(), etag_strength, request)
+            }))
+        }
+    });
+
+    let server = Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(make_service);
+    let base_url = Url::parse(&format!("http://{}/", server.local_addr()))
+        .expect("Expected stand-in server URL to be valid.");
+    tokio::spawn(server);
+
+    (base_url, objects)
+}
+
+async fn object_request_handle(
+    objects: Objects,
+    etag_strength: EtagStrength,
+    request: Request<Body>,
+) -> Result<Response<Body>, hyper::Error> {
+    let etag = |version| etag(etag_strength, version);
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let header = |name| {
+        request
+            .headers()
+            .get(name)
+            .and_then(|value: &hyper::header::HeaderValue| value.to_str().ok())
+            .map(String::from)
+    };
+    let if_match = header(IF_MATCH);
+    let if_none_match = header(IF_NONE_MATCH);
+    let body = hyper::body::to_bytes(request.into_body()).await?;
+
+    let mut objects = objects.lock().unwrap();
+    let etag_current = objects.get(&path).map(|(_, version)| etag(*version));
+    // `If-Match` uses strong comparison, so weak `ETag`s never match.
+    let if_match_failed = if_match.is_some_and(|if_match| match &etag_current {
+        Some(etag) => etag.starts_with("W/") || etag != &if_match,
+        None => true,
+    });
+    let if_none_match_failed = if_none_match.as_deref() == Some("*") && etag_current.is_some();
+    let precondition_failed = if_match_failed || if_none_match_failed;
+
+    let response = match method {
+        Method::GET if path.ends_with('/') => {
+            let names = objects
+                .keys()
+                .filter_map(|key| key.strip_prefix(path.as_str()))
+                .filter(|name| !name.contains('/'))
+                .collect::<Vec<&str>>();
+            if names.is_empty() {
+                status_response(StatusCode::NOT_FOUND)
+            } else {
+                Response::new(Body::from(names.join("\n")))
+            }
+        }
+        Method::GET | Method::HEAD => match objects.get(&path) {
+            Some((bytes, version)) => {
+                let body = if method == Method::GET {
+                    Body::from(bytes.clone())
+                } else {
+                    Body::empty()
+                };
+                let mut response = Response::new(body);
+                response
+                    .headers_mut()
+                    .insert(ETAG, etag(*version).parse().unwrap());
+                response
+            }
+            None => status_response(StatusCode::NOT_FOUND),
+        },
+        Method::PUT if precondition_failed => status_response(StatusCode::PRECONDITION_FAILED),
+        Method::PUT => {
+            let version = objects
+                .values()
+                .map(|(_, version)| *version)
+                .max()
+                .unwrap_or(0)
+                + 1;
+            objects.insert(path, (body.to_vec(), version));
+            let mut response = Response::new(Body::empty());
+            response
+                .headers_mut()
+                .insert(ETAG, etag(version).parse().unwrap());
+            response
+        }
+        Method::DELETE if precondition_failed => status_response(StatusCode::PRECONDITION_FAILED),
+        Method::DELETE => match objects.remove(&path) {
+            Some(_) => status_response(StatusCode::NO_CONTENT),
+            None => status_response(StatusCode::NOT_FOUND),
+        },
+        _ => status_response(StatusCode::METHOD_NOT_ALLOWED),
+    };
+
+    Ok(response)
+}
+
+fn etag(etag_strength: EtagStrength, version: u64) -> String {
+    match etag_strength {
+        EtagStrength::Strong => format!("\"{version}\""),
+        EtagStrength::Weak => format!("W/\"{version}\""),
+    }
+}
+
+fn status_response(status: StatusCode) -> Response<Body> {
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = status;
+    response
+}
@@ -1,5 +1,8 @@
-use peace::rt_model::{
-    fn_graph::FnGraph, Error, ItemSpecBoxed, ItemSpecGraph, ItemSpecGraphBuilder,
+use std::collections::HashMap;
+
+use peace::{
+    cfg::{concurrency_group_id, ConcurrencyGroupId},
+    rt_model::{fn_graph::FnGraph, Error, ItemSpecBoxed, ItemSpecGraph, ItemSpecGraphBuilder},
 };
 
 #[test]
@@ -33,3 +36,22 @@ fn from() {
     let fn_graph = FnGraph::<ItemSpecBoxed<Error>>::new();
     let _item_spec_graph = ItemSpecGraph::<Error>::from(fn_graph);
 }
+
+#[test]
+fn concurrency_group_limits() {
+    let mut builder = ItemSpecGraphBuilder::<Error>::new();
+    builder.add_concurrency_group(concurrency_group_id!("iam"), 2);
+    builder.add_concurrency_group(concurrency_group_id!("downloads"), 0);
+    let item_spec_graph = builder.build();
+
+    let concurrency_group_limits_expected = [
+        (concurrency_group_id!("iam"), 2),
+        (concurrency_group_id!("downloads"), 1),
+    ]
+    .into_iter()
+    .collect::<HashMap<ConcurrencyGroupId, usize>>();
+    assert_eq!(
+        &concurrency_group_limits_expected,
+        item_spec_graph.concurrency_group_limits()
+    );
+}
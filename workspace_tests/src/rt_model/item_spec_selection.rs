@@ -0,0 +1,97 @@
+use std::collections::HashSet;
+
+use peace::{
+    cfg::{item_spec_id, ItemSpecId},
+    rt_model::{
+        Error, ItemSpecGraph, ItemSpecGraphBuilder, ItemSpecSelection, ItemSpecSelectionClosure,
+    },
+};
+use peace_item_specs::blank::{BlankError, BlankItemSpec};
+
+#[test]
+fn resolve_selected_only_returns_selected_item_specs() -> Result<(), Box<dyn std::error::Error>> {
+    let item_spec_graph = item_spec_graph()?;
+    let item_spec_selection = ItemSpecSelection::new([item_spec_id!("b")]);
+
+    let item_spec_ids_selected = item_spec_selection.resolve(&item_spec_graph)?;
+
+    assert_eq!(item_spec_ids(["b"]), item_spec_ids_selected);
+    Ok(())
+}
+
+#[test]
+fn resolve_with_predecessors_returns_transitive_predecessors()
+-> Result<(), Box<dyn std::error::Error>> {
+    let item_spec_graph = item_spec_graph()?;
+    let item_spec_selection = ItemSpecSelection::new([item_spec_id!("c")])
+        .with_closure(ItemSpecSelectionClosure::WithPredecessors);
+
+    let item_spec_ids_selected = item_spec_selection.resolve(&item_spec_graph)?;
+
+    assert_eq!(item_spec_ids(["a", "b", "c"]), item_spec_ids_selected);
+    Ok(())
+}
+
+#[test]
+fn resolve_with_successors_returns_transitive_successors() -> Result<(), Box<dyn std::error::Error>>
+{
+    let item_spec_graph = item_spec_graph()?;
+    let item_spec_selection = ItemSpecSelection::new([item_spec_id!("a")])
+        .with_closure(ItemSpecSelectionClosure::WithSuccessors);
+
+    let item_spec_ids_selected = item_spec_selection.resolve(&item_spec_graph)?;
+
+    assert_eq!(item_spec_ids(["a", "b", "c"]), item_spec_ids_selected);
+    Ok(())
+}
+
+#[test]
+fn resolve_returns_error_when_item_spec_id_not_in_graph() -> Result<(), Box<dyn std::error::Error>>
+{
+    let item_spec_graph = item_spec_graph()?;
+    let item_spec_selection = ItemSpecSelection::new([item_spec_id!("a"), item_spec_id!("z")]);
+
+    let error = item_spec_selection.resolve(&item_spec_graph).unwrap_err();
+
+    assert!(
+        matches!(
+            &error,
+            Error::ItemSpecSelectionIdNotFound { item_spec_id }
+            if item_spec_id == &item_spec_id!("z")
+        ),
+        "Expected error to be `ItemSpecSelectionIdNotFound`, but was {error:?}"
+    );
+    Ok(())
+}
+
+#[test]
+fn closure_defaults_to_selected_only() {
+    let item_spec_selection = ItemSpecSelection::new([item_spec_id!("a")]);
+
+    assert_eq!(
+        ItemSpecSelectionClosure::SelectedOnly,
+        item_spec_selection.closure()
+    );
+    assert_eq!(&[item_spec_id!("a")], item_spec_selection.item_spec_ids());
+}
+
+/// Returns a graph of `a -> b -> c`, and `d` which is independent.
+fn item_spec_graph() -> Result<ItemSpecGraph<BlankError>, Box<dyn std::error::Error>> {
+    let mut graph_builder = ItemSpecGraphBuilder::<BlankError>::new();
+    let [a, b, c] = graph_builder.add_fns([
+        BlankItemSpec::<()>::new(item_spec_id!("a")).into(),
+        BlankItemSpec::<()>::new(item_spec_id!("b")).into(),
+        BlankItemSpec::<()>::new(item_spec_id!("c")).into(),
+    ]);
+    graph_builder.add_fn(BlankItemSpec::<()>::new(item_spec_id!("d")).into());
+    graph_builder.add_edges([(a, b), (b, c)])?;
+
+    Ok(graph_builder.build())
+}
+
+fn item_spec_ids<const N: usize>(item_spec_ids: [&'static str; N]) -> HashSet<ItemSpecId> {
+    item_spec_ids
+        .into_iter()
+        .map(|item_spec_id| ItemSpecId::new(item_spec_id).expect("Expected valid item spec ID."))
+        .collect()
+}
@@ -1,7 +1,13 @@
+use std::{any::TypeId, time::Duration};
+
 use diff::{VecDiff, VecDiffType};
 use peace::{
-    cfg::{OpCheckStatus, OpCtx},
-    data::marker::{ApplyDry, Clean, Current, Desired},
+    cfg::{concurrency_group_id, ConcurrencyGroupId, InterruptSignal, OpCheckStatus, OpCtx},
+    data::{
+        marker::{ApplyDry, Clean, Current, Desired},
+        params::{ParamsResolveError, ParamsSpec, ValueResolutionMode, ValueSpec},
+        DataAccessDyn,
+    },
     resources::{
         internal::StatesMut,
         resources::ts::SetUp,
@@ -9,7 +15,7 @@ use peace::{
         type_reg::untagged::BoxDataTypeDowncast,
         Resources,
     },
-    rt_model::{ItemSpecRt, ItemSpecWrapper},
+    rt_model::{params::ParamsLimits, ItemSpecRt, ItemSpecWrapper, RetryPolicy},
 };
 cfg_if::cfg_if! {
     if #[cfg(feature = "output_progress")] {
@@ -40,6 +46,42 @@ async fn deref_mut_to_dyn_item_spec_rt() {
     assert_eq!(format!("{VecCopyItemSpec:?}"), format!("{item_spec_rt:?}"));
 }
 
+#[test]
+fn with_retry_policy_sets_retry_policy() {
+    let item_spec_wrapper =
+        ItemSpecWrapper::<_, VecCopyError, _, _, _, _, _, _>::from(VecCopyItemSpec)
+            .with_retry_policy(RetryPolicy::new(3));
+
+    assert_eq!(3, item_spec_wrapper.retry_policy().max_attempts());
+}
+
+#[test]
+fn with_timeout_sets_timeout() {
+    let item_spec_wrapper =
+        ItemSpecWrapper::<_, VecCopyError, _, _, _, _, _, _>::from(VecCopyItemSpec);
+    assert_eq!(None, item_spec_wrapper.timeout());
+
+    let item_spec_wrapper = item_spec_wrapper.with_timeout(Duration::from_secs(5));
+    assert_eq!(Some(Duration::from_secs(5)), item_spec_wrapper.timeout());
+}
+
+#[test]
+fn with_concurrency_group_sets_concurrency_group() {
+    let item_spec_wrapper =
+        ItemSpecWrapper::<_, VecCopyError, _, _, _, _, _, _>::from(VecCopyItemSpec);
+    assert_eq!(
+        None,
+        <dyn ItemSpecRt<_>>::concurrency_group(&item_spec_wrapper)
+    );
+
+    let item_spec_wrapper =
+        item_spec_wrapper.with_concurrency_group(concurrency_group_id!("downloads"));
+    assert_eq!(
+        Some(&concurrency_group_id!("downloads")),
+        <dyn ItemSpecRt<_>>::concurrency_group(&item_spec_wrapper)
+    );
+}
+
 #[tokio::test]
 async fn setup() -> Result<(), Box<dyn std::error::Error>> {
     let item_spec_wrapper =
@@ -57,8 +99,36 @@ async fn setup() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[tokio::test]
+async fn setup_inserts_params_none_without_resolving_params_spec(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let item_spec_wrapper =
+        ItemSpecWrapper::<_, VecCopyError, _, _, _, _, _, _>::from(VecCopyItemSpec)
+            .with_params_spec(vec_a_spec_from_predecessor());
+    let mut resources = Resources::new();
+    <dyn ItemSpecRt<_>>::setup(&item_spec_wrapper, &mut resources).await?;
+
+    assert!(resources.borrow::<Option<VecA>>().is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn data_access_declares_params_spec_borrows() {
+    let item_spec_wrapper =
+        ItemSpecWrapper::<_, VecCopyError, _, _, _, _, _, _>::from(VecCopyItemSpec)
+            .with_params_spec(LabelParamsSpec(ValueSpec::Stored));
+
+    assert!(DataAccessDyn::borrows(&item_spec_wrapper).contains(&TypeId::of::<LabelParams>()));
+    assert!(
+        DataAccessDyn::borrow_muts(&item_spec_wrapper)
+            .contains(&TypeId::of::<Option<LabelParams>>())
+    );
+}
+
 #[tokio::test]
 async fn state_current_try_exec() -> Result<(), Box<dyn std::error::Error>> {
+    let interrupt_signal = InterruptSignal::new();
     let item_spec_wrapper =
         ItemSpecWrapper::<_, VecCopyError, _, _, _, _, _, _>::from(VecCopyItemSpec);
     let resources = resources_set_up(&item_spec_wrapper).await?;
@@ -73,13 +143,15 @@ async fn state_current_try_exec() -> Result<(), Box<dyn std::error::Error>> {
     }
     let op_ctx = OpCtx::new(
         VecCopyItemSpec::ID,
+        &interrupt_signal,
         #[cfg(feature = "output_progress")]
         progress_sender,
     );
 
     let state = item_spec_wrapper
         .state_current_try_exec(op_ctx, &resources)
-        .await?
+        .await
+        .result?
         .unwrap();
 
     assert_eq!(
@@ -95,8 +167,46 @@ async fn state_current_try_exec() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[tokio::test]
+async fn state_current_try_exec_returns_none_when_predecessor_state_current_not_available(
+) -> Result<(), VecCopyError> {
+    let interrupt_signal = InterruptSignal::new();
+    let item_spec_wrapper =
+        ItemSpecWrapper::<_, VecCopyError, _, _, _, _, _, _>::from(VecCopyItemSpec)
+            .with_params_spec(vec_a_spec_from_predecessor());
+    let mut resources = resources_set_up(&item_spec_wrapper).await?;
+    resources.insert(Current::<PredecessorState>(None));
+    resources.insert(Desired(Some(PredecessorState(vec![1u8, 2, 3]))));
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "output_progress")] {
+            let (progress_tx, _progress_rx) = mpsc::channel(10);
+            let progress_sender = ProgressSender::new(
+                VecCopyItemSpec::ID,
+                &progress_tx,
+            );
+        }
+    }
+    let op_ctx = OpCtx::new(
+        VecCopyItemSpec::ID,
+        &interrupt_signal,
+        #[cfg(feature = "output_progress")]
+        progress_sender,
+    );
+
+    let state = item_spec_wrapper
+        .state_current_try_exec(op_ctx, &resources)
+        .await
+        .result?;
+
+    assert!(state.is_none(), "was {state:?}");
+    assert!(resources.borrow::<Current<VecCopyState>>().is_none());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn state_desired_try_exec() -> Result<(), VecCopyError> {
+    let interrupt_signal = InterruptSignal::new();
     let item_spec_wrapper =
         ItemSpecWrapper::<_, VecCopyError, _, _, _, _, _, _>::from(VecCopyItemSpec);
     let resources = resources_set_up(&item_spec_wrapper).await?;
@@ -111,13 +221,15 @@ async fn state_desired_try_exec() -> Result<(), VecCopyError> {
     }
     let op_ctx = OpCtx::new(
         VecCopyItemSpec::ID,
+        &interrupt_signal,
         #[cfg(feature = "output_progress")]
         progress_sender,
     );
 
     let state_desired = item_spec_wrapper
         .state_desired_try_exec(op_ctx, &resources)
-        .await?
+        .await
+        .result?
         .unwrap();
 
     assert_eq!(
@@ -133,6 +245,101 @@ async fn state_desired_try_exec() -> Result<(), VecCopyError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn state_desired_try_exec_resolves_params_from_predecessor_state() -> Result<(), VecCopyError>
+{
+    let interrupt_signal = InterruptSignal::new();
+    let item_spec_wrapper =
+        ItemSpecWrapper::<_, VecCopyError, _, _, _, _, _, _>::from(VecCopyItemSpec)
+            .with_params_spec(vec_a_spec_from_predecessor());
+    let mut resources = resources_set_up(&item_spec_wrapper).await?;
+    resources.insert(Desired(Some(PredecessorState(vec![1u8, 2, 3]))));
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "output_progress")] {
+            let (progress_tx, _progress_rx) = mpsc::channel(10);
+            let progress_sender = ProgressSender::new(
+                VecCopyItemSpec::ID,
+                &progress_tx,
+            );
+        }
+    }
+    let op_ctx = OpCtx::new(
+        VecCopyItemSpec::ID,
+        &interrupt_signal,
+        #[cfg(feature = "output_progress")]
+        progress_sender,
+    );
+
+    let state_desired = item_spec_wrapper
+        .state_desired_try_exec(op_ctx, &resources)
+        .await
+        .result?
+        .unwrap();
+
+    assert!(BoxDataTypeDowncast::<VecCopyState>::downcast_ref(&state_desired).is_some());
+    assert_eq!(
+        Some(vec![1u8, 2, 3]).as_ref(),
+        resources
+            .borrow::<Option<VecA>>()
+            .as_ref()
+            .map(|vec_a| &vec_a.0)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn state_desired_try_exec_returns_error_when_params_cannot_be_resolved(
+) -> Result<(), VecCopyError> {
+    let interrupt_signal = InterruptSignal::new();
+    let item_spec_wrapper =
+        ItemSpecWrapper::<_, VecCopyError, _, _, _, _, _, _>::from(VecCopyItemSpec)
+            .with_params_spec(vec_a_spec_from_predecessor());
+    let resources = resources_set_up(&item_spec_wrapper).await?;
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "output_progress")] {
+            let (progress_tx, _progress_rx) = mpsc::channel(10);
+            let progress_sender = ProgressSender::new(
+                VecCopyItemSpec::ID,
+                &progress_tx,
+            );
+        }
+    }
+    let op_ctx = OpCtx::new(
+        VecCopyItemSpec::ID,
+        &interrupt_signal,
+        #[cfg(feature = "output_progress")]
+        progress_sender,
+    );
+
+    let result = item_spec_wrapper
+        .state_desired_try_exec(op_ctx, &resources)
+        .await
+        .result;
+
+    assert!(
+        matches!(
+            &result,
+            Err(VecCopyError::PeaceRtError(
+                peace::rt_model::Error::ParamsResolve {
+                    item_spec_id,
+                    params_type_name,
+                    error: ParamsResolveError::FromMap {
+                        field_name: "0",
+                        value_resolution_mode: ValueResolutionMode::Desired,
+                        ..
+                    },
+                }
+            ))
+            if item_spec_id == VecCopyItemSpec::ID
+            && *params_type_name == std::any::type_name::<VecA>()
+        ),
+        "was {result:?}"
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn state_diff_exec_with_states_saved() -> Result<(), VecCopyError> {
     let item_spec_wrapper =
@@ -160,6 +367,7 @@ async fn state_diff_exec_with_states_saved() -> Result<(), VecCopyError> {
 
 #[tokio::test]
 async fn ensure_prepare() -> Result<(), VecCopyError> {
+    let interrupt_signal = InterruptSignal::new();
     let item_spec_wrapper =
         ItemSpecWrapper::<_, VecCopyError, _, _, _, _, _, _>::from(VecCopyItemSpec);
     let resources = resources_set_up(&item_spec_wrapper).await?;
@@ -174,6 +382,7 @@ async fn ensure_prepare() -> Result<(), VecCopyError> {
     }
     let op_ctx = OpCtx::new(
         VecCopyItemSpec::ID,
+        &interrupt_signal,
         #[cfg(feature = "output_progress")]
         progress_sender,
     );
@@ -198,6 +407,7 @@ async fn ensure_prepare() -> Result<(), VecCopyError> {
 
 #[tokio::test]
 async fn apply_exec_dry_for_ensure() -> Result<(), VecCopyError> {
+    let interrupt_signal = InterruptSignal::new();
     let item_spec_wrapper =
         ItemSpecWrapper::<_, VecCopyError, _, _, _, _, _, _>::from(VecCopyItemSpec);
     let resources = resources_set_up(&item_spec_wrapper).await?;
@@ -212,6 +422,7 @@ async fn apply_exec_dry_for_ensure() -> Result<(), VecCopyError> {
     }
     let op_ctx = OpCtx::new(
         VecCopyItemSpec::ID,
+        &interrupt_signal,
         #[cfg(feature = "output_progress")]
         progress_sender,
     );
@@ -231,6 +442,7 @@ async fn apply_exec_dry_for_ensure() -> Result<(), VecCopyError> {
     }
     let op_ctx = OpCtx::new(
         VecCopyItemSpec::ID,
+        &interrupt_signal,
         #[cfg(feature = "output_progress")]
         progress_sender,
     );
@@ -265,8 +477,58 @@ async fn apply_exec_dry_for_ensure() -> Result<(), VecCopyError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn apply_exec_dry_for_ensure_sets_apply_dry_to_state_current_when_exec_not_required(
+) -> Result<(), VecCopyError> {
+    let interrupt_signal = InterruptSignal::new();
+    let item_spec_wrapper =
+        ItemSpecWrapper::<_, VecCopyError, _, _, _, _, _, _>::from(VecCopyItemSpec);
+    let resources = resources_set_up_pre_saved(&item_spec_wrapper).await?;
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "output_progress")] {
+            let (progress_tx, _progress_rx) = mpsc::channel(10);
+            let progress_sender = ProgressSender::new(
+                VecCopyItemSpec::ID,
+                &progress_tx,
+            );
+        }
+    }
+    let op_ctx = OpCtx::new(
+        VecCopyItemSpec::ID,
+        &interrupt_signal,
+        #[cfg(feature = "output_progress")]
+        progress_sender,
+    );
+
+    let mut item_apply_boxed =
+        <dyn ItemSpecRt<_>>::ensure_prepare(&item_spec_wrapper, op_ctx, &resources)
+            .await
+            .map_err(|(error, _)| error)?;
+    assert_eq!(
+        OpCheckStatus::ExecNotRequired,
+        item_apply_boxed.op_check_status()
+    );
+
+    <dyn ItemSpecRt<_>>::apply_exec_dry(
+        &item_spec_wrapper,
+        op_ctx,
+        &resources,
+        &mut item_apply_boxed,
+    )
+    .await?;
+
+    // Automatic `ApplyDry<State>` insertion, even though the item is not applied.
+    assert_eq!(
+        Some(VecCopyState::from(vec![0u8, 1, 2, 3, 4, 5, 6, 7])).as_ref(),
+        resources.borrow::<ApplyDry<VecCopyState>>().as_ref()
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn apply_exec_for_ensure() -> Result<(), VecCopyError> {
+    let interrupt_signal = InterruptSignal::new();
     let item_spec_wrapper =
         ItemSpecWrapper::<_, VecCopyError, _, _, _, _, _, _>::from(VecCopyItemSpec);
     let resources = resources_set_up(&item_spec_wrapper).await?;
@@ -281,6 +543,7 @@ async fn apply_exec_for_ensure() -> Result<(), VecCopyError> {
     }
     let op_ctx = OpCtx::new(
         VecCopyItemSpec::ID,
+        &interrupt_signal,
         #[cfg(feature = "output_progress")]
         progress_sender,
     );
@@ -300,6 +563,7 @@ async fn apply_exec_for_ensure() -> Result<(), VecCopyError> {
     }
     let op_ctx = OpCtx::new(
         VecCopyItemSpec::ID,
+        &interrupt_signal,
         #[cfg(feature = "output_progress")]
         progress_sender,
     );
@@ -329,8 +593,70 @@ async fn apply_exec_for_ensure() -> Result<(), VecCopyError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn apply_exec_for_ensure_returns_error_when_resolved_params_exceed_limits(
+) -> Result<(), VecCopyError> {
+    let interrupt_signal = InterruptSignal::new();
+    let item_spec_wrapper =
+        ItemSpecWrapper::<_, VecCopyError, _, _, _, _, _, _>::from(VecCopyItemSpec)
+            .with_params_spec(vec_a_spec_from_predecessor());
+    let mut resources = resources_set_up(&item_spec_wrapper).await?;
+    resources.insert(Current(Some(PredecessorState(vec![0u8, 1, 2, 3, 4, 5]))));
+    resources.insert(Desired(Some(PredecessorState(vec![0u8, 1, 2, 3, 4, 5]))));
+    resources.insert(ParamsLimits::new().with_limit(|vec_a: &VecA| {
+        if vec_a.0.len() > 4 {
+            Err(format!("{} bytes exceeds limit 4", vec_a.0.len()))
+        } else {
+            Ok(())
+        }
+    }));
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "output_progress")] {
+            let (progress_tx, _progress_rx) = mpsc::channel(10);
+            let progress_sender = ProgressSender::new(
+                VecCopyItemSpec::ID,
+                &progress_tx,
+            );
+        }
+    }
+    let op_ctx = OpCtx::new(
+        VecCopyItemSpec::ID,
+        &interrupt_signal,
+        #[cfg(feature = "output_progress")]
+        progress_sender,
+    );
+
+    let mut item_apply_boxed =
+        <dyn ItemSpecRt<_>>::ensure_prepare(&item_spec_wrapper, op_ctx, &resources)
+            .await
+            .map_err(|(error, _)| error)?;
+    let result = <dyn ItemSpecRt<_>>::apply_exec(
+        &item_spec_wrapper,
+        op_ctx,
+        &resources,
+        &mut item_apply_boxed,
+    )
+    .await;
+
+    assert!(
+        matches!(
+            &result,
+            Err(VecCopyError::PeaceRtError(
+                peace::rt_model::Error::ParamsLimitsExceeded { violations }
+            ))
+            if violations.len() == 1
+            && violations[0].description() == "6 bytes exceeds limit 4"
+        ),
+        "was {result:?}"
+    );
+    assert!(resources.borrow::<VecB>().0.is_empty());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn clean_prepare() -> Result<(), VecCopyError> {
+    let interrupt_signal = InterruptSignal::new();
     let item_spec_wrapper =
         ItemSpecWrapper::<_, VecCopyError, _, _, _, _, _, _>::from(VecCopyItemSpec);
     let resources = resources_set_up_pre_saved(&item_spec_wrapper).await?;
@@ -345,6 +671,7 @@ async fn clean_prepare() -> Result<(), VecCopyError> {
     }
     let op_ctx = OpCtx::new(
         VecCopyItemSpec::ID,
+        &interrupt_signal,
         #[cfg(feature = "output_progress")]
         progress_sender,
     );
@@ -369,6 +696,7 @@ async fn clean_prepare() -> Result<(), VecCopyError> {
 
 #[tokio::test]
 async fn apply_exec_dry_for_clean() -> Result<(), VecCopyError> {
+    let interrupt_signal = InterruptSignal::new();
     let item_spec_wrapper =
         ItemSpecWrapper::<_, VecCopyError, _, _, _, _, _, _>::from(VecCopyItemSpec);
     let resources = resources_set_up_pre_saved(&item_spec_wrapper).await?;
@@ -383,6 +711,7 @@ async fn apply_exec_dry_for_clean() -> Result<(), VecCopyError> {
     }
     let op_ctx = OpCtx::new(
         VecCopyItemSpec::ID,
+        &interrupt_signal,
         #[cfg(feature = "output_progress")]
         progress_sender,
     );
@@ -402,6 +731,7 @@ async fn apply_exec_dry_for_clean() -> Result<(), VecCopyError> {
     }
     let op_ctx = OpCtx::new(
         VecCopyItemSpec::ID,
+        &interrupt_signal,
         #[cfg(feature = "output_progress")]
         progress_sender,
     );
@@ -438,6 +768,7 @@ async fn apply_exec_dry_for_clean() -> Result<(), VecCopyError> {
 
 #[tokio::test]
 async fn apply_exec_for_clean() -> Result<(), VecCopyError> {
+    let interrupt_signal = InterruptSignal::new();
     let item_spec_wrapper =
         ItemSpecWrapper::<_, VecCopyError, _, _, _, _, _, _>::from(VecCopyItemSpec);
     let resources = resources_set_up_pre_saved(&item_spec_wrapper).await?;
@@ -452,6 +783,7 @@ async fn apply_exec_for_clean() -> Result<(), VecCopyError> {
     }
     let op_ctx = OpCtx::new(
         VecCopyItemSpec::ID,
+        &interrupt_signal,
         #[cfg(feature = "output_progress")]
         progress_sender,
     );
@@ -471,6 +803,7 @@ async fn apply_exec_for_clean() -> Result<(), VecCopyError> {
     }
     let op_ctx = OpCtx::new(
         VecCopyItemSpec::ID,
+        &interrupt_signal,
         #[cfg(feature = "output_progress")]
         progress_sender,
     );
@@ -522,6 +855,7 @@ async fn resources_set_up_pre_saved(
 async fn resources_and_states_saved_and_desired(
     item_spec_wrapper: &VecCopyItemSpecWrapper,
 ) -> Result<(Resources<SetUp>, StatesSaved, StatesDesired), VecCopyError> {
+    let interrupt_signal = InterruptSignal::new();
     let resources = resources_set_up(item_spec_wrapper).await?;
     cfg_if::cfg_if! {
         if #[cfg(feature = "output_progress")] {
@@ -534,6 +868,7 @@ async fn resources_and_states_saved_and_desired(
     }
     let op_ctx = OpCtx::new(
         VecCopyItemSpec::ID,
+        &interrupt_signal,
         #[cfg(feature = "output_progress")]
         progress_sender,
     );
@@ -542,7 +877,8 @@ async fn resources_and_states_saved_and_desired(
         let mut states_mut = StatesMut::new();
         let state =
             <dyn ItemSpecRt<_>>::state_current_try_exec(item_spec_wrapper, op_ctx, &resources)
-                .await?;
+                .await
+                .result?;
         if let Some(state) = state {
             states_mut.insert_raw(<dyn ItemSpecRt<_>>::id(item_spec_wrapper).clone(), state);
         }
@@ -553,7 +889,8 @@ async fn resources_and_states_saved_and_desired(
         let mut states_desired_mut = StatesMut::<states::ts::Desired>::new();
         let state_desired = item_spec_wrapper
             .state_desired_try_exec(op_ctx, &resources)
-            .await?
+            .await
+            .result?
             .unwrap();
         states_desired_mut.insert_raw(
             <dyn ItemSpecRt<_>>::id(item_spec_wrapper).clone(),
@@ -564,3 +901,58 @@ async fn resources_and_states_saved_and_desired(
     };
     Ok((resources, states_saved, states_desired))
 }
+
+fn vec_a_spec_from_predecessor() -> VecASpec {
+    VecASpec(ValueSpec::from_map(
+        |predecessor_state: &PredecessorState| Some(predecessor_state.0.clone()),
+    ))
+}
+
+/// Spec to resolve `VecA` from a predecessor's state.
+#[derive(Debug)]
+struct VecASpec(ValueSpec<Vec<u8>>);
+
+impl ParamsSpec for VecASpec {
+    type Params = VecA;
+
+    fn resolve(
+        &self,
+        resources: &peace::data::Resources,
+        value_resolution_mode: ValueResolutionMode,
+    ) -> Result<VecA, ParamsResolveError> {
+        self.0
+            .resolve(resources, value_resolution_mode, "0", None)
+            .map(VecA)
+    }
+}
+
+#[derive(Debug)]
+struct PredecessorState(Vec<u8>);
+
+/// Params that are not `Default`, and are not inserted by the item spec.
+#[derive(Debug)]
+struct LabelParams(String);
+
+/// Spec to resolve `LabelParams`.
+#[derive(Debug)]
+struct LabelParamsSpec(ValueSpec<String>);
+
+impl ParamsSpec for LabelParamsSpec {
+    type Params = LabelParams;
+
+    fn resolve(
+        &self,
+        resources: &peace::data::Resources,
+        value_resolution_mode: ValueResolutionMode,
+    ) -> Result<LabelParams, ParamsResolveError> {
+        let params_stored = resources.try_borrow::<LabelParams>().ok();
+        self.0
+            .resolve(
+                resources,
+                value_resolution_mode,
+                "0",
+                params_stored.as_ref().map(|params| &params.0),
+            )
+            .map(LabelParams)
+    }
+}
@@ -4,3 +4,5 @@ mod item_apply_partial;
 mod item_apply_partial_boxed;
 mod item_apply_partial_rt;
 mod item_apply_rt;
+mod state_drift;
+mod state_drifts;
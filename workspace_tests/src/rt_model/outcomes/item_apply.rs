@@ -13,6 +13,7 @@ fn try_from_returns_ok_when_state_saved_is_none_and_others_are_some()
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let item_apply = ItemApply::try_from((item_apply_partial, None)).unwrap();
@@ -25,6 +26,7 @@ fn try_from_returns_ok_when_state_saved_is_none_and_others_are_some()
             state_diff: 8u8,
             op_check_status: OpCheckStatus::ExecNotRequired,
             state_applied: None,
+            retry_count: 0,
         },
         item_apply
     );
@@ -39,6 +41,7 @@ fn try_from_returns_ok_when_all_fields_are_some() -> Result<(), Box<dyn std::err
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let item_apply = ItemApply::try_from((item_apply_partial, None)).unwrap();
@@ -51,6 +54,7 @@ fn try_from_returns_ok_when_all_fields_are_some() -> Result<(), Box<dyn std::err
             state_diff: 8u8,
             op_check_status: OpCheckStatus::ExecNotRequired,
             state_applied: None,
+            retry_count: 0,
         },
         item_apply
     );
@@ -65,6 +69,7 @@ fn try_from_passes_through_state_applied() -> Result<(), Box<dyn std::error::Err
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let item_apply = ItemApply::try_from((item_apply_partial, Some(789))).unwrap();
@@ -77,6 +82,7 @@ fn try_from_passes_through_state_applied() -> Result<(), Box<dyn std::error::Err
             state_diff: 8u8,
             op_check_status: OpCheckStatus::ExecNotRequired,
             state_applied: Some(789),
+            retry_count: 0,
         },
         item_apply
     );
@@ -91,6 +97,7 @@ fn try_from_returns_err_when_state_current_is_none() -> Result<(), Box<dyn std::
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let (item_apply_partial, state_applied) =
@@ -103,6 +110,7 @@ fn try_from_returns_err_when_state_current_is_none() -> Result<(), Box<dyn std::
             state_target: Some(789u32),
             state_diff: Some(8u8),
             op_check_status: Some(OpCheckStatus::ExecNotRequired),
+            retry_count: 0,
         },
         item_apply_partial
     );
@@ -118,6 +126,7 @@ fn try_from_returns_err_when_state_target_is_none() -> Result<(), Box<dyn std::e
         state_target: None,
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let (item_apply_partial, state_applied) =
@@ -130,6 +139,7 @@ fn try_from_returns_err_when_state_target_is_none() -> Result<(), Box<dyn std::e
             state_target: None,
             state_diff: Some(8u8),
             op_check_status: Some(OpCheckStatus::ExecNotRequired),
+            retry_count: 0,
         },
         item_apply_partial
     );
@@ -145,6 +155,7 @@ fn try_from_returns_err_when_state_diff_is_none() -> Result<(), Box<dyn std::err
         state_target: Some(789u32),
         state_diff: None::<u8>,
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let (item_apply_partial, state_applied) =
@@ -157,6 +168,7 @@ fn try_from_returns_err_when_state_diff_is_none() -> Result<(), Box<dyn std::err
             state_target: Some(789u32),
             state_diff: None,
             op_check_status: Some(OpCheckStatus::ExecNotRequired),
+            retry_count: 0,
         },
         item_apply_partial
     );
@@ -172,6 +184,7 @@ fn try_from_returns_err_when_op_check_status_is_none() -> Result<(), Box<dyn std
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: None,
+        retry_count: 0,
     };
 
     let (item_apply_partial, state_applied) =
@@ -184,6 +197,7 @@ fn try_from_returns_err_when_op_check_status_is_none() -> Result<(), Box<dyn std
             state_target: Some(789u32),
             state_diff: Some(8u8),
             op_check_status: None,
+            retry_count: 0,
         },
         item_apply_partial
     );
@@ -199,6 +213,7 @@ fn item_apply_rt_state_saved_returns_state_saved() -> Result<(), Box<dyn std::er
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let item_apply = ItemApply::try_from((item_apply_partial, None)).unwrap();
@@ -219,6 +234,7 @@ fn item_apply_rt_state_current_returns_state_current() -> Result<(), Box<dyn std
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let item_apply = ItemApply::try_from((item_apply_partial, None)).unwrap();
@@ -239,6 +255,7 @@ fn item_apply_rt_state_target_returns_state_target() -> Result<(), Box<dyn std::
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let item_apply = ItemApply::try_from((item_apply_partial, None)).unwrap();
@@ -259,6 +276,7 @@ fn item_apply_rt_state_diff_returns_state_diff() -> Result<(), Box<dyn std::erro
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let item_apply = ItemApply::try_from((item_apply_partial, None)).unwrap();
@@ -280,6 +298,7 @@ fn item_apply_rt_op_check_status_returns_op_check_status() -> Result<(), Box<dyn
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let item_apply = ItemApply::try_from((item_apply_partial, None)).unwrap();
@@ -297,6 +316,7 @@ fn item_apply_rt_state_applied_returns_state_applied() -> Result<(), Box<dyn std
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let item_apply = ItemApply::try_from((item_apply_partial, Some(456u32))).unwrap();
@@ -317,6 +337,7 @@ fn item_apply_rt_as_data_type_returns_self() -> Result<(), Box<dyn std::error::E
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let item_apply = ItemApply::try_from((item_apply_partial, None)).unwrap();
@@ -337,6 +358,7 @@ fn item_apply_rt_as_data_type_mut_returns_self() -> Result<(), Box<dyn std::erro
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let item_apply = ItemApply::try_from((item_apply_partial, None)).unwrap();
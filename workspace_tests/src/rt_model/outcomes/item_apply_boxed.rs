@@ -54,6 +54,7 @@ fn debug() {
         state_diff: 2,
         op_check_status: ExecNotRequired,
         state_applied: None,
+        retry_count: 0,
     },
 )"#,
         format!("{item_apply_boxed:#?}")
@@ -88,6 +89,7 @@ state_target:
 state_diff: 2
 op_check_status: ExecNotRequired
 state_applied: null
+retry_count: 0
 "#,
         serde_yaml::to_string(data_type_wrapper)?
     );
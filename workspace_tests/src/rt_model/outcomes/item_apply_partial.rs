@@ -12,6 +12,7 @@ fn item_apply_rt_state_saved_returns_state_saved() -> Result<(), Box<dyn std::er
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let state_saved = ItemApplyPartialRt::state_saved(&item_apply_partial).unwrap();
@@ -32,6 +33,7 @@ fn item_apply_rt_state_current_returns_state_current() -> Result<(), Box<dyn std
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let state_current = ItemApplyPartialRt::state_current(&item_apply_partial).unwrap();
@@ -51,6 +53,7 @@ fn item_apply_rt_state_target_returns_state_target() -> Result<(), Box<dyn std::
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let state_target = ItemApplyPartialRt::state_target(&item_apply_partial).unwrap();
@@ -70,6 +73,7 @@ fn item_apply_rt_state_diff_returns_state_diff() -> Result<(), Box<dyn std::erro
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let state_diff = ItemApplyPartialRt::state_diff(&item_apply_partial).unwrap();
@@ -90,6 +94,7 @@ fn item_apply_rt_op_check_status_returns_op_check_status() -> Result<(), Box<dyn
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let op_check_status = ItemApplyPartialRt::op_check_status(&item_apply_partial).unwrap();
@@ -106,6 +111,7 @@ fn item_apply_rt_as_data_type_returns_self() -> Result<(), Box<dyn std::error::E
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     #[allow(clippy::redundant_clone)] // https://github.com/rust-lang/rust-clippy/issues/9011
@@ -129,6 +135,7 @@ fn item_apply_rt_as_data_type_mut_returns_self() -> Result<(), Box<dyn std::erro
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let mut item_apply_partial_clone = item_apply_partial.clone();
@@ -61,6 +61,7 @@ fn debug() {
         op_check_status: Some(
             ExecNotRequired,
         ),
+        retry_count: 0,
     },
 )"#,
         format!("{item_apply_partial_boxed:#?}")
@@ -94,6 +95,7 @@ state_target:
   physical: !Tbd null
 state_diff: 2
 op_check_status: ExecNotRequired
+retry_count: 0
 "#,
         serde_yaml::to_string(data_type_wrapper)?
     );
@@ -12,6 +12,7 @@ fn item_apply_rt_state_saved_returns_state_saved() -> Result<(), Box<dyn std::er
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
     let item_apply_partial_boxed = Box::new(item_apply_partial) as Box<dyn ItemApplyPartialRt>;
 
@@ -32,6 +33,7 @@ fn item_apply_rt_state_current_returns_state_current() -> Result<(), Box<dyn std
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
     let item_apply_partial_boxed = Box::new(item_apply_partial) as Box<dyn ItemApplyPartialRt>;
 
@@ -52,6 +54,7 @@ fn item_apply_rt_state_target_returns_state_target() -> Result<(), Box<dyn std::
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
     let item_apply_partial_boxed = Box::new(item_apply_partial) as Box<dyn ItemApplyPartialRt>;
 
@@ -72,6 +75,7 @@ fn item_apply_rt_state_diff_returns_state_diff() -> Result<(), Box<dyn std::erro
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
     let item_apply_partial_boxed = Box::new(item_apply_partial) as Box<dyn ItemApplyPartialRt>;
 
@@ -93,6 +97,7 @@ fn item_apply_rt_op_check_status_returns_op_check_status() -> Result<(), Box<dyn
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
     let item_apply_partial_boxed = Box::new(item_apply_partial) as Box<dyn ItemApplyPartialRt>;
 
@@ -110,6 +115,7 @@ fn item_apply_rt_as_data_type_returns_self() -> Result<(), Box<dyn std::error::E
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let item_apply_partial_clone = item_apply_partial.clone();
@@ -134,6 +140,7 @@ fn item_apply_rt_as_data_type_mut_returns_self() -> Result<(), Box<dyn std::erro
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let item_apply_partial_clone = item_apply_partial.clone();
@@ -158,6 +165,7 @@ fn serialize() -> Result<(), Box<dyn std::error::Error>> {
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
     let item_apply_partial_boxed = Box::new(item_apply_partial) as Box<dyn ItemApplyPartialRt>;
 
@@ -168,6 +176,7 @@ state_current: 123
 state_target: 789
 state_diff: 8
 op_check_status: ExecNotRequired
+retry_count: 0
 "#,
         serialized
     );
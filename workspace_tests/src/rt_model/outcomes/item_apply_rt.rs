@@ -12,6 +12,7 @@ fn state_saved_returns_state_saved() -> Result<(), Box<dyn std::error::Error>> {
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let item_apply = ItemApply::try_from((item_apply_partial, None)).unwrap();
@@ -33,6 +34,7 @@ fn state_current_returns_state_current() -> Result<(), Box<dyn std::error::Error
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let item_apply = ItemApply::try_from((item_apply_partial, None)).unwrap();
@@ -54,6 +56,7 @@ fn state_target_returns_state_target() -> Result<(), Box<dyn std::error::Error>>
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let item_apply = ItemApply::try_from((item_apply_partial, None)).unwrap();
@@ -75,6 +78,7 @@ fn state_diff_returns_state_diff() -> Result<(), Box<dyn std::error::Error>> {
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let item_apply = ItemApply::try_from((item_apply_partial, None)).unwrap();
@@ -96,6 +100,7 @@ fn op_check_status_returns_op_check_status() -> Result<(), Box<dyn std::error::E
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let item_apply = ItemApply::try_from((item_apply_partial, None)).unwrap();
@@ -114,6 +119,7 @@ fn state_applied_returns_state_applied() -> Result<(), Box<dyn std::error::Error
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let item_apply = ItemApply::try_from((item_apply_partial, Some(456u32))).unwrap();
@@ -135,6 +141,7 @@ fn as_data_type_returns_self() -> Result<(), Box<dyn std::error::Error>> {
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let item_apply = ItemApply::try_from((item_apply_partial, None)).unwrap();
@@ -157,6 +164,7 @@ fn as_data_type_mut_returns_self() -> Result<(), Box<dyn std::error::Error>> {
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let item_apply = ItemApply::try_from((item_apply_partial, None)).unwrap();
@@ -179,6 +187,7 @@ fn serialize() -> Result<(), Box<dyn std::error::Error>> {
         state_target: Some(789u32),
         state_diff: Some(8u8),
         op_check_status: Some(OpCheckStatus::ExecNotRequired),
+        retry_count: 0,
     };
 
     let item_apply = ItemApply::try_from((item_apply_partial, Some(456u32))).unwrap();
@@ -192,6 +201,7 @@ state_target: 789
 state_diff: 8
 op_check_status: ExecNotRequired
 state_applied: 456
+retry_count: 0
 "#,
         serialized
     );
@@ -0,0 +1,43 @@
+use peace::{resources::type_reg::untagged::BoxDtDisplay, rt_model::outcomes::StateDrift};
+
+#[test]
+fn is_drifted_returns_true_only_for_drifted() {
+    assert!(
+        StateDrift::Drifted {
+            state_diff: BoxDtDisplay::new(1u8)
+        }
+        .is_drifted()
+    );
+    assert!(!StateDrift::InSync.is_drifted());
+    assert!(!StateDrift::StateSavedNone.is_drifted());
+    assert!(!StateDrift::StateCurrentNone.is_drifted());
+}
+
+#[test]
+fn display() {
+    assert_eq!("in sync", StateDrift::InSync.to_string());
+    assert_eq!(
+        "drifted: 1",
+        StateDrift::Drifted {
+            state_diff: BoxDtDisplay::new(1u8)
+        }
+        .to_string()
+    );
+    assert_eq!("state not saved", StateDrift::StateSavedNone.to_string());
+    assert_eq!(
+        "current state not discovered",
+        StateDrift::StateCurrentNone.to_string()
+    );
+}
+
+#[test]
+fn serialize() -> Result<(), serde_yaml::Error> {
+    assert_eq!("in_sync\n", serde_yaml::to_string(&StateDrift::InSync)?);
+    assert_eq!(
+        "!drifted\nstate_diff: 1\n",
+        serde_yaml::to_string(&StateDrift::Drifted {
+            state_diff: BoxDtDisplay::new(1u8)
+        })?
+    );
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use peace::{
+    cfg::{item_spec_id, ItemSpecId},
+    resources::type_reg::untagged::BoxDtDisplay,
+    rt_model::{
+        outcomes::{StateDrift, StateDrifts},
+        IndexMap,
+    },
+};
+
+#[test]
+fn is_drifted_returns_true_when_any_item_drifted() {
+    let state_drifts = StateDrifts::from(
+        [
+            (item_spec_id!("a"), StateDrift::InSync),
+            (
+                item_spec_id!("b"),
+                StateDrift::Drifted {
+                    state_diff: BoxDtDisplay::new(1u8),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect::<IndexMap<_, _>>(),
+    );
+
+    assert!(state_drifts.is_drifted());
+}
+
+#[test]
+fn is_drifted_returns_false_when_no_item_drifted() {
+    let state_drifts = StateDrifts::from(
+        [
+            (item_spec_id!("a"), StateDrift::InSync),
+            (item_spec_id!("b"), StateDrift::StateSavedNone),
+        ]
+        .into_iter()
+        .collect::<IndexMap<_, _>>(),
+    );
+
+    assert!(!state_drifts.is_drifted());
+    assert!(!StateDrifts::new().is_drifted());
+}
+
+#[test]
+fn serialize() -> Result<(), serde_yaml::Error> {
+    let state_drifts = StateDrifts::from(
+        [
+            (item_spec_id!("a"), StateDrift::InSync),
+            (item_spec_id!("b"), StateDrift::StateCurrentNone),
+        ]
+        .into_iter()
+        .collect::<IndexMap<_, _>>(),
+    );
+
+    assert_eq!(
+        "a: in_sync\nb: state_current_none\n",
+        serde_yaml::to_string(&state_drifts)?
+    );
+    Ok(())
+}
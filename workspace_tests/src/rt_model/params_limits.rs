@@ -0,0 +1,127 @@
+use peace::{
+    resources::Resources,
+    rt_model::{
+        params::{ParamsLimitViolation, ParamsLimits},
+        Error,
+    },
+};
+
+#[test]
+fn violations_is_empty_when_values_are_within_limits() -> Result<(), Error> {
+    let params_limits = params_limits();
+    let mut resources = Resources::new();
+    resources.insert(InstanceCount(10));
+
+    assert!(params_limits.violations(&resources)?.is_empty());
+    assert!(params_limits.check(&resources).is_ok());
+    Ok(())
+}
+
+#[test]
+fn check_returns_error_when_value_is_not_in_resources() {
+    let params_limits = params_limits().with_override(true);
+    let resources = Resources::new();
+
+    let result = params_limits.check(&resources);
+
+    assert!(
+        matches!(
+            &result,
+            Err(Error::ParamsLimitParamsNotFound { params_type_name })
+            if *params_type_name == std::any::type_name::<InstanceCount>()
+        ),
+        "Expected `Error::ParamsLimitParamsNotFound`, but was {result:?}"
+    );
+}
+
+#[test]
+fn check_returns_error_when_value_exceeds_limit() {
+    let params_limits = params_limits();
+    let mut resources = Resources::new();
+    resources.insert(InstanceCount(500));
+
+    let result = params_limits.check(&resources);
+
+    assert!(
+        matches!(
+            &result,
+            Err(Error::ParamsLimitsExceeded { violations })
+            if violations == &[ParamsLimitViolation::new(
+                std::any::type_name::<InstanceCount>(),
+                String::from("instance count 500 exceeds limit 10"),
+            )]
+        ),
+        "Expected `Error::ParamsLimitsExceeded`, but was {result:?}"
+    );
+}
+
+#[test]
+fn check_returns_ok_when_value_exceeds_limit_and_overridden() -> Result<(), Error> {
+    let params_limits = params_limits().with_override(true);
+    let mut resources = Resources::new();
+    resources.insert(InstanceCount(500));
+
+    assert!(params_limits.overridden());
+    assert_eq!(1, params_limits.violations(&resources)?.len());
+    assert!(params_limits.check(&resources).is_ok());
+    Ok(())
+}
+
+#[test]
+fn check_returns_each_violation() -> Result<(), Error> {
+    let params_limits = params_limits().with_limit(|instance_count: &InstanceCount| {
+        if instance_count.0 > 100 {
+            Err(format!(
+                "instance count {} exceeds limit 100",
+                instance_count.0
+            ))
+        } else {
+            Ok(())
+        }
+    });
+    let mut resources = Resources::new();
+    resources.insert(InstanceCount(501));
+
+    let violations = params_limits.violations(&resources)?;
+
+    assert_eq!(
+        vec![
+            "instance count 501 exceeds limit 10",
+            "instance count 501 exceeds limit 100"
+        ],
+        violations
+            .iter()
+            .map(ParamsLimitViolation::description)
+            .collect::<Vec<_>>()
+    );
+    Ok(())
+}
+
+#[test]
+fn debug() {
+    let params_limits = params_limits();
+
+    assert_eq!(
+        "ParamsLimits { \
+            limits: [\"workspace_tests::rt_model::params_limits::InstanceCount\"], \
+            overridden: false \
+        }",
+        format!("{params_limits:?}")
+    );
+}
+
+fn params_limits() -> ParamsLimits {
+    ParamsLimits::new().with_limit(|instance_count: &InstanceCount| {
+        if instance_count.0 > 10 {
+            Err(format!(
+                "instance count {} exceeds limit 10",
+                instance_count.0
+            ))
+        } else {
+            Ok(())
+        }
+    })
+}
+
+#[derive(Debug)]
+struct InstanceCount(u32);
@@ -0,0 +1,400 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use peace::{
+    cfg::{async_trait, profile, Profile},
+    resources::paths::ProfileLockFile,
+    rt_model::{
+        storage::{InMemoryStorage, StorageBackend},
+        Error, ProfileLock, ProfileLockInfo, ProfileLockMode, Storage,
+    },
+};
+
+#[tokio::test]
+async fn acquire_writes_lock_file_and_drop_removes_it() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = Storage::default();
+    let profile_lock_file = ProfileLockFile::new(tempdir.path().join(ProfileLockFile::NAME));
+
+    let profile_lock = ProfileLock::acquire(
+        &storage,
+        &profile!("test_profile"),
+        profile_lock_file.clone(),
+        ProfileLockMode::Fail,
+    )
+    .await?;
+
+    let profile_lock_info = serde_yaml::from_str::<ProfileLockInfo>(
+        &tokio::fs::read_to_string(&profile_lock_file).await?,
+    )?;
+    assert_eq!(&profile_lock_file, profile_lock.profile_lock_file());
+    assert_eq!(ProfileLockInfo::current(), profile_lock_info);
+
+    drop(profile_lock);
+    assert!(!profile_lock_file.exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn acquire_returns_profile_locked_when_lock_is_held() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tempdir = tempfile::tempdir()?;
+    let storage = Storage::default();
+    let profile_lock_file = ProfileLockFile::new(tempdir.path().join(ProfileLockFile::NAME));
+    let _profile_lock = ProfileLock::acquire(
+        &storage,
+        &profile!("test_profile"),
+        profile_lock_file.clone(),
+        ProfileLockMode::Fail,
+    )
+    .await?;
+
+    let error = ProfileLock::acquire(
+        &storage,
+        &profile!("test_profile"),
+        profile_lock_file.clone(),
+        ProfileLockMode::Fail,
+    )
+    .await
+    .unwrap_err();
+
+    let ProfileLockInfo {
+        pid,
+        start_time: _,
+        hostname,
+    } = ProfileLockInfo::current();
+    assert!(
+        matches!(
+            &error,
+            Error::ProfileLocked {
+                profile,
+                profile_lock_file: profile_lock_file_locked,
+                pid: pid_locked,
+                hostname: hostname_locked,
+            }
+            if profile == &profile!("test_profile")
+            && profile_lock_file_locked == &*profile_lock_file
+            && pid_locked == &pid
+            && hostname_locked == &hostname
+        ),
+        "Expected error to be `ProfileLocked`, but was {error:?}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn acquire_wait_returns_profile_locked_when_timeout_elapses()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = Storage::default();
+    let profile_lock_file = ProfileLockFile::new(tempdir.path().join(ProfileLockFile::NAME));
+    let _profile_lock = ProfileLock::acquire(
+        &storage,
+        &profile!("test_profile"),
+        profile_lock_file.clone(),
+        ProfileLockMode::Fail,
+    )
+    .await?;
+
+    let error = ProfileLock::acquire(
+        &storage,
+        &profile!("test_profile"),
+        profile_lock_file,
+        ProfileLockMode::Wait {
+            timeout: Some(Duration::from_millis(150)),
+        },
+    )
+    .await
+    .unwrap_err();
+
+    assert!(
+        matches!(&error, Error::ProfileLocked { .. }),
+        "Expected error to be `ProfileLocked`, but was {error:?}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn acquire_wait_acquires_lock_when_released() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = Storage::default();
+    let profile_lock_file = ProfileLockFile::new(tempdir.path().join(ProfileLockFile::NAME));
+    let profile_lock = ProfileLock::acquire(
+        &storage,
+        &profile!("test_profile"),
+        profile_lock_file.clone(),
+        ProfileLockMode::Fail,
+    )
+    .await?;
+
+    let profile = profile!("test_profile");
+    let release = async move {
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        drop(profile_lock);
+    };
+    let acquire = ProfileLock::acquire(
+        &storage,
+        &profile,
+        profile_lock_file.clone(),
+        ProfileLockMode::Wait { timeout: None },
+    );
+    let ((), profile_lock) = tokio::join!(release, acquire);
+
+    assert_eq!(&profile_lock_file, profile_lock?.profile_lock_file());
+    Ok(())
+}
+
+#[tokio::test]
+async fn acquire_replaces_stale_lock_on_same_host() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = Storage::default();
+    let profile_lock_file = ProfileLockFile::new(tempdir.path().join(ProfileLockFile::NAME));
+    let profile_lock_info_stale = ProfileLockInfo {
+        pid: u32::MAX,
+        start_time: 0,
+        hostname: ProfileLockInfo::current().hostname,
+    };
+    tokio::fs::write(
+        &profile_lock_file,
+        serde_yaml::to_string(&profile_lock_info_stale)?,
+    )
+    .await?;
+
+    let _profile_lock = ProfileLock::acquire(
+        &storage,
+        &profile!("test_profile"),
+        profile_lock_file.clone(),
+        ProfileLockMode::Fail,
+    )
+    .await?;
+
+    let profile_lock_info = serde_yaml::from_str::<ProfileLockInfo>(
+        &tokio::fs::read_to_string(&profile_lock_file).await?,
+    )?;
+    assert_eq!(ProfileLockInfo::current(), profile_lock_info);
+    Ok(())
+}
+
+#[tokio::test]
+async fn acquire_replaces_stale_lock_whose_process_id_is_reused()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = Storage::default();
+    let profile_lock_file = ProfileLockFile::new(tempdir.path().join(ProfileLockFile::NAME));
+    let profile_lock_info_current = ProfileLockInfo::current();
+    let profile_lock_info_stale = ProfileLockInfo {
+        start_time: profile_lock_info_current.start_time.wrapping_sub(1),
+        ..profile_lock_info_current.clone()
+    };
+    tokio::fs::write(
+        &profile_lock_file,
+        serde_yaml::to_string(&profile_lock_info_stale)?,
+    )
+    .await?;
+
+    let _profile_lock = ProfileLock::acquire(
+        &storage,
+        &profile!("test_profile"),
+        profile_lock_file.clone(),
+        ProfileLockMode::Fail,
+    )
+    .await?;
+
+    let profile_lock_info = serde_yaml::from_str::<ProfileLockInfo>(
+        &tokio::fs::read_to_string(&profile_lock_file).await?,
+    )?;
+    assert_eq!(profile_lock_info_current, profile_lock_info);
+    Ok(())
+}
+
+#[tokio::test]
+async fn acquire_does_not_replace_lock_from_other_host() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = Storage::default();
+    let profile_lock_file = ProfileLockFile::new(tempdir.path().join(ProfileLockFile::NAME));
+    let profile_lock_info_other = ProfileLockInfo {
+        pid: u32::MAX,
+        start_time: 0,
+        hostname: format!("{}_other", ProfileLockInfo::current().hostname),
+    };
+    tokio::fs::write(
+        &profile_lock_file,
+        serde_yaml::to_string(&profile_lock_info_other)?,
+    )
+    .await?;
+
+    let error = ProfileLock::acquire(
+        &storage,
+        &profile!("test_profile"),
+        profile_lock_file,
+        ProfileLockMode::Fail,
+    )
+    .await
+    .unwrap_err();
+
+    assert!(
+        matches!(
+            &error,
+            Error::ProfileLocked { pid, hostname, .. }
+            if pid == &u32::MAX && hostname == &profile_lock_info_other.hostname
+        ),
+        "Expected error to be `ProfileLocked`, but was {error:?}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn acquire_does_not_remove_lock_acquired_after_stale_lock_is_read()
+-> Result<(), Box<dyn std::error::Error>> {
+    let profile_lock_file = ProfileLockFile::new(PathBuf::from(ProfileLockFile::NAME));
+    let profile_lock_info_stale = ProfileLockInfo {
+        pid: u32::MAX,
+        start_time: 0,
+        hostname: ProfileLockInfo::current().hostname,
+    };
+    let profile_lock_info_other = ProfileLockInfo {
+        pid: u32::MAX,
+        start_time: 0,
+        hostname: format!("{}_other", ProfileLockInfo::current().hostname),
+    };
+    let lock_stealing_storage = LockStealingStorage {
+        in_memory_storage: InMemoryStorage::new(),
+        profile_lock_info_other: serde_yaml::to_string(&profile_lock_info_other)?,
+        stolen: Arc::new(AtomicBool::new(false)),
+    };
+    lock_stealing_storage
+        .in_memory_storage
+        .write(
+            &profile_lock_file,
+            serde_yaml::to_string(&profile_lock_info_stale)?.as_bytes(),
+        )
+        .await?;
+    let storage = Storage::new(lock_stealing_storage.clone());
+
+    let error = ProfileLock::acquire(
+        &storage,
+        &profile!("test_profile"),
+        profile_lock_file.clone(),
+        ProfileLockMode::Fail,
+    )
+    .await
+    .unwrap_err();
+
+    assert!(
+        matches!(
+            &error,
+            Error::ProfileLocked { hostname, .. }
+            if hostname == &profile_lock_info_other.hostname
+        ),
+        "Expected error to be `ProfileLocked`, but was {error:?}"
+    );
+    let profile_lock_info = serde_yaml::from_slice::<ProfileLockInfo>(
+        &lock_stealing_storage
+            .in_memory_storage
+            .read(&profile_lock_file)
+            .await?
+            .expect("Expected lock file to exist."),
+    )?;
+    assert_eq!(profile_lock_info_other, profile_lock_info);
+    Ok(())
+}
+
+#[tokio::test]
+async fn acquire_and_release_through_storage_backend() -> Result<(), Box<dyn std::error::Error>> {
+    let in_memory_storage = InMemoryStorage::new();
+    let storage = Storage::new(in_memory_storage.clone());
+    let profile_lock_file = ProfileLockFile::new(PathBuf::from(ProfileLockFile::NAME));
+
+    let profile_lock = ProfileLock::acquire(
+        &storage,
+        &profile!("test_profile"),
+        profile_lock_file.clone(),
+        ProfileLockMode::Fail,
+    )
+    .await?;
+    assert!(in_memory_storage.contains(&profile_lock_file).await?);
+
+    let error = ProfileLock::acquire(
+        &storage,
+        &profile!("test_profile"),
+        profile_lock_file.clone(),
+        ProfileLockMode::Fail,
+    )
+    .await
+    .unwrap_err();
+    assert!(
+        matches!(&error, Error::ProfileLocked { .. }),
+        "Expected error to be `ProfileLocked`, but was {error:?}"
+    );
+
+    profile_lock.release().await?;
+    assert!(!in_memory_storage.contains(&profile_lock_file).await?);
+    Ok(())
+}
+
+#[test]
+fn profile_lock_mode_default_is_fail() {
+    assert_eq!(ProfileLockMode::Fail, ProfileLockMode::default());
+}
+
+/// Storage where another command acquires the lock after it is first read.
+///
+/// This simulates another command removing the same stale lock, and acquiring
+/// the lock before this command removes the stale lock.
+#[derive(Clone, Debug)]
+struct LockStealingStorage {
+    in_memory_storage: InMemoryStorage,
+    profile_lock_info_other: String,
+    stolen: Arc<AtomicBool>,
+}
+
+#[async_trait]
+impl StorageBackend for LockStealingStorage {
+    async fn contains(&self, path: &Path) -> Result<bool, Error> {
+        self.in_memory_storage.contains(path).await
+    }
+
+    async fn read(&self, path: &Path) -> Result<Option<Vec<u8>>, Error> {
+        let bytes = self.in_memory_storage.read(path).await?;
+        if !self.stolen.swap(true, Ordering::SeqCst) {
+            self.in_memory_storage
+                .write(path, self.profile_lock_info_other.as_bytes())
+                .await?;
+        }
+        Ok(bytes)
+    }
+
+    async fn write(&self, path: &Path, bytes: &[u8]) -> Result<(), Error> {
+        self.in_memory_storage.write(path, bytes).await
+    }
+
+    async fn write_new(&self, path: &Path, bytes: &[u8]) -> Result<bool, Error> {
+        self.in_memory_storage.write_new(path, bytes).await
+    }
+
+    async fn remove(&self, path: &Path) -> Result<(), Error> {
+        self.in_memory_storage.remove(path).await
+    }
+
+    async fn remove_if_unchanged(&self, path: &Path, bytes: &[u8]) -> Result<bool, Error> {
+        self.in_memory_storage.remove_if_unchanged(path, bytes).await
+    }
+
+    async fn dir_create(&self, dir: &Path) -> Result<(), Error> {
+        self.in_memory_storage.dir_create(dir).await
+    }
+
+    async fn dir_entries(&self, dir: &Path) -> Result<Vec<PathBuf>, Error> {
+        self.in_memory_storage.dir_entries(dir).await
+    }
+
+    fn is_file_system(&self) -> bool {
+        false
+    }
+}
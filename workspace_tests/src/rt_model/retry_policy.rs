@@ -0,0 +1,274 @@
+use std::{
+    cell::Cell,
+    time::{Duration, Instant},
+};
+
+use peace::{
+    cfg::{item_spec_id, InterruptSignal, ItemSpecId, OpCtx},
+    rt_model::{RetryOutcome, RetryPolicy},
+};
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "output_progress")] {
+        use peace::cfg::progress::ProgressSender;
+        use tokio::sync::mpsc;
+    }
+}
+
+#[test]
+fn default_makes_single_attempt() {
+    let retry_policy = RetryPolicy::<TestError>::default();
+
+    assert_eq!(1, retry_policy.max_attempts());
+}
+
+#[test]
+fn new_treats_zero_max_attempts_as_one() {
+    let retry_policy = RetryPolicy::<TestError>::new(0);
+
+    assert_eq!(1, retry_policy.max_attempts());
+}
+
+#[test]
+fn backoff_doubles_each_retry_and_is_capped_at_backoff_max() {
+    let retry_policy = RetryPolicy::<TestError>::new(10)
+        .with_backoff(Duration::from_millis(100), Duration::from_millis(500));
+
+    assert_eq!(Duration::from_millis(100), retry_policy.backoff(1));
+    assert_eq!(Duration::from_millis(200), retry_policy.backoff(2));
+    assert_eq!(Duration::from_millis(400), retry_policy.backoff(3));
+    assert_eq!(Duration::from_millis(500), retry_policy.backoff(4));
+    assert_eq!(Duration::from_millis(500), retry_policy.backoff(100));
+}
+
+#[test]
+fn with_backoff_uses_backoff_initial_when_backoff_max_is_less() {
+    let retry_policy = RetryPolicy::<TestError>::new(3)
+        .with_backoff(Duration::from_millis(100), Duration::from_millis(10));
+
+    assert_eq!(Duration::from_millis(100), retry_policy.backoff_max());
+}
+
+#[test]
+fn is_retryable_uses_classifier() {
+    let retry_policy = RetryPolicy::<TestError>::new(3)
+        .with_retryable(|error| matches!(error, TestError::Transient));
+
+    assert!(retry_policy.is_retryable(&TestError::Transient));
+    assert!(!retry_policy.is_retryable(&TestError::Permanent));
+}
+
+#[tokio::test]
+async fn exec_retries_retryable_errors_until_success() -> Result<(), Box<dyn std::error::Error>> {
+    let retry_policy = retry_policy_no_delay(3);
+    let interrupt_signal = InterruptSignal::new();
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "output_progress")] {
+            let (progress_tx, _progress_rx) = mpsc::channel(10);
+            let progress_sender = ProgressSender::new(ITEM_SPEC_ID, &progress_tx);
+        }
+    }
+    let op_ctx = OpCtx::new(
+        ITEM_SPEC_ID,
+        &interrupt_signal,
+        #[cfg(feature = "output_progress")]
+        progress_sender,
+    );
+    let attempts = Cell::new(0u32);
+
+    let RetryOutcome {
+        result,
+        retry_count,
+    } = retry_policy
+        .exec(op_ctx, || async {
+            attempts.set(attempts.get() + 1);
+            if attempts.get() < 3 {
+                Err(TestError::Transient)
+            } else {
+                Ok(123)
+            }
+        })
+        .await;
+
+    assert_eq!(123, result?);
+    assert_eq!(3, attempts.get());
+    assert_eq!(2, retry_count);
+    Ok(())
+}
+
+#[tokio::test]
+async fn exec_returns_error_when_max_attempts_reached() {
+    let retry_policy = retry_policy_no_delay(3);
+    let interrupt_signal = InterruptSignal::new();
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "output_progress")] {
+            let (progress_tx, _progress_rx) = mpsc::channel(10);
+            let progress_sender = ProgressSender::new(ITEM_SPEC_ID, &progress_tx);
+        }
+    }
+    let op_ctx = OpCtx::new(
+        ITEM_SPEC_ID,
+        &interrupt_signal,
+        #[cfg(feature = "output_progress")]
+        progress_sender,
+    );
+    let attempts = Cell::new(0u32);
+
+    let RetryOutcome {
+        result,
+        retry_count,
+    } = retry_policy
+        .exec(op_ctx, || async {
+            attempts.set(attempts.get() + 1);
+            Result::<(), _>::Err(TestError::Transient)
+        })
+        .await;
+
+    assert!(matches!(result, Err(TestError::Transient)));
+    assert_eq!(3, attempts.get());
+    assert_eq!(2, retry_count);
+}
+
+#[tokio::test]
+async fn exec_does_not_retry_non_retryable_errors() {
+    let retry_policy =
+        retry_policy_no_delay(3).with_retryable(|error| matches!(error, TestError::Transient));
+    let interrupt_signal = InterruptSignal::new();
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "output_progress")] {
+            let (progress_tx, _progress_rx) = mpsc::channel(10);
+            let progress_sender = ProgressSender::new(ITEM_SPEC_ID, &progress_tx);
+        }
+    }
+    let op_ctx = OpCtx::new(
+        ITEM_SPEC_ID,
+        &interrupt_signal,
+        #[cfg(feature = "output_progress")]
+        progress_sender,
+    );
+    let attempts = Cell::new(0u32);
+
+    let RetryOutcome {
+        result,
+        retry_count,
+    } = retry_policy
+        .exec(op_ctx, || async {
+            attempts.set(attempts.get() + 1);
+            Result::<(), _>::Err(TestError::Permanent)
+        })
+        .await;
+
+    assert!(matches!(result, Err(TestError::Permanent)));
+    assert_eq!(1, attempts.get());
+    assert_eq!(0, retry_count);
+}
+
+#[tokio::test]
+async fn exec_does_not_retry_when_interrupted() {
+    let retry_policy = retry_policy_no_delay(3);
+    let interrupt_signal = InterruptSignal::new();
+    interrupt_signal.interrupt();
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "output_progress")] {
+            let (progress_tx, _progress_rx) = mpsc::channel(10);
+            let progress_sender = ProgressSender::new(ITEM_SPEC_ID, &progress_tx);
+        }
+    }
+    let op_ctx = OpCtx::new(
+        ITEM_SPEC_ID,
+        &interrupt_signal,
+        #[cfg(feature = "output_progress")]
+        progress_sender,
+    );
+    let attempts = Cell::new(0u32);
+
+    let RetryOutcome {
+        result,
+        retry_count,
+    } = retry_policy
+        .exec(op_ctx, || async {
+            attempts.set(attempts.get() + 1);
+            Result::<(), _>::Err(TestError::Transient)
+        })
+        .await;
+
+    assert!(matches!(result, Err(TestError::Transient)));
+    assert_eq!(1, attempts.get());
+    assert_eq!(0, retry_count);
+}
+
+#[tokio::test]
+async fn exec_stops_waiting_for_backoff_when_interrupted() {
+    let retry_policy = RetryPolicy::<TestError>::new(3)
+        .with_backoff(Duration::from_secs(60), Duration::from_secs(60))
+        .with_jitter(false);
+    let interrupt_signal = InterruptSignal::new();
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "output_progress")] {
+            let (progress_tx, _progress_rx) = mpsc::channel(10);
+            let progress_sender = ProgressSender::new(ITEM_SPEC_ID, &progress_tx);
+        }
+    }
+    let op_ctx = OpCtx::new(
+        ITEM_SPEC_ID,
+        &interrupt_signal,
+        #[cfg(feature = "output_progress")]
+        progress_sender,
+    );
+    let attempts = Cell::new(0u32);
+
+    let start = Instant::now();
+    let (
+        RetryOutcome {
+            result,
+            retry_count,
+        },
+        (),
+    ) = futures::join!(
+        retry_policy.exec(op_ctx, || async {
+            attempts.set(attempts.get() + 1);
+            Result::<(), _>::Err(TestError::Transient)
+        }),
+        async {
+            peace::rt_model::time::sleep(Duration::from_millis(10)).await;
+            interrupt_signal.interrupt();
+        }
+    );
+
+    assert!(matches!(result, Err(TestError::Transient)));
+    assert!(start.elapsed() < Duration::from_secs(30));
+    assert_eq!(1, attempts.get());
+    assert_eq!(0, retry_count);
+}
+
+#[test]
+fn debug() {
+    let retry_policy = RetryPolicy::<TestError>::new(3)
+        .with_backoff(Duration::from_millis(100), Duration::from_secs(1))
+        .with_jitter(false);
+
+    assert_eq!(
+        "RetryPolicy { \
+            max_attempts: 3, \
+            backoff_initial: 100ms, \
+            backoff_max: 1s, \
+            jitter: false, \
+            retryable: \"fn(&Error) -> bool\" \
+        }",
+        format!("{retry_policy:?}")
+    );
+}
+
+const ITEM_SPEC_ID: &ItemSpecId = &item_spec_id!("retry_policy_test");
+
+fn retry_policy_no_delay(max_attempts: u32) -> RetryPolicy<TestError> {
+    RetryPolicy::new(max_attempts).with_backoff(Duration::ZERO, Duration::ZERO)
+}
+
+#[derive(Debug, thiserror::Error)]
+enum TestError {
+    #[error("transient error")]
+    Transient,
+    #[error("permanent error")]
+    Permanent,
+}
@@ -1,17 +1,23 @@
 use peace::{
     cfg::{flow_id, item_spec_id, FlowId, ItemSpecId},
     resources::{
-        internal::StatesMut, paths::StatesSavedFile, states::StatesSaved,
+        internal::StatesMut,
+        paths::StatesSavedFile,
+        states::{StateOrphaned, StatesSaved},
         type_reg::untagged::TypeReg,
     },
-    rt_model::{Error, StatesSerializer, Storage},
+    rt_model::{
+        storage::StorageFormat,
+        Error, StatesSerializer, Storage,
+    },
 };
 use pretty_assertions::assert_eq;
+use serde::Serialize;
 
 #[tokio::test]
 async fn serialize() -> Result<(), Box<dyn std::error::Error>> {
     let tempdir = tempfile::tempdir()?;
-    let storage = Storage;
+    let storage = Storage::default();
     let states_saved_file = StatesSavedFile::new(tempdir.path().join("states_saved.yaml"));
 
     let states = {
@@ -31,7 +37,7 @@ async fn serialize() -> Result<(), Box<dyn std::error::Error>> {
 async fn deserialize_saved() -> Result<(), Box<dyn std::error::Error>> {
     let tempdir = tempfile::tempdir()?;
     let flow_id = flow_id!("test_flow");
-    let storage = Storage;
+    let storage = Storage::default();
     let item_spec_id = item_spec_id!("a");
     let mut states_type_reg = TypeReg::new_typed();
     states_type_reg.register::<u32>(item_spec_id.clone());
@@ -60,11 +66,155 @@ async fn deserialize_saved() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[tokio::test]
+async fn deserialize_saved_retains_orphaned_states() -> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let flow_id = flow_id!("test_flow");
+    let storage = Storage::default();
+    let item_spec_id = item_spec_id!("a");
+    let mut states_type_reg = TypeReg::new_typed();
+    states_type_reg.register::<u32>(item_spec_id.clone());
+    let states_saved_file = StatesSavedFile::new(tempdir.path().join("states_saved.yaml"));
+
+    let contents = "a: 123\nb:\n- 1\n- 2\n";
+    tokio::fs::write(&states_saved_file, contents).await?;
+
+    let states_deserialized = StatesSerializer::<Error>::deserialize_saved(
+        &flow_id,
+        &storage,
+        &states_type_reg,
+        &states_saved_file,
+    )
+    .await?;
+
+    assert_eq!(
+        Some(123),
+        states_deserialized.get::<u32, _>(&item_spec_id).copied()
+    );
+    let state_orphaned_expected = StateOrphaned::new(serde_yaml::from_str("[1, 2]")?);
+    assert_eq!(
+        vec![(&item_spec_id!("b"), &state_orphaned_expected)],
+        states_deserialized.orphans().collect::<Vec<_>>()
+    );
+
+    // Orphaned states are written back when serializing.
+    StatesSerializer::<Error>::serialize(&storage, &states_deserialized, &states_saved_file)
+        .await?;
+    let serialized = tokio::fs::read_to_string(&states_saved_file).await?;
+    assert_eq!(contents, serialized);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn deserialize_saved_retains_orphaned_states_in_every_storage_format()
+-> Result<(), Box<dyn std::error::Error>> {
+    #[derive(Serialize)]
+    enum StateOrphanedStored {
+        Files { paths: Vec<String>, size: u64 },
+    }
+
+    #[derive(Serialize)]
+    struct StatesStored {
+        a: u32,
+        b: StateOrphanedStored,
+        c: Option<i64>,
+    }
+
+    let states_stored = StatesStored {
+        a: 123,
+        b: StateOrphanedStored::Files {
+            paths: vec![String::from("a.txt"), String::from("b.txt")],
+            size: 4,
+        },
+        c: Some(-1),
+    };
+
+    let tempdir = tempfile::tempdir()?;
+    let flow_id = flow_id!("test_flow");
+    let mut states_type_reg = TypeReg::new_typed();
+    states_type_reg.register::<u32>(item_spec_id!("a"));
+
+    for format in [
+        StorageFormat::Yaml,
+        #[cfg(feature = "storage_format_json")]
+        StorageFormat::Json,
+        #[cfg(feature = "storage_format_message_pack")]
+        StorageFormat::MessagePack,
+    ] {
+        let storage = Storage::default().with_format(format);
+        let states_saved_file = StatesSavedFile::new(
+            tempdir
+                .path()
+                .join("states_saved")
+                .with_extension(format.extension()),
+        );
+        let contents = format.serialize(&states_stored)?;
+        tokio::fs::write(&states_saved_file, &contents).await?;
+
+        let states_deserialized = StatesSerializer::<Error>::deserialize_saved(
+            &flow_id,
+            &storage,
+            &states_type_reg,
+            &states_saved_file,
+        )
+        .await?;
+        assert_eq!(
+            vec![&item_spec_id!("b"), &item_spec_id!("c")],
+            states_deserialized
+                .orphans()
+                .map(|(item_spec_id, _)| item_spec_id)
+                .collect::<Vec<_>>(),
+            "{format:?}"
+        );
+
+        StatesSerializer::<Error>::serialize(&storage, &states_deserialized, &states_saved_file)
+            .await?;
+        let serialized = tokio::fs::read(&states_saved_file).await?;
+        assert_eq!(contents, serialized, "{format:?}");
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn deserialize_saved_returns_no_state_for_item_spec_not_saved()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let flow_id = flow_id!("test_flow");
+    let storage = Storage::default();
+    let mut states_type_reg = TypeReg::new_typed();
+    states_type_reg.register::<u32>(item_spec_id!("a"));
+    states_type_reg.register::<u32>(item_spec_id!("b"));
+    let states_saved_file = StatesSavedFile::new(tempdir.path().join("states_saved.yaml"));
+
+    tokio::fs::write(&states_saved_file, "a: 123\n").await?;
+
+    let states_deserialized = StatesSerializer::<Error>::deserialize_saved(
+        &flow_id,
+        &storage,
+        &states_type_reg,
+        &states_saved_file,
+    )
+    .await?;
+
+    assert_eq!(
+        Some(123),
+        states_deserialized
+            .get::<u32, _>(&item_spec_id!("a"))
+            .copied()
+    );
+    assert_eq!(None, states_deserialized.get::<u32, _>(&item_spec_id!("b")));
+    assert_eq!(0, states_deserialized.orphans().count());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn deserialize_saved_error_maps_byte_indices() -> Result<(), Box<dyn std::error::Error>> {
     let tempdir = tempfile::tempdir()?;
     let flow_id = flow_id!("test_flow");
-    let storage = Storage;
+    let storage = Storage::default();
     let item_spec_id = item_spec_id!("a");
     let mut states_type_reg = TypeReg::new_typed();
     states_type_reg.register::<u32>(item_spec_id.clone());
@@ -1,6 +1,12 @@
+use std::path::PathBuf;
+
 use peace::{
     resources::type_reg::untagged::TypeReg,
-    rt_model::{params::WorkspaceParams, Error, Storage},
+    rt_model::{
+        params::WorkspaceParams,
+        storage::{FsStorage, InMemoryStorage, SerializedStorage, StorageBackend},
+        Error, Storage,
+    },
 };
 use serde::{Deserialize, Serialize};
 
@@ -15,12 +21,10 @@ async fn serialized_read_returns_t_when_path_exists() -> Result<(), Box<dyn std:
     let file_path = tempdir.path().join("t.yaml");
     tokio::fs::write(&file_path, br#"a: 1"#).await?;
 
-    let test_struct = Storage
-        .serialized_read::<TestStruct, _>(
-            crate::fn_name_short!().to_string(),
-            &file_path,
-            |_error| panic!("Expected `test_struct` to be deserialized."),
-        )
+    let test_struct = Storage::default()
+        .serialized_read::<TestStruct, _>(&file_path, |_error| {
+            panic!("Expected `test_struct` to be deserialized.")
+        })
         .await?;
 
     assert_eq!(TestStruct { a: 1 }, test_struct);
@@ -34,12 +38,10 @@ async fn serialized_read_returns_error_when_path_not_exists()
     let tempdir = tempfile::tempdir()?;
     let file_path = tempdir.path().join("t.yaml");
 
-    let error = Storage
-        .serialized_read::<TestStruct, _>(
-            crate::fn_name_short!().to_string(),
-            &file_path,
-            |_error| panic!("Expected `Error::ItemNotExists` to be returned."),
-        )
+    let error = Storage::default()
+        .serialized_read::<TestStruct, _>(&file_path, |_error| {
+            panic!("Expected `Error::ItemNotExists` to be returned.")
+        })
         .await
         .unwrap_err();
 
@@ -55,12 +57,10 @@ async fn serialized_read_opt_returns_t_when_path_exists() -> Result<(), Box<dyn
     let file_path = tempdir.path().join("t.yaml");
     tokio::fs::write(&file_path, br#"a: 1"#).await?;
 
-    let test_struct = Storage
-        .serialized_read_opt::<TestStruct, _>(
-            crate::fn_name_short!().to_string(),
-            &file_path,
-            |_error| panic!("Expected `test_struct` to be deserialized."),
-        )
+    let test_struct = Storage::default()
+        .serialized_read_opt::<TestStruct, _>(&file_path, |_error| {
+            panic!("Expected `test_struct` to be deserialized.")
+        })
         .await?;
 
     assert_eq!(Some(TestStruct { a: 1 }), test_struct);
@@ -74,12 +74,10 @@ async fn serialized_read_opt_returns_none_when_path_not_exists()
     let tempdir = tempfile::tempdir()?;
     let file_path = tempdir.path().join("t.yaml");
 
-    let test_struct = Storage
-        .serialized_read_opt::<TestStruct, _>(
-            crate::fn_name_short!().to_string(),
-            &file_path,
-            |_error| panic!("Expected `None` to be returned."),
-        )
+    let test_struct = Storage::default()
+        .serialized_read_opt::<TestStruct, _>(&file_path, |_error| {
+            panic!("Expected `None` to be returned.")
+        })
         .await?;
 
     assert!(test_struct.is_none());
@@ -96,13 +94,10 @@ async fn serialized_typemap_read_opt_returns_typemap_when_path_exists()
     let mut type_reg = TypeReg::new();
     type_reg.register::<TestStruct>(0);
 
-    let workspace_params: WorkspaceParams<u32> = Storage
-        .serialized_typemap_read_opt(
-            crate::fn_name_short!().to_string(),
-            &type_reg,
-            &file_path,
-            |_error| panic!("Expected `workspace_params` to be deserialized."),
-        )
+    let workspace_params: WorkspaceParams<u32> = Storage::default()
+        .serialized_typemap_read_opt(&type_reg, &file_path, |_error| {
+            panic!("Expected `workspace_params` to be deserialized.")
+        })
         .await?
         .unwrap();
 
@@ -119,13 +114,10 @@ async fn serialized_typemap_read_opt_returns_none_when_path_not_exists()
     let mut type_reg = TypeReg::new();
     type_reg.register::<TestStruct>(0);
 
-    let workspace_params: Option<WorkspaceParams<u32>> = Storage
-        .serialized_typemap_read_opt(
-            crate::fn_name_short!().to_string(),
-            &type_reg,
-            &file_path,
-            |_error| panic!("Expected `None` to be returned."),
-        )
+    let workspace_params: Option<WorkspaceParams<u32>> = Storage::default()
+        .serialized_typemap_read_opt(&type_reg, &file_path, |_error| {
+            panic!("Expected `None` to be returned.")
+        })
         .await?;
 
     assert!(workspace_params.is_none());
@@ -139,13 +131,10 @@ async fn serialized_write_serializes_t() -> Result<(), Box<dyn std::error::Error
     let file_path = tempdir.path().join("t.yaml");
 
     let test_struct = TestStruct { a: 1 };
-    Storage
-        .serialized_write(
-            crate::fn_name_short!().to_string(),
-            &file_path,
-            &test_struct,
-            |_error| panic!("Expected `test_struct` to be serialized."),
-        )
+    Storage::default()
+        .serialized_write(&file_path, &test_struct, |_error| {
+            panic!("Expected `test_struct` to be serialized.")
+        })
         .await?;
 
     let serialized = tokio::fs::read_to_string(&file_path).await?;
@@ -154,3 +143,170 @@ async fn serialized_write_serializes_t() -> Result<(), Box<dyn std::error::Error
 
     Ok(())
 }
+
+#[tokio::test]
+async fn in_memory_serialized_write_then_read_round_trips_without_file()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let file_path = tempdir.path().join("t.yaml");
+    let in_memory_storage = InMemoryStorage::new();
+    let storage = Storage::new(in_memory_storage.clone());
+
+    storage
+        .serialized_write(&file_path, &TestStruct { a: 1 }, |_error| {
+            panic!("Expected `test_struct` to be serialized.")
+        })
+        .await?;
+    let test_struct = storage
+        .serialized_read::<TestStruct, _>(&file_path, |_error| {
+            panic!("Expected `test_struct` to be deserialized.")
+        })
+        .await?;
+
+    assert_eq!(TestStruct { a: 1 }, test_struct);
+    assert_eq!(vec![file_path.clone()], in_memory_storage.paths());
+    assert!(!file_path.exists());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn in_memory_serialized_read_opt_returns_none_when_path_not_exists()
+-> Result<(), Box<dyn std::error::Error>> {
+    let storage = Storage::new(InMemoryStorage::new());
+
+    let test_struct = storage
+        .serialized_read_opt::<TestStruct, _>(&PathBuf::from("t.yaml"), |_error| {
+            panic!("Expected `None` to be returned.")
+        })
+        .await?;
+
+    assert_eq!(None, test_struct);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn in_memory_remove_removes_item() -> Result<(), Box<dyn std::error::Error>> {
+    let in_memory_storage = InMemoryStorage::new();
+    let path = PathBuf::from("dir/t.yaml");
+    in_memory_storage.write(&path, b"a: 1").await?;
+
+    assert!(in_memory_storage.contains(&path).await?);
+    in_memory_storage.remove(&path).await?;
+    assert!(!in_memory_storage.contains(&path).await?);
+    assert_eq!(None, in_memory_storage.read(&path).await?);
+
+    // Removing an item that does not exist is not an error.
+    in_memory_storage.remove(&path).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn in_memory_dir_entries_returns_items_directly_within_dir()
+-> Result<(), Box<dyn std::error::Error>> {
+    let in_memory_storage = InMemoryStorage::new();
+    in_memory_storage
+        .write(&PathBuf::from("dir/a.yaml"), b"")
+        .await?;
+    in_memory_storage
+        .write(&PathBuf::from("dir/b.yaml"), b"")
+        .await?;
+    in_memory_storage
+        .write(&PathBuf::from("dir/sub/c.yaml"), b"")
+        .await?;
+    in_memory_storage
+        .write(&PathBuf::from("other/d.yaml"), b"")
+        .await?;
+
+    let mut dir_entries = in_memory_storage.dir_entries(&PathBuf::from("dir")).await?;
+    dir_entries.sort();
+
+    assert_eq!(
+        vec![PathBuf::from("dir/a.yaml"), PathBuf::from("dir/b.yaml")],
+        dir_entries
+    );
+    assert!(in_memory_storage
+        .dir_entries(&PathBuf::from("missing"))
+        .await?
+        .is_empty());
+    assert!(!in_memory_storage.is_file_system());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn fs_dir_entries_returns_empty_when_dir_not_exists() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tempdir = tempfile::tempdir()?;
+    let dir = tempdir.path().join("missing");
+
+    let dir_entries = FsStorage.dir_entries(&dir).await?;
+
+    assert!(dir_entries.is_empty());
+    assert!(FsStorage.is_file_system());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn in_memory_write_new_does_not_overwrite_existing_item()
+-> Result<(), Box<dyn std::error::Error>> {
+    let in_memory_storage = InMemoryStorage::new();
+    let path = PathBuf::from("dir/t.yaml");
+
+    assert!(in_memory_storage.write_new(&path, b"a: 1").await?);
+    assert!(!in_memory_storage.write_new(&path, b"a: 2").await?);
+    assert_eq!(Some(b"a: 1".to_vec()), in_memory_storage.read(&path).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn in_memory_remove_if_unchanged_only_removes_item_with_same_bytes()
+-> Result<(), Box<dyn std::error::Error>> {
+    let in_memory_storage = InMemoryStorage::new();
+    let path = PathBuf::from("dir/t.yaml");
+    in_memory_storage.write(&path, b"a: 1").await?;
+
+    assert!(!in_memory_storage.remove_if_unchanged(&path, b"a: 2").await?);
+    assert_eq!(Some(b"a: 1".to_vec()), in_memory_storage.read(&path).await?);
+    assert!(in_memory_storage.remove_if_unchanged(&path, b"a: 1").await?);
+    assert!(!in_memory_storage.contains(&path).await?);
+    assert!(!in_memory_storage.remove_if_unchanged(&path, b"a: 1").await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn fs_remove_if_unchanged_only_removes_file_with_same_bytes()
+-> Result<(), Box<dyn std::error::Error>> {
+    let tempdir = tempfile::tempdir()?;
+    let path = tempdir.path().join("t.yaml");
+    FsStorage.write(&path, b"a: 1").await?;
+
+    assert!(!FsStorage.remove_if_unchanged(&path, b"a: 2").await?);
+    assert_eq!(b"a: 1".to_vec(), tokio::fs::read(&path).await?);
+    assert!(FsStorage.remove_if_unchanged(&path, b"a: 1").await?);
+    assert!(!path.exists());
+    assert!(!FsStorage.remove_if_unchanged(&path, b"a: 1").await?);
+
+    // No temporary files are left behind.
+    assert!(FsStorage.dir_entries(tempdir.path()).await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn fs_write_new_does_not_overwrite_existing_file() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tempdir = tempfile::tempdir()?;
+    let path = tempdir.path().join("t.yaml");
+
+    assert!(FsStorage.write_new(&path, b"a: 1").await?);
+    assert!(!FsStorage.write_new(&path, b"a: 2").await?);
+    assert_eq!(b"a: 1".to_vec(), tokio::fs::read(&path).await?);
+
+    Ok(())
+}
@@ -0,0 +1,301 @@
+use std::{fmt, path::PathBuf};
+
+use peace::{
+    cfg::{flow_id, item_spec_id, FlowId, ItemSpecId},
+    resources::{
+        internal::StatesMut, paths::StatesSavedFile, states::StatesSaved,
+        type_reg::untagged::TypeReg,
+    },
+    rt_model::{
+        params::WorkspaceParams,
+        storage::{
+            InMemoryStorage, SerializedStorage, StorageBackend, StorageFormat, StorageFormatError,
+        },
+        Error, StatesSerializer, Storage,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+enum TestEnum {
+    A(u32),
+    B { b: String },
+}
+
+impl fmt::Display for TestEnum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+struct TestStruct {
+    a: u32,
+    e: Vec<TestEnum>,
+}
+
+impl TestStruct {
+    fn new() -> Self {
+        Self {
+            a: 1,
+            e: vec![TestEnum::A(2), TestEnum::B { b: "3".to_string() }],
+        }
+    }
+}
+
+#[test]
+fn detect_returns_format_of_serialized_bytes() -> Result<(), Box<dyn std::error::Error>> {
+    let test_struct = TestStruct::new();
+
+    [
+        StorageFormat::Yaml,
+        StorageFormat::Json,
+        StorageFormat::MessagePack,
+    ]
+    .into_iter()
+    .try_for_each(|format| {
+        let serialized = format.serialize(&test_struct)?;
+        assert_eq!(format, StorageFormat::detect(&serialized)?);
+        Ok::<_, StorageFormatError>(())
+    })?;
+    assert_eq!(StorageFormat::Yaml, StorageFormat::detect(b"")?);
+    assert_eq!(StorageFormat::Json, StorageFormat::detect(b"  \n[1]")?);
+
+    Ok(())
+}
+
+#[test]
+fn default_is_yaml() {
+    assert_eq!(StorageFormat::Yaml, StorageFormat::default());
+}
+
+#[test]
+fn from_extension_returns_format_with_extension() {
+    [
+        StorageFormat::Yaml,
+        StorageFormat::Json,
+        StorageFormat::MessagePack,
+    ]
+    .into_iter()
+    .for_each(|format| {
+        assert_eq!(
+            Some(format),
+            StorageFormat::from_extension(format.extension())
+        );
+    });
+    assert_eq!(None, StorageFormat::from_extension("toml"));
+}
+
+#[test]
+fn paths_other_formats_returns_paths_with_other_extensions() {
+    assert_eq!(
+        vec![
+            PathBuf::from("dir/states_saved.yaml"),
+            PathBuf::from("dir/states_saved.msgpack"),
+        ],
+        StorageFormat::paths_other_formats(&PathBuf::from("dir/states_saved.json"))
+    );
+    assert!(StorageFormat::paths_other_formats(&PathBuf::from("dir/.lock")).is_empty());
+    assert!(StorageFormat::paths_other_formats(&PathBuf::from("dir/t.toml")).is_empty());
+}
+
+#[tokio::test]
+async fn serialized_write_writes_in_storage_format_and_read_round_trips()
+-> Result<(), Box<dyn std::error::Error>> {
+    let path = PathBuf::from("t.yaml");
+    let test_struct = TestStruct::new();
+
+    for format in [
+        StorageFormat::Yaml,
+        StorageFormat::Json,
+        StorageFormat::MessagePack,
+    ] {
+        let in_memory_storage = InMemoryStorage::new();
+        let storage = Storage::new(in_memory_storage.clone()).with_format(format);
+        storage
+            .serialized_write(&path, &test_struct, |_error| {
+                panic!("Expected `test_struct` to be serialized.")
+            })
+            .await?;
+
+        let serialized = in_memory_storage.read(&path).await?.unwrap();
+        assert_eq!(format, StorageFormat::detect(&serialized)?);
+        assert_eq!(format, storage.format());
+        let test_struct_read = storage
+            .serialized_read::<TestStruct, _>(&path, |_error| {
+                panic!("Expected `test_struct` to be deserialized.")
+            })
+            .await?;
+        assert_eq!(test_struct, test_struct_read);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn serialized_read_detects_format_different_to_storage_format()
+-> Result<(), Box<dyn std::error::Error>> {
+    let in_memory_storage = InMemoryStorage::new();
+    let path = PathBuf::from("t.yaml");
+    let test_struct = TestStruct::new();
+    Storage::new(in_memory_storage.clone())
+        .with_format(StorageFormat::MessagePack)
+        .serialized_write(&path, &test_struct, |_error| {
+            panic!("Expected `test_struct` to be serialized.")
+        })
+        .await?;
+
+    let test_struct_read = Storage::new(in_memory_storage)
+        .serialized_read::<TestStruct, _>(&path, |_error| {
+            panic!("Expected `test_struct` to be deserialized.")
+        })
+        .await?;
+
+    assert_eq!(test_struct, test_struct_read);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn serialized_read_reads_item_with_other_format_extension()
+-> Result<(), Box<dyn std::error::Error>> {
+    let in_memory_storage = InMemoryStorage::new();
+    let test_struct = TestStruct::new();
+    Storage::new(in_memory_storage.clone())
+        .serialized_write(&PathBuf::from("t.yaml"), &test_struct, |_error| {
+            panic!("Expected `test_struct` to be serialized.")
+        })
+        .await?;
+
+    let test_struct_read = Storage::new(in_memory_storage)
+        .with_format(StorageFormat::Json)
+        .serialized_read::<TestStruct, _>(&PathBuf::from("t.json"), |_error| {
+            panic!("Expected `test_struct` to be deserialized.")
+        })
+        .await?;
+
+    assert_eq!(test_struct, test_struct_read);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn serialized_write_keeps_item_with_other_format_extension()
+-> Result<(), Box<dyn std::error::Error>> {
+    let in_memory_storage = InMemoryStorage::new();
+    let yaml_path = PathBuf::from("t.yaml");
+    let json_path = PathBuf::from("t.json");
+    let test_struct = TestStruct::new();
+    Storage::new(in_memory_storage.clone())
+        .serialized_write(&yaml_path, &test_struct, |_error| {
+            panic!("Expected `test_struct` to be serialized.")
+        })
+        .await?;
+
+    Storage::new(in_memory_storage.clone())
+        .with_format(StorageFormat::Json)
+        .serialized_write(&json_path, &test_struct, |_error| {
+            panic!("Expected `test_struct` to be serialized.")
+        })
+        .await?;
+
+    assert!(in_memory_storage.contains(&yaml_path).await?);
+    assert!(in_memory_storage.contains(&json_path).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn serialized_typemap_read_opt_returns_typemap_in_each_format()
+-> Result<(), Box<dyn std::error::Error>> {
+    let path = PathBuf::from("workspace_params.yaml");
+    let mut type_reg = TypeReg::new();
+    type_reg.register::<TestStruct>(String::from("test_struct"));
+    let mut workspace_params = WorkspaceParams::<String>::new();
+    workspace_params.insert(String::from("test_struct"), TestStruct::new());
+
+    for format in [
+        StorageFormat::Yaml,
+        StorageFormat::Json,
+        StorageFormat::MessagePack,
+    ] {
+        let storage = Storage::new(InMemoryStorage::new()).with_format(format);
+        storage
+            .serialized_write(&path, &workspace_params, Error::WorkspaceParamsSerialize)
+            .await?;
+
+        let workspace_params_read: WorkspaceParams<String> = storage
+            .serialized_typemap_read_opt(&type_reg, &path, Error::WorkspaceParamsDeserialize)
+            .await?
+            .unwrap();
+
+        assert_eq!(
+            Some(TestStruct::new()).as_ref(),
+            workspace_params_read.get::<TestStruct, _>("test_struct")
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn states_serializer_round_trips_states_in_each_format()
+-> Result<(), Box<dyn std::error::Error>> {
+    let flow_id = flow_id!("test_flow");
+    let item_spec_id = item_spec_id!("a");
+    let mut states_type_reg = TypeReg::new_typed();
+    states_type_reg.register::<TestEnum>(item_spec_id.clone());
+    let states_saved_file = StatesSavedFile::new(PathBuf::from("states_saved.yaml"));
+    let states = {
+        let mut states = StatesMut::new();
+        states.insert(item_spec_id.clone(), TestEnum::A(123));
+        StatesSaved::from(states)
+    };
+
+    for format in [
+        StorageFormat::Yaml,
+        StorageFormat::Json,
+        StorageFormat::MessagePack,
+    ] {
+        let storage = Storage::new(InMemoryStorage::new()).with_format(format);
+        StatesSerializer::<Error>::serialize(&storage, &states, &states_saved_file).await?;
+
+        let states_deserialized = StatesSerializer::<Error>::deserialize_saved(
+            &flow_id,
+            &storage,
+            &states_type_reg,
+            &states_saved_file,
+        )
+        .await?;
+
+        assert_eq!(
+            Some(&TestEnum::A(123)),
+            states_deserialized.get::<TestEnum, _>(&item_spec_id)
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn serialized_read_returns_json_error_when_json_is_invalid()
+-> Result<(), Box<dyn std::error::Error>> {
+    let in_memory_storage = InMemoryStorage::new();
+    let path = PathBuf::from("t.yaml");
+    in_memory_storage.write(&path, br#"{ "a": 1 }"#).await?;
+
+    let error = Storage::new(in_memory_storage)
+        .serialized_read::<TestStruct, _>(&path, Error::WorkspaceParamsDeserialize)
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(
+            &error,
+            Error::WorkspaceParamsDeserialize(StorageFormatError::Json(_))
+        ),
+        "Expected `error` to be a JSON error, but was {error:?}"
+    );
+
+    Ok(())
+}